@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::types::{GitHubChecksResponse, WorktreeSetupRun};
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
@@ -24,8 +26,47 @@ pub(crate) struct TerminalExit {
     pub(crate) terminal_id: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitHubChecksFinished {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+    #[serde(rename = "timedOut")]
+    pub(crate) timed_out: bool,
+    pub(crate) checks: GitHubChecksResponse,
+    /// Ready-to-send follow-up message describing the failed checks.
+    #[serde(rename = "followUp")]
+    pub(crate) follow_up: Option<String>,
+}
+
+/// Files that changed in a watched workspace since the last event. Git
+/// metadata changes (staging, commits, checkouts) are reported under `.git/`.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitStatusChanged {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) paths: Vec<String>,
+    /// Whether more paths changed than are listed.
+    pub(crate) truncated: bool,
+}
+
+/// A line of output from a backend-run worktree setup script.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct WorktreeSetupOutput {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    /// `stdout` or `stderr`.
+    pub(crate) stream: String,
+    pub(crate) data: String,
+}
+
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
     fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished);
+    fn emit_git_status_changed(&self, event: GitStatusChanged);
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput);
+    fn emit_worktree_setup_status(&self, event: WorktreeSetupRun);
 }
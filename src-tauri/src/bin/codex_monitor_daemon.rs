@@ -76,20 +76,33 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, GitStatusChanged, TerminalExit,
+    TerminalOutput, WorktreeSetupOutput,
+};
 use shared::codex_core::CodexLoginCancelState;
 use shared::process_core::kill_child_process_tree;
-use shared::prompts_core::{self, CustomPromptEntry};
+use shared::prompt_repo_core::{PromptVersion, TeamPromptRepo};
+use shared::prompts_core::{self, CustomPromptEntry, PromptVariable};
+use shared::skills_core::{self, InstallSkillInput, SkillEntry};
 use shared::{
-    agents_config_core, codex_aux_core, codex_core, files_core, git_core, git_ui_core,
-    local_usage_core, mcp_config_core, settings_core, thread_usage_core, workspaces_core,
-    worktree_core,
+    agent_templates_core, agents_config_core, codex_aux_core, codex_core, codex_home_core,
+    config_history_core, config_profiles_core, files_core, git_core, git_ui_core, issue_agent_core,
+    local_usage_core, mcp_config_core, mcp_probe_core, model_provider_probe_core,
+    model_providers_core, project_config_core, settings_core, settings_sync_core,
+    thread_usage_core, workspaces_core, worktree_core,
 };
-use storage::{read_settings, read_workspaces};
+use storage::{load_settings, load_workspaces, read_workspaces};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalThreadUsageSnapshot,
-    LocalUsageSnapshot, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, CodexHomeProfileInfo, CodexHomeScope, CodexHomeSwitchResult, ForgeInfo,
+    GitCommitDiff, GitFileDiff, GitHubChecksResponse, GitHubIssuesResponse, GitHubListQuery,
+    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestReviewComment, GitHubPullRequestReviewEvent, GitHubPullRequestReviewResult,
+    GitHubPullRequestsResponse, GitHubRelease, GitLogResponse, GitTagInfo, IssueAgentStart,
+    LocalThreadUsageSnapshot, LocalUsageSnapshot, StorageRecovery, WorkspaceCheckoutOptions,
+    WorkspaceEntry, WorkspaceExportResult, WorkspaceImportResult, WorkspaceInfo, WorkspaceSettings,
+    WorktreeApplyOptions, WorktreeApplyResult, WorktreeGcApplyResult, WorktreeGcReport,
+    WorktreeSetupRun, WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -127,6 +140,10 @@ enum DaemonEvent {
     TerminalOutput(TerminalOutput),
     #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    GitHubChecksFinished(GitHubChecksFinished),
+    GitStatusChanged(GitStatusChanged),
+    WorktreeSetupOutput(WorktreeSetupOutput),
+    WorktreeSetupStatus(WorktreeSetupRun),
 }
 
 impl EventSink for DaemonEventSink {
@@ -141,6 +158,22 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.tx.send(DaemonEvent::TerminalExit(event));
     }
+
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished) {
+        let _ = self.tx.send(DaemonEvent::GitHubChecksFinished(event));
+    }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        let _ = self.tx.send(DaemonEvent::GitStatusChanged(event));
+    }
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        let _ = self.tx.send(DaemonEvent::WorktreeSetupOutput(event));
+    }
+
+    fn emit_worktree_setup_status(&self, event: WorktreeSetupRun) {
+        let _ = self.tx.send(DaemonEvent::WorktreeSetupStatus(event));
+    }
 }
 
 struct DaemonConfig {
@@ -156,9 +189,15 @@ struct DaemonState {
     storage_path: PathBuf,
     settings_path: PathBuf,
     app_settings: Mutex<AppSettings>,
+    settings_sync: Mutex<settings_sync_core::SettingsSyncState>,
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     daemon_binary_path: Option<String>,
+    github_checks_watchers: git_ui_core::GitHubChecksWatchers,
+    git_status_watchers: git_ui_core::GitStatusWatchers,
+    worktree_setup_runs: workspaces_core::WorktreeSetupRuns,
+    /// Storage files that were unreadable at startup and how they were recovered.
+    storage_recoveries: Vec<StorageRecovery>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -171,8 +210,17 @@ impl DaemonState {
     fn load(config: &DaemonConfig, event_sink: DaemonEventSink) -> Self {
         let storage_path = config.data_dir.join("workspaces.json");
         let settings_path = config.data_dir.join("settings.json");
-        let workspaces = read_workspaces(&storage_path).unwrap_or_default();
-        let app_settings = read_settings(&settings_path).unwrap_or_default();
+        config_history_core::init_config_history(config.data_dir.join("config-history"));
+        agent_templates_core::init_agent_templates(config.data_dir.join("agent-templates"));
+        let (workspaces, workspaces_recovery) = load_workspaces(&storage_path);
+        let (app_settings, settings_recovery) = load_settings(&settings_path);
+        let storage_recoveries: Vec<_> = workspaces_recovery
+            .into_iter()
+            .chain(settings_recovery)
+            .collect();
+        for recovery in &storage_recoveries {
+            eprintln!("{}", recovery.summary());
+        }
         let daemon_binary_path = std::env::current_exe()
             .ok()
             .and_then(|path| path.to_str().map(str::to_string));
@@ -183,9 +231,14 @@ impl DaemonState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            settings_sync: Mutex::new(settings_sync_core::SettingsSyncState::default()),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_binary_path,
+            github_checks_watchers: Mutex::new(HashMap::new()),
+            git_status_watchers: Mutex::new(HashMap::new()),
+            worktree_setup_runs: workspaces_core::WorktreeSetupRuns::default(),
+            storage_recoveries,
         }
     }
 
@@ -279,6 +332,7 @@ impl DaemonState {
         url: String,
         destination_path: String,
         target_folder_name: Option<String>,
+        checkout: Option<WorkspaceCheckoutOptions>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
@@ -286,6 +340,7 @@ impl DaemonState {
             url,
             destination_path,
             target_folder_name,
+            checkout,
             &self.workspaces,
             &self.sessions,
             &self.app_settings,
@@ -310,6 +365,33 @@ impl DaemonState {
         branch: String,
         name: Option<String>,
         copy_agents_md: bool,
+        base_ref: Option<String>,
+        detach: bool,
+        client_version: String,
+    ) -> Result<WorkspaceInfo, String> {
+        let workspace = self
+            .create_worktree(
+                parent_id,
+                branch,
+                name,
+                copy_agents_md,
+                base_ref,
+                detach,
+                client_version,
+            )
+            .await?;
+        self.start_worktree_setup_after_add(&workspace).await;
+        Ok(workspace)
+    }
+
+    async fn create_worktree(
+        &self,
+        parent_id: String,
+        branch: String,
+        name: Option<String>,
+        copy_agents_md: bool,
+        base_ref: Option<String>,
+        detach: bool,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
@@ -318,6 +400,8 @@ impl DaemonState {
             branch,
             name,
             copy_agents_md,
+            base_ref,
+            detach,
             &self.data_dir,
             &self.workspaces,
             &self.sessions,
@@ -352,12 +436,43 @@ impl DaemonState {
         .await
     }
 
+    async fn start_issue_agent(
+        &self,
+        workspace_id: String,
+        issue_number: u64,
+        model: Option<String>,
+        effort: Option<String>,
+        access_mode: Option<String>,
+        client_version: String,
+    ) -> Result<IssueAgentStart, String> {
+        issue_agent_core::start_issue_agent_core(
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.storage_path,
+            workspace_id,
+            issue_number,
+            model,
+            effort,
+            access_mode,
+            |parent_id, branch, name| {
+                self.add_worktree(parent_id, branch, name, true, None, false, client_version)
+            },
+        )
+        .await
+    }
+
     async fn worktree_setup_status(
         &self,
         workspace_id: String,
     ) -> Result<WorktreeSetupStatus, String> {
-        workspaces_core::worktree_setup_status_core(&self.workspaces, &workspace_id, &self.data_dir)
-            .await
+        workspaces_core::worktree_setup_status_core(
+            &self.workspaces,
+            &self.worktree_setup_runs,
+            &workspace_id,
+            &self.data_dir,
+        )
+        .await
     }
 
     async fn worktree_setup_mark_ran(&self, workspace_id: String) -> Result<(), String> {
@@ -369,6 +484,44 @@ impl DaemonState {
         .await
     }
 
+    async fn worktree_setup_run(
+        &self,
+        workspace_id: String,
+        force: bool,
+        timeout_secs: Option<u64>,
+    ) -> Result<Option<WorktreeSetupRun>, String> {
+        workspaces_core::start_worktree_setup_core(
+            &self.workspaces,
+            &self.worktree_setup_runs,
+            &self.data_dir,
+            self.event_sink.clone(),
+            workspace_id,
+            force,
+            timeout_secs,
+        )
+        .await
+    }
+
+    /// Starts the setup script of a freshly added worktree or clone. Failures
+    /// are logged rather than failing the add.
+    async fn start_worktree_setup_after_add(&self, workspace: &WorkspaceInfo) {
+        if let Err(err) = self
+            .worktree_setup_run(workspace.id.clone(), false, None)
+            .await
+        {
+            eprintln!("Failed to start setup script for {}: {err}", workspace.id);
+        }
+    }
+
+    async fn worktree_setup_cancel(&self, workspace_id: String) -> bool {
+        workspaces_core::cancel_worktree_setup_core(&self.worktree_setup_runs, &workspace_id).await
+    }
+
+    async fn worktree_setup_log(&self, workspace_id: String) -> Result<String, String> {
+        workspaces_core::worktree_setup_log_core(&self.workspaces, &self.data_dir, &workspace_id)
+            .await
+    }
+
     async fn remove_workspace(&self, id: String) -> Result<(), String> {
         workspaces_core::remove_workspace_core(
             id,
@@ -407,6 +560,152 @@ impl DaemonState {
         .await
     }
 
+    async fn export_workspaces(
+        &self,
+        root: String,
+        path: String,
+    ) -> Result<WorkspaceExportResult, String> {
+        workspaces_core::export_workspaces_core(root, path, &self.workspaces, &self.app_settings)
+            .await
+    }
+
+    async fn import_workspaces(
+        &self,
+        root: String,
+        path: String,
+        client_version: String,
+    ) -> Result<WorkspaceImportResult, String> {
+        let client_version = client_version.clone();
+        workspaces_core::import_workspaces_core(
+            root,
+            path,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.storage_path,
+            &self.settings_path,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn list_codex_home_profiles(&self) -> Vec<CodexHomeProfileInfo> {
+        codex_home_core::list_codex_home_profiles_core(&self.workspaces, &self.app_settings).await
+    }
+
+    async fn create_codex_home_profile(
+        &self,
+        name: String,
+        path: Option<String>,
+    ) -> Result<CodexHomeProfileInfo, String> {
+        codex_home_core::create_codex_home_profile_core(
+            name,
+            path,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
+    async fn switch_codex_home_profile(
+        &self,
+        scope: CodexHomeScope,
+        target_id: String,
+        profile_id: Option<String>,
+        client_version: String,
+    ) -> Result<CodexHomeSwitchResult, String> {
+        codex_home_core::switch_codex_home_profile_core(
+            scope,
+            target_id,
+            profile_id,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.settings_path,
+            &self.storage_path,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn delete_codex_home_profile(
+        &self,
+        profile_id: String,
+        client_version: String,
+    ) -> Result<CodexHomeSwitchResult, String> {
+        codex_home_core::delete_codex_home_profile_core(
+            profile_id,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.settings_path,
+            &self.storage_path,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn worktree_gc_report(&self) -> Result<WorktreeGcReport, String> {
+        workspaces_core::worktree_gc_report_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.data_dir,
+        )
+        .await
+    }
+
+    async fn worktree_gc_apply(
+        &self,
+        ids: Vec<String>,
+        allow_dirty: bool,
+    ) -> Result<WorktreeGcApplyResult, String> {
+        workspaces_core::worktree_gc_apply_core(
+            ids,
+            allow_dirty,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.data_dir,
+            &self.storage_path,
+            |root, args| {
+                workspaces_core::run_git_command_unit(root, args, git_core::run_git_command_owned)
+            },
+            |error| git_core::is_missing_worktree_error(error),
+            |path| {
+                std::fs::remove_dir_all(path)
+                    .map_err(|err| format!("Failed to remove worktree folder: {err}"))
+            },
+        )
+        .await
+    }
+
     async fn rename_worktree(
         &self,
         id: String,
@@ -581,12 +880,25 @@ impl DaemonState {
     }
 
     async fn get_app_settings(&self) -> AppSettings {
-        settings_core::get_app_settings_core(&self.app_settings).await
+        settings_core::get_app_settings_core(&self.app_settings, &self.settings_sync).await
     }
 
     async fn update_app_settings(&self, settings: AppSettings) -> Result<AppSettings, String> {
-        settings_core::update_app_settings_core(settings, &self.app_settings, &self.settings_path)
-            .await
+        settings_core::update_app_settings_core(
+            settings,
+            &self.app_settings,
+            &self.settings_path,
+            &self.settings_sync,
+        )
+        .await
+    }
+
+    async fn get_settings_sync_report(&self) -> settings_sync_core::SettingsSyncReport {
+        settings_sync_core::settings_sync_report_core(&self.settings_sync).await
+    }
+
+    fn get_storage_recovery_report(&self) -> Vec<StorageRecovery> {
+        self.storage_recoveries.clone()
     }
 
     async fn set_codex_feature_flag(
@@ -601,29 +913,196 @@ impl DaemonState {
         agents_config_core::get_agents_settings_core()
     }
 
-    async fn get_mcp_settings(&self) -> Result<mcp_config_core::McpSettingsDto, String> {
-        mcp_config_core::get_mcp_settings_core()
+    async fn get_mcp_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> Result<mcp_config_core::McpSettingsDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            workspace_id.as_deref(),
+        )
+        .await?;
+        mcp_config_core::get_mcp_settings_core(&target)
     }
 
     async fn create_mcp_server(
         &self,
         input: mcp_config_core::CreateMcpServerInput,
     ) -> Result<mcp_config_core::McpSettingsDto, String> {
-        mcp_config_core::create_mcp_server_core(input)
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        mcp_config_core::create_mcp_server_core(&target, input)
     }
 
     async fn update_mcp_server(
         &self,
         input: mcp_config_core::UpdateMcpServerInput,
     ) -> Result<mcp_config_core::McpSettingsDto, String> {
-        mcp_config_core::update_mcp_server_core(input)
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        mcp_config_core::update_mcp_server_core(&target, input)
     }
 
     async fn delete_mcp_server(
         &self,
         input: mcp_config_core::DeleteMcpServerInput,
     ) -> Result<mcp_config_core::McpSettingsDto, String> {
-        mcp_config_core::delete_mcp_server_core(input)
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        mcp_config_core::delete_mcp_server_core(&target, input)
+    }
+
+    async fn test_mcp_server(
+        &self,
+        input: mcp_config_core::McpServerDto,
+    ) -> Result<mcp_probe_core::McpProbeResult, String> {
+        mcp_probe_core::test_mcp_server_core(input).await
+    }
+
+    async fn get_config_profiles(
+        &self,
+        workspace_id: Option<String>,
+    ) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            workspace_id.as_deref(),
+        )
+        .await?;
+        config_profiles_core::get_config_profiles_core(&target)
+    }
+
+    async fn create_config_profile(
+        &self,
+        input: config_profiles_core::CreateConfigProfileInput,
+    ) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        config_profiles_core::create_config_profile_core(&target, input)
+    }
+
+    async fn update_config_profile(
+        &self,
+        input: config_profiles_core::UpdateConfigProfileInput,
+    ) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        config_profiles_core::update_config_profile_core(&target, input)
+    }
+
+    async fn delete_config_profile(
+        &self,
+        input: config_profiles_core::DeleteConfigProfileInput,
+    ) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        config_profiles_core::delete_config_profile_core(&target, input)
+    }
+
+    async fn get_model_providers(
+        &self,
+        workspace_id: Option<String>,
+    ) -> Result<model_providers_core::ModelProvidersDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            workspace_id.as_deref(),
+        )
+        .await?;
+        model_providers_core::get_model_providers_core(&target)
+    }
+
+    async fn create_model_provider(
+        &self,
+        input: model_providers_core::CreateModelProviderInput,
+    ) -> Result<model_providers_core::ModelProvidersDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        model_providers_core::create_model_provider_core(&target, input)
+    }
+
+    async fn update_model_provider(
+        &self,
+        input: model_providers_core::UpdateModelProviderInput,
+    ) -> Result<model_providers_core::ModelProvidersDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        model_providers_core::update_model_provider_core(&target, input)
+    }
+
+    async fn delete_model_provider(
+        &self,
+        input: model_providers_core::DeleteModelProviderInput,
+    ) -> Result<model_providers_core::ModelProvidersDto, String> {
+        let target = project_config_core::resolve_config_target_core(
+            &self.workspaces,
+            input.workspace_id.as_deref(),
+        )
+        .await?;
+        model_providers_core::delete_model_provider_core(&target, input)
+    }
+
+    async fn test_model_provider(
+        &self,
+        input: model_providers_core::ModelProviderDto,
+    ) -> Result<model_provider_probe_core::ModelProviderProbeResult, String> {
+        model_provider_probe_core::test_model_provider_core(input).await
+    }
+
+    async fn config_preview_write(
+        &self,
+        target: config_history_core::ConfigFileTarget,
+        content: String,
+    ) -> Result<config_history_core::ConfigWritePreview, String> {
+        config_history_core::preview_config_write_core(&self.workspaces, target, content).await
+    }
+
+    async fn config_history_list(
+        &self,
+        target: Option<config_history_core::ConfigFileTarget>,
+    ) -> Result<Vec<config_history_core::ConfigHistoryEntry>, String> {
+        config_history_core::config_history_list_core(&self.workspaces, target).await
+    }
+
+    fn config_history_restore(
+        &self,
+        id: String,
+    ) -> Result<config_history_core::ConfigHistoryRestore, String> {
+        config_history_core::config_history_restore_core(&id)
+    }
+
+    async fn get_effective_config(
+        &self,
+        workspace_id: String,
+    ) -> Result<project_config_core::EffectiveConfig, String> {
+        project_config_core::get_effective_config_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+        )
+        .await
     }
 
     async fn set_agents_core_settings(
@@ -654,6 +1133,38 @@ impl DaemonState {
         agents_config_core::delete_agent_core(input)
     }
 
+    fn list_agent_templates(&self) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+        agent_templates_core::list_agent_templates_core()
+    }
+
+    fn save_agent_template(
+        &self,
+        input: agent_templates_core::SaveAgentTemplateInput,
+    ) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+        agent_templates_core::save_agent_template_core(input)
+    }
+
+    fn delete_agent_template(
+        &self,
+        id: String,
+    ) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+        agent_templates_core::delete_agent_template_core(&id)
+    }
+
+    fn export_agent(
+        &self,
+        name: String,
+    ) -> Result<agent_templates_core::AgentBundleExport, String> {
+        agent_templates_core::export_agent_core(&name)
+    }
+
+    fn import_agent(
+        &self,
+        input: agent_templates_core::ImportAgentInput,
+    ) -> Result<agents_config_core::AgentsSettingsDto, String> {
+        agent_templates_core::import_agent_core(input)
+    }
+
     async fn read_agent_config_toml(&self, agent_name: String) -> Result<String, String> {
         agents_config_core::read_agent_config_toml_core(agent_name.as_str())
     }
@@ -706,6 +1217,37 @@ impl DaemonState {
         files_core::file_write_core(&self.workspaces, scope, kind, workspace_id, content).await
     }
 
+    async fn list_workspace_agents_files(
+        &self,
+        workspace_id: String,
+    ) -> Result<Vec<project_config_core::WorkspaceAgentsFile>, String> {
+        project_config_core::list_workspace_agents_files_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn read_workspace_agents_file(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<file_io::TextFileResponse, String> {
+        project_config_core::read_workspace_agents_file_core(&self.workspaces, workspace_id, path)
+            .await
+    }
+
+    async fn write_workspace_agents_file(
+        &self,
+        workspace_id: String,
+        path: String,
+        content: String,
+    ) -> Result<(), String> {
+        project_config_core::write_workspace_agents_file_core(
+            &self.workspaces,
+            workspace_id,
+            path,
+            content,
+        )
+        .await
+    }
+
     async fn start_thread(&self, workspace_id: String) -> Result<Value, String> {
         codex_core::start_thread_core(&self.sessions, &self.workspaces, workspace_id).await
     }
@@ -913,7 +1455,13 @@ impl DaemonState {
     }
 
     async fn account_read(&self, workspace_id: String) -> Result<Value, String> {
-        codex_core::account_read_core(&self.sessions, &self.workspaces, workspace_id).await
+        codex_core::account_read_core(
+            &self.sessions,
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+        )
+        .await
     }
 
     async fn codex_login(&self, workspace_id: String) -> Result<Value, String> {
@@ -960,11 +1508,17 @@ impl DaemonState {
         workspace_id: String,
         command: Vec<String>,
     ) -> Result<Value, String> {
-        codex_core::remember_approval_rule_core(&self.workspaces, workspace_id, command).await
+        codex_core::remember_approval_rule_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            command,
+        )
+        .await
     }
 
     async fn get_config_model(&self, workspace_id: String) -> Result<Value, String> {
-        codex_core::get_config_model_core(&self.workspaces, workspace_id).await
+        codex_core::get_config_model_core(&self.workspaces, &self.app_settings, workspace_id).await
     }
 
     async fn add_clone(
@@ -974,7 +1528,7 @@ impl DaemonState {
         copy_name: String,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
-        workspaces_core::add_clone_core(
+        let workspace = workspaces_core::add_clone_core(
             source_workspace_id,
             copy_name,
             copies_folder,
@@ -993,11 +1547,17 @@ impl DaemonState {
                 )
             },
         )
-        .await
+        .await?;
+        self.start_worktree_setup_after_add(&workspace).await;
+        Ok(workspace)
     }
 
-    async fn apply_worktree_changes(&self, workspace_id: String) -> Result<(), String> {
-        workspaces_core::apply_worktree_changes_core(&self.workspaces, workspace_id).await
+    async fn apply_worktree_changes(
+        &self,
+        workspace_id: String,
+        options: WorktreeApplyOptions,
+    ) -> Result<WorktreeApplyResult, String> {
+        workspaces_core::apply_worktree_changes_core(&self.workspaces, workspace_id, options).await
     }
 
     async fn open_workspace_in(
@@ -1095,105 +1655,391 @@ impl DaemonState {
         git_ui_core::get_git_remote_core(&self.workspaces, workspace_id).await
     }
 
-    async fn stage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::stage_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn stage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
+        git_ui_core::stage_git_file_core(&self.workspaces, workspace_id, path).await
+    }
+
+    async fn stage_git_all(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::stage_git_all_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn unstage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
+        git_ui_core::unstage_git_file_core(&self.workspaces, workspace_id, path).await
+    }
+
+    async fn revert_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
+        git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path).await
+    }
+
+    async fn revert_git_all(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
+        git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
+    }
+
+    async fn push_git(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::push_git_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn pull_git(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::pull_git_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn fetch_git(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::fetch_git_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn sync_git(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::sync_git_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn get_workspace_forge(&self, workspace_id: String) -> Result<ForgeInfo, String> {
+        git_ui_core::get_workspace_forge_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn get_github_issues(
+        &self,
+        workspace_id: String,
+        query: GitHubListQuery,
+    ) -> Result<GitHubIssuesResponse, String> {
+        git_ui_core::get_github_issues_core(&self.workspaces, workspace_id, query).await
+    }
+
+    async fn get_github_pull_requests(
+        &self,
+        workspace_id: String,
+        query: GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        git_ui_core::get_github_pull_requests_core(&self.workspaces, workspace_id, query).await
+    }
+
+    async fn get_github_pull_request_diff(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubPullRequestDiff>, String> {
+        git_ui_core::get_github_pull_request_diff_core(&self.workspaces, workspace_id, pr_number)
+            .await
+    }
+
+    async fn get_github_pull_request_comments(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        git_ui_core::get_github_pull_request_comments_core(
+            &self.workspaces,
+            workspace_id,
+            pr_number,
+        )
+        .await
+    }
+
+    async fn checkout_github_pull_request(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+    ) -> Result<(), String> {
+        git_ui_core::checkout_github_pull_request_core(&self.workspaces, workspace_id, pr_number)
+            .await
+    }
+
+    async fn list_git_branches(&self, workspace_id: String) -> Result<Value, String> {
+        git_ui_core::list_git_branches_core(&self.workspaces, workspace_id).await
     }
 
-    async fn stage_git_all(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::stage_git_all_core(&self.workspaces, workspace_id).await
+    async fn checkout_git_branch(&self, workspace_id: String, name: String) -> Result<(), String> {
+        git_ui_core::checkout_git_branch_core(&self.workspaces, workspace_id, name).await
     }
 
-    async fn unstage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::unstage_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn create_git_branch(&self, workspace_id: String, name: String) -> Result<(), String> {
+        git_ui_core::create_git_branch_core(&self.workspaces, workspace_id, name).await
     }
 
-    async fn revert_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn list_git_tags(&self, workspace_id: String) -> Result<Vec<GitTagInfo>, String> {
+        git_ui_core::list_git_tags_core(&self.workspaces, workspace_id).await
     }
 
-    async fn revert_git_all(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
+    async fn create_git_tag(
+        &self,
+        workspace_id: String,
+        name: String,
+        message: Option<String>,
+        target: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::create_git_tag_core(&self.workspaces, workspace_id, name, message, target)
+            .await
     }
 
-    async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
-        git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
+    async fn delete_git_tag(
+        &self,
+        workspace_id: String,
+        name: String,
+        remote: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::delete_git_tag_core(&self.workspaces, workspace_id, name, remote).await
     }
 
-    async fn push_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::push_git_core(&self.workspaces, workspace_id).await
+    async fn push_git_tags(
+        &self,
+        workspace_id: String,
+        name: Option<String>,
+        remote: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::push_git_tags_core(&self.workspaces, workspace_id, name, remote).await
     }
 
-    async fn pull_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::pull_git_core(&self.workspaces, workspace_id).await
+    async fn generate_release_notes(
+        &self,
+        workspace_id: String,
+        tag: String,
+        model_id: Option<String>,
+    ) -> Result<String, String> {
+        let range =
+            git_ui_core::release_commit_range_core(&self.workspaces, workspace_id.clone(), tag)
+                .await?;
+        git_ui_core::release_notes_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            &range,
+            None,
+            model_id.as_deref(),
+            |workspace_id, thread_id| {
+                emit_background_thread_hide(&self.event_sink, workspace_id, thread_id);
+            },
+        )
+        .await
     }
 
-    async fn fetch_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::fetch_git_core(&self.workspaces, workspace_id).await
+    async fn create_github_release(
+        &self,
+        workspace_id: String,
+        tag: String,
+        title: Option<String>,
+        notes: Option<String>,
+        draft: bool,
+        model_id: Option<String>,
+    ) -> Result<GitHubRelease, String> {
+        let range =
+            git_ui_core::release_commit_range_core(&self.workspaces, workspace_id.clone(), tag)
+                .await?;
+        let notes = git_ui_core::release_notes_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id.clone(),
+            &range,
+            notes,
+            model_id.as_deref(),
+            |workspace_id, thread_id| {
+                emit_background_thread_hide(&self.event_sink, workspace_id, thread_id);
+            },
+        )
+        .await?;
+        git_ui_core::create_github_release_core(
+            &self.workspaces,
+            workspace_id,
+            range,
+            title,
+            notes,
+            draft,
+        )
+        .await
     }
 
-    async fn sync_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::sync_git_core(&self.workspaces, workspace_id).await
+    async fn generate_github_pull_request_content(
+        &self,
+        workspace_id: String,
+        base: Option<String>,
+        model_id: Option<String>,
+    ) -> Result<codex_aux_core::GeneratedPullRequestContent, String> {
+        let diff = git_ui_core::get_github_pull_request_branch_diff_core(
+            &self.workspaces,
+            workspace_id.clone(),
+            base,
+        )
+        .await?;
+        codex_aux_core::generate_pull_request_content_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            &diff,
+            model_id.as_deref(),
+            |workspace_id, thread_id| {
+                emit_background_thread_hide(&self.event_sink, workspace_id, thread_id);
+            },
+        )
+        .await
     }
 
-    async fn get_github_issues(
+    async fn create_github_pull_request(
         &self,
         workspace_id: String,
-    ) -> Result<GitHubIssuesResponse, String> {
-        git_ui_core::get_github_issues_core(&self.workspaces, workspace_id).await
+        title: Option<String>,
+        body: Option<String>,
+        base: Option<String>,
+        draft: bool,
+        reviewers: Vec<String>,
+        generate: bool,
+        model_id: Option<String>,
+    ) -> Result<GitHubPullRequest, String> {
+        let title = title.filter(|value| !value.trim().is_empty());
+        let (title, body) = if generate || title.is_none() {
+            let generated = self
+                .generate_github_pull_request_content(workspace_id.clone(), base.clone(), model_id)
+                .await?;
+            (
+                title.unwrap_or(generated.title),
+                body.unwrap_or(generated.body),
+            )
+        } else {
+            (title.unwrap_or_default(), body.unwrap_or_default())
+        };
+        git_ui_core::create_github_pull_request_core(
+            &self.workspaces,
+            workspace_id,
+            title,
+            body,
+            base,
+            draft,
+            reviewers,
+        )
+        .await
     }
 
-    async fn get_github_pull_requests(
+    async fn update_github_pull_request(
         &self,
         workspace_id: String,
-    ) -> Result<GitHubPullRequestsResponse, String> {
-        git_ui_core::get_github_pull_requests_core(&self.workspaces, workspace_id).await
+        pr_number: u64,
+        title: Option<String>,
+        body: Option<String>,
+    ) -> Result<GitHubPullRequest, String> {
+        git_ui_core::update_github_pull_request_core(
+            &self.workspaces,
+            workspace_id,
+            pr_number,
+            title,
+            body,
+        )
+        .await
     }
 
-    async fn get_github_pull_request_diff(
+    async fn mark_github_pull_request_ready(
         &self,
         workspace_id: String,
         pr_number: u64,
-    ) -> Result<Vec<GitHubPullRequestDiff>, String> {
-        git_ui_core::get_github_pull_request_diff_core(&self.workspaces, workspace_id, pr_number)
+    ) -> Result<GitHubPullRequest, String> {
+        git_ui_core::mark_github_pull_request_ready_core(&self.workspaces, workspace_id, pr_number)
             .await
     }
 
-    async fn get_github_pull_request_comments(
+    async fn post_github_pull_request_review(
         &self,
         workspace_id: String,
         pr_number: u64,
-    ) -> Result<Vec<GitHubPullRequestComment>, String> {
-        git_ui_core::get_github_pull_request_comments_core(
+        body: Option<String>,
+        event: GitHubPullRequestReviewEvent,
+        comments: Vec<GitHubPullRequestReviewComment>,
+        review_thread_id: Option<String>,
+    ) -> Result<GitHubPullRequestReviewResult, String> {
+        let codex_review = match review_thread_id {
+            Some(thread_id) => Some(
+                codex_core::latest_review_output_core(
+                    &self.sessions,
+                    workspace_id.clone(),
+                    thread_id,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+        git_ui_core::post_github_pull_request_review_core(
             &self.workspaces,
             workspace_id,
             pr_number,
+            body,
+            event,
+            comments,
+            codex_review,
         )
         .await
     }
 
-    async fn checkout_github_pull_request(
+    async fn get_github_checks(
         &self,
         workspace_id: String,
-        pr_number: u64,
-    ) -> Result<(), String> {
-        git_ui_core::checkout_github_pull_request_core(&self.workspaces, workspace_id, pr_number)
+        git_ref: Option<String>,
+        pr_number: Option<u64>,
+    ) -> Result<GitHubChecksResponse, String> {
+        git_ui_core::get_github_checks_core(&self.workspaces, workspace_id, git_ref, pr_number)
             .await
     }
 
-    async fn list_git_branches(&self, workspace_id: String) -> Result<Value, String> {
-        git_ui_core::list_git_branches_core(&self.workspaces, workspace_id).await
+    async fn watch_github_checks(
+        &self,
+        workspace_id: String,
+        git_ref: Option<String>,
+        pr_number: Option<u64>,
+        thread_id: Option<String>,
+        interval_secs: Option<u64>,
+    ) -> Result<(), String> {
+        git_ui_core::watch_github_checks_core(
+            &self.workspaces,
+            &self.github_checks_watchers,
+            self.event_sink.clone(),
+            workspace_id,
+            git_ref,
+            pr_number,
+            thread_id,
+            interval_secs,
+        )
+        .await
     }
 
-    async fn checkout_git_branch(&self, workspace_id: String, name: String) -> Result<(), String> {
-        git_ui_core::checkout_git_branch_core(&self.workspaces, workspace_id, name).await
+    async fn unwatch_github_checks(
+        &self,
+        workspace_id: String,
+        git_ref: Option<String>,
+        pr_number: Option<u64>,
+    ) -> bool {
+        git_ui_core::unwatch_github_checks_core(
+            &self.github_checks_watchers,
+            workspace_id,
+            git_ref,
+            pr_number,
+        )
+        .await
     }
 
-    async fn create_git_branch(&self, workspace_id: String, name: String) -> Result<(), String> {
-        git_ui_core::create_git_branch_core(&self.workspaces, workspace_id, name).await
+    async fn watch_git_status(
+        &self,
+        workspace_id: String,
+        debounce_ms: Option<u64>,
+    ) -> Result<(), String> {
+        git_ui_core::watch_git_status_core(
+            &self.workspaces,
+            &self.git_status_watchers,
+            self.event_sink.clone(),
+            workspace_id,
+            debounce_ms,
+        )
+        .await
+    }
+
+    async fn unwatch_git_status(&self, workspace_id: String) -> bool {
+        git_ui_core::unwatch_git_status_core(&self.git_status_watchers, workspace_id).await
     }
 
     async fn prompts_list(&self, workspace_id: String) -> Result<Vec<CustomPromptEntry>, String> {
-        prompts_core::prompts_list_core(&self.workspaces, &self.settings_path, workspace_id).await
+        prompts_core::prompts_list_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+        )
+        .await
     }
 
     async fn prompts_workspace_dir(&self, workspace_id: String) -> Result<String, String> {
@@ -1206,7 +2052,8 @@ impl DaemonState {
     }
 
     async fn prompts_global_dir(&self, workspace_id: String) -> Result<String, String> {
-        prompts_core::prompts_global_dir_core(&self.workspaces, workspace_id).await
+        prompts_core::prompts_global_dir_core(&self.workspaces, &self.app_settings, workspace_id)
+            .await
     }
 
     async fn prompts_create(
@@ -1216,16 +2063,19 @@ impl DaemonState {
         name: String,
         description: Option<String>,
         argument_hint: Option<String>,
+        variables: Option<Vec<PromptVariable>>,
         content: String,
     ) -> Result<CustomPromptEntry, String> {
         prompts_core::prompts_create_core(
             &self.workspaces,
+            &self.app_settings,
             &self.settings_path,
             workspace_id,
             scope,
             name,
             description,
             argument_hint,
+            variables,
             content,
         )
         .await
@@ -1238,24 +2088,33 @@ impl DaemonState {
         name: String,
         description: Option<String>,
         argument_hint: Option<String>,
+        variables: Option<Vec<PromptVariable>>,
         content: String,
     ) -> Result<CustomPromptEntry, String> {
         prompts_core::prompts_update_core(
             &self.workspaces,
+            &self.app_settings,
             &self.settings_path,
             workspace_id,
             path,
             name,
             description,
             argument_hint,
+            variables,
             content,
         )
         .await
     }
 
     async fn prompts_delete(&self, workspace_id: String, path: String) -> Result<(), String> {
-        prompts_core::prompts_delete_core(&self.workspaces, &self.settings_path, workspace_id, path)
-            .await
+        prompts_core::prompts_delete_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+            path,
+        )
+        .await
     }
 
     async fn prompts_move(
@@ -1266,14 +2125,126 @@ impl DaemonState {
     ) -> Result<CustomPromptEntry, String> {
         prompts_core::prompts_move_core(
             &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+            path,
+            scope,
+        )
+        .await
+    }
+
+    async fn prompts_history(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<Vec<PromptVersion>, String> {
+        prompts_core::prompts_history_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+            path,
+        )
+        .await
+    }
+
+    async fn prompts_restore_version(
+        &self,
+        workspace_id: String,
+        path: String,
+        commit: String,
+    ) -> Result<CustomPromptEntry, String> {
+        prompts_core::prompts_restore_version_core(
+            &self.workspaces,
+            &self.app_settings,
             &self.settings_path,
             workspace_id,
             path,
+            commit,
+        )
+        .await
+    }
+
+    async fn prompts_team_repo(&self) -> Result<TeamPromptRepo, String> {
+        prompts_core::prompts_team_repo_core(&self.settings_path).await
+    }
+
+    async fn prompts_team_repo_set(&self, url: Option<String>) -> Result<TeamPromptRepo, String> {
+        prompts_core::prompts_team_repo_set_core(&self.settings_path, url).await
+    }
+
+    async fn prompts_team_repo_sync(&self) -> Result<TeamPromptRepo, String> {
+        prompts_core::prompts_team_repo_sync_core(&self.settings_path).await
+    }
+
+    async fn skills_local_list(&self, workspace_id: String) -> Result<Vec<SkillEntry>, String> {
+        skills_core::skills_local_list_core(&self.workspaces, &self.app_settings, workspace_id)
+            .await
+    }
+
+    async fn skills_create(
+        &self,
+        workspace_id: String,
+        scope: String,
+        content: String,
+    ) -> Result<SkillEntry, String> {
+        skills_core::skills_create_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
             scope,
+            content,
+        )
+        .await
+    }
+
+    async fn skills_update(
+        &self,
+        workspace_id: String,
+        path: String,
+        content: String,
+    ) -> Result<SkillEntry, String> {
+        skills_core::skills_update_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            path,
+            content,
+        )
+        .await
+    }
+
+    async fn skills_set_enabled(
+        &self,
+        workspace_id: String,
+        path: String,
+        enabled: bool,
+    ) -> Result<SkillEntry, String> {
+        skills_core::skills_set_enabled_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            path,
+            enabled,
         )
         .await
     }
 
+    async fn skills_delete(&self, workspace_id: String, path: String) -> Result<(), String> {
+        skills_core::skills_delete_core(&self.workspaces, &self.app_settings, workspace_id, path)
+            .await
+    }
+
+    async fn skills_install(
+        &self,
+        workspace_id: String,
+        input: InstallSkillInput,
+    ) -> Result<SkillEntry, String> {
+        skills_core::skills_install_core(&self.workspaces, &self.app_settings, workspace_id, input)
+            .await
+    }
+
     async fn codex_doctor(
         &self,
         codex_bin: Option<String>,
@@ -1369,7 +2340,13 @@ impl DaemonState {
         days: Option<u32>,
         workspace_path: Option<String>,
     ) -> Result<LocalUsageSnapshot, String> {
-        local_usage_core::local_usage_snapshot_core(&self.workspaces, days, workspace_path).await
+        local_usage_core::local_usage_snapshot_core(
+            &self.workspaces,
+            &self.app_settings,
+            days,
+            workspace_path,
+        )
+        .await
     }
 
     async fn local_thread_usage_snapshot(
@@ -1379,6 +2356,7 @@ impl DaemonState {
     ) -> Result<LocalThreadUsageSnapshot, String> {
         thread_usage_core::local_thread_usage_snapshot_core(
             &self.workspaces,
+            &self.app_settings,
             thread_ids,
             workspace_path,
         )
@@ -1675,9 +2653,14 @@ mod tests {
             storage_path: data_dir.join("workspaces.json"),
             settings_path: data_dir.join("settings.json"),
             app_settings: Mutex::new(AppSettings::default()),
+            settings_sync: Mutex::new(settings_sync_core::SettingsSyncState::default()),
             event_sink: DaemonEventSink { tx },
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
+            github_checks_watchers: Mutex::new(HashMap::new()),
+            git_status_watchers: Mutex::new(HashMap::new()),
+            worktree_setup_runs: workspaces_core::WorktreeSetupRuns::default(),
+            storage_recoveries: Vec::new(),
         }
     }
 
@@ -1733,6 +2716,7 @@ mod tests {
 
         Arc::new(WorkspaceSession {
             codex_args: None,
+            codex_home: None,
             child: Mutex::new(child),
             stdin: Mutex::new(stdin),
             pending: Mutex::new(HashMap::new()),
@@ -1851,6 +2835,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn rpc_get_storage_recovery_report_lists_files_recovered_at_load() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-storage-recovery");
+            std::fs::write(tmp.join("settings.json"), "{ not json").expect("write settings");
+            let (tx, _rx) = broadcast::channel::<DaemonEvent>(32);
+            let config = DaemonConfig {
+                listen: "127.0.0.1:0".parse().expect("listen addr"),
+                token: None,
+                data_dir: tmp.clone(),
+            };
+            let state = DaemonState::load(&config, DaemonEventSink { tx });
+
+            let result = rpc::handle_rpc_request(
+                &state,
+                "get_storage_recovery_report",
+                json!({}),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("get_storage_recovery_report should succeed");
+
+            let recoveries = result.as_array().expect("array result");
+            assert_eq!(recoveries.len(), 1);
+            assert!(recoveries[0]
+                .get("file")
+                .and_then(Value::as_str)
+                .is_some_and(|file| file.ends_with("settings.json")));
+            assert!(recoveries[0]
+                .get("recoveredFrom")
+                .is_some_and(Value::is_null));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn rpc_daemon_info_reports_identity() {
         run_async_test(async {
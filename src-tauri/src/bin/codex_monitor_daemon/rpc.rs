@@ -10,6 +10,8 @@ mod dispatcher;
 mod git;
 #[path = "rpc/prompts.rs"]
 mod prompts;
+#[path = "rpc/skills.rs"]
+mod skills;
 #[path = "rpc/workspace.rs"]
 mod workspace;
 
@@ -49,6 +51,22 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::GitHubChecksFinished(payload) => json!({
+            "method": "github-checks-finished",
+            "params": payload,
+        }),
+        DaemonEvent::GitStatusChanged(payload) => json!({
+            "method": "git-status-changed",
+            "params": payload,
+        }),
+        DaemonEvent::WorktreeSetupOutput(payload) => json!({
+            "method": "worktree-setup-output",
+            "params": payload,
+        }),
+        DaemonEvent::WorktreeSetupStatus(payload) => json!({
+            "method": "worktree-setup-status",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
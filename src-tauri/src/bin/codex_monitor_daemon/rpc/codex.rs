@@ -296,12 +296,15 @@ pub(super) async fn try_handle(
                 .await
                 .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
         ),
-        "get_mcp_settings" => Some(
-            state
-                .get_mcp_settings()
-                .await
-                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
-        ),
+        "get_mcp_settings" => {
+            let workspace_id = parse_optional_string(params, "workspaceId");
+            Some(
+                state
+                    .get_mcp_settings(workspace_id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "create_mcp_server" => {
             let input = match parse_input::<mcp_config_core::CreateMcpServerInput>(params) {
                 Ok(value) => value,
@@ -338,6 +341,184 @@ pub(super) async fn try_handle(
                     .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
             )
         }
+        "get_config_profiles" => {
+            let workspace_id = parse_optional_string(params, "workspaceId");
+            Some(
+                state
+                    .get_config_profiles(workspace_id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "create_config_profile" => {
+            let input = match parse_input::<config_profiles_core::CreateConfigProfileInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .create_config_profile(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "update_config_profile" => {
+            let input = match parse_input::<config_profiles_core::UpdateConfigProfileInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .update_config_profile(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "delete_config_profile" => {
+            let input = match parse_input::<config_profiles_core::DeleteConfigProfileInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_config_profile(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "get_model_providers" => {
+            let workspace_id = parse_optional_string(params, "workspaceId");
+            Some(
+                state
+                    .get_model_providers(workspace_id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "create_model_provider" => {
+            let input = match parse_input::<model_providers_core::CreateModelProviderInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .create_model_provider(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "update_model_provider" => {
+            let input = match parse_input::<model_providers_core::UpdateModelProviderInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .update_model_provider(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "delete_model_provider" => {
+            let input = match parse_input::<model_providers_core::DeleteModelProviderInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_model_provider(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "test_model_provider" => {
+            let input = match parse_input::<model_providers_core::ModelProviderDto>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .test_model_provider(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "config_preview_write" => {
+            let target = match parse_optional_value(params, "target")
+                .ok_or_else(|| "missing `target`".to_string())
+                .and_then(|value| serde_json::from_value(value).map_err(|err| err.to_string()))
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let content = match parse_string(params, "content") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .config_preview_write(target, content)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "config_history_list" => {
+            let target = match parse_optional_value(params, "target")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            Some(
+                state
+                    .config_history_list(target)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "config_history_restore" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .config_history_restore(id)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "get_effective_config" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .get_effective_config(workspace_id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "test_mcp_server" => {
+            let input = match parse_input::<mcp_config_core::McpServerDto>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .test_mcp_server(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "set_agents_core_settings" => {
             let input = match parse_input::<agents_config_core::SetAgentsCoreInput>(params) {
                 Ok(value) => value,
@@ -386,6 +567,55 @@ pub(super) async fn try_handle(
                     .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
             )
         }
+        "list_agent_templates" => Some(
+            state
+                .list_agent_templates()
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "save_agent_template" => {
+            let input = match parse_input::<agent_templates_core::SaveAgentTemplateInput>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .save_agent_template(input)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "delete_agent_template" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_agent_template(id)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "export_agent" => {
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .export_agent(name)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "import_agent" => {
+            let input = match parse_input::<agent_templates_core::ImportAgentInput>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .import_agent(input)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "read_agent_config_toml" => {
             let agent_name = match parse_string(params, "agentName") {
                 Ok(value) => value,
@@ -134,13 +134,21 @@ pub(super) async fn try_handle(
             let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
             Some(serialize_ok(state.sync_git(request.workspace_id)).await)
         }
-        git_rpc::METHOD_GET_GITHUB_ISSUES => {
+        git_rpc::METHOD_GET_WORKSPACE_FORGE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(serialize_result(state.get_github_issues(request.workspace_id)).await)
+            Some(serialize_result(state.get_workspace_forge(request.workspace_id)).await)
+        }
+        git_rpc::METHOD_GET_GITHUB_ISSUES => {
+            let request = parse_request_or_err!(params, git_rpc::GitHubListRequest);
+            let query = request.query.unwrap_or_default();
+            Some(serialize_result(state.get_github_issues(request.workspace_id, query)).await)
         }
         git_rpc::METHOD_GET_GITHUB_PULL_REQUESTS => {
-            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(serialize_result(state.get_github_pull_requests(request.workspace_id)).await)
+            let request = parse_request_or_err!(params, git_rpc::GitHubListRequest);
+            let query = request.query.unwrap_or_default();
+            Some(
+                serialize_result(state.get_github_pull_requests(request.workspace_id, query)).await,
+            )
         }
         git_rpc::METHOD_GET_GITHUB_PULL_REQUEST_DIFF => {
             let request = parse_request_or_err!(params, git_rpc::GitHubPullRequestRequest);
@@ -181,6 +189,174 @@ pub(super) async fn try_handle(
             let request = parse_request_or_err!(params, git_rpc::WorkspaceNameRequest);
             Some(serialize_ok(state.create_git_branch(request.workspace_id, request.name)).await)
         }
+        git_rpc::METHOD_LIST_GIT_TAGS => {
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.list_git_tags(request.workspace_id)).await)
+        }
+        git_rpc::METHOD_CREATE_GIT_TAG => {
+            let request = parse_request_or_err!(params, git_rpc::CreateGitTagRequest);
+            Some(
+                serialize_ok(state.create_git_tag(
+                    request.workspace_id,
+                    request.name,
+                    request.message,
+                    request.target,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_DELETE_GIT_TAG => {
+            let request = parse_request_or_err!(params, git_rpc::DeleteGitTagRequest);
+            Some(
+                serialize_ok(state.delete_git_tag(
+                    request.workspace_id,
+                    request.name,
+                    request.remote,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_PUSH_GIT_TAGS => {
+            let request = parse_request_or_err!(params, git_rpc::PushGitTagsRequest);
+            Some(
+                serialize_ok(state.push_git_tags(
+                    request.workspace_id,
+                    request.name,
+                    request.remote,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_GENERATE_RELEASE_NOTES => {
+            let request = parse_request_or_err!(params, git_rpc::GenerateReleaseNotesRequest);
+            Some(
+                state
+                    .generate_release_notes(request.workspace_id, request.tag, request.model_id)
+                    .await
+                    .map(Value::String),
+            )
+        }
+        git_rpc::METHOD_CREATE_GITHUB_RELEASE => {
+            let request = parse_request_or_err!(params, git_rpc::CreateGitHubReleaseRequest);
+            Some(
+                serialize_result(state.create_github_release(
+                    request.workspace_id,
+                    request.tag,
+                    request.title,
+                    request.notes,
+                    request.draft.unwrap_or(true),
+                    request.model_id,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_GENERATE_GITHUB_PULL_REQUEST_CONTENT => {
+            let request =
+                parse_request_or_err!(params, git_rpc::GenerateGitHubPullRequestContentRequest);
+            Some(
+                serialize_result(state.generate_github_pull_request_content(
+                    request.workspace_id,
+                    request.base,
+                    request.model_id,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_CREATE_GITHUB_PULL_REQUEST => {
+            let request = parse_request_or_err!(params, git_rpc::CreateGitHubPullRequestRequest);
+            Some(
+                serialize_result(state.create_github_pull_request(
+                    request.workspace_id,
+                    request.title,
+                    request.body,
+                    request.base,
+                    request.draft.unwrap_or(false),
+                    request.reviewers.unwrap_or_default(),
+                    request.generate.unwrap_or(false),
+                    request.model_id,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_UPDATE_GITHUB_PULL_REQUEST => {
+            let request = parse_request_or_err!(params, git_rpc::UpdateGitHubPullRequestRequest);
+            Some(
+                serialize_result(state.update_github_pull_request(
+                    request.workspace_id,
+                    request.pr_number,
+                    request.title,
+                    request.body,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_MARK_GITHUB_PULL_REQUEST_READY => {
+            let request = parse_request_or_err!(params, git_rpc::GitHubPullRequestRequest);
+            Some(
+                serialize_result(
+                    state.mark_github_pull_request_ready(request.workspace_id, request.pr_number),
+                )
+                .await,
+            )
+        }
+        git_rpc::METHOD_POST_GITHUB_PULL_REQUEST_REVIEW => {
+            let request =
+                parse_request_or_err!(params, git_rpc::PostGitHubPullRequestReviewRequest);
+            Some(
+                serialize_result(state.post_github_pull_request_review(
+                    request.workspace_id,
+                    request.pr_number,
+                    request.body,
+                    request.event.unwrap_or_default(),
+                    request.comments.unwrap_or_default(),
+                    request.review_thread_id,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_GET_GITHUB_CHECKS => {
+            let request = parse_request_or_err!(params, git_rpc::GitHubChecksRequest);
+            Some(
+                serialize_result(state.get_github_checks(
+                    request.workspace_id,
+                    request.git_ref,
+                    request.pr_number,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_WATCH_GITHUB_CHECKS => {
+            let request = parse_request_or_err!(params, git_rpc::WatchGitHubChecksRequest);
+            Some(
+                serialize_ok(state.watch_github_checks(
+                    request.workspace_id,
+                    request.git_ref,
+                    request.pr_number,
+                    request.thread_id,
+                    request.interval_secs,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_UNWATCH_GITHUB_CHECKS => {
+            let request = parse_request_or_err!(params, git_rpc::GitHubChecksRequest);
+            let stopped = state
+                .unwatch_github_checks(request.workspace_id, request.git_ref, request.pr_number)
+                .await;
+            Some(Ok(Value::Bool(stopped)))
+        }
+        git_rpc::METHOD_WATCH_GIT_STATUS => {
+            let request = parse_request_or_err!(params, git_rpc::WatchGitStatusRequest);
+            Some(
+                serialize_ok(state.watch_git_status(request.workspace_id, request.debounce_ms))
+                    .await,
+            )
+        }
+        git_rpc::METHOD_UNWATCH_GIT_STATUS => {
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
+            let stopped = state.unwatch_git_status(request.workspace_id).await;
+            Some(Ok(Value::Bool(stopped)))
+        }
         git_rpc::METHOD_GENERATE_COMMIT_MESSAGE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
             let commit_message_model_id = parse_optional_string(params, "commitMessageModelId");
@@ -54,6 +54,10 @@ pub(super) async fn try_handle(
             };
             let description = parse_optional_string(params, "description");
             let argument_hint = parse_optional_string(params, "argumentHint");
+            let variables = match parse_prompt_variables(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
             let content = match parse_string(params, "content") {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
@@ -65,6 +69,7 @@ pub(super) async fn try_handle(
                     name,
                     description,
                     argument_hint,
+                    variables,
                     content,
                 )
                 .await
@@ -89,6 +94,10 @@ pub(super) async fn try_handle(
             };
             let description = parse_optional_string(params, "description");
             let argument_hint = parse_optional_string(params, "argumentHint");
+            let variables = match parse_prompt_variables(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
             let content = match parse_string(params, "content") {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
@@ -100,6 +109,7 @@ pub(super) async fn try_handle(
                     name,
                     description,
                     argument_hint,
+                    variables,
                     content,
                 )
                 .await
@@ -144,6 +154,73 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
         }
+        "prompts_history" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let versions = match state.prompts_history(workspace_id, path).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(versions).map_err(|err| err.to_string()))
+        }
+        "prompts_restore_version" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let commit = match parse_string(params, "commit") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let prompt = match state
+                .prompts_restore_version(workspace_id, path, commit)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
+        }
+        "prompts_team_repo" => Some(
+            state
+                .prompts_team_repo()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "prompts_team_repo_set" => {
+            let url = parse_optional_string(params, "url");
+            Some(
+                state
+                    .prompts_team_repo_set(url)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "prompts_team_repo_sync" => Some(
+            state
+                .prompts_team_repo_sync()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
         _ => None,
     }
 }
+
+fn parse_prompt_variables(params: &Value) -> Result<Option<Vec<PromptVariable>>, String> {
+    match parse_optional_value(params, "variables") {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(|err| format!("invalid `variables`: {err}")),
+    }
+}
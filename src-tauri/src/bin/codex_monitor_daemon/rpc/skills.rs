@@ -0,0 +1,120 @@
+use super::*;
+
+pub(super) async fn try_handle(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Option<Result<Value, String>> {
+    match method {
+        "skills_local_list" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .skills_local_list(workspace_id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "skills_create" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let scope = match parse_string(params, "scope") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let content = match parse_string(params, "content") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .skills_create(workspace_id, scope, content)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "skills_update" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let content = match parse_string(params, "content") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .skills_update(workspace_id, path, content)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "skills_set_enabled" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let enabled = match parse_optional_bool(params, "enabled") {
+                Some(value) => value,
+                None => return Some(Err("missing or invalid `enabled`".to_string())),
+            };
+            Some(
+                state
+                    .skills_set_enabled(workspace_id, path, enabled)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "skills_delete" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .skills_delete(workspace_id, path)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "skills_install" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let input = match parse_install_input(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .skills_install(workspace_id, input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn parse_install_input(params: &Value) -> Result<InstallSkillInput, String> {
+    let value = parse_optional_value(params, "input").ok_or("missing `input`")?;
+    serde_json::from_value(value).map_err(|err| format!("invalid `input`: {err}"))
+}
@@ -90,6 +90,7 @@ pub(super) async fn try_handle(
                     request.url,
                     request.destination_path,
                     request.target_folder_name,
+                    request.checkout,
                     client_version.to_string(),
                 ))
                 .await,
@@ -103,6 +104,22 @@ pub(super) async fn try_handle(
                     request.branch,
                     request.name,
                     request.copy_agents_md,
+                    request.base_ref,
+                    request.detach,
+                    client_version.to_string(),
+                ))
+                .await,
+            )
+        }
+        "start_issue_agent" => {
+            let request = parse_request_or_err!(params, workspace_rpc::StartIssueAgentRequest);
+            Some(
+                serialize_result(state.start_issue_agent(
+                    request.workspace_id,
+                    request.issue_number,
+                    request.model,
+                    request.effort,
+                    request.access_mode,
                     client_version.to_string(),
                 ))
                 .await,
@@ -116,6 +133,78 @@ pub(super) async fn try_handle(
             let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
             Some(serialize_ok(state.worktree_setup_mark_ran(request.workspace_id)).await)
         }
+        "worktree_setup_run" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorktreeSetupRunRequest);
+            Some(
+                serialize_result(state.worktree_setup_run(
+                    request.workspace_id,
+                    true,
+                    request.timeout_secs,
+                ))
+                .await,
+            )
+        }
+        "worktree_setup_cancel" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_value(
+                state.worktree_setup_cancel(request.workspace_id).await,
+            ))
+        }
+        "worktree_setup_log" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.worktree_setup_log(request.workspace_id)).await)
+        }
+        "export_workspaces" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceBundleRequest);
+            Some(serialize_result(state.export_workspaces(request.root, request.path)).await)
+        }
+        "import_workspaces" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceBundleRequest);
+            Some(
+                serialize_result(state.import_workspaces(
+                    request.root,
+                    request.path,
+                    client_version.to_string(),
+                ))
+                .await,
+            )
+        }
+        "list_codex_home_profiles" => Some(serialize_value(state.list_codex_home_profiles().await)),
+        "create_codex_home_profile" => {
+            let request =
+                parse_request_or_err!(params, workspace_rpc::CreateCodexHomeProfileRequest);
+            Some(
+                serialize_result(state.create_codex_home_profile(request.name, request.path)).await,
+            )
+        }
+        "switch_codex_home_profile" => {
+            let request =
+                parse_request_or_err!(params, workspace_rpc::SwitchCodexHomeProfileRequest);
+            Some(
+                serialize_result(state.switch_codex_home_profile(
+                    request.scope,
+                    request.target_id,
+                    request.profile_id,
+                    client_version.to_string(),
+                ))
+                .await,
+            )
+        }
+        "delete_codex_home_profile" => {
+            let request =
+                parse_request_or_err!(params, workspace_rpc::DeleteCodexHomeProfileRequest);
+            Some(
+                serialize_result(
+                    state.delete_codex_home_profile(request.profile_id, client_version.to_string()),
+                )
+                .await,
+            )
+        }
+        "worktree_gc_report" => Some(serialize_result(state.worktree_gc_report()).await),
+        "worktree_gc_apply" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorktreeGcApplyRequest);
+            Some(serialize_result(state.worktree_gc_apply(request.ids, request.allow_dirty)).await)
+        }
         "connect_workspace" => {
             let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
             Some(
@@ -188,6 +277,31 @@ pub(super) async fn try_handle(
                     .await,
             )
         }
+        "list_workspace_agents_files" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.list_workspace_agents_files(request.workspace_id)).await)
+        }
+        "read_workspace_agents_file" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ReadWorkspaceFileRequest);
+            Some(
+                serialize_result(
+                    state.read_workspace_agents_file(request.workspace_id, request.path),
+                )
+                .await,
+            )
+        }
+        "write_workspace_agents_file" => {
+            let request =
+                parse_request_or_err!(params, workspace_rpc::WriteWorkspaceAgentsFileRequest);
+            Some(
+                serialize_ok(state.write_workspace_agents_file(
+                    request.workspace_id,
+                    request.path,
+                    request.content,
+                ))
+                .await,
+            )
+        }
         "add_clone" => {
             let request = parse_request_or_err!(params, workspace_rpc::AddCloneRequest);
             Some(
@@ -230,6 +344,8 @@ pub(super) async fn try_handle(
             )
         }
         "get_app_settings" => Some(serialize_value(state.get_app_settings().await)),
+        "get_settings_sync_report" => Some(serialize_value(state.get_settings_sync_report().await)),
+        "get_storage_recovery_report" => Some(serialize_value(state.get_storage_recovery_report())),
         "update_app_settings" => {
             let settings_value = match params {
                 Value::Object(map) => map.get("settings").cloned().unwrap_or(Value::Null),
@@ -242,8 +358,15 @@ pub(super) async fn try_handle(
             Some(serialize_result(state.update_app_settings(settings)).await)
         }
         "apply_worktree_changes" => {
-            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
-            Some(serialize_ok(state.apply_worktree_changes(request.workspace_id)).await)
+            let request = parse_request_or_err!(params, workspace_rpc::ApplyWorktreeChangesRequest);
+            let options = WorktreeApplyOptions {
+                strategy: request.strategy.unwrap_or_default(),
+                dry_run: request.dry_run.unwrap_or(false),
+                commit_message: request.commit_message,
+            };
+            Some(
+                serialize_result(state.apply_worktree_changes(request.workspace_id, options)).await,
+            )
         }
         "open_workspace_in" => {
             let request = parse_request_or_err!(params, workspace_rpc::OpenWorkspaceInRequest);
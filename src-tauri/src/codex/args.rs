@@ -10,17 +10,47 @@ pub(crate) fn parse_codex_args(value: Option<&str>) -> Result<Vec<String>, Strin
         .map(|args| args.into_iter().filter(|arg| !arg.is_empty()).collect())
 }
 
+/// App-wide Codex args plus the workspace's active config profile (or its
+/// parent's). Args that already pick a profile win over the workspace one.
 pub(crate) fn resolve_workspace_codex_args(
-    _entry: &WorkspaceEntry,
-    _parent_entry: Option<&WorkspaceEntry>,
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
     app_settings: Option<&AppSettings>,
 ) -> Option<String> {
-    if let Some(settings) = app_settings {
-        if let Some(value) = settings.codex_args.as_deref() {
-            return normalize_codex_args(value);
-        }
+    let base = app_settings
+        .and_then(|settings| settings.codex_args.as_deref())
+        .and_then(normalize_codex_args);
+    let profile = std::iter::once(entry)
+        .chain(parent_entry)
+        .find_map(|candidate| candidate.settings.active_profile.as_deref())
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let Some(profile) = profile else {
+        return base;
+    };
+    if base.as_deref().is_some_and(selects_profile) {
+        return base;
     }
-    None
+    let profile_arg = format!(
+        "-c {}",
+        shell_words::quote(&format!("profile={}", toml_edit::Value::from(profile)))
+    );
+    Some(match base {
+        Some(base) => format!("{base} {profile_arg}"),
+        None => profile_arg,
+    })
+}
+
+fn selects_profile(args: &str) -> bool {
+    let Ok(args) = parse_codex_args(Some(args)) else {
+        return false;
+    };
+    args.iter().any(|arg| {
+        arg == "-p"
+            || arg == "--profile"
+            || arg.starts_with("--profile=")
+            || arg.trim_start().starts_with("profile=")
+    })
 }
 
 fn normalize_codex_args(value: &str) -> Option<String> {
@@ -97,4 +127,48 @@ mod tests {
         let resolved_main = resolve_workspace_codex_args(&main, None, Some(&app_settings));
         assert_eq!(resolved_main.as_deref(), Some("--profile app"));
     }
+
+    #[test]
+    fn appends_the_active_profile_unless_args_pick_one() {
+        let mut app_settings = AppSettings::default();
+        app_settings.codex_args = Some("--search".to_string());
+
+        let mut parent = WorkspaceEntry {
+            id: "parent".to_string(),
+            name: "Parent".to_string(),
+            path: "/tmp/parent".to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        parent.settings.active_profile = Some("deep review".to_string());
+        let child = WorkspaceEntry {
+            id: "child".to_string(),
+            name: "Child".to_string(),
+            path: "/tmp/child".to_string(),
+            kind: WorkspaceKind::Worktree,
+            parent_id: Some(parent.id.clone()),
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+
+        let resolved =
+            resolve_workspace_codex_args(&child, Some(&parent), Some(&app_settings)).expect("args");
+        assert_eq!(resolved, "--search -c 'profile=\"deep review\"'");
+        assert_eq!(
+            parse_codex_args(Some(&resolved)).expect("parse args"),
+            vec!["--search", "-c", "profile=\"deep review\""]
+        );
+
+        let resolved_without_app_args = resolve_workspace_codex_args(&parent, None, None);
+        assert_eq!(
+            resolved_without_app_args.as_deref(),
+            Some("-c 'profile=\"deep review\"'")
+        );
+
+        app_settings.codex_args = Some("--profile fast".to_string());
+        let resolved = resolve_workspace_codex_args(&parent, None, Some(&app_settings));
+        assert_eq!(resolved.as_deref(), Some("--profile fast"));
+    }
 }
@@ -2,46 +2,6 @@ use std::path::PathBuf;
 
 use crate::shared::config_toml_core;
 
-pub(crate) fn read_steer_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("steer")
-}
-
-pub(crate) fn read_collaboration_modes_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("collaboration_modes")
-}
-
-pub(crate) fn read_unified_exec_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("unified_exec")
-}
-
-pub(crate) fn read_apps_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("apps")
-}
-
-pub(crate) fn read_personality() -> Result<Option<String>, String> {
-    let Some(root) = resolve_default_codex_home() else {
-        return Ok(None);
-    };
-    let (_, document) = config_toml_core::load_global_config_document(&root)?;
-    Ok(read_personality_from_document(&document))
-}
-
-pub(crate) fn write_steer_enabled(enabled: bool) -> Result<(), String> {
-    write_feature_flag("steer", enabled)
-}
-
-pub(crate) fn write_collaboration_modes_enabled(enabled: bool) -> Result<(), String> {
-    write_feature_flag("collaboration_modes", enabled)
-}
-
-pub(crate) fn write_unified_exec_enabled(enabled: bool) -> Result<(), String> {
-    write_feature_flag("unified_exec", enabled)
-}
-
-pub(crate) fn write_apps_enabled(enabled: bool) -> Result<(), String> {
-    write_feature_flag("apps", enabled)
-}
-
 pub(crate) fn write_feature_enabled(feature_key: &str, enabled: bool) -> Result<(), String> {
     let key = feature_key.trim();
     if key.is_empty() {
@@ -53,24 +13,6 @@ pub(crate) fn write_feature_enabled(feature_key: &str, enabled: bool) -> Result<
     write_feature_flag(key, enabled)
 }
 
-pub(crate) fn write_personality(personality: &str) -> Result<(), String> {
-    let Some(root) = resolve_default_codex_home() else {
-        return Ok(());
-    };
-    let (_, mut document) = config_toml_core::load_global_config_document(&root)?;
-    let normalized = normalize_personality_value(personality);
-    config_toml_core::set_top_level_string(&mut document, "personality", normalized);
-    config_toml_core::persist_global_config_document(&root, &document)
-}
-
-fn read_feature_flag(key: &str) -> Result<Option<bool>, String> {
-    let Some(root) = resolve_default_codex_home() else {
-        return Ok(None);
-    };
-    let (_, document) = config_toml_core::load_global_config_document(&root)?;
-    Ok(config_toml_core::read_feature_flag(&document, key))
-}
-
 fn write_feature_flag(key: &str, enabled: bool) -> Result<(), String> {
     let Some(root) = resolve_default_codex_home() else {
         return Ok(());
@@ -96,51 +38,3 @@ pub(crate) fn read_config_model(codex_home: Option<PathBuf>) -> Result<Option<St
 fn resolve_default_codex_home() -> Option<PathBuf> {
     crate::codex::home::resolve_default_codex_home()
 }
-
-fn read_personality_from_document(document: &toml_edit::Document) -> Option<String> {
-    config_toml_core::read_top_level_string(document, "personality")
-        .as_deref()
-        .and_then(normalize_personality_value)
-        .map(|value| value.to_string())
-}
-
-fn normalize_personality_value(value: &str) -> Option<&'static str> {
-    match value.trim().to_ascii_lowercase().as_str() {
-        "friendly" => Some("friendly"),
-        "pragmatic" => Some("pragmatic"),
-        _ => None,
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{normalize_personality_value, read_personality_from_document};
-    use crate::shared::config_toml_core;
-
-    #[test]
-    fn parse_personality_reads_supported_values() {
-        let friendly =
-            config_toml_core::parse_document("personality = \"friendly\"\n").expect("parse");
-        let pragmatic =
-            config_toml_core::parse_document("personality = \"pragmatic\"\n").expect("parse");
-        let unknown =
-            config_toml_core::parse_document("personality = \"unknown\"\n").expect("parse");
-
-        assert_eq!(
-            read_personality_from_document(&friendly),
-            Some("friendly".to_string())
-        );
-        assert_eq!(
-            read_personality_from_document(&pragmatic),
-            Some("pragmatic".to_string())
-        );
-        assert_eq!(read_personality_from_document(&unknown), None);
-    }
-
-    #[test]
-    fn normalize_personality_is_case_insensitive() {
-        assert_eq!(normalize_personality_value("Friendly"), Some("friendly"));
-        assert_eq!(normalize_personality_value("PRAGMATIC"), Some("pragmatic"));
-        assert_eq!(normalize_personality_value("unknown"), None);
-    }
-}
@@ -1,13 +1,61 @@
 use std::env;
 use std::path::PathBuf;
 
-use crate::types::WorkspaceEntry;
+use crate::types::{AppSettings, WorkspaceEntry};
 
+/// Codex home for a workspace: its own profile, then its parent's, then the
+/// profile of its group (or its parent's group), then the global default.
 pub(crate) fn resolve_workspace_codex_home(
-    _entry: &WorkspaceEntry,
-    _parent_entry: Option<&WorkspaceEntry>,
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+    app_settings: Option<&AppSettings>,
 ) -> Option<PathBuf> {
-    resolve_default_codex_home()
+    app_settings
+        .and_then(|settings| {
+            let profile_id = workspace_codex_home_profile_id(entry, parent_entry, settings)?;
+            resolve_codex_home_profile(settings, &profile_id)
+        })
+        .or_else(resolve_default_codex_home)
+}
+
+pub(crate) fn workspace_codex_home_profile_id(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+    settings: &AppSettings,
+) -> Option<String> {
+    let candidates = || std::iter::once(entry).chain(parent_entry);
+    candidates()
+        .find_map(|candidate| non_empty(candidate.settings.codex_home_profile_id.as_deref()))
+        .or_else(|| {
+            candidates()
+                .filter_map(|candidate| candidate.settings.group_id.as_deref())
+                .find_map(|group_id| {
+                    let group = settings
+                        .workspace_groups
+                        .iter()
+                        .find(|group| group.id == group_id)?;
+                    non_empty(group.codex_home_profile_id.as_deref())
+                })
+        })
+}
+
+/// Path of a profile; unknown profiles resolve to `None`.
+pub(crate) fn resolve_codex_home_profile(
+    settings: &AppSettings,
+    profile_id: &str,
+) -> Option<PathBuf> {
+    settings
+        .codex_home_profiles
+        .iter()
+        .find(|profile| profile.id == profile_id)
+        .and_then(|profile| normalize_codex_home(&profile.path))
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
 }
 
 pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
@@ -19,7 +67,7 @@ pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
     resolve_home_dir().map(|home| home.join(".codex"))
 }
 
-fn normalize_codex_home(value: &str) -> Option<PathBuf> {
+pub(crate) fn normalize_codex_home(value: &str) -> Option<PathBuf> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return None;
@@ -172,7 +220,9 @@ pub(crate) fn resolve_home_dir() -> Option<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{WorkspaceKind, WorkspaceSettings, WorktreeInfo};
+    use crate::types::{
+        CodexHomeProfile, WorkspaceGroup, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
+    };
     use std::sync::Mutex;
 
     static ENV_LOCK: Mutex<()> = Mutex::new(());
@@ -204,7 +254,72 @@ mod tests {
         let prev_codex_home = std::env::var("CODEX_HOME").ok();
         std::env::set_var("CODEX_HOME", "/tmp/codex-global");
 
-        let resolved = resolve_workspace_codex_home(&entry, None);
+        let resolved = resolve_workspace_codex_home(&entry, None, None);
+        assert_eq!(resolved, Some(PathBuf::from("/tmp/codex-global")));
+        let resolved = resolve_workspace_codex_home(&entry, None, Some(&AppSettings::default()));
+        assert_eq!(resolved, Some(PathBuf::from("/tmp/codex-global")));
+
+        match prev_codex_home {
+            Some(value) => std::env::set_var("CODEX_HOME", value),
+            None => std::env::remove_var("CODEX_HOME"),
+        }
+    }
+
+    #[test]
+    fn workspace_codex_home_prefers_workspace_then_parent_then_group_profile() {
+        let mut settings = AppSettings::default();
+        settings.codex_home_profiles = vec![
+            CodexHomeProfile {
+                id: "client".to_string(),
+                name: "Client".to_string(),
+                path: "/tmp/codex-client".to_string(),
+            },
+            CodexHomeProfile {
+                id: "internal".to_string(),
+                name: "Internal".to_string(),
+                path: "/tmp/codex-internal".to_string(),
+            },
+        ];
+        settings.workspace_groups = vec![WorkspaceGroup {
+            id: "group-1".to_string(),
+            name: "Internal".to_string(),
+            sort_order: None,
+            copies_folder: None,
+            codex_home_profile_id: Some("internal".to_string()),
+        }];
+
+        let mut parent = workspace_entry(WorkspaceKind::Main, "/repo");
+        parent.settings.group_id = Some("group-1".to_string());
+        let mut worktree = workspace_entry(WorkspaceKind::Worktree, "/repo-wt");
+        worktree.parent_id = Some(parent.id.clone());
+
+        assert_eq!(
+            resolve_workspace_codex_home(&worktree, Some(&parent), Some(&settings)),
+            Some(PathBuf::from("/tmp/codex-internal"))
+        );
+
+        parent.settings.codex_home_profile_id = Some("client".to_string());
+        assert_eq!(
+            resolve_workspace_codex_home(&worktree, Some(&parent), Some(&settings)),
+            Some(PathBuf::from("/tmp/codex-client"))
+        );
+
+        worktree.settings.codex_home_profile_id = Some("internal".to_string());
+        assert_eq!(
+            resolve_workspace_codex_home(&worktree, Some(&parent), Some(&settings)),
+            Some(PathBuf::from("/tmp/codex-internal"))
+        );
+    }
+
+    #[test]
+    fn workspace_codex_home_ignores_unknown_profiles() {
+        let _guard = ENV_LOCK.lock().expect("lock env");
+        let prev_codex_home = std::env::var("CODEX_HOME").ok();
+        std::env::set_var("CODEX_HOME", "/tmp/codex-global");
+
+        let mut entry = workspace_entry(WorkspaceKind::Main, "/repo");
+        entry.settings.codex_home_profile_id = Some("removed".to_string());
+        let resolved = resolve_workspace_codex_home(&entry, None, Some(&AppSettings::default()));
         assert_eq!(resolved, Some(PathBuf::from("/tmp/codex-global")));
 
         match prev_codex_home {
@@ -13,9 +13,16 @@ pub(crate) use crate::backend::app_server::WorkspaceSession;
 use crate::backend::events::AppServerEvent;
 use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
+use crate::shared::agent_templates_core;
 use crate::shared::agents_config_core;
 use crate::shared::codex_core;
+use crate::shared::config_history_core;
+use crate::shared::config_profiles_core;
 use crate::shared::mcp_config_core;
+use crate::shared::mcp_probe_core;
+use crate::shared::model_provider_probe_core;
+use crate::shared::model_providers_core;
+use crate::shared::project_config_core;
 use crate::state::AppState;
 use crate::types::WorkspaceEntry;
 
@@ -573,15 +580,25 @@ pub(crate) async fn get_agents_settings(
 
 #[tauri::command]
 pub(crate) async fn get_mcp_settings(
+    workspace_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<mcp_config_core::McpSettingsDto, String> {
     if remote_backend::is_remote_mode(&*state).await {
-        let response = remote_backend::call_remote(&*state, app, "get_mcp_settings", json!({})).await?;
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_mcp_settings",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    mcp_config_core::get_mcp_settings_core()
+    let target =
+        project_config_core::resolve_config_target_core(&state.workspaces, workspace_id.as_deref())
+            .await?;
+    mcp_config_core::get_mcp_settings_core(&target)
 }
 
 #[tauri::command]
@@ -597,7 +614,12 @@ pub(crate) async fn create_mcp_server(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    mcp_config_core::create_mcp_server_core(input)
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    mcp_config_core::create_mcp_server_core(&target, input)
 }
 
 #[tauri::command]
@@ -613,7 +635,12 @@ pub(crate) async fn update_mcp_server(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    mcp_config_core::update_mcp_server_core(input)
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    mcp_config_core::update_mcp_server_core(&target, input)
 }
 
 #[tauri::command]
@@ -629,7 +656,330 @@ pub(crate) async fn delete_mcp_server(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    mcp_config_core::delete_mcp_server_core(input)
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    mcp_config_core::delete_mcp_server_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn get_config_profiles(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_config_profiles",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target =
+        project_config_core::resolve_config_target_core(&state.workspaces, workspace_id.as_deref())
+            .await?;
+    config_profiles_core::get_config_profiles_core(&target)
+}
+
+#[tauri::command]
+pub(crate) async fn create_config_profile(
+    input: config_profiles_core::CreateConfigProfileInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_config_profile",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    config_profiles_core::create_config_profile_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn update_config_profile(
+    input: config_profiles_core::UpdateConfigProfileInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "update_config_profile",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    config_profiles_core::update_config_profile_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_config_profile(
+    input: config_profiles_core::DeleteConfigProfileInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_profiles_core::ConfigProfilesDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_config_profile",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    config_profiles_core::delete_config_profile_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn get_model_providers(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<model_providers_core::ModelProvidersDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_model_providers",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target =
+        project_config_core::resolve_config_target_core(&state.workspaces, workspace_id.as_deref())
+            .await?;
+    model_providers_core::get_model_providers_core(&target)
+}
+
+#[tauri::command]
+pub(crate) async fn create_model_provider(
+    input: model_providers_core::CreateModelProviderInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<model_providers_core::ModelProvidersDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_model_provider",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    model_providers_core::create_model_provider_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn update_model_provider(
+    input: model_providers_core::UpdateModelProviderInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<model_providers_core::ModelProvidersDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "update_model_provider",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    model_providers_core::update_model_provider_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_model_provider(
+    input: model_providers_core::DeleteModelProviderInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<model_providers_core::ModelProvidersDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_model_provider",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let target = project_config_core::resolve_config_target_core(
+        &state.workspaces,
+        input.workspace_id.as_deref(),
+    )
+    .await?;
+    model_providers_core::delete_model_provider_core(&target, input)
+}
+
+#[tauri::command]
+pub(crate) async fn test_model_provider(
+    input: model_providers_core::ModelProviderDto,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<model_provider_probe_core::ModelProviderProbeResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "test_model_provider",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    model_provider_probe_core::test_model_provider_core(input).await
+}
+
+#[tauri::command]
+pub(crate) async fn config_preview_write(
+    target: config_history_core::ConfigFileTarget,
+    content: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_history_core::ConfigWritePreview, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "config_preview_write",
+            json!({ "target": target, "content": content }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    config_history_core::preview_config_write_core(&state.workspaces, target, content).await
+}
+
+#[tauri::command]
+pub(crate) async fn config_history_list(
+    target: Option<config_history_core::ConfigFileTarget>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<config_history_core::ConfigHistoryEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "config_history_list",
+            json!({ "target": target }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    config_history_core::config_history_list_core(&state.workspaces, target).await
+}
+
+#[tauri::command]
+pub(crate) async fn config_history_restore(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<config_history_core::ConfigHistoryRestore, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "config_history_restore",
+            json!({ "id": id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    config_history_core::config_history_restore_core(&id)
+}
+
+#[tauri::command]
+pub(crate) async fn test_mcp_server(
+    input: mcp_config_core::McpServerDto,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<mcp_probe_core::McpProbeResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "test_mcp_server", json!({ "input": input }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    mcp_probe_core::test_mcp_server_core(input).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_effective_config(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<project_config_core::EffectiveConfig, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_effective_config",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    project_config_core::get_effective_config_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -700,6 +1050,88 @@ pub(crate) async fn delete_agent(
     agents_config_core::delete_agent_core(input)
 }
 
+#[tauri::command]
+pub(crate) async fn list_agent_templates(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_agent_templates", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_templates_core::list_agent_templates_core()
+}
+
+#[tauri::command]
+pub(crate) async fn save_agent_template(
+    input: agent_templates_core::SaveAgentTemplateInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "save_agent_template",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_templates_core::save_agent_template_core(input)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_agent_template(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<agent_templates_core::AgentTemplateDto>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "delete_agent_template", json!({ "id": id }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_templates_core::delete_agent_template_core(&id)
+}
+
+#[tauri::command]
+pub(crate) async fn export_agent(
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<agent_templates_core::AgentBundleExport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "export_agent", json!({ "name": name }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_templates_core::export_agent_core(&name)
+}
+
+#[tauri::command]
+pub(crate) async fn import_agent(
+    input: agent_templates_core::ImportAgentInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<agents_config_core::AgentsSettingsDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "import_agent", json!({ "input": input }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_templates_core::import_agent_core(input)
+}
+
 #[tauri::command]
 pub(crate) async fn read_agent_config_toml(
     agent_name: String,
@@ -779,7 +1211,13 @@ pub(crate) async fn account_read(
         .await;
     }
 
-    codex_core::account_read_core(&state.sessions, &state.workspaces, workspace_id).await
+    codex_core::account_read_core(
+        &state.sessions,
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -896,7 +1334,13 @@ pub(crate) async fn remember_approval_rule(
     command: Vec<String>,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
-    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await
+    codex_core::remember_approval_rule_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        command,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -915,7 +1359,7 @@ pub(crate) async fn get_config_model(
         .await;
     }
 
-    codex_core::get_config_model_core(&state.workspaces, workspace_id).await
+    codex_core::get_config_model_core(&state.workspaces, &state.app_settings, workspace_id).await
 }
 
 /// Generates a commit message in the background without showing in the main chat
@@ -1,6 +1,10 @@
 use tauri::{AppHandle, Emitter};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, GitStatusChanged, TerminalExit,
+    TerminalOutput, WorktreeSetupOutput,
+};
+use crate::types::WorktreeSetupRun;
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -25,4 +29,20 @@ impl EventSink for TauriEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.app.emit("terminal-exit", event);
     }
+
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished) {
+        let _ = self.app.emit("github-checks-finished", event);
+    }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        let _ = self.app.emit("git-status-changed", event);
+    }
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        let _ = self.app.emit("worktree-setup-output", event);
+    }
+
+    fn emit_worktree_setup_status(&self, event: WorktreeSetupRun) {
+        let _ = self.app.emit("worktree-setup-status", event);
+    }
 }
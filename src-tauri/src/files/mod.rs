@@ -7,6 +7,8 @@ use self::policy::{FileKind, FileScope};
 use crate::remote_backend;
 use crate::shared::codex_core;
 use crate::shared::files_core::{file_read_core, file_write_core};
+use crate::shared::project_config_core::{self, WorkspaceAgentsFile};
+use crate::shared::workspace_rpc;
 use crate::state::AppState;
 
 pub(crate) mod io;
@@ -111,6 +113,81 @@ pub(crate) async fn file_write(
     file_write_impl(scope, kind, workspace_id, content, &*state, &app).await
 }
 
+#[tauri::command]
+pub(crate) async fn list_workspace_agents_files(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceAgentsFile>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_workspace_agents_files",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    project_config_core::list_workspace_agents_files_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn read_workspace_agents_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TextFileResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "read_workspace_agents_file",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    project_config_core::read_workspace_agents_file_core(&state.workspaces, workspace_id, path)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn write_workspace_agents_file(
+    workspace_id: String,
+    path: String,
+    content: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WriteWorkspaceAgentsFileRequest {
+            workspace_id,
+            path,
+            content,
+        };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "write_workspace_agents_file",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    project_config_core::write_workspace_agents_file_core(
+        &state.workspaces,
+        workspace_id,
+        path,
+        content,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn read_image_as_data_url(
     path: String,
@@ -3,12 +3,25 @@ use std::path::PathBuf;
 use crate::files::io::{read_text_file_within, write_text_file_within, TextFileResponse};
 use crate::files::policy::FilePolicy;
 
+/// Directory the policy's file lives in. With a `root_subdir` the scope root
+/// itself must already exist; only the subfolder may be created.
+fn policy_root(root: &PathBuf, policy: FilePolicy) -> Result<PathBuf, String> {
+    let Some(subdir) = policy.root_subdir else {
+        return Ok(root.clone());
+    };
+    if !root.is_dir() {
+        return Err(format!("Failed to resolve {}", policy.root_context));
+    }
+    Ok(root.join(subdir))
+}
+
 pub(crate) fn read_with_policy(
     root: &PathBuf,
     policy: FilePolicy,
 ) -> Result<TextFileResponse, String> {
+    let root = policy_root(root, policy)?;
     read_text_file_within(
-        root,
+        &root,
         policy.filename,
         policy.root_may_be_missing,
         policy.root_context,
@@ -22,8 +35,9 @@ pub(crate) fn write_with_policy(
     policy: FilePolicy,
     content: &str,
 ) -> Result<(), String> {
+    let root = policy_root(root, policy)?;
     write_text_file_within(
-        root,
+        &root,
         policy.filename,
         content,
         policy.create_root,
@@ -94,6 +108,24 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn workspace_config_write_creates_dot_codex_folder() {
+        let root = temp_dir("workspace-config");
+        let policy = policy_for(FileScope::Workspace, FileKind::Config).expect("policy");
+        assert!(write_with_policy(&root, policy, "model = \"o3\"\n").is_err());
+
+        fs::create_dir_all(&root).expect("create workspace root");
+        let initial = read_with_policy(&root, policy).expect("initial read");
+        assert!(!initial.exists);
+
+        write_with_policy(&root, policy, "model = \"o3\"\n").expect("write config");
+        assert!(root.join(".codex").join("config.toml").is_file());
+        let response = read_with_policy(&root, policy).expect("read config");
+        assert_eq!(response.content, "model = \"o3\"\n");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn global_config_write_creates_root() {
         let root = temp_dir("global-config");
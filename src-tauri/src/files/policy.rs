@@ -17,6 +17,8 @@ pub(crate) enum FileKind {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct FilePolicy {
     pub(crate) filename: &'static str,
+    /// Folder under the scope root that holds the file (e.g. `.codex`).
+    pub(crate) root_subdir: Option<&'static str>,
     pub(crate) root_context: &'static str,
     pub(crate) root_may_be_missing: bool,
     pub(crate) create_root: bool,
@@ -25,11 +27,13 @@ pub(crate) struct FilePolicy {
 
 const AGENTS_FILENAME: &str = "AGENTS.md";
 const CONFIG_FILENAME: &str = "config.toml";
+pub(crate) const PROJECT_CONFIG_DIR: &str = ".codex";
 
 pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy, String> {
     match (scope, kind) {
         (FileScope::Workspace, FileKind::Agents) => Ok(FilePolicy {
             filename: AGENTS_FILENAME,
+            root_subdir: None,
             root_context: "workspace root",
             root_may_be_missing: false,
             create_root: false,
@@ -37,6 +41,7 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
         }),
         (FileScope::Global, FileKind::Agents) => Ok(FilePolicy {
             filename: AGENTS_FILENAME,
+            root_subdir: None,
             root_context: "CODEX_HOME",
             root_may_be_missing: true,
             create_root: true,
@@ -44,14 +49,20 @@ pub(crate) fn policy_for(scope: FileScope, kind: FileKind) -> Result<FilePolicy,
         }),
         (FileScope::Global, FileKind::Config) => Ok(FilePolicy {
             filename: CONFIG_FILENAME,
+            root_subdir: None,
             root_context: "CODEX_HOME",
             root_may_be_missing: true,
             create_root: true,
             allow_external_symlink_target: false,
         }),
-        (FileScope::Workspace, FileKind::Config) => {
-            Err("config.toml is only supported for global scope".to_string())
-        }
+        (FileScope::Workspace, FileKind::Config) => Ok(FilePolicy {
+            filename: CONFIG_FILENAME,
+            root_subdir: Some(PROJECT_CONFIG_DIR),
+            root_context: "workspace .codex folder",
+            root_may_be_missing: true,
+            create_root: true,
+            allow_external_symlink_target: false,
+        }),
     }
 }
 
@@ -90,8 +101,12 @@ mod tests {
     }
 
     #[test]
-    fn workspace_config_is_rejected() {
-        let result = policy_for(FileScope::Workspace, FileKind::Config);
-        assert!(result.is_err());
+    fn workspace_config_policy_lives_in_dot_codex() {
+        let policy = policy_for(FileScope::Workspace, FileKind::Config).expect("policy");
+        assert_eq!(policy.filename, "config.toml");
+        assert_eq!(policy.root_subdir, Some(".codex"));
+        assert!(policy.root_may_be_missing);
+        assert!(policy.create_root);
+        assert!(!policy.allow_external_symlink_target);
     }
 }
@@ -1,14 +1,19 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use serde_json::Value;
-use tauri::{AppHandle, State};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, State};
 
+use crate::backend::events::AppServerEvent;
+use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
-use crate::shared::{git_rpc, git_ui_core};
+use crate::shared::codex_aux_core::{self, GeneratedPullRequestContent};
+use crate::shared::{codex_core, git_rpc, git_ui_core};
 use crate::state::AppState;
 use crate::types::{
-    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    ForgeInfo, GitCommitDiff, GitFileDiff, GitHubChecksResponse, GitHubIssuesResponse,
+    GitHubListQuery, GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestReviewComment, GitHubPullRequestReviewEvent, GitHubPullRequestReviewResult,
+    GitHubPullRequestsResponse, GitHubRelease, GitLogResponse, GitTagInfo,
 };
 
 fn git_remote_params<T: Serialize>(request: &T) -> Result<Value, String> {
@@ -452,14 +457,35 @@ pub(crate) async fn get_git_remote(
     git_ui_core::get_git_remote_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn get_workspace_forge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ForgeInfo, String> {
+    let request = git_rpc::WorkspaceIdRequest {
+        workspace_id: workspace_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GET_WORKSPACE_FORGE,
+        git_remote_params(&request)?,
+        ForgeInfo
+    );
+    git_ui_core::get_workspace_forge_core(&state.workspaces, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn get_github_issues(
     workspace_id: String,
+    query: Option<GitHubListQuery>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitHubIssuesResponse, String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::GitHubListRequest {
         workspace_id: workspace_id.clone(),
+        query: query.clone(),
     };
     try_remote_typed!(
         state,
@@ -468,17 +494,20 @@ pub(crate) async fn get_github_issues(
         git_remote_params(&request)?,
         GitHubIssuesResponse
     );
-    git_ui_core::get_github_issues_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_github_issues_core(&state.workspaces, workspace_id, query.unwrap_or_default())
+        .await
 }
 
 #[tauri::command]
 pub(crate) async fn get_github_pull_requests(
     workspace_id: String,
+    query: Option<GitHubListQuery>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitHubPullRequestsResponse, String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::GitHubListRequest {
         workspace_id: workspace_id.clone(),
+        query: query.clone(),
     };
     try_remote_typed!(
         state,
@@ -487,7 +516,12 @@ pub(crate) async fn get_github_pull_requests(
         git_remote_params(&request)?,
         GitHubPullRequestsResponse
     );
-    git_ui_core::get_github_pull_requests_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_github_pull_requests_core(
+        &state.workspaces,
+        workspace_id,
+        query.unwrap_or_default(),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -553,6 +587,355 @@ pub(crate) async fn checkout_github_pull_request(
     git_ui_core::checkout_github_pull_request_core(&state.workspaces, workspace_id, pr_number).await
 }
 
+async fn generate_pull_request_content_for_base(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: String,
+    base: Option<String>,
+    model_id: Option<&str>,
+) -> Result<GeneratedPullRequestContent, String> {
+    let diff = git_ui_core::get_github_pull_request_branch_diff_core(
+        &state.workspaces,
+        workspace_id.clone(),
+        base,
+    )
+    .await?;
+    codex_aux_core::generate_pull_request_content_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        &diff,
+        model_id,
+        |workspace_id, thread_id| emit_background_thread_hide(app, workspace_id, thread_id),
+    )
+    .await
+}
+
+/// Generates a pull request title and body from the branch diff without showing a thread.
+#[tauri::command]
+pub(crate) async fn generate_github_pull_request_content(
+    workspace_id: String,
+    base: Option<String>,
+    model_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GeneratedPullRequestContent, String> {
+    let request = git_rpc::GenerateGitHubPullRequestContentRequest {
+        workspace_id: workspace_id.clone(),
+        base: base.clone(),
+        model_id: model_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GENERATE_GITHUB_PULL_REQUEST_CONTENT,
+        git_remote_params(&request)?,
+        GeneratedPullRequestContent
+    );
+    generate_pull_request_content_for_base(&state, &app, workspace_id, base, model_id.as_deref())
+        .await
+}
+
+/// Pushes the current branch and opens a pull request for it. A missing title, or
+/// `generate`, fills the title and body from the branch diff; explicit values win.
+#[tauri::command]
+pub(crate) async fn create_github_pull_request(
+    workspace_id: String,
+    title: Option<String>,
+    body: Option<String>,
+    base: Option<String>,
+    draft: Option<bool>,
+    reviewers: Option<Vec<String>>,
+    generate: Option<bool>,
+    model_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubPullRequest, String> {
+    let request = git_rpc::CreateGitHubPullRequestRequest {
+        workspace_id: workspace_id.clone(),
+        title: title.clone(),
+        body: body.clone(),
+        base: base.clone(),
+        draft,
+        reviewers: reviewers.clone(),
+        generate,
+        model_id: model_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_CREATE_GITHUB_PULL_REQUEST,
+        git_remote_params(&request)?,
+        GitHubPullRequest
+    );
+    let title = title.filter(|value| !value.trim().is_empty());
+    let (title, body) = if generate.unwrap_or(false) || title.is_none() {
+        let generated = generate_pull_request_content_for_base(
+            &state,
+            &app,
+            workspace_id.clone(),
+            base.clone(),
+            model_id.as_deref(),
+        )
+        .await?;
+        (
+            title.unwrap_or(generated.title),
+            body.unwrap_or(generated.body),
+        )
+    } else {
+        (title.unwrap_or_default(), body.unwrap_or_default())
+    };
+    git_ui_core::create_github_pull_request_core(
+        &state.workspaces,
+        workspace_id,
+        title,
+        body,
+        base,
+        draft.unwrap_or(false),
+        reviewers.unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn update_github_pull_request(
+    workspace_id: String,
+    pr_number: u64,
+    title: Option<String>,
+    body: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubPullRequest, String> {
+    let request = git_rpc::UpdateGitHubPullRequestRequest {
+        workspace_id: workspace_id.clone(),
+        pr_number,
+        title: title.clone(),
+        body: body.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_UPDATE_GITHUB_PULL_REQUEST,
+        git_remote_params(&request)?,
+        GitHubPullRequest
+    );
+    git_ui_core::update_github_pull_request_core(
+        &state.workspaces,
+        workspace_id,
+        pr_number,
+        title,
+        body,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn mark_github_pull_request_ready(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubPullRequest, String> {
+    let request = git_rpc::GitHubPullRequestRequest {
+        workspace_id: workspace_id.clone(),
+        pr_number,
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_MARK_GITHUB_PULL_REQUEST_READY,
+        git_remote_params(&request)?,
+        GitHubPullRequest
+    );
+    git_ui_core::mark_github_pull_request_ready_core(&state.workspaces, workspace_id, pr_number)
+        .await
+}
+
+/// Posts a review to a pull request. With `review_thread_id`, the findings of
+/// that thread's latest completed Codex review are added as inline comments.
+#[tauri::command]
+pub(crate) async fn post_github_pull_request_review(
+    workspace_id: String,
+    pr_number: u64,
+    body: Option<String>,
+    event: Option<GitHubPullRequestReviewEvent>,
+    comments: Option<Vec<GitHubPullRequestReviewComment>>,
+    review_thread_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubPullRequestReviewResult, String> {
+    let request = git_rpc::PostGitHubPullRequestReviewRequest {
+        workspace_id: workspace_id.clone(),
+        pr_number,
+        body: body.clone(),
+        event,
+        comments: comments.clone(),
+        review_thread_id: review_thread_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_POST_GITHUB_PULL_REQUEST_REVIEW,
+        git_remote_params(&request)?,
+        GitHubPullRequestReviewResult
+    );
+    let codex_review = match review_thread_id {
+        Some(thread_id) => Some(
+            codex_core::latest_review_output_core(&state.sessions, workspace_id.clone(), thread_id)
+                .await?,
+        ),
+        None => None,
+    };
+    git_ui_core::post_github_pull_request_review_core(
+        &state.workspaces,
+        workspace_id,
+        pr_number,
+        body,
+        event.unwrap_or_default(),
+        comments.unwrap_or_default(),
+        codex_review,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_github_checks(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubChecksResponse, String> {
+    let request = git_rpc::GitHubChecksRequest {
+        workspace_id: workspace_id.clone(),
+        git_ref: git_ref.clone(),
+        pr_number,
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GET_GITHUB_CHECKS,
+        git_remote_params(&request)?,
+        GitHubChecksResponse
+    );
+    git_ui_core::get_github_checks_core(&state.workspaces, workspace_id, git_ref, pr_number).await
+}
+
+/// Starts polling checks for a ref or pull request; `github-checks-finished`
+/// fires once they complete, carrying `thread_id` so the thread can follow up.
+#[tauri::command]
+pub(crate) async fn watch_github_checks(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    thread_id: Option<String>,
+    interval_secs: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let request = git_rpc::WatchGitHubChecksRequest {
+        workspace_id: workspace_id.clone(),
+        git_ref: git_ref.clone(),
+        pr_number,
+        thread_id: thread_id.clone(),
+        interval_secs,
+    };
+    try_remote_unit!(
+        state,
+        app,
+        git_rpc::METHOD_WATCH_GITHUB_CHECKS,
+        git_remote_params(&request)?
+    );
+    git_ui_core::watch_github_checks_core(
+        &state.workspaces,
+        &state.github_checks_watchers,
+        TauriEventSink::new(app.clone()),
+        workspace_id,
+        git_ref,
+        pr_number,
+        thread_id,
+        interval_secs,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn unwatch_github_checks(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let request = git_rpc::GitHubChecksRequest {
+        workspace_id: workspace_id.clone(),
+        git_ref: git_ref.clone(),
+        pr_number,
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_UNWATCH_GITHUB_CHECKS,
+        git_remote_params(&request)?,
+        bool
+    );
+    Ok(git_ui_core::unwatch_github_checks_core(
+        &state.github_checks_watchers,
+        workspace_id,
+        git_ref,
+        pr_number,
+    )
+    .await)
+}
+
+/// Starts watching the workspace tree; `git-status-changed` fires with the
+/// changed paths so the UI can refresh status and diffs only when needed.
+#[tauri::command]
+pub(crate) async fn watch_git_status(
+    workspace_id: String,
+    debounce_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let request = git_rpc::WatchGitStatusRequest {
+        workspace_id: workspace_id.clone(),
+        debounce_ms,
+    };
+    try_remote_unit!(
+        state,
+        app,
+        git_rpc::METHOD_WATCH_GIT_STATUS,
+        git_remote_params(&request)?
+    );
+    git_ui_core::watch_git_status_core(
+        &state.workspaces,
+        &state.git_status_watchers,
+        TauriEventSink::new(app.clone()),
+        workspace_id,
+        debounce_ms,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn unwatch_git_status(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let request = git_rpc::WorkspaceIdRequest {
+        workspace_id: workspace_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_UNWATCH_GIT_STATUS,
+        git_remote_params(&request)?,
+        bool
+    );
+    Ok(git_ui_core::unwatch_git_status_core(&state.git_status_watchers, workspace_id).await)
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
@@ -610,3 +993,193 @@ pub(crate) async fn create_git_branch(
     );
     git_ui_core::create_git_branch_core(&state.workspaces, workspace_id, name).await
 }
+
+#[tauri::command]
+pub(crate) async fn list_git_tags(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitTagInfo>, String> {
+    let request = git_rpc::WorkspaceIdRequest {
+        workspace_id: workspace_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_LIST_GIT_TAGS,
+        git_remote_params(&request)?,
+        Vec<GitTagInfo>
+    );
+    git_ui_core::list_git_tags_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn create_git_tag(
+    workspace_id: String,
+    name: String,
+    message: Option<String>,
+    target: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let request = git_rpc::CreateGitTagRequest {
+        workspace_id: workspace_id.clone(),
+        name: name.clone(),
+        message: message.clone(),
+        target: target.clone(),
+    };
+    try_remote_unit!(
+        state,
+        app,
+        git_rpc::METHOD_CREATE_GIT_TAG,
+        git_remote_params(&request)?
+    );
+    git_ui_core::create_git_tag_core(&state.workspaces, workspace_id, name, message, target).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_git_tag(
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let request = git_rpc::DeleteGitTagRequest {
+        workspace_id: workspace_id.clone(),
+        name: name.clone(),
+        remote: remote.clone(),
+    };
+    try_remote_unit!(
+        state,
+        app,
+        git_rpc::METHOD_DELETE_GIT_TAG,
+        git_remote_params(&request)?
+    );
+    git_ui_core::delete_git_tag_core(&state.workspaces, workspace_id, name, remote).await
+}
+
+#[tauri::command]
+pub(crate) async fn push_git_tags(
+    workspace_id: String,
+    name: Option<String>,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let request = git_rpc::PushGitTagsRequest {
+        workspace_id: workspace_id.clone(),
+        name: name.clone(),
+        remote: remote.clone(),
+    };
+    try_remote_unit!(
+        state,
+        app,
+        git_rpc::METHOD_PUSH_GIT_TAGS,
+        git_remote_params(&request)?
+    );
+    git_ui_core::push_git_tags_core(&state.workspaces, workspace_id, name, remote).await
+}
+
+fn emit_background_thread_hide(app: &AppHandle, workspace_id: &str, thread_id: &str) {
+    let _ = app.emit(
+        "app-server-event",
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": "codex/backgroundThread",
+                "params": {
+                    "threadId": thread_id,
+                    "action": "hide"
+                }
+            }),
+        },
+    );
+}
+
+/// Generates release notes from the commits since the previous tag without showing a thread.
+#[tauri::command]
+pub(crate) async fn generate_release_notes(
+    workspace_id: String,
+    tag: String,
+    model_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let request = git_rpc::GenerateReleaseNotesRequest {
+        workspace_id: workspace_id.clone(),
+        tag: tag.clone(),
+        model_id: model_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GENERATE_RELEASE_NOTES,
+        git_remote_params(&request)?,
+        String
+    );
+    let range =
+        git_ui_core::release_commit_range_core(&state.workspaces, workspace_id.clone(), tag)
+            .await?;
+    git_ui_core::release_notes_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        &range,
+        None,
+        model_id.as_deref(),
+        |workspace_id, thread_id| emit_background_thread_hide(&app, workspace_id, thread_id),
+    )
+    .await
+}
+
+/// Creates a GitHub release for an existing tag, generating notes when none are given.
+#[tauri::command]
+pub(crate) async fn create_github_release(
+    workspace_id: String,
+    tag: String,
+    title: Option<String>,
+    notes: Option<String>,
+    draft: Option<bool>,
+    model_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubRelease, String> {
+    let request = git_rpc::CreateGitHubReleaseRequest {
+        workspace_id: workspace_id.clone(),
+        tag: tag.clone(),
+        title: title.clone(),
+        notes: notes.clone(),
+        draft,
+        model_id: model_id.clone(),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_CREATE_GITHUB_RELEASE,
+        git_remote_params(&request)?,
+        GitHubRelease
+    );
+    let range =
+        git_ui_core::release_commit_range_core(&state.workspaces, workspace_id.clone(), tag)
+            .await?;
+    let notes = git_ui_core::release_notes_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id.clone(),
+        &range,
+        notes,
+        model_id.as_deref(),
+        |workspace_id, thread_id| emit_background_thread_hide(&app, workspace_id, thread_id),
+    )
+    .await?;
+    git_ui_core::create_github_release_core(
+        &state.workspaces,
+        workspace_id,
+        range,
+        title,
+        notes,
+        draft.unwrap_or(true),
+    )
+    .await
+}
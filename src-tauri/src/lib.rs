@@ -27,6 +27,7 @@ mod remote_backend;
 mod rules;
 mod settings;
 mod shared;
+mod skills;
 mod state;
 mod storage;
 mod tailscale;
@@ -211,8 +212,13 @@ pub fn run() {
             settings::get_app_settings,
             settings::update_app_settings,
             settings::get_codex_config_path,
+            settings::get_storage_recovery_report,
+            settings::get_settings_sync_report,
             files::file_read,
             files::file_write,
+            files::list_workspace_agents_files,
+            files::read_workspace_agents_file,
+            files::write_workspace_agents_file,
             files::read_image_as_data_url,
             files::read_clipboard_file_as_data_url,
             files::write_text_file,
@@ -225,9 +231,21 @@ pub fn run() {
             workspaces::add_workspace,
             workspaces::add_workspace_from_git_url,
             workspaces::add_clone,
+            workspaces::export_workspaces,
+            workspaces::import_workspaces,
+            workspaces::list_codex_home_profiles,
+            workspaces::create_codex_home_profile,
+            workspaces::switch_codex_home_profile,
+            workspaces::delete_codex_home_profile,
             workspaces::add_worktree,
+            workspaces::start_issue_agent,
             workspaces::worktree_setup_status,
             workspaces::worktree_setup_mark_ran,
+            workspaces::worktree_setup_run,
+            workspaces::worktree_setup_cancel,
+            workspaces::worktree_setup_log,
+            workspaces::worktree_gc_report,
+            workspaces::worktree_gc_apply,
             workspaces::remove_workspace,
             workspaces::remove_worktree,
             workspaces::rename_worktree,
@@ -275,6 +293,7 @@ pub fn run() {
             git::pull_git,
             git::fetch_git,
             git::sync_git,
+            git::get_workspace_forge,
             git::get_github_issues,
             git::get_github_pull_requests,
             git::get_github_pull_request_diff,
@@ -287,6 +306,22 @@ pub fn run() {
             git::list_git_branches,
             git::checkout_git_branch,
             git::create_git_branch,
+            git::list_git_tags,
+            git::create_git_tag,
+            git::delete_git_tag,
+            git::push_git_tags,
+            git::generate_release_notes,
+            git::create_github_release,
+            git::generate_github_pull_request_content,
+            git::create_github_pull_request,
+            git::update_github_pull_request,
+            git::mark_github_pull_request_ready,
+            git::post_github_pull_request_review,
+            git::get_github_checks,
+            git::watch_github_checks,
+            git::unwatch_github_checks,
+            git::watch_git_status,
+            git::unwatch_git_status,
             codex::model_list,
             codex::experimental_feature_list,
             codex::set_codex_feature_flag,
@@ -295,10 +330,29 @@ pub fn run() {
             codex::create_mcp_server,
             codex::update_mcp_server,
             codex::delete_mcp_server,
+            codex::test_mcp_server,
+            codex::get_effective_config,
+            codex::get_config_profiles,
+            codex::create_config_profile,
+            codex::update_config_profile,
+            codex::delete_config_profile,
+            codex::get_model_providers,
+            codex::create_model_provider,
+            codex::update_model_provider,
+            codex::delete_model_provider,
+            codex::test_model_provider,
+            codex::config_preview_write,
+            codex::config_history_list,
+            codex::config_history_restore,
             codex::set_agents_core_settings,
             codex::create_agent,
             codex::update_agent,
             codex::delete_agent,
+            codex::list_agent_templates,
+            codex::save_agent_template,
+            codex::delete_agent_template,
+            codex::export_agent,
+            codex::import_agent,
             codex::read_agent_config_toml,
             codex::write_agent_config_toml,
             codex::account_rate_limits,
@@ -306,6 +360,12 @@ pub fn run() {
             codex::codex_login,
             codex::codex_login_cancel,
             codex::skills_list,
+            skills::skills_local_list,
+            skills::skills_create,
+            skills::skills_update,
+            skills::skills_set_enabled,
+            skills::skills_delete,
+            skills::skills_install,
             codex::apps_list,
             codex::debug_emit_app_server_event,
             prompts::prompts_list,
@@ -315,6 +375,11 @@ pub fn run() {
             prompts::prompts_move,
             prompts::prompts_workspace_dir,
             prompts::prompts_global_dir,
+            prompts::prompts_history,
+            prompts::prompts_restore_version,
+            prompts::prompts_team_repo,
+            prompts::prompts_team_repo_set,
+            prompts::prompts_team_repo_sync,
             terminal::terminal_open,
             terminal::terminal_write,
             terminal::terminal_resize,
@@ -26,6 +26,7 @@ pub(crate) async fn local_thread_usage_snapshot(
 
     thread_usage_core::local_thread_usage_snapshot_core(
         &state.workspaces,
+        &state.app_settings,
         thread_ids,
         workspace_path,
     )
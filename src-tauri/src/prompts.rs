@@ -1,6 +1,7 @@
 use tauri::State;
 
-use crate::shared::prompts_core::{self, CustomPromptEntry};
+use crate::shared::prompt_repo_core::{PromptVersion, TeamPromptRepo};
+use crate::shared::prompts_core::{self, CustomPromptEntry, PromptVariable};
 use crate::state::AppState;
 
 #[tauri::command]
@@ -8,7 +9,13 @@ pub(crate) async fn prompts_list(
     state: State<'_, AppState>,
     workspace_id: String,
 ) -> Result<Vec<CustomPromptEntry>, String> {
-    prompts_core::prompts_list_core(&state.workspaces, &state.settings_path, workspace_id).await
+    prompts_core::prompts_list_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -25,7 +32,8 @@ pub(crate) async fn prompts_global_dir(
     state: State<'_, AppState>,
     workspace_id: String,
 ) -> Result<String, String> {
-    prompts_core::prompts_global_dir_core(&state.workspaces, workspace_id).await
+    prompts_core::prompts_global_dir_core(&state.workspaces, &state.app_settings, workspace_id)
+        .await
 }
 
 #[tauri::command]
@@ -36,16 +44,19 @@ pub(crate) async fn prompts_create(
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
+    variables: Option<Vec<PromptVariable>>,
     content: String,
 ) -> Result<CustomPromptEntry, String> {
     prompts_core::prompts_create_core(
         &state.workspaces,
+        &state.app_settings,
         &state.settings_path,
         workspace_id,
         scope,
         name,
         description,
         argument_hint,
+        variables,
         content,
     )
     .await
@@ -59,16 +70,19 @@ pub(crate) async fn prompts_update(
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
+    variables: Option<Vec<PromptVariable>>,
     content: String,
 ) -> Result<CustomPromptEntry, String> {
     prompts_core::prompts_update_core(
         &state.workspaces,
+        &state.app_settings,
         &state.settings_path,
         workspace_id,
         path,
         name,
         description,
         argument_hint,
+        variables,
         content,
     )
     .await
@@ -80,8 +94,14 @@ pub(crate) async fn prompts_delete(
     workspace_id: String,
     path: String,
 ) -> Result<(), String> {
-    prompts_core::prompts_delete_core(&state.workspaces, &state.settings_path, workspace_id, path)
-        .await
+    prompts_core::prompts_delete_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+        path,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -93,6 +113,7 @@ pub(crate) async fn prompts_move(
 ) -> Result<CustomPromptEntry, String> {
     prompts_core::prompts_move_core(
         &state.workspaces,
+        &state.app_settings,
         &state.settings_path,
         workspace_id,
         path,
@@ -100,3 +121,59 @@ pub(crate) async fn prompts_move(
     )
     .await
 }
+
+#[tauri::command]
+pub(crate) async fn prompts_history(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+) -> Result<Vec<PromptVersion>, String> {
+    prompts_core::prompts_history_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+        path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_restore_version(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    commit: String,
+) -> Result<CustomPromptEntry, String> {
+    prompts_core::prompts_restore_version_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+        path,
+        commit,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_team_repo(
+    state: State<'_, AppState>,
+) -> Result<TeamPromptRepo, String> {
+    prompts_core::prompts_team_repo_core(&state.settings_path).await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_team_repo_set(
+    state: State<'_, AppState>,
+    url: Option<String>,
+) -> Result<TeamPromptRepo, String> {
+    prompts_core::prompts_team_repo_set_core(&state.settings_path, url).await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_team_repo_sync(
+    state: State<'_, AppState>,
+) -> Result<TeamPromptRepo, String> {
+    prompts_core::prompts_team_repo_sync_core(&state.settings_path).await
+}
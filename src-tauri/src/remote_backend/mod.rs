@@ -156,6 +156,7 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "get_agents_settings"
             | "get_mcp_settings"
             | "get_config_model"
+            | "get_storage_recovery_report"
             | "get_git_commit_diff"
             | "get_git_diffs"
             | "get_git_log"
@@ -165,6 +166,7 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "get_github_pull_request_comments"
             | "get_github_pull_request_diff"
             | "get_github_pull_requests"
+            | "get_workspace_forge"
             | "is_workspace_path_dir"
             | "list_git_branches"
             | "list_git_roots"
@@ -181,7 +183,20 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "thread_live_subscribe"
             | "thread_live_unsubscribe"
             | "skills_list"
+            | "skills_local_list"
             | "worktree_setup_status"
+            | "worktree_setup_log"
+            | "worktree_gc_report"
+            | "list_codex_home_profiles"
+            | "get_effective_config"
+            | "get_config_profiles"
+            | "get_model_providers"
+            | "config_preview_write"
+            | "config_history_list"
+            | "list_agent_templates"
+            | "export_agent"
+            | "list_workspace_agents_files"
+            | "read_workspace_agents_file"
     )
 }
 
@@ -269,6 +284,7 @@ mod tests {
         assert!(can_retry_after_disconnect("list_threads"));
         assert!(can_retry_after_disconnect("local_usage_snapshot"));
         assert!(can_retry_after_disconnect("local_thread_usage_snapshot"));
+        assert!(can_retry_after_disconnect("get_storage_recovery_report"));
         assert!(!can_retry_after_disconnect("send_user_message"));
         assert!(!can_retry_after_disconnect("start_thread"));
         assert!(!can_retry_after_disconnect("remove_workspace"));
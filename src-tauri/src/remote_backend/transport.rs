@@ -141,6 +141,18 @@ pub(crate) async fn dispatch_incoming_line(
             "terminal-exit" => {
                 let _ = app.emit("terminal-exit", params);
             }
+            "github-checks-finished" => {
+                let _ = app.emit("github-checks-finished", params);
+            }
+            "git-status-changed" => {
+                let _ = app.emit("git-status-changed", params);
+            }
+            "worktree-setup-output" => {
+                let _ = app.emit("worktree-setup-output", params);
+            }
+            "worktree-setup-status" => {
+                let _ = app.emit("worktree-setup-status", params);
+            }
             _ => {}
         },
     }
@@ -1,10 +1,13 @@
-use tauri::{State, Window};
+use serde_json::json;
+use tauri::{AppHandle, State, Window};
 
+use crate::remote_backend;
 use crate::shared::settings_core::{
     get_app_settings_core, get_codex_config_path_core, update_app_settings_core,
 };
+use crate::shared::settings_sync_core::{settings_sync_report_core, SettingsSyncReport};
 use crate::state::AppState;
-use crate::types::{AppSettings, BackendMode};
+use crate::types::{AppSettings, BackendMode, StorageRecovery};
 use crate::window;
 
 #[tauri::command]
@@ -12,7 +15,7 @@ pub(crate) async fn get_app_settings(
     state: State<'_, AppState>,
     window: Window,
 ) -> Result<AppSettings, String> {
-    let settings = get_app_settings_core(&state.app_settings).await;
+    let settings = get_app_settings_core(&state.app_settings, &state.settings_sync).await;
     let _ = window::apply_window_appearance(&window, settings.theme.as_str());
     Ok(settings)
 }
@@ -24,8 +27,13 @@ pub(crate) async fn update_app_settings(
     window: Window,
 ) -> Result<AppSettings, String> {
     let previous = state.app_settings.lock().await.clone();
-    let updated =
-        update_app_settings_core(settings, &state.app_settings, &state.settings_path).await?;
+    let updated = update_app_settings_core(
+        settings,
+        &state.app_settings,
+        &state.settings_path,
+        &state.settings_sync,
+    )
+    .await?;
     if should_reset_remote_backend(&previous, &updated) {
         *state.remote_backend.lock().await = None;
     }
@@ -39,6 +47,32 @@ pub(crate) async fn get_codex_config_path() -> Result<String, String> {
     get_codex_config_path_core()
 }
 
+/// Outcome of the last sync between app settings and `config.toml`: adopted
+/// external edits, conflicts and write failures.
+#[tauri::command]
+pub(crate) async fn get_settings_sync_report(
+    state: State<'_, AppState>,
+) -> Result<SettingsSyncReport, String> {
+    Ok(settings_sync_report_core(&state.settings_sync).await)
+}
+
+/// Local storage files that were unreadable at startup, with the backup they
+/// were restored from or a note that defaults were used. In remote mode the
+/// report comes from the daemon, which owns the workspace storage.
+#[tauri::command]
+pub(crate) async fn get_storage_recovery_report(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<StorageRecovery>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_storage_recovery_report", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    Ok(state.storage_recoveries.clone())
+}
+
 fn should_reset_remote_backend(previous: &AppSettings, updated: &AppSettings) -> bool {
     let backend_mode_changed = !matches!(
         (&previous.backend_mode, &updated.backend_mode),
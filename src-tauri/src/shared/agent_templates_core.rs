@@ -0,0 +1,517 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use toml_edit::{value, Document};
+
+use crate::shared::agents_config_core::{self, AgentsSettingsDto};
+use crate::shared::config_profiles_core::REASONING_EFFORTS;
+use crate::shared::config_validation_core;
+
+pub(crate) const TEMPLATE_BLANK: &str = "blank";
+const BUNDLE_FORMAT: &str = "codex-monitor-agent";
+const BUNDLE_VERSION: u32 = 1;
+const BUNDLE_EXTENSION: &str = "codex-agent.json";
+const MAX_BUNDLE_BYTES: usize = 256 * 1024;
+const MAX_TEMPLATE_ID_LEN: usize = 48;
+/// Keys that can launch processes; a shared bundle must not bring them along.
+const BUNDLE_FORBIDDEN_KEYS: &[&str] = &["agents", "mcp_servers", "notify"];
+
+static TEMPLATES_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentTemplateDto {
+    pub id: String,
+    pub label: String,
+    /// Becomes the role description when the agent is created without one.
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<String>,
+    pub developer_instructions: Option<String>,
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveAgentTemplateInput {
+    pub id: String,
+    pub label: String,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<String>,
+    pub developer_instructions: Option<String>,
+}
+
+/// Single-file, shareable form of a managed agent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentBundle {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    pub description: Option<String>,
+    pub developer_instructions: Option<String>,
+    /// The agent's config file without `developer_instructions`.
+    pub config_toml: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentBundleExport {
+    pub file_name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportAgentInput {
+    pub content: String,
+    /// Installs the bundle under a different name, e.g. to avoid a conflict.
+    pub name: Option<String>,
+}
+
+struct BuiltinTemplate {
+    id: &'static str,
+    label: &'static str,
+    description: Option<&'static str>,
+    reasoning_effort: Option<&'static str>,
+    developer_instructions: Option<&'static str>,
+}
+
+const BUILTIN_TEMPLATES: &[BuiltinTemplate] = &[
+    BuiltinTemplate {
+        id: TEMPLATE_BLANK,
+        label: "Blank",
+        description: None,
+        reasoning_effort: None,
+        developer_instructions: None,
+    },
+    BuiltinTemplate {
+        id: "reviewer",
+        label: "Code reviewer",
+        description: Some("Reviews diffs for bugs, regressions and missing tests"),
+        reasoning_effort: Some("high"),
+        developer_instructions: Some(
+            "Review the changes you are given; do not edit files.\n\
+             Report correctness bugs, behaviour regressions, security issues and missing tests first, ordered by severity, each with a file and line reference.\n\
+             Mention style only when it hides a real problem.",
+        ),
+    },
+    BuiltinTemplate {
+        id: "test-writer",
+        label: "Test writer",
+        description: Some("Adds focused tests that follow the project's conventions"),
+        reasoning_effort: Some("medium"),
+        developer_instructions: Some(
+            "Write tests for the behaviour you are pointed at.\n\
+             Match the existing test layout, helpers and naming; cover edge cases and failure paths, not just the happy path.\n\
+             Run the tests you add and report anything that still fails.",
+        ),
+    },
+    BuiltinTemplate {
+        id: "explorer",
+        label: "Codebase explorer",
+        description: Some("Answers questions about the codebase without changing it"),
+        reasoning_effort: Some("low"),
+        developer_instructions: Some(
+            "Investigate the codebase to answer the question; do not modify files.\n\
+             Cite the files and symbols your answer relies on and say what you could not confirm.",
+        ),
+    },
+];
+
+/// Sets where user templates are stored. Until this is called only the
+/// built-in templates are available.
+pub(crate) fn init_agent_templates(root: PathBuf) {
+    TEMPLATES_ROOT.get_or_init(|| root);
+}
+
+/// Built-in templates first, then user templates by id.
+pub(crate) fn list_agent_templates_core() -> Result<Vec<AgentTemplateDto>, String> {
+    let mut templates: Vec<AgentTemplateDto> = BUILTIN_TEMPLATES.iter().map(builtin_dto).collect();
+    if let Some(root) = TEMPLATES_ROOT.get() {
+        templates.extend(read_user_templates(root)?);
+    }
+    Ok(templates)
+}
+
+/// Looks up `id` (blank when empty) among built-in and user templates.
+pub(crate) fn resolve_agent_template(id: Option<&str>) -> Result<AgentTemplateDto, String> {
+    let id = id
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .unwrap_or(TEMPLATE_BLANK);
+    if let Some(builtin) = BUILTIN_TEMPLATES.iter().find(|template| template.id == id) {
+        return Ok(builtin_dto(builtin));
+    }
+    let root = TEMPLATES_ROOT
+        .get()
+        .ok_or_else(|| format!("Unknown agent template `{id}`"))?;
+    read_user_templates(root)?
+        .into_iter()
+        .find(|template| template.id == id)
+        .ok_or_else(|| format!("Unknown agent template `{id}`"))
+}
+
+pub(crate) fn save_agent_template_core(
+    input: SaveAgentTemplateInput,
+) -> Result<Vec<AgentTemplateDto>, String> {
+    let root = templates_root()?;
+    save_user_template(root, input)?;
+    list_agent_templates_core()
+}
+
+pub(crate) fn delete_agent_template_core(id: &str) -> Result<Vec<AgentTemplateDto>, String> {
+    let root = templates_root()?;
+    delete_user_template(root, id)?;
+    list_agent_templates_core()
+}
+
+pub(crate) fn export_agent_core(name: &str) -> Result<AgentBundleExport, String> {
+    let settings = agents_config_core::get_agents_settings_core()?;
+    let name = name.trim();
+    let agent = settings
+        .agents
+        .into_iter()
+        .find(|agent| agent.name == name)
+        .ok_or_else(|| format!("agent '{name}' not found"))?;
+    if !agent.file_exists {
+        return Err(format!("agent '{name}' has no config file to export"));
+    }
+    let config_toml = agents_config_core::read_agent_config_toml_core(&agent.name)?;
+    let bundle = build_bundle(&agent.name, agent.description, &config_toml)?;
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|err| format!("Failed to serialize agent bundle: {err}"))?;
+    Ok(AgentBundleExport {
+        file_name: format!("{}.{BUNDLE_EXTENSION}", agent.name),
+        content,
+    })
+}
+
+pub(crate) fn import_agent_core(input: ImportAgentInput) -> Result<AgentsSettingsDto, String> {
+    let bundle = parse_bundle(&input.content)?;
+    let name = input
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(bundle.name.as_str());
+    let name = agents_config_core::normalize_agent_name(name)?;
+    let content = render_bundle_config(&bundle)?;
+    agents_config_core::install_managed_agent_core(&name, bundle.description, &content)
+}
+
+fn templates_root() -> Result<&'static Path, String> {
+    TEMPLATES_ROOT
+        .get()
+        .map(PathBuf::as_path)
+        .ok_or_else(|| "Agent templates are not available".to_string())
+}
+
+fn builtin_dto(template: &BuiltinTemplate) -> AgentTemplateDto {
+    AgentTemplateDto {
+        id: template.id.to_string(),
+        label: template.label.to_string(),
+        description: template.description.map(str::to_string),
+        model: None,
+        reasoning_effort: template.reasoning_effort.map(str::to_string),
+        developer_instructions: template.developer_instructions.map(str::to_string),
+        built_in: true,
+    }
+}
+
+fn normalize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn validate_template_id(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("template id is required".to_string());
+    }
+    if id.len() > MAX_TEMPLATE_ID_LEN {
+        return Err(format!(
+            "template id must be {MAX_TEMPLATE_ID_LEN} characters or fewer"
+        ));
+    }
+    if !id.starts_with(|ch: char| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+        || !id
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+    {
+        return Err("template id must use only lowercase letters, digits, '_' or '-'".to_string());
+    }
+    Ok(())
+}
+
+fn read_user_templates(root: &Path) -> Result<Vec<AgentTemplateDto>, String> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("Failed to read agent templates: {err}")),
+    };
+    let mut templates = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let raw = std::fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to read agent template: {err}"))?;
+        let mut template: AgentTemplateDto = serde_json::from_str(&raw).map_err(|err| {
+            format!(
+                "Invalid agent template {}: {err}",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            )
+        })?;
+        template.built_in = false;
+        templates.push(template);
+    }
+    templates.sort_by(|left, right| left.id.cmp(&right.id));
+    Ok(templates)
+}
+
+fn save_user_template(root: &Path, input: SaveAgentTemplateInput) -> Result<(), String> {
+    let id = input.id.trim().to_string();
+    validate_template_id(&id)?;
+    if BUILTIN_TEMPLATES.iter().any(|template| template.id == id) {
+        return Err(format!(
+            "`{id}` is a built-in template and cannot be changed"
+        ));
+    }
+    let label = input.label.trim().to_string();
+    if label.is_empty() {
+        return Err("template label is required".to_string());
+    }
+    let reasoning_effort = normalize_optional(input.reasoning_effort);
+    if let Some(effort) = reasoning_effort.as_deref() {
+        if !REASONING_EFFORTS.contains(&effort) {
+            return Err(format!(
+                "reasoning effort must be one of: {}",
+                REASONING_EFFORTS.join(", ")
+            ));
+        }
+    }
+    let template = AgentTemplateDto {
+        id: id.clone(),
+        label,
+        description: normalize_optional(input.description),
+        model: normalize_optional(input.model),
+        reasoning_effort,
+        developer_instructions: normalize_optional(input.developer_instructions),
+        built_in: false,
+    };
+    let content = serde_json::to_string_pretty(&template)
+        .map_err(|err| format!("Failed to serialize agent template: {err}"))?;
+    std::fs::create_dir_all(root)
+        .map_err(|err| format!("Failed to create agent templates folder: {err}"))?;
+    std::fs::write(root.join(format!("{id}.json")), content)
+        .map_err(|err| format!("Failed to save agent template: {err}"))
+}
+
+fn delete_user_template(root: &Path, id: &str) -> Result<(), String> {
+    let id = id.trim();
+    validate_template_id(id)?;
+    if BUILTIN_TEMPLATES.iter().any(|template| template.id == id) {
+        return Err(format!(
+            "`{id}` is a built-in template and cannot be deleted"
+        ));
+    }
+    match std::fs::remove_file(root.join(format!("{id}.json"))) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            Err(format!("Unknown agent template `{id}`"))
+        }
+        Err(err) => Err(format!("Failed to delete agent template: {err}")),
+    }
+}
+
+fn build_bundle(
+    name: &str,
+    description: Option<String>,
+    config_toml: &str,
+) -> Result<AgentBundle, String> {
+    let mut document = parse_bundle_toml(config_toml)?;
+    let developer_instructions = document
+        .remove("developer_instructions")
+        .and_then(|item| item.as_str().map(str::to_string));
+    Ok(AgentBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        name: name.to_string(),
+        description,
+        developer_instructions,
+        config_toml: document.to_string(),
+    })
+}
+
+/// Parses and validates an exported bundle before anything touches disk.
+fn parse_bundle(content: &str) -> Result<AgentBundle, String> {
+    if content.len() > MAX_BUNDLE_BYTES {
+        return Err(format!(
+            "Agent bundle is larger than {} KiB",
+            MAX_BUNDLE_BYTES / 1024
+        ));
+    }
+    let bundle: AgentBundle = serde_json::from_str(content)
+        .map_err(|err| format!("Agent bundle is not valid JSON: {err}"))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!(
+            "Not an agent bundle (expected format `{BUNDLE_FORMAT}`)"
+        ));
+    }
+    if bundle.version == 0 || bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Agent bundle version {} is not supported; update CodexMonitor to import it",
+            bundle.version
+        ));
+    }
+    agents_config_core::normalize_agent_name(&bundle.name)?;
+
+    let document = parse_bundle_toml(&bundle.config_toml)?;
+    for key in BUNDLE_FORBIDDEN_KEYS {
+        if document.contains_key(key) {
+            return Err(format!(
+                "Agent bundle config must not define `{key}`; add it after importing"
+            ));
+        }
+    }
+    let errors: Vec<_> = config_validation_core::validate_config_toml(&bundle.config_toml)
+        .into_iter()
+        .filter(|diagnostic| {
+            diagnostic.severity == config_validation_core::ConfigDiagnosticSeverity::Error
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(config_validation_core::format_diagnostics(
+            "Agent bundle config",
+            &errors,
+        ));
+    }
+    Ok(bundle)
+}
+
+fn parse_bundle_toml(contents: &str) -> Result<Document, String> {
+    contents
+        .parse::<Document>()
+        .map_err(|err| format!("Agent config is not valid TOML: {err}"))
+}
+
+fn render_bundle_config(bundle: &AgentBundle) -> Result<String, String> {
+    let mut document = parse_bundle_toml(&bundle.config_toml)?;
+    if let Some(instructions) = normalize_optional(bundle.developer_instructions.clone()) {
+        document["developer_instructions"] = value(instructions);
+    }
+    let mut rendered = document.to_string();
+    if !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+
+    fn template_input(id: &str) -> SaveAgentTemplateInput {
+        SaveAgentTemplateInput {
+            id: id.to_string(),
+            label: "Migration helper".to_string(),
+            description: Some(" Writes schema migrations ".to_string()),
+            model: Some("gpt-5".to_string()),
+            reasoning_effort: Some("high".to_string()),
+            developer_instructions: None,
+        }
+    }
+
+    #[test]
+    fn user_templates_round_trip_and_protect_builtins() {
+        let root = temp_dir("agent-templates");
+
+        save_user_template(&root, template_input("migrations")).expect("save template");
+        let templates = read_user_templates(&root).expect("read templates");
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id, "migrations");
+        assert_eq!(
+            templates[0].description.as_deref(),
+            Some("Writes schema migrations")
+        );
+        assert!(!templates[0].built_in);
+
+        assert!(save_user_template(&root, template_input("reviewer")).is_err());
+        assert!(save_user_template(&root, template_input("../escape")).is_err());
+        let mut bad_effort = template_input("bad-effort");
+        bad_effort.reasoning_effort = Some("extreme".to_string());
+        assert!(save_user_template(&root, bad_effort).is_err());
+
+        delete_user_template(&root, "migrations").expect("delete template");
+        assert!(read_user_templates(&root).expect("read").is_empty());
+        assert!(delete_user_template(&root, TEMPLATE_BLANK).is_err());
+    }
+
+    #[test]
+    fn builtin_templates_resolve_without_a_templates_folder() {
+        assert_eq!(
+            resolve_agent_template(None).expect("blank").id,
+            TEMPLATE_BLANK
+        );
+        let reviewer = resolve_agent_template(Some("reviewer")).expect("reviewer");
+        assert!(reviewer.built_in);
+        assert_eq!(reviewer.reasoning_effort.as_deref(), Some("high"));
+        assert!(resolve_agent_template(Some("does-not-exist")).is_err());
+    }
+
+    #[test]
+    fn bundles_round_trip_config_and_instructions() {
+        let config = "# Agent-specific overrides\nmodel = \"gpt-5\"\nmodel_reasoning_effort = \"high\"\ndeveloper_instructions = \"Review carefully.\"\n";
+        let bundle = build_bundle("reviewer", Some("Reviews diffs".to_string()), config)
+            .expect("build bundle");
+        assert_eq!(
+            bundle.developer_instructions.as_deref(),
+            Some("Review carefully.")
+        );
+        assert!(!bundle.config_toml.contains("developer_instructions"));
+
+        let serialized = serde_json::to_string(&bundle).expect("serialize");
+        let parsed = parse_bundle(&serialized).expect("parse bundle");
+        assert_eq!(parsed, bundle);
+        let rendered = render_bundle_config(&parsed).expect("render");
+        assert!(rendered.contains("model = \"gpt-5\""));
+        assert!(rendered.contains("developer_instructions = \"Review carefully.\""));
+    }
+
+    #[test]
+    fn bundle_validation_rejects_unsafe_or_invalid_content() {
+        let bundle = |name: &str, config_toml: &str| {
+            serde_json::to_string(&AgentBundle {
+                format: BUNDLE_FORMAT.to_string(),
+                version: BUNDLE_VERSION,
+                name: name.to_string(),
+                description: None,
+                developer_instructions: None,
+                config_toml: config_toml.to_string(),
+            })
+            .expect("serialize")
+        };
+
+        assert!(parse_bundle(&bundle("ok", "model = \"gpt-5\"\n")).is_ok());
+        assert!(parse_bundle(&bundle("../escape", "model = \"gpt-5\"\n")).is_err());
+        assert!(parse_bundle(&bundle("ok", "model = [\n")).is_err());
+        assert!(parse_bundle(&bundle("ok", "sandbox_mode = \"yolo\"\n"))
+            .expect_err("invalid sandbox mode")
+            .starts_with("Agent bundle config was not saved"));
+        assert!(
+            parse_bundle(&bundle("ok", "[mcp_servers.shell]\ncommand = \"sh\"\n"))
+                .expect_err("mcp servers")
+                .contains("`mcp_servers`")
+        );
+        assert!(parse_bundle("{\"format\":\"other\"}").is_err());
+        let future = bundle("ok", "").replace("\"version\":1", "\"version\":2");
+        assert!(parse_bundle(&future)
+            .expect_err("future version")
+            .contains("not supported"));
+    }
+}
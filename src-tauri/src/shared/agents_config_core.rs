@@ -5,7 +5,7 @@ use std::path::{Component, Path, PathBuf};
 use toml_edit::{value, Document, Item, Table};
 
 use crate::codex::home as codex_home;
-use crate::shared::config_toml_core;
+use crate::shared::{agent_templates_core, config_history_core, config_toml_core};
 
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: u32 = 6;
 pub(crate) const DEFAULT_AGENT_MAX_DEPTH: u32 = 1;
@@ -14,7 +14,6 @@ const MAX_AGENT_MAX_THREADS: u32 = 12;
 const MIN_AGENT_MAX_DEPTH: u32 = 1;
 const MAX_AGENT_MAX_DEPTH: u32 = 4;
 const MANAGED_AGENTS_DIR: &str = "agents";
-const TEMPLATE_BLANK: &str = "blank";
 const DEFAULT_AGENT_MODEL: &str = "gpt-5-codex";
 const DEFAULT_REASONING_EFFORT: &str = "medium";
 
@@ -126,10 +125,36 @@ pub(crate) fn set_agents_core_settings_core(
 }
 
 pub(crate) fn create_agent_core(input: CreateAgentInput) -> Result<AgentsSettingsDto, String> {
-    let name = normalize_agent_name(input.name.as_str())?;
-    let description = normalize_optional_string(input.description.as_deref());
-    let developer_instructions = normalize_optional_string(input.developer_instructions.as_deref());
+    let template = agent_templates_core::resolve_agent_template(input.template.as_deref())?;
+    let description = normalize_optional_string(input.description.as_deref())
+        .or_else(|| template.description.clone());
+    let developer_instructions = normalize_optional_string(input.developer_instructions.as_deref())
+        .or_else(|| template.developer_instructions.clone());
 
+    let codex_home = resolve_codex_home()?;
+    let (_, document) = config_toml_core::load_global_config_document(&codex_home)?;
+    // Without an explicit or template model, new agents follow the global one.
+    let model = normalize_optional_string(input.model.as_deref())
+        .or_else(|| template.model.clone())
+        .or_else(|| config_toml_core::read_top_level_string(&document, "model"));
+    let reasoning_effort = normalize_optional_string(input.reasoning_effort.as_deref())
+        .or_else(|| template.reasoning_effort.clone());
+    let template_content = build_template_content(
+        model.as_deref(),
+        reasoning_effort.as_deref(),
+        developer_instructions.as_deref(),
+    );
+    install_managed_agent_core(input.name.as_str(), description, &template_content)
+}
+
+/// Writes `agents/<name>.toml` with `content` and registers the role in
+/// `config.toml`. The file is removed again if the registration fails.
+pub(crate) fn install_managed_agent_core(
+    raw_name: &str,
+    description: Option<String>,
+    content: &str,
+) -> Result<AgentsSettingsDto, String> {
+    let name = normalize_agent_name(raw_name)?;
     let codex_home = resolve_codex_home()?;
     let (_, mut document) = config_toml_core::load_global_config_document(&codex_home)?;
 
@@ -148,13 +173,8 @@ pub(crate) fn create_agent_core(input: CreateAgentInput) -> Result<AgentsSetting
             target_path.display()
         ));
     }
-    let template_content = build_template_content(
-        input.template.as_deref(),
-        input.model.as_deref(),
-        input.reasoning_effort.as_deref(),
-        developer_instructions.as_deref(),
-    );
-    std::fs::write(&target_path, template_content)
+    config_history_core::guard_config_write(&target_path, None, content)?;
+    std::fs::write(&target_path, content)
         .map_err(|err| format!("Failed to create agent config file: {err}"))?;
 
     {
@@ -376,9 +396,24 @@ pub(crate) fn read_agent_config_toml_core(agent_name: &str) -> Result<String, St
 pub(crate) fn write_agent_config_toml_core(agent_name: &str, content: &str) -> Result<(), String> {
     let (codex_home, relative_path) = resolve_managed_agent_config_relative_path(agent_name)?;
     let path = resolve_safe_managed_abs_path_for_write(&codex_home, &relative_path)?;
+    let previous = if path.exists() {
+        Some(
+            std::fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read agent config file: {err}"))?,
+        )
+    } else {
+        None
+    };
+    config_history_core::guard_config_write(&path, previous.as_deref(), content)?;
     std::fs::write(path, content).map_err(|err| format!("Failed to write agent config file: {err}"))
 }
 
+/// Absolute path of the agent's managed config file, whether or not it exists.
+pub(crate) fn agent_config_path_core(agent_name: &str) -> Result<PathBuf, String> {
+    let (codex_home, relative_path) = resolve_managed_agent_config_relative_path(agent_name)?;
+    resolve_safe_managed_abs_path_for_read(&codex_home, &relative_path)
+}
+
 fn resolve_codex_home() -> Result<PathBuf, String> {
     codex_home::resolve_default_codex_home()
         .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
@@ -473,7 +508,7 @@ fn resolve_config_file_path_for_display(codex_home: &Path, raw_value: &str) -> O
     Some(codex_home.join(normalized_relative))
 }
 
-fn normalize_agent_name(raw_name: &str) -> Result<String, String> {
+pub(crate) fn normalize_agent_name(raw_name: &str) -> Result<String, String> {
     let mut name = String::new();
     let mut previous_was_space = false;
     for char in raw_name.trim().to_ascii_lowercase().chars() {
@@ -794,18 +829,21 @@ fn upsert_developer_instructions_in_agent_config_file(
     if !rendered.ends_with('\n') {
         rendered.push('\n');
     }
+    config_history_core::guard_config_write(
+        path,
+        previous.is_some().then_some(existing),
+        &rendered,
+    )?;
     std::fs::write(path, rendered)
         .map_err(|err| format!("Failed to update agent config file: {err}"))?;
     Ok(previous)
 }
 
 fn build_template_content(
-    template: Option<&str>,
     model: Option<&str>,
     reasoning_effort: Option<&str>,
     developer_instructions: Option<&str>,
 ) -> String {
-    let template = template.map(str::trim).unwrap_or(TEMPLATE_BLANK);
     let model = normalize_optional_string(model).unwrap_or_else(|| DEFAULT_AGENT_MODEL.to_string());
     let reasoning_effort = normalize_optional_string(reasoning_effort)
         .unwrap_or_else(|| DEFAULT_REASONING_EFFORT.to_string());
@@ -819,20 +857,10 @@ fn build_template_content(
             overrides["developer_instructions"] = value(normalized);
         }
     }
-    match template {
-        TEMPLATE_BLANK => {
-            let mut rendered = String::from("# Agent-specific overrides\n");
-            rendered.push_str(overrides.to_string().as_str());
-            rendered.push('\n');
-            rendered
-        }
-        _ => {
-            let mut rendered = String::from("# Agent-specific overrides\n");
-            rendered.push_str(overrides.to_string().as_str());
-            rendered.push('\n');
-            rendered
-        }
-    }
+    let mut rendered = String::from("# Agent-specific overrides\n");
+    rendered.push_str(overrides.to_string().as_str());
+    rendered.push('\n');
+    rendered
 }
 
 fn pathbuf_to_string(path: &Path) -> Result<String, String> {
@@ -1008,7 +1036,7 @@ config_file = "agents/researcher.toml"
 
     #[test]
     fn build_template_content_uses_provided_model_and_reasoning() {
-        let content = build_template_content(Some("blank"), Some("gpt-5.1"), Some("high"), None);
+        let content = build_template_content(Some("gpt-5.1"), Some("high"), None);
         assert!(content.contains("model = \"gpt-5.1\""));
         assert!(content.contains("model_reasoning_effort = \"high\""));
     }
@@ -1016,7 +1044,6 @@ config_file = "agents/researcher.toml"
     #[test]
     fn build_template_content_includes_developer_instructions() {
         let content = build_template_content(
-            Some("blank"),
             Some("gpt-5.1"),
             Some("medium"),
             Some("Investigate root causes first.\nCall out risks."),
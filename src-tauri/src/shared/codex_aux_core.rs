@@ -20,6 +20,21 @@ Keep the summary line under 72 characters. \
 Only output the commit message, nothing else.\n\n\
 Changes:\n{diff}";
 
+const DEFAULT_PULL_REQUEST_PROMPT: &str =
+    "Write a GitHub pull request title and description for the following branch changes. \
+Put the title on the first line, under 72 characters, without a conventional commit prefix. \
+Leave a blank line, then write the description in markdown: a short summary paragraph \
+followed by a bulleted list of the notable changes. \
+Only output the title and description, nothing else.\n\n\
+Changes:\n{diff}";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GeneratedPullRequestContent {
+    pub title: String,
+    pub body: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GeneratedAgentConfiguration {
@@ -50,6 +65,33 @@ pub(crate) fn build_commit_message_prompt_for_diff(
     Ok(build_commit_message_prompt(diff, template))
 }
 
+pub(crate) fn parse_pull_request_content(raw: &str) -> Result<GeneratedPullRequestContent, String> {
+    let cleaned = raw
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut lines = cleaned.trim().lines();
+    let title = lines
+        .next()
+        .map(|line| {
+            let line = line.trim().trim_start_matches('#').trim();
+            line.strip_prefix("Title:")
+                .unwrap_or(line)
+                .trim()
+                .to_string()
+        })
+        .filter(|title| !title.is_empty())
+        .ok_or_else(|| "No pull request title was generated".to_string())?;
+    let body = lines.collect::<Vec<_>>().join("\n");
+    let body = body.trim();
+    let body = body.strip_prefix("Description:").unwrap_or(body).trim();
+    Ok(GeneratedPullRequestContent {
+        title,
+        body: body.to_string(),
+    })
+}
+
 pub(crate) fn build_run_metadata_prompt(cleaned_prompt: &str) -> String {
     format!(
         "You create concise run metadata for a coding task.\n\
@@ -234,6 +276,38 @@ pub(crate) fn extract_json_value(raw: &str) -> Option<Value> {
     serde_json::from_str::<Value>(&raw[start..=end]).ok()
 }
 
+/// Thread id from a `thread/start` response, surfacing the server error when
+/// the thread could not be started.
+pub(crate) fn thread_id_from_start_response(thread_result: &Value) -> Result<String, String> {
+    if let Some(error) = thread_result.get("error") {
+        let error_msg = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error starting thread");
+        return Err(error_msg.to_string());
+    }
+
+    thread_result
+        .get("result")
+        .and_then(|r| r.get("threadId"))
+        .or_else(|| {
+            thread_result
+                .get("result")
+                .and_then(|r| r.get("thread"))
+                .and_then(|t| t.get("id"))
+        })
+        .or_else(|| thread_result.get("threadId"))
+        .or_else(|| thread_result.get("thread").and_then(|t| t.get("id")))
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| {
+            format!(
+                "Failed to get threadId from thread/start response: {:?}",
+                thread_result
+            )
+        })
+        .map(str::to_string)
+}
+
 pub(crate) fn sanitize_run_worktree_name(value: &str) -> String {
     let trimmed = value.trim().to_lowercase();
     let mut cleaned = String::new();
@@ -427,33 +501,7 @@ where
         .send_request_for_workspace(&workspace_id, "thread/start", thread_params)
         .await?;
 
-    if let Some(error) = thread_result.get("error") {
-        let error_msg = error
-            .get("message")
-            .and_then(|m| m.as_str())
-            .unwrap_or("Unknown error starting thread");
-        return Err(error_msg.to_string());
-    }
-
-    let thread_id = thread_result
-        .get("result")
-        .and_then(|r| r.get("threadId"))
-        .or_else(|| {
-            thread_result
-                .get("result")
-                .and_then(|r| r.get("thread"))
-                .and_then(|t| t.get("id"))
-        })
-        .or_else(|| thread_result.get("threadId"))
-        .or_else(|| thread_result.get("thread").and_then(|t| t.get("id")))
-        .and_then(|t| t.as_str())
-        .ok_or_else(|| {
-            format!(
-                "Failed to get threadId from thread/start response: {:?}",
-                thread_result
-            )
-        })?
-        .to_string();
+    let thread_id = thread_id_from_start_response(&thread_result)?;
 
     on_hide_thread(&workspace_id, &thread_id);
 
@@ -592,6 +640,34 @@ where
     .await
 }
 
+pub(crate) async fn generate_pull_request_content_core<F>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    diff: &str,
+    model: Option<&str>,
+    on_hide_thread: F,
+) -> Result<GeneratedPullRequestContent, String>
+where
+    F: Fn(&str, &str),
+{
+    let prompt = build_commit_message_prompt_for_diff(diff, DEFAULT_PULL_REQUEST_PROMPT)?;
+    let response = run_background_prompt_core(
+        sessions,
+        workspaces,
+        workspace_id,
+        prompt,
+        model,
+        None,
+        on_hide_thread,
+        "Timeout waiting for pull request generation",
+        "Unknown error during pull request generation",
+    )
+    .await?;
+
+    parse_pull_request_content(&response)
+}
+
 pub(crate) async fn generate_run_metadata_core<F>(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
@@ -705,7 +781,7 @@ where
 mod tests {
     use super::{
         build_commit_message_prompt_for_diff, build_predict_response_prompt,
-        parse_agent_description_value, parse_run_metadata_value,
+        parse_agent_description_value, parse_pull_request_content, parse_run_metadata_value,
     };
 
     #[test]
@@ -717,6 +793,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_pull_request_content_splits_title_and_body() {
+        let parsed = parse_pull_request_content(
+            "```markdown\n# Add tag management\n\nAdds tag commands.\n\n- List tags\n```",
+        )
+        .expect("parse");
+        assert_eq!(parsed.title, "Add tag management");
+        assert_eq!(parsed.body, "Adds tag commands.\n\n- List tags");
+        assert!(parse_pull_request_content("   ").is_err());
+    }
+
     #[test]
     fn parse_run_metadata_value_normalizes_worktree_name_alias() {
         let raw =
@@ -13,10 +13,10 @@ use tokio::time::Instant;
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::config as codex_config;
-use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::codex::home::resolve_workspace_codex_home;
 use crate::rules;
 use crate::shared::account::{build_account_response, read_auth_account};
-use crate::types::WorkspaceEntry;
+use crate::types::{AppSettings, WorkspaceEntry};
 
 const LOGIN_START_TIMEOUT: Duration = Duration::from_secs(30);
 #[allow(dead_code)]
@@ -144,13 +144,14 @@ async fn resolve_workspace_and_parent(
     Ok((entry, parent_entry))
 }
 
-async fn resolve_codex_home_for_workspace_core(
+pub(crate) async fn resolve_codex_home_for_workspace_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     workspace_id: &str,
 ) -> Result<PathBuf, String> {
     let (entry, parent_entry) = resolve_workspace_and_parent(workspaces, workspace_id).await?;
-    resolve_workspace_codex_home(&entry, parent_entry.as_ref())
-        .or_else(resolve_default_codex_home)
+    let settings = app_settings.lock().await;
+    resolve_workspace_codex_home(&entry, parent_entry.as_ref(), Some(&settings))
         .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
 }
 
@@ -489,6 +490,34 @@ pub(crate) async fn start_review_core(
         .await
 }
 
+/// Returns the rendered output of the most recent completed review in a thread.
+pub(crate) async fn latest_review_output_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+) -> Result<String, String> {
+    let response = resume_thread_core(sessions, workspace_id, thread_id).await?;
+    latest_review_output(&response)
+        .ok_or_else(|| "No completed review found in this thread.".to_string())
+}
+
+fn latest_review_output(response: &Value) -> Option<String> {
+    let payload = response.get("result").unwrap_or(response);
+    let thread = payload.get("thread").unwrap_or(payload);
+    thread
+        .get("turns")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter_map(|turn| turn.get("items").and_then(Value::as_array))
+        .flat_map(|items| items.iter().rev())
+        .find(|item| item.get("type").and_then(Value::as_str) == Some("exitedReviewMode"))
+        .and_then(|item| item.get("review").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|review| !review.is_empty())
+        .map(str::to_string)
+}
+
 pub(crate) async fn model_list_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -525,6 +554,7 @@ pub(crate) async fn account_rate_limits_core(
 pub(crate) async fn account_read_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     workspace_id: String,
 ) -> Result<Value, String> {
     let session = {
@@ -541,8 +571,10 @@ pub(crate) async fn account_read_core(
     };
 
     let (entry, parent_entry) = resolve_workspace_and_parent(workspaces, &workspace_id).await?;
-    let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref())
-        .or_else(resolve_default_codex_home);
+    let codex_home = {
+        let settings = app_settings.lock().await;
+        resolve_workspace_codex_home(&entry, parent_entry.as_ref(), Some(&settings))
+    };
     let fallback = read_auth_account(codex_home);
 
     Ok(build_account_response(response, fallback))
@@ -755,6 +787,7 @@ pub(crate) async fn respond_to_server_request_core(
 
 pub(crate) async fn remember_approval_rule_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     workspace_id: String,
     command: Vec<String>,
 ) -> Result<Value, String> {
@@ -767,7 +800,8 @@ pub(crate) async fn remember_approval_rule_core(
         return Err("empty command".to_string());
     }
 
-    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let codex_home =
+        resolve_codex_home_for_workspace_core(workspaces, app_settings, &workspace_id).await?;
     let rules_path = rules::default_rules_path(&codex_home);
     rules::append_prefix_rule(&rules_path, &command)?;
 
@@ -779,9 +813,11 @@ pub(crate) async fn remember_approval_rule_core(
 
 pub(crate) async fn get_config_model_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     workspace_id: String,
 ) -> Result<Value, String> {
-    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let codex_home =
+        resolve_codex_home_for_workspace_core(workspaces, app_settings, &workspace_id).await?;
     let model = codex_config::read_config_model(Some(codex_home))?;
     Ok(json!({ "model": model }))
 }
@@ -790,6 +826,34 @@ pub(crate) async fn get_config_model_core(
 mod tests {
     use super::*;
 
+    #[test]
+    fn latest_review_output_reads_last_exited_review_item() {
+        let response = json!({
+            "result": {
+                "thread": {
+                    "turns": [
+                        { "items": [{ "type": "exitedReviewMode", "review": "Old review" }] },
+                        {
+                            "items": [
+                                { "type": "exitedReviewMode", "review": " New review " },
+                                { "type": "agentMessage", "text": "Done" }
+                            ]
+                        },
+                        { "items": [{ "type": "userMessage" }] }
+                    ]
+                }
+            }
+        });
+        assert_eq!(
+            latest_review_output(&response).as_deref(),
+            Some("New review")
+        );
+        assert_eq!(
+            latest_review_output(&json!({ "thread": { "turns": [] } })),
+            None
+        );
+    }
+
     #[test]
     fn normalize_strips_file_uri_prefix() {
         assert_eq!(
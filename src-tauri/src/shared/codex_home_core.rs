@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::codex::home::{normalize_codex_home, resolve_workspace_codex_home};
+use crate::shared::workspaces_core::reconnect_workspaces_with_changed_runtime;
+use crate::storage::{write_settings, write_workspaces};
+use crate::types::{
+    AppSettings, CodexHomeProfile, CodexHomeProfileInfo, CodexHomeScope, CodexHomeSwitchResult,
+    WorkspaceEntry,
+};
+
+/// Default location of new profiles, under the app data directory.
+const PROFILES_DIR: &str = "codex-homes";
+
+fn profile_path(profile: &CodexHomeProfile) -> PathBuf {
+    normalize_codex_home(&profile.path).unwrap_or_else(|| PathBuf::from(&profile.path))
+}
+
+fn profile_info(
+    profile: &CodexHomeProfile,
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
+) -> CodexHomeProfileInfo {
+    let path = profile_path(profile);
+    let mut workspace_ids = workspaces
+        .values()
+        .filter(|entry| entry.settings.codex_home_profile_id.as_deref() == Some(&profile.id))
+        .map(|entry| entry.id.clone())
+        .collect::<Vec<_>>();
+    workspace_ids.sort();
+    let group_ids = settings
+        .workspace_groups
+        .iter()
+        .filter(|group| group.codex_home_profile_id.as_deref() == Some(&profile.id))
+        .map(|group| group.id.clone())
+        .collect();
+    CodexHomeProfileInfo {
+        id: profile.id.clone(),
+        name: profile.name.clone(),
+        path: profile.path.clone(),
+        exists: path.is_dir(),
+        signed_in: path.join("auth.json").is_file(),
+        workspace_ids,
+        group_ids,
+    }
+}
+
+fn profile_dir_name(name: &str) -> String {
+    let slug = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Resolved Codex home of every workspace.
+fn resolve_all_codex_homes(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
+) -> HashMap<String, Option<PathBuf>> {
+    workspaces
+        .values()
+        .map(|entry| {
+            let parent_entry = entry
+                .parent_id
+                .as_ref()
+                .and_then(|parent_id| workspaces.get(parent_id));
+            (
+                entry.id.clone(),
+                resolve_workspace_codex_home(entry, parent_entry, Some(settings)),
+            )
+        })
+        .collect()
+}
+
+pub(crate) async fn list_codex_home_profiles_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+) -> Vec<CodexHomeProfileInfo> {
+    let settings = app_settings.lock().await.clone();
+    let workspaces = workspaces.lock().await;
+    settings
+        .codex_home_profiles
+        .iter()
+        .map(|profile| profile_info(profile, &workspaces, &settings))
+        .collect()
+}
+
+/// Adds a profile and creates its directory. Without a path the home goes
+/// under the app data directory, named after the profile.
+pub(crate) async fn create_codex_home_profile_core(
+    name: String,
+    path: Option<String>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<CodexHomeProfileInfo, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name is required.".to_string());
+    }
+    let mut settings = app_settings.lock().await;
+    if settings
+        .codex_home_profiles
+        .iter()
+        .any(|profile| profile.name.eq_ignore_ascii_case(&name))
+    {
+        return Err(format!("A profile named `{name}` already exists."));
+    }
+    let requested = path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty());
+    let path = match requested {
+        Some(path) => normalize_codex_home(path).ok_or("Invalid Codex home path.".to_string())?,
+        None => settings_path
+            .parent()
+            .ok_or("Unable to resolve app data dir.".to_string())?
+            .join(PROFILES_DIR)
+            .join(profile_dir_name(&name)),
+    };
+    if !path.is_absolute() {
+        return Err("Codex home path must be absolute.".to_string());
+    }
+    if let Some(existing) = settings
+        .codex_home_profiles
+        .iter()
+        .find(|profile| profile_path(profile) == path)
+    {
+        return Err(format!(
+            "Profile `{}` already uses `{}`.",
+            existing.name,
+            path.display()
+        ));
+    }
+    std::fs::create_dir_all(&path)
+        .map_err(|err| format!("Failed to create `{}`: {err}", path.display()))?;
+
+    let profile = CodexHomeProfile {
+        id: Uuid::new_v4().to_string(),
+        name,
+        path: path.to_string_lossy().to_string(),
+    };
+    let mut next = settings.clone();
+    next.codex_home_profiles.push(profile.clone());
+    write_settings(settings_path, &next)?;
+    *settings = next;
+    Ok(profile_info(&profile, &HashMap::new(), &settings))
+}
+
+/// Applies `update` to the workspaces and settings, persists both, and moves
+/// connected workspaces whose Codex home changed onto a matching session.
+async fn apply_codex_home_change<U, F, Fut>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+    storage_path: &PathBuf,
+    spawn_session: F,
+    update: U,
+) -> Result<CodexHomeSwitchResult, String>
+where
+    U: FnOnce(&mut HashMap<String, WorkspaceEntry>, &mut AppSettings) -> Result<(), String>,
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let workspace_ids = {
+        let mut current_workspaces = workspaces.lock().await;
+        let mut current_settings = app_settings.lock().await;
+        let mut next_workspaces = current_workspaces.clone();
+        let mut next_settings = current_settings.clone();
+        update(&mut next_workspaces, &mut next_settings)?;
+
+        let before = resolve_all_codex_homes(&current_workspaces, &current_settings);
+        let after = resolve_all_codex_homes(&next_workspaces, &next_settings);
+        let mut changed = after
+            .iter()
+            .filter(|(id, home)| before.get(*id) != Some(*home))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        changed.sort();
+
+        let list: Vec<_> = next_workspaces.values().cloned().collect();
+        write_workspaces(storage_path, &list)?;
+        write_settings(settings_path, &next_settings)?;
+        *current_workspaces = next_workspaces;
+        *current_settings = next_settings;
+        changed
+    };
+    let reconnected = reconnect_workspaces_with_changed_runtime(
+        &workspace_ids,
+        workspaces,
+        sessions,
+        app_settings,
+        spawn_session,
+    )
+    .await?;
+    Ok(CodexHomeSwitchResult {
+        workspace_ids,
+        reconnected,
+    })
+}
+
+/// Assigns a profile to a workspace or group; `None` falls back to the
+/// parent, group or global default.
+pub(crate) async fn switch_codex_home_profile_core<F, Fut>(
+    scope: CodexHomeScope,
+    target_id: String,
+    profile_id: Option<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+    storage_path: &PathBuf,
+    spawn_session: F,
+) -> Result<CodexHomeSwitchResult, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let profile_id = profile_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    apply_codex_home_change(
+        workspaces,
+        sessions,
+        app_settings,
+        settings_path,
+        storage_path,
+        spawn_session,
+        |workspaces, settings| {
+            if let Some(profile_id) = profile_id.as_deref() {
+                if !settings
+                    .codex_home_profiles
+                    .iter()
+                    .any(|profile| profile.id == profile_id)
+                {
+                    return Err("Codex home profile not found.".to_string());
+                }
+            }
+            match scope {
+                CodexHomeScope::Workspace => {
+                    let entry = workspaces
+                        .get_mut(&target_id)
+                        .ok_or_else(|| "workspace not found".to_string())?;
+                    entry.settings.codex_home_profile_id = profile_id;
+                }
+                CodexHomeScope::Group => {
+                    let group = settings
+                        .workspace_groups
+                        .iter_mut()
+                        .find(|group| group.id == target_id)
+                        .ok_or_else(|| "group not found".to_string())?;
+                    group.codex_home_profile_id = profile_id;
+                }
+            }
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Removes a profile and its assignments. The home directory, with its
+/// login and sessions, is left on disk.
+pub(crate) async fn delete_codex_home_profile_core<F, Fut>(
+    profile_id: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+    storage_path: &PathBuf,
+    spawn_session: F,
+) -> Result<CodexHomeSwitchResult, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    apply_codex_home_change(
+        workspaces,
+        sessions,
+        app_settings,
+        settings_path,
+        storage_path,
+        spawn_session,
+        |workspaces, settings| {
+            let before = settings.codex_home_profiles.len();
+            settings
+                .codex_home_profiles
+                .retain(|profile| profile.id != profile_id);
+            if settings.codex_home_profiles.len() == before {
+                return Err("Codex home profile not found.".to_string());
+            }
+            let assigned = Some(profile_id.as_str());
+            for entry in workspaces.values_mut() {
+                if entry.settings.codex_home_profile_id.as_deref() == assigned {
+                    entry.settings.codex_home_profile_id = None;
+                }
+            }
+            for group in &mut settings.workspace_groups {
+                if group.codex_home_profile_id.as_deref() == assigned {
+                    group.codex_home_profile_id = None;
+                }
+            }
+            Ok(())
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::run_async;
+    use crate::types::{WorkspaceGroup, WorkspaceKind, WorkspaceSettings};
+    use std::path::Path;
+
+    fn workspace(id: &str, group_id: Option<&str>) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("/tmp/{id}"),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                group_id: group_id.map(str::to_string),
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    fn not_connected(
+        _entry: WorkspaceEntry,
+        _default_bin: Option<String>,
+        _codex_args: Option<String>,
+        _codex_home: Option<PathBuf>,
+    ) -> impl Future<Output = Result<Arc<WorkspaceSession>, String>> {
+        async { Err("no workspace is connected".to_string()) }
+    }
+
+    #[test]
+    fn profile_dir_names_are_slugs() {
+        assert_eq!(profile_dir_name("Client Work"), "client-work");
+        assert_eq!(profile_dir_name("  ACME / internal "), "acme-internal");
+        assert_eq!(profile_dir_name("***"), "profile");
+    }
+
+    #[test]
+    fn create_switch_and_delete_profiles() {
+        run_async(async {
+            let temp_dir =
+                std::env::temp_dir().join(format!("codex-home-profiles-{}", Uuid::new_v4()));
+            let settings_path = temp_dir.join("settings.json");
+            let storage_path = temp_dir.join("workspaces.json");
+            let mut settings = AppSettings::default();
+            settings.workspace_groups = vec![WorkspaceGroup {
+                id: "group-1".to_string(),
+                name: "Clients".to_string(),
+                sort_order: None,
+                copies_folder: None,
+                codex_home_profile_id: None,
+            }];
+            let app_settings = Mutex::new(settings);
+            let workspaces = Mutex::new(HashMap::from([
+                ("a".to_string(), workspace("a", Some("group-1"))),
+                ("b".to_string(), workspace("b", None)),
+            ]));
+            let sessions = Mutex::new(HashMap::new());
+
+            let profile = create_codex_home_profile_core(
+                "Client Work".to_string(),
+                None,
+                &app_settings,
+                &settings_path,
+            )
+            .await
+            .expect("create profile");
+            assert_eq!(
+                PathBuf::from(&profile.path),
+                temp_dir.join(PROFILES_DIR).join("client-work")
+            );
+            assert!(profile.exists);
+            assert!(!profile.signed_in);
+            let duplicate = create_codex_home_profile_core(
+                "client work".to_string(),
+                None,
+                &app_settings,
+                &settings_path,
+            )
+            .await
+            .expect_err("duplicate name");
+            assert!(duplicate.contains("already exists"));
+
+            let result = switch_codex_home_profile_core(
+                CodexHomeScope::Group,
+                "group-1".to_string(),
+                Some(profile.id.clone()),
+                &workspaces,
+                &sessions,
+                &app_settings,
+                &settings_path,
+                &storage_path,
+                not_connected,
+            )
+            .await
+            .expect("switch group");
+            assert_eq!(result.workspace_ids, vec!["a".to_string()]);
+            assert!(result.reconnected.is_empty());
+
+            let listed = list_codex_home_profiles_core(&workspaces, &app_settings).await;
+            assert_eq!(listed[0].group_ids, vec!["group-1".to_string()]);
+
+            let result = delete_codex_home_profile_core(
+                profile.id.clone(),
+                &workspaces,
+                &sessions,
+                &app_settings,
+                &settings_path,
+                &storage_path,
+                not_connected,
+            )
+            .await
+            .expect("delete profile");
+            assert_eq!(result.workspace_ids, vec!["a".to_string()]);
+            let settings = app_settings.lock().await;
+            assert!(settings.codex_home_profiles.is_empty());
+            assert!(settings.workspace_groups[0].codex_home_profile_id.is_none());
+            assert!(Path::new(&profile.path).is_dir());
+            drop(settings);
+            let _ = std::fs::remove_dir_all(&temp_dir);
+        });
+    }
+}
@@ -0,0 +1,559 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use tokio::sync::Mutex;
+
+use crate::shared::config_toml_core;
+use crate::shared::config_validation_core::{self, ConfigDiagnostic};
+use crate::shared::{agents_config_core, project_config_core};
+use crate::types::WorkspaceEntry;
+
+/// Snapshots kept per file; older ones are pruned on each new snapshot.
+const MAX_SNAPSHOTS_PER_FILE: usize = 50;
+const META_FILE: &str = "meta.json";
+const DIFF_CONTEXT_LINES: usize = 3;
+/// Above this many LCS cells the diff falls back to replacing the changed span.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+static HISTORY_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Config file the preview and history commands operate on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum ConfigFileTarget {
+    /// `config.toml` in the Codex home, or the workspace's `.codex/config.toml`.
+    #[serde(rename_all = "camelCase")]
+    Config { workspace_id: Option<String> },
+    /// A managed agent role file under `agents/`.
+    Agent { name: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigWritePreview {
+    pub path: String,
+    pub exists: bool,
+    pub changed: bool,
+    /// Unified diff from the file on disk to the proposed content.
+    pub diff: String,
+    pub diagnostics: Vec<ConfigDiagnostic>,
+    /// False when the content adds errors the file on disk does not have.
+    pub can_save: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigHistoryEntry {
+    pub id: String,
+    pub path: String,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: i64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigHistoryRestore {
+    pub path: String,
+    pub diagnostics: Vec<ConfigDiagnostic>,
+}
+
+/// Sets where snapshots are stored. Until this is called (e.g. in unit tests)
+/// writes are still validated but not snapshotted.
+pub(crate) fn init_config_history(root: PathBuf) {
+    HISTORY_ROOT.get_or_init(|| root);
+}
+
+/// Runs before every write of a config or agent TOML file: refuses content
+/// that introduces new schema errors and snapshots the version on disk.
+pub(crate) fn guard_config_write(
+    path: &Path,
+    previous: Option<&str>,
+    next: &str,
+) -> Result<(), String> {
+    let errors = config_validation_core::new_errors(previous, next);
+    if !errors.is_empty() {
+        return Err(config_validation_core::format_diagnostics(
+            &file_label(path),
+            &errors,
+        ));
+    }
+    let Some(previous) = previous.filter(|previous| !previous.trim().is_empty()) else {
+        return Ok(());
+    };
+    if previous == next {
+        return Ok(());
+    }
+    if let Some(root) = HISTORY_ROOT.get() {
+        snapshot_file(root, path, previous)?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn preview_config_write_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    target: ConfigFileTarget,
+    content: String,
+) -> Result<ConfigWritePreview, String> {
+    let (path, current) = resolve_target_file(workspaces, &target).await?;
+    let label = file_label(&path);
+    Ok(ConfigWritePreview {
+        path: path.to_string_lossy().to_string(),
+        exists: current.is_some(),
+        changed: current.as_deref() != Some(content.as_str()),
+        diff: unified_diff(current.as_deref().unwrap_or_default(), &content, &label),
+        diagnostics: config_validation_core::validate_config_toml(&content),
+        can_save: config_validation_core::new_errors(current.as_deref(), &content).is_empty(),
+    })
+}
+
+/// Snapshots of `target` (or of every file when `None`), newest first.
+pub(crate) async fn config_history_list_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    target: Option<ConfigFileTarget>,
+) -> Result<Vec<ConfigHistoryEntry>, String> {
+    let root = history_root()?;
+    let path = match target {
+        Some(target) => Some(resolve_target_file(workspaces, &target).await?.0),
+        None => None,
+    };
+    list_snapshots(root, path.as_deref())
+}
+
+/// Writes snapshot `id` back to its file. The current content is snapshotted
+/// first, so a restore can itself be undone.
+pub(crate) fn config_history_restore_core(id: &str) -> Result<ConfigHistoryRestore, String> {
+    restore_snapshot(history_root()?, id)
+}
+
+fn history_root() -> Result<&'static Path, String> {
+    HISTORY_ROOT
+        .get()
+        .map(PathBuf::as_path)
+        .ok_or_else(|| "Config history is not available".to_string())
+}
+
+async fn resolve_target_file(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    target: &ConfigFileTarget,
+) -> Result<(PathBuf, Option<String>), String> {
+    match target {
+        ConfigFileTarget::Config { workspace_id } => {
+            let target = project_config_core::resolve_config_target_core(
+                workspaces,
+                workspace_id.as_deref(),
+            )
+            .await?;
+            let current = config_toml_core::read_config_text(&target)?;
+            Ok((target.config_path(), current))
+        }
+        ConfigFileTarget::Agent { name } => {
+            let path = agents_config_core::agent_config_path_core(name)?;
+            let current = if path.exists() {
+                Some(agents_config_core::read_agent_config_toml_core(name)?)
+            } else {
+                None
+            };
+            Ok((path, current))
+        }
+    }
+}
+
+fn file_label(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+    match path.parent().and_then(Path::file_name) {
+        Some(parent) if parent == "agents" => format!("agents/{name}"),
+        _ => name,
+    }
+}
+
+/// One folder per file, named by a stable hash of its absolute path.
+pub(crate) fn file_key(path: &Path) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in path.to_string_lossy().as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn snapshot_file(root: &Path, path: &Path, contents: &str) -> Result<ConfigHistoryEntry, String> {
+    let key = file_key(path);
+    let dir = root.join(&key);
+    std::fs::create_dir_all(&dir)
+        .map_err(|err| format!("Failed to create config history folder: {err}"))?;
+    let meta = json!({ "path": path.to_string_lossy() });
+    std::fs::write(dir.join(META_FILE), meta.to_string())
+        .map_err(|err| format!("Failed to record config history: {err}"))?;
+
+    let created_at = chrono::Utc::now().timestamp_millis();
+    let mut file_name = format!("{created_at}.toml");
+    let mut suffix = 1;
+    while dir.join(&file_name).exists() {
+        file_name = format!("{created_at}-{suffix}.toml");
+        suffix += 1;
+    }
+    std::fs::write(dir.join(&file_name), contents)
+        .map_err(|err| format!("Failed to snapshot {}: {err}", file_label(path)))?;
+
+    let snapshots = snapshot_names(&dir)?;
+    if snapshots.len() > MAX_SNAPSHOTS_PER_FILE {
+        for (stale, _) in &snapshots[MAX_SNAPSHOTS_PER_FILE..] {
+            std::fs::remove_file(dir.join(stale))
+                .map_err(|err| format!("Failed to prune config history: {err}"))?;
+        }
+    }
+
+    Ok(ConfigHistoryEntry {
+        id: format!("{key}/{file_name}"),
+        path: path.to_string_lossy().to_string(),
+        created_at,
+        size_bytes: contents.len() as u64,
+    })
+}
+
+/// Snapshot file names in `dir` with their timestamps, newest first.
+fn snapshot_names(dir: &Path) -> Result<Vec<(String, i64)>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| format!("Failed to read config history: {err}"))?;
+    let mut names: Vec<(String, i64, u32)> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (created_at, suffix) = parse_snapshot_name(&name)?;
+            Some((name, created_at, suffix))
+        })
+        .collect();
+    names.sort_by(|left, right| (right.1, right.2).cmp(&(left.1, left.2)));
+    Ok(names
+        .into_iter()
+        .map(|(name, created_at, _)| (name, created_at))
+        .collect())
+}
+
+/// `<millis>.toml` or `<millis>-<n>.toml`.
+fn parse_snapshot_name(name: &str) -> Option<(i64, u32)> {
+    let stem = name.strip_suffix(".toml")?;
+    let (millis, suffix) = match stem.split_once('-') {
+        Some((millis, suffix)) => (millis, suffix.parse().ok()?),
+        None => (stem, 0),
+    };
+    if millis.is_empty() || !millis.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    Some((millis.parse().ok()?, suffix))
+}
+
+fn read_meta_path(dir: &Path) -> Option<PathBuf> {
+    let raw = std::fs::read_to_string(dir.join(META_FILE)).ok()?;
+    let meta: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    meta.get("path")
+        .and_then(serde_json::Value::as_str)
+        .map(PathBuf::from)
+}
+
+fn list_snapshots(root: &Path, path: Option<&Path>) -> Result<Vec<ConfigHistoryEntry>, String> {
+    let dirs: Vec<PathBuf> = match path {
+        Some(path) => vec![root.join(file_key(path))],
+        None => match std::fs::read_dir(root) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|dir| dir.is_dir())
+                .collect(),
+            Err(_) => Vec::new(),
+        },
+    };
+
+    let mut history = Vec::new();
+    for dir in dirs {
+        let Some(file_path) = read_meta_path(&dir) else {
+            continue;
+        };
+        let key = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for (name, created_at) in snapshot_names(&dir)? {
+            let size_bytes = std::fs::metadata(dir.join(&name))
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            history.push(ConfigHistoryEntry {
+                id: format!("{key}/{name}"),
+                path: file_path.to_string_lossy().to_string(),
+                created_at,
+                size_bytes,
+            });
+        }
+    }
+    history.sort_by(|left, right| right.created_at.cmp(&left.created_at));
+    Ok(history)
+}
+
+fn restore_snapshot(root: &Path, id: &str) -> Result<ConfigHistoryRestore, String> {
+    let invalid = || format!("Unknown config snapshot `{id}`");
+    let (key, name) = id.split_once('/').ok_or_else(invalid)?;
+    if key.len() != 16
+        || !key.chars().all(|ch| ch.is_ascii_hexdigit())
+        || parse_snapshot_name(name).is_none()
+    {
+        return Err(invalid());
+    }
+    let dir = root.join(key);
+    let snapshot = dir.join(name);
+    let path = read_meta_path(&dir).ok_or_else(invalid)?;
+    let contents = std::fs::read_to_string(&snapshot).map_err(|_| invalid())?;
+
+    let current = match std::fs::read_to_string(&path) {
+        Ok(current) => Some(current),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(format!("Failed to read {}: {err}", file_label(&path))),
+    };
+    if let Some(current) = current.filter(|current| *current != contents) {
+        snapshot_file(root, &path, &current)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to restore {}: {err}", file_label(&path)))?;
+    }
+    std::fs::write(&path, &contents)
+        .map_err(|err| format!("Failed to restore {}: {err}", file_label(&path)))?;
+
+    Ok(ConfigHistoryRestore {
+        path: path.to_string_lossy().to_string(),
+        diagnostics: config_validation_core::validate_config_toml(&contents),
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DiffTag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+struct DiffLine<'a> {
+    tag: DiffTag,
+    text: &'a str,
+    /// Lines of the old/new file before this one.
+    old_index: usize,
+    new_index: usize,
+}
+
+/// Line-based unified diff with three lines of context; empty when equal.
+pub(crate) fn unified_diff(old: &str, new: &str, label: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.tag != DiffTag::Equal)
+        .map(|(index, _)| index)
+        .collect();
+    let mut output = format!("--- a/{label}\n+++ b/{label}\n");
+    let mut cursor = 0;
+    while cursor < changes.len() {
+        let mut last = cursor;
+        while last + 1 < changes.len()
+            && changes[last + 1] - changes[last] <= DIFF_CONTEXT_LINES * 2 + 1
+        {
+            last += 1;
+        }
+        let start = changes[cursor].saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (changes[last] + DIFF_CONTEXT_LINES + 1).min(lines.len());
+        let hunk = &lines[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|line| line.tag != DiffTag::Insert)
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| line.tag != DiffTag::Delete)
+            .count();
+        let old_start = hunk[0].old_index + usize::from(old_count > 0);
+        let new_start = hunk[0].new_index + usize::from(new_count > 0);
+        output.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for line in hunk {
+            let marker = match line.tag {
+                DiffTag::Equal => ' ',
+                DiffTag::Delete => '-',
+                DiffTag::Insert => '+',
+            };
+            output.push(marker);
+            output.push_str(line.text);
+            output.push('\n');
+        }
+        cursor = last + 1;
+    }
+    output
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut tags = Vec::with_capacity(old.len() + new.len());
+    tags.extend(std::iter::repeat(DiffTag::Equal).take(prefix));
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+        tags.extend(std::iter::repeat(DiffTag::Delete).take(old_middle.len()));
+        tags.extend(std::iter::repeat(DiffTag::Insert).take(new_middle.len()));
+    } else {
+        tags.extend(lcs_tags(old_middle, new_middle));
+    }
+    tags.extend(std::iter::repeat(DiffTag::Equal).take(suffix));
+
+    let (mut old_index, mut new_index) = (0, 0);
+    tags.into_iter()
+        .map(|tag| {
+            let text = match tag {
+                DiffTag::Equal | DiffTag::Delete => old[old_index],
+                DiffTag::Insert => new[new_index],
+            };
+            let line = DiffLine {
+                tag,
+                text,
+                old_index,
+                new_index,
+            };
+            match tag {
+                DiffTag::Equal => {
+                    old_index += 1;
+                    new_index += 1;
+                }
+                DiffTag::Delete => old_index += 1,
+                DiffTag::Insert => new_index += 1,
+            }
+            line
+        })
+        .collect()
+}
+
+fn lcs_tags(old: &[&str], new: &[&str]) -> Vec<DiffTag> {
+    let width = new.len() + 1;
+    // lengths[i * width + j] = LCS of old[i..] and new[j..].
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut tags = Vec::with_capacity(old.len() + new.len());
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            tags.push(DiffTag::Equal);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            tags.push(DiffTag::Delete);
+            i += 1;
+        } else {
+            tags.push(DiffTag::Insert);
+            j += 1;
+        }
+    }
+    tags.extend(std::iter::repeat(DiffTag::Delete).take(old.len() - i));
+    tags.extend(std::iter::repeat(DiffTag::Insert).take(new.len() - j));
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+
+    #[test]
+    fn unified_diff_groups_changes_into_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+
+        let diff = unified_diff(old, new, "config.toml");
+
+        assert_eq!(
+            diff,
+            "--- a/config.toml\n+++ b/config.toml\n@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n@@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+        assert_eq!(unified_diff(old, old, "config.toml"), "");
+        assert_eq!(
+            unified_diff("", "model = \"o3\"\n", "config.toml"),
+            "--- a/config.toml\n+++ b/config.toml\n@@ -0,0 +1,1 @@\n+model = \"o3\"\n"
+        );
+    }
+
+    #[test]
+    fn snapshots_list_newest_first_and_restore() {
+        let root = temp_dir("config-history");
+        let home = temp_dir("config-history-home");
+        let path = home.join("config.toml");
+        std::fs::write(&path, "model = \"o3\"\n").expect("write config");
+
+        let first = snapshot_file(&root, &path, "model = \"gpt-5\"\n").expect("first snapshot");
+        let second = snapshot_file(&root, &path, "model = \"gpt-5-mini\"\n").expect("second");
+        let history = list_snapshots(&root, Some(&path)).expect("list");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].id, second.id);
+        assert_eq!(history[1].id, first.id);
+        assert_eq!(history[0].path, path.to_string_lossy());
+
+        let restored = restore_snapshot(&root, &first.id).expect("restore");
+        assert!(restored.diagnostics.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read config"),
+            "model = \"gpt-5\"\n"
+        );
+        // The content that was overwritten is kept as the newest snapshot.
+        let history = list_snapshots(&root, None).expect("list all");
+        assert_eq!(history.len(), 3);
+        let newest = std::fs::read_to_string(root.join(&history[0].id)).expect("read newest");
+        assert_eq!(newest, "model = \"o3\"\n");
+
+        assert!(restore_snapshot(&root, "../../etc/passwd").is_err());
+        assert!(restore_snapshot(&root, &format!("{}/meta.json", file_key(&path))).is_err());
+    }
+
+    #[test]
+    fn guard_rejects_only_writes_that_add_errors() {
+        let home = temp_dir("config-guard");
+        let path = home.join("config.toml");
+
+        let err = guard_config_write(&path, Some("model = \"o3\"\n"), "sandbox_mode = \"yolo\"\n")
+            .expect_err("invalid sandbox mode");
+        assert!(err.starts_with("config.toml was not saved: line 1:"));
+        assert!(guard_config_write(&path, None, "model = \"o3\"\n").is_ok());
+        // Errors the file already had do not block unrelated edits.
+        assert!(guard_config_write(
+            &path,
+            Some("sandbox_mode = \"yolo\"\n"),
+            "sandbox_mode = \"yolo\"\nmodel = \"o3\"\n",
+        )
+        .is_ok());
+    }
+}
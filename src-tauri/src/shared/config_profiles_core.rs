@@ -0,0 +1,378 @@
+use serde::{Deserialize, Serialize};
+use toml_edit::{value, Document, Item, Table};
+
+use crate::shared::config_toml_core::{self, ConfigTarget};
+
+const PROFILES_KEY: &str = "profiles";
+const DEFAULT_PROFILE_KEY: &str = "profile";
+
+pub(crate) const REASONING_EFFORTS: &[&str] =
+    &["none", "minimal", "low", "medium", "high", "xhigh"];
+pub(crate) const APPROVAL_POLICIES: &[&str] = &["untrusted", "on-failure", "on-request", "never"];
+pub(crate) const SANDBOX_MODES: &[&str] = &["read-only", "workspace-write", "danger-full-access"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigProfileDto {
+    pub name: String,
+    pub model: Option<String>,
+    pub model_reasoning_effort: Option<String>,
+    pub approval_policy: Option<String>,
+    pub sandbox_mode: Option<String>,
+    pub model_provider: Option<String>,
+    /// Selected by the top-level `profile` key.
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigProfilesDto {
+    pub config_path: String,
+    pub default_profile: Option<String>,
+    pub profiles: Vec<ConfigProfileDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateConfigProfileInput {
+    pub name: String,
+    pub model: Option<String>,
+    pub model_reasoning_effort: Option<String>,
+    pub approval_policy: Option<String>,
+    pub sandbox_mode: Option<String>,
+    pub model_provider: Option<String>,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpdateConfigProfileInput {
+    pub original_name: String,
+    pub name: String,
+    pub model: Option<String>,
+    pub model_reasoning_effort: Option<String>,
+    pub approval_policy: Option<String>,
+    pub sandbox_mode: Option<String>,
+    pub model_provider: Option<String>,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeleteConfigProfileInput {
+    pub name: String,
+    pub workspace_id: Option<String>,
+}
+
+struct ProfileFields {
+    model: Option<String>,
+    model_reasoning_effort: Option<String>,
+    approval_policy: Option<String>,
+    sandbox_mode: Option<String>,
+    model_provider: Option<String>,
+}
+
+pub(crate) fn get_config_profiles_core(target: &ConfigTarget) -> Result<ConfigProfilesDto, String> {
+    let config_path = target.config_path();
+    let config_path_string = config_path
+        .to_str()
+        .ok_or_else(|| "Unable to resolve config.toml path".to_string())?
+        .to_string();
+
+    let (_, document) = config_toml_core::load_config_document(target)?;
+    let default_profile = config_toml_core::read_top_level_string(&document, DEFAULT_PROFILE_KEY);
+    let mut profiles = collect_profiles(&document, default_profile.as_deref());
+    profiles.sort_by(|left, right| left.name.cmp(&right.name));
+
+    Ok(ConfigProfilesDto {
+        config_path: config_path_string,
+        default_profile,
+        profiles,
+    })
+}
+
+pub(crate) fn create_config_profile_core(
+    target: &ConfigTarget,
+    input: CreateConfigProfileInput,
+) -> Result<ConfigProfilesDto, String> {
+    let name = normalize_profile_name(&input.name)?;
+    let fields = validate_fields(ProfileFields {
+        model: input.model,
+        model_reasoning_effort: input.model_reasoning_effort,
+        approval_policy: input.approval_policy,
+        sandbox_mode: input.sandbox_mode,
+        model_provider: input.model_provider,
+    })?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let profiles = config_toml_core::ensure_table(&mut document, PROFILES_KEY)?;
+    if profiles.get(&name).is_some() {
+        return Err(format!("Profile '{name}' already exists"));
+    }
+
+    let mut table = Table::new();
+    apply_fields(&mut table, fields);
+    profiles[&name] = Item::Table(table);
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_config_profiles_core(target)
+}
+
+pub(crate) fn update_config_profile_core(
+    target: &ConfigTarget,
+    input: UpdateConfigProfileInput,
+) -> Result<ConfigProfilesDto, String> {
+    let original_name = normalize_profile_name(&input.original_name)?;
+    let name = normalize_profile_name(&input.name)?;
+    let fields = validate_fields(ProfileFields {
+        model: input.model,
+        model_reasoning_effort: input.model_reasoning_effort,
+        approval_policy: input.approval_policy,
+        sandbox_mode: input.sandbox_mode,
+        model_provider: input.model_provider,
+    })?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let profiles = config_toml_core::ensure_table(&mut document, PROFILES_KEY)?;
+
+    if name != original_name && profiles.get(&name).is_some() {
+        return Err(format!("Profile '{name}' already exists"));
+    }
+    let Some(existing_item) = profiles.remove(&original_name) else {
+        return Err(format!("Profile '{original_name}' not found"));
+    };
+    // Keys the form does not manage (e.g. `model_verbosity`) are kept as-is.
+    let mut table = match existing_item {
+        Item::Table(table) => table,
+        Item::Value(toml_edit::Value::InlineTable(inline)) => inline.into_table(),
+        _ => return Err(format!("Profile '{original_name}' must be a table")),
+    };
+    apply_fields(&mut table, fields);
+    profiles[&name] = Item::Table(table);
+
+    if name != original_name
+        && config_toml_core::read_top_level_string(&document, DEFAULT_PROFILE_KEY).as_deref()
+            == Some(original_name.as_str())
+    {
+        config_toml_core::set_top_level_string(&mut document, DEFAULT_PROFILE_KEY, Some(&name));
+    }
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_config_profiles_core(target)
+}
+
+pub(crate) fn delete_config_profile_core(
+    target: &ConfigTarget,
+    input: DeleteConfigProfileInput,
+) -> Result<ConfigProfilesDto, String> {
+    let name = normalize_profile_name(&input.name)?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let profiles = config_toml_core::ensure_table(&mut document, PROFILES_KEY)?;
+    if profiles.remove(&name).is_none() {
+        return Err(format!("Profile '{name}' not found"));
+    }
+    if config_toml_core::read_top_level_string(&document, DEFAULT_PROFILE_KEY).as_deref()
+        == Some(name.as_str())
+    {
+        config_toml_core::set_top_level_string(&mut document, DEFAULT_PROFILE_KEY, None);
+    }
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_config_profiles_core(target)
+}
+
+pub(crate) fn normalize_profile_name(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if trimmed.len() > 64 {
+        return Err("Profile name must be 64 characters or fewer".to_string());
+    }
+    if trimmed
+        .chars()
+        .any(|ch| !ch.is_ascii_alphanumeric() && ch != '_' && ch != '-' && ch != '.')
+    {
+        return Err("Profile name must use only letters, digits, '.', '_' or '-'".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+fn validate_fields(fields: ProfileFields) -> Result<ProfileFields, String> {
+    let model_reasoning_effort = normalize_optional_string(fields.model_reasoning_effort);
+    validate_choice(
+        "model_reasoning_effort",
+        model_reasoning_effort.as_deref(),
+        REASONING_EFFORTS,
+    )?;
+    let approval_policy = normalize_optional_string(fields.approval_policy);
+    validate_choice(
+        "approval_policy",
+        approval_policy.as_deref(),
+        APPROVAL_POLICIES,
+    )?;
+    let sandbox_mode = normalize_optional_string(fields.sandbox_mode);
+    validate_choice("sandbox_mode", sandbox_mode.as_deref(), SANDBOX_MODES)?;
+    Ok(ProfileFields {
+        model: normalize_optional_string(fields.model),
+        model_reasoning_effort,
+        approval_policy,
+        sandbox_mode,
+        model_provider: normalize_optional_string(fields.model_provider),
+    })
+}
+
+fn validate_choice(key: &str, value: Option<&str>, allowed: &[&str]) -> Result<(), String> {
+    match value {
+        Some(value) if !allowed.contains(&value) => {
+            Err(format!("`{key}` must be one of {}", allowed.join(", ")))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn apply_fields(table: &mut Table, fields: ProfileFields) {
+    set_or_remove(table, "model", fields.model);
+    set_or_remove(
+        table,
+        "model_reasoning_effort",
+        fields.model_reasoning_effort,
+    );
+    set_or_remove(table, "approval_policy", fields.approval_policy);
+    set_or_remove(table, "sandbox_mode", fields.sandbox_mode);
+    set_or_remove(table, "model_provider", fields.model_provider);
+}
+
+fn set_or_remove(table: &mut Table, key: &str, field: Option<String>) {
+    match field {
+        Some(field) => table[key] = value(field),
+        None => {
+            table.remove(key);
+        }
+    }
+}
+
+fn collect_profiles(document: &Document, default_profile: Option<&str>) -> Vec<ConfigProfileDto> {
+    let Some(profiles) = document.get(PROFILES_KEY).and_then(Item::as_table_like) else {
+        return Vec::new();
+    };
+    profiles
+        .iter()
+        .filter_map(|(name, item)| {
+            let table = item.as_table_like()?;
+            let read = |key: &str| {
+                table
+                    .get(key)
+                    .and_then(Item::as_str)
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            Some(ConfigProfileDto {
+                name: name.to_string(),
+                model: read("model"),
+                model_reasoning_effort: read("model_reasoning_effort"),
+                approval_policy: read("approval_policy"),
+                sandbox_mode: read("sandbox_mode"),
+                model_provider: read("model_provider"),
+                is_default: default_profile == Some(name),
+            })
+        })
+        .collect()
+}
+
+fn normalize_optional_string(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+    use std::fs;
+
+    fn create_input(name: &str) -> CreateConfigProfileInput {
+        CreateConfigProfileInput {
+            name: name.to_string(),
+            model: Some("gpt-5-codex".to_string()),
+            model_reasoning_effort: Some("high".to_string()),
+            approval_policy: Some("on-request".to_string()),
+            sandbox_mode: Some("workspace-write".to_string()),
+            model_provider: None,
+            workspace_id: None,
+        }
+    }
+
+    #[test]
+    fn profiles_round_trip_and_keep_unknown_keys() {
+        let home = temp_dir("profiles");
+        fs::write(
+            home.join("config.toml"),
+            "profile = \"fast\"\n\n[profiles.fast]\nmodel = \"gpt-5-mini\"\nmodel_verbosity = \"low\"\n",
+        )
+        .expect("write config");
+        let target = ConfigTarget::Global(home.clone());
+
+        let created =
+            create_config_profile_core(&target, create_input("deep-review")).expect("create");
+        assert_eq!(created.profiles.len(), 2);
+        assert_eq!(created.default_profile.as_deref(), Some("fast"));
+
+        let updated = update_config_profile_core(
+            &target,
+            UpdateConfigProfileInput {
+                original_name: "fast".to_string(),
+                name: "fast-cheap".to_string(),
+                model: Some("gpt-5-mini".to_string()),
+                model_reasoning_effort: Some("low".to_string()),
+                approval_policy: None,
+                sandbox_mode: None,
+                model_provider: Some("openai".to_string()),
+                workspace_id: None,
+            },
+        )
+        .expect("update");
+        assert_eq!(updated.default_profile.as_deref(), Some("fast-cheap"));
+        let fast = updated
+            .profiles
+            .iter()
+            .find(|profile| profile.name == "fast-cheap")
+            .expect("renamed profile");
+        assert!(fast.is_default);
+        assert_eq!(fast.model_provider.as_deref(), Some("openai"));
+        let contents = fs::read_to_string(home.join("config.toml")).expect("read config");
+        assert!(contents.contains("model_verbosity = \"low\""));
+
+        let deleted = delete_config_profile_core(
+            &target,
+            DeleteConfigProfileInput {
+                name: "fast-cheap".to_string(),
+                workspace_id: None,
+            },
+        )
+        .expect("delete");
+        assert_eq!(deleted.default_profile, None);
+        assert_eq!(deleted.profiles.len(), 1);
+        assert_eq!(deleted.profiles[0].name, "deep-review");
+    }
+
+    #[test]
+    fn rejects_unknown_choices_and_duplicates() {
+        let target = ConfigTarget::Global(temp_dir("profiles"));
+        let mut input = create_input("review");
+        input.sandbox_mode = Some("yolo".to_string());
+        let err = create_config_profile_core(&target, input).expect_err("invalid sandbox");
+        assert!(err.contains("sandbox_mode"));
+
+        create_config_profile_core(&target, create_input("review")).expect("create");
+        let err =
+            create_config_profile_core(&target, create_input("review")).expect_err("duplicate");
+        assert!(err.contains("already exists"));
+        assert!(normalize_profile_name("deep review").is_err());
+    }
+}
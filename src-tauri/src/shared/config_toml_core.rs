@@ -1,14 +1,39 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use toml_edit::{value, Document, Item, Table};
 
 use crate::files::ops::{read_with_policy, write_with_policy};
-use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::files::policy::{policy_for, FileKind, FileScope, PROJECT_CONFIG_DIR};
+use crate::shared::config_history_core;
 
-pub(crate) fn load_global_config_document(codex_home: &Path) -> Result<(bool, Document), String> {
-    let policy = policy_for(FileScope::Global, FileKind::Config)?;
-    let root = codex_home.to_path_buf();
-    let response = read_with_policy(&root, policy)?;
+/// A `config.toml` layer: the global one in a Codex home, or the repo-local
+/// `.codex/config.toml` of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ConfigTarget {
+    Global(PathBuf),
+    Workspace(PathBuf),
+}
+
+impl ConfigTarget {
+    fn scope_and_root(&self) -> (FileScope, &Path) {
+        match self {
+            ConfigTarget::Global(codex_home) => (FileScope::Global, codex_home),
+            ConfigTarget::Workspace(root) => (FileScope::Workspace, root),
+        }
+    }
+
+    pub(crate) fn config_path(&self) -> PathBuf {
+        match self {
+            ConfigTarget::Global(codex_home) => codex_home.join("config.toml"),
+            ConfigTarget::Workspace(root) => root.join(PROJECT_CONFIG_DIR).join("config.toml"),
+        }
+    }
+}
+
+pub(crate) fn load_config_document(target: &ConfigTarget) -> Result<(bool, Document), String> {
+    let (scope, root) = target.scope_and_root();
+    let policy = policy_for(scope, FileKind::Config)?;
+    let response = read_with_policy(&root.to_path_buf(), policy)?;
     let document = if response.exists {
         parse_document(response.content.as_str())?
     } else {
@@ -17,17 +42,44 @@ pub(crate) fn load_global_config_document(codex_home: &Path) -> Result<(bool, Do
     Ok((response.exists, document))
 }
 
-pub(crate) fn persist_global_config_document(
-    codex_home: &Path,
+pub(crate) fn persist_config_document(
+    target: &ConfigTarget,
     document: &Document,
 ) -> Result<(), String> {
-    let policy = policy_for(FileScope::Global, FileKind::Config)?;
-    let root = codex_home.to_path_buf();
     let mut rendered = document.to_string();
     if !rendered.ends_with('\n') {
         rendered.push('\n');
     }
-    write_with_policy(&root, policy, rendered.as_str())
+    write_config_text(target, rendered.as_str())
+}
+
+/// Raw contents of the target's `config.toml`, or `None` when it is missing.
+pub(crate) fn read_config_text(target: &ConfigTarget) -> Result<Option<String>, String> {
+    let (scope, root) = target.scope_and_root();
+    let policy = policy_for(scope, FileKind::Config)?;
+    let response = read_with_policy(&root.to_path_buf(), policy)?;
+    Ok(response.exists.then_some(response.content))
+}
+
+/// Every `config.toml` write goes through here so it is validated and the
+/// previous version lands in the config history.
+pub(crate) fn write_config_text(target: &ConfigTarget, content: &str) -> Result<(), String> {
+    let previous = read_config_text(target)?;
+    config_history_core::guard_config_write(&target.config_path(), previous.as_deref(), content)?;
+    let (scope, root) = target.scope_and_root();
+    let policy = policy_for(scope, FileKind::Config)?;
+    write_with_policy(&root.to_path_buf(), policy, content)
+}
+
+pub(crate) fn load_global_config_document(codex_home: &Path) -> Result<(bool, Document), String> {
+    load_config_document(&ConfigTarget::Global(codex_home.to_path_buf()))
+}
+
+pub(crate) fn persist_global_config_document(
+    codex_home: &Path,
+    document: &Document,
+) -> Result<(), String> {
+    persist_config_document(&ConfigTarget::Global(codex_home.to_path_buf()), document)
 }
 
 pub(crate) fn parse_document(contents: &str) -> Result<Document, String> {
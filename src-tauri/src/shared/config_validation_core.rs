@@ -0,0 +1,664 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use toml_edit::{Document, Item, TableLike};
+
+use crate::shared::config_profiles_core::{APPROVAL_POLICIES, REASONING_EFFORTS, SANDBOX_MODES};
+use crate::shared::model_providers_core::{RESERVED_PROVIDER_IDS, WIRE_APIS};
+
+const REASONING_SUMMARIES: &[&str] = &["auto", "concise", "detailed", "none"];
+const VERBOSITIES: &[&str] = &["low", "medium", "high"];
+const FILE_OPENERS: &[&str] = &["vscode", "vscode-insiders", "windsurf", "cursor", "none"];
+const TRUST_LEVELS: &[&str] = &["trusted", "untrusted"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ConfigDiagnosticSeverity {
+    /// Codex refuses to start (or the file cannot be parsed).
+    Error,
+    /// Codex ignores the value or the reference may not resolve.
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigDiagnostic {
+    pub severity: ConfigDiagnosticSeverity,
+    /// 1-based line of the offending key (or its closest table header).
+    pub line: usize,
+    pub column: Option<usize>,
+    /// Dotted key path, e.g. `profiles.fast.approval_policy`.
+    pub key: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, Copy)]
+enum Expect {
+    String,
+    OneOf(&'static [&'static str]),
+    Bool,
+    Integer,
+    Number,
+    StringArray,
+    StringMap,
+    Table,
+}
+
+/// Checks `contents` against the parts of Codex's config schema the app knows
+/// about. Unknown keys are warnings; wrong types and values are errors.
+pub(crate) fn validate_config_toml(contents: &str) -> Vec<ConfigDiagnostic> {
+    if contents.trim().is_empty() {
+        return Vec::new();
+    }
+    let document = match contents.parse::<Document>() {
+        Ok(document) => document,
+        Err(err) => return vec![parse_error_diagnostic(&err.to_string())],
+    };
+    let mut validator = Validator {
+        lines: KeyLines::index(contents),
+        diagnostics: Vec::new(),
+    };
+    validator.check_document(&document);
+    validator
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.line);
+    validator.diagnostics
+}
+
+/// Errors in `next` that `previous` did not already have. Saving is allowed
+/// as long as an edit does not make the file worse.
+pub(crate) fn new_errors(previous: Option<&str>, next: &str) -> Vec<ConfigDiagnostic> {
+    let existing: Vec<(Option<String>, String)> = previous
+        .map(validate_config_toml)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == ConfigDiagnosticSeverity::Error)
+        .map(|diagnostic| (diagnostic.key, diagnostic.message))
+        .collect();
+    validate_config_toml(next)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == ConfigDiagnosticSeverity::Error)
+        .filter(|diagnostic| {
+            // Parse errors move with the text, so they always count as new.
+            diagnostic.key.is_none()
+                || !existing.contains(&(diagnostic.key.clone(), diagnostic.message.clone()))
+        })
+        .collect()
+}
+
+pub(crate) fn format_diagnostics(file_label: &str, diagnostics: &[ConfigDiagnostic]) -> String {
+    let details: Vec<String> = diagnostics
+        .iter()
+        .map(|diagnostic| format!("line {}: {}", diagnostic.line, diagnostic.message))
+        .collect();
+    format!("{file_label} was not saved: {}", details.join("; "))
+}
+
+/// `toml_edit` reports `TOML parse error at line L, column C` followed by a
+/// source excerpt and the reason.
+fn parse_error_diagnostic(raw: &str) -> ConfigDiagnostic {
+    let position = raw.lines().next().and_then(|header| {
+        let (_, rest) = header.split_once("at line ")?;
+        let (line, column) = rest.split_once(", column ")?;
+        Some((line.trim().parse().ok()?, column.trim().parse().ok()?))
+    });
+    let reason: Vec<&str> = raw
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim_start().starts_with('|') && !line.contains(" | "))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let message = if reason.is_empty() {
+        raw.trim().to_string()
+    } else {
+        format!("Invalid TOML: {}", reason.join(" "))
+    };
+    ConfigDiagnostic {
+        severity: ConfigDiagnosticSeverity::Error,
+        line: position.map(|(line, _)| line).unwrap_or(1),
+        column: position.map(|(_, column)| column),
+        key: None,
+        message,
+    }
+}
+
+struct Validator {
+    lines: KeyLines,
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl Validator {
+    fn check_document(&mut self, document: &Document) {
+        let root = document.as_table();
+        for (key, item) in root.iter() {
+            let key_path = vec![key.to_string()];
+            match top_level_expectation(key) {
+                Some(expect) => self.expect(&key_path, item, expect),
+                None => self.warn(&key_path, format!("Unknown key `{key}`; Codex ignores it")),
+            }
+        }
+
+        let profiles = root.get("profiles").and_then(Item::as_table_like);
+        let providers = root.get("model_providers").and_then(Item::as_table_like);
+        if let Some(profile) = root.get("profile").and_then(Item::as_str) {
+            if !profiles.is_some_and(|profiles| profiles.get(profile).is_some()) {
+                self.warn(
+                    &["profile".to_string()],
+                    format!("Profile `{profile}` is not defined in this file's [profiles]"),
+                );
+            }
+        }
+        if let Some(provider) = root.get("model_provider").and_then(Item::as_str) {
+            self.check_provider_reference(&["model_provider".to_string()], provider, providers);
+        }
+        if let Some(features) = root.get("features").and_then(Item::as_table_like) {
+            for (key, item) in features.iter() {
+                self.expect(&path(&["features", key]), item, Expect::Bool);
+            }
+        }
+        if let Some(profiles) = profiles {
+            self.check_entries("profiles", profiles, |validator, entry_path, entry| {
+                validator.check_profile(entry_path, entry, providers);
+            });
+        }
+        if let Some(providers) = providers {
+            self.check_entries("model_providers", providers, Self::check_provider);
+        }
+        if let Some(servers) = root.get("mcp_servers").and_then(Item::as_table_like) {
+            self.check_entries("mcp_servers", servers, Self::check_mcp_server);
+        }
+        if let Some(sandbox) = root
+            .get("sandbox_workspace_write")
+            .and_then(Item::as_table_like)
+        {
+            self.check_fields(
+                &["sandbox_workspace_write".to_string()],
+                sandbox,
+                &[
+                    ("writable_roots", Expect::StringArray),
+                    ("network_access", Expect::Bool),
+                    ("exclude_tmpdir_env_var", Expect::Bool),
+                    ("exclude_slash_tmp", Expect::Bool),
+                ],
+            );
+        }
+        if let Some(projects) = root.get("projects").and_then(Item::as_table_like) {
+            self.check_entries("projects", projects, |validator, entry_path, entry| {
+                validator.check_fields(
+                    entry_path,
+                    entry,
+                    &[("trust_level", Expect::OneOf(TRUST_LEVELS))],
+                );
+            });
+        }
+    }
+
+    /// Runs `check` on every `[<table>.<name>]`, flagging entries that are not
+    /// tables.
+    fn check_entries(
+        &mut self,
+        table: &str,
+        entries: &dyn TableLike,
+        check: impl Fn(&mut Self, &[String], &dyn TableLike),
+    ) {
+        for (name, item) in entries.iter() {
+            let entry_path = path(&[table, name]);
+            match item.as_table_like() {
+                Some(entry) => check(self, &entry_path, entry),
+                None => self.error(
+                    &entry_path,
+                    format!("`{}` must be a table", entry_path.join(".")),
+                ),
+            }
+        }
+    }
+
+    fn check_profile(
+        &mut self,
+        profile_path: &[String],
+        profile: &dyn TableLike,
+        providers: Option<&dyn TableLike>,
+    ) {
+        self.check_fields(
+            profile_path,
+            profile,
+            &[
+                ("model", Expect::String),
+                ("review_model", Expect::String),
+                ("model_provider", Expect::String),
+                ("approval_policy", Expect::OneOf(APPROVAL_POLICIES)),
+                ("sandbox_mode", Expect::OneOf(SANDBOX_MODES)),
+                ("model_reasoning_effort", Expect::OneOf(REASONING_EFFORTS)),
+                (
+                    "model_reasoning_summary",
+                    Expect::OneOf(REASONING_SUMMARIES),
+                ),
+                ("model_verbosity", Expect::OneOf(VERBOSITIES)),
+                ("chatgpt_base_url", Expect::String),
+                ("experimental_instructions_file", Expect::String),
+                ("model_instructions_file", Expect::String),
+                ("developer_instructions", Expect::String),
+                ("personality", Expect::String),
+                ("include_apply_patch_tool", Expect::Bool),
+                ("tools_web_search", Expect::Bool),
+                ("tools_view_image", Expect::Bool),
+                ("features", Expect::Table),
+                ("sandbox_workspace_write", Expect::Table),
+                ("tools", Expect::Table),
+            ],
+        );
+        if let Some(provider) = profile.get("model_provider").and_then(Item::as_str) {
+            let mut provider_path = profile_path.to_vec();
+            provider_path.push("model_provider".to_string());
+            self.check_provider_reference(&provider_path, provider, providers);
+        }
+    }
+
+    fn check_provider(&mut self, provider_path: &[String], provider: &dyn TableLike) {
+        if let Some(id) = provider_path.last() {
+            if RESERVED_PROVIDER_IDS.contains(&id.as_str()) {
+                self.warn(
+                    provider_path,
+                    format!("`{id}` is a built-in provider; Codex ignores this definition"),
+                );
+            }
+        }
+        self.check_fields(
+            provider_path,
+            provider,
+            &[
+                ("name", Expect::String),
+                ("base_url", Expect::String),
+                ("env_key", Expect::String),
+                ("env_key_instructions", Expect::String),
+                ("wire_api", Expect::OneOf(WIRE_APIS)),
+                ("query_params", Expect::StringMap),
+                ("http_headers", Expect::StringMap),
+                ("env_http_headers", Expect::StringMap),
+                ("request_max_retries", Expect::Integer),
+                ("stream_max_retries", Expect::Integer),
+                ("stream_idle_timeout_ms", Expect::Integer),
+                ("requires_openai_auth", Expect::Bool),
+            ],
+        );
+    }
+
+    fn check_mcp_server(&mut self, server_path: &[String], server: &dyn TableLike) {
+        let has_command = server.get("command").is_some();
+        let has_url = server.get("url").is_some();
+        if has_command == has_url {
+            self.error(
+                server_path,
+                format!(
+                    "`{}` needs exactly one of `command` or `url`",
+                    server_path.join(".")
+                ),
+            );
+        }
+        self.check_fields(
+            server_path,
+            server,
+            &[
+                ("command", Expect::String),
+                ("args", Expect::StringArray),
+                ("env", Expect::StringMap),
+                ("env_vars", Expect::StringArray),
+                ("cwd", Expect::String),
+                ("url", Expect::String),
+                ("bearer_token_env_var", Expect::String),
+                ("http_headers", Expect::StringMap),
+                ("env_http_headers", Expect::StringMap),
+                ("headers", Expect::StringMap),
+                ("enabled", Expect::Bool),
+                ("startup_timeout_sec", Expect::Number),
+                ("startup_timeout_ms", Expect::Integer),
+                ("tool_timeout_sec", Expect::Number),
+                ("enabled_tools", Expect::StringArray),
+                ("disabled_tools", Expect::StringArray),
+            ],
+        );
+    }
+
+    fn check_provider_reference(
+        &mut self,
+        key_path: &[String],
+        provider: &str,
+        providers: Option<&dyn TableLike>,
+    ) {
+        let defined = RESERVED_PROVIDER_IDS.contains(&provider)
+            || providers.is_some_and(|providers| providers.get(provider).is_some());
+        if !defined {
+            self.warn(
+                key_path,
+                format!(
+                    "Model provider `{provider}` is not defined in this file's [model_providers]"
+                ),
+            );
+        }
+    }
+
+    /// Type-checks the known `fields` of `table` and warns about the rest.
+    fn check_fields(
+        &mut self,
+        table_path: &[String],
+        table: &dyn TableLike,
+        fields: &[(&str, Expect)],
+    ) {
+        for (key, item) in table.iter() {
+            let mut key_path = table_path.to_vec();
+            key_path.push(key.to_string());
+            match fields.iter().find(|(name, _)| *name == key) {
+                Some((_, expect)) => self.expect(&key_path, item, *expect),
+                None => self.warn(
+                    &key_path,
+                    format!("Unknown key `{}`; Codex ignores it", key_path.join(".")),
+                ),
+            }
+        }
+    }
+
+    fn expect(&mut self, key_path: &[String], item: &Item, expect: Expect) {
+        let dotted = key_path.join(".");
+        let problem = match expect {
+            Expect::String => item
+                .as_str()
+                .is_none()
+                .then(|| format!("`{dotted}` must be a string")),
+            Expect::OneOf(allowed) => match item.as_str() {
+                Some(value) if allowed.contains(&value) => None,
+                _ => Some(format!("`{dotted}` must be one of {}", allowed.join(", "))),
+            },
+            Expect::Bool => item
+                .as_bool()
+                .is_none()
+                .then(|| format!("`{dotted}` must be true or false")),
+            Expect::Integer => item
+                .as_integer()
+                .is_none()
+                .then(|| format!("`{dotted}` must be an integer")),
+            Expect::Number => (item.as_integer().is_none() && item.as_float().is_none())
+                .then(|| format!("`{dotted}` must be a number")),
+            Expect::StringArray => {
+                let valid = item
+                    .as_array()
+                    .is_some_and(|values| values.iter().all(|value| value.as_str().is_some()));
+                (!valid).then(|| format!("`{dotted}` must be an array of strings"))
+            }
+            Expect::StringMap => {
+                let valid = item
+                    .as_table_like()
+                    .is_some_and(|table| table.iter().all(|(_, value)| value.as_str().is_some()));
+                (!valid).then(|| format!("`{dotted}` must be a table of strings"))
+            }
+            Expect::Table => item
+                .as_table_like()
+                .is_none()
+                .then(|| format!("`{dotted}` must be a table")),
+        };
+        if let Some(message) = problem {
+            self.error(key_path, message);
+        }
+    }
+
+    fn error(&mut self, key_path: &[String], message: String) {
+        self.push(ConfigDiagnosticSeverity::Error, key_path, message);
+    }
+
+    fn warn(&mut self, key_path: &[String], message: String) {
+        self.push(ConfigDiagnosticSeverity::Warning, key_path, message);
+    }
+
+    fn push(&mut self, severity: ConfigDiagnosticSeverity, key_path: &[String], message: String) {
+        self.diagnostics.push(ConfigDiagnostic {
+            severity,
+            line: self.lines.line_for(key_path),
+            column: None,
+            key: Some(key_path.join(".")),
+            message,
+        });
+    }
+}
+
+fn top_level_expectation(key: &str) -> Option<Expect> {
+    let expect = match key {
+        "model"
+        | "review_model"
+        | "model_provider"
+        | "profile"
+        | "instructions"
+        | "developer_instructions"
+        | "compact_prompt"
+        | "experimental_instructions_file"
+        | "experimental_compact_prompt_file"
+        | "model_instructions_file"
+        | "chatgpt_base_url"
+        | "personality"
+        | "forced_chatgpt_workspace_id"
+        | "oss_provider" => Expect::String,
+        "approval_policy" => Expect::OneOf(APPROVAL_POLICIES),
+        "sandbox_mode" => Expect::OneOf(SANDBOX_MODES),
+        "model_reasoning_effort" => Expect::OneOf(REASONING_EFFORTS),
+        "model_reasoning_summary" => Expect::OneOf(REASONING_SUMMARIES),
+        "model_verbosity" => Expect::OneOf(VERBOSITIES),
+        "file_opener" => Expect::OneOf(FILE_OPENERS),
+        "forced_login_method" => Expect::OneOf(&["chatgpt", "api"]),
+        "model_context_window" | "model_auto_compact_token_limit" | "project_doc_max_bytes" => {
+            Expect::Integer
+        }
+        "hide_agent_reasoning"
+        | "show_raw_agent_reasoning"
+        | "disable_response_storage"
+        | "model_supports_reasoning_summaries"
+        | "check_for_update_on_startup"
+        | "windows_wsl_setup_acknowledged" => Expect::Bool,
+        "notify" | "project_doc_fallback_filenames" => Expect::StringArray,
+        "profiles"
+        | "model_providers"
+        | "mcp_servers"
+        | "features"
+        | "sandbox_workspace_write"
+        | "history"
+        | "tui"
+        | "projects"
+        | "tools"
+        | "shell_environment_policy"
+        | "otel"
+        | "notice"
+        | "agents"
+        | "skills"
+        | "analytics"
+        | "feedback"
+        | "ghost_snapshot" => Expect::Table,
+        _ => return None,
+    };
+    Some(expect)
+}
+
+fn path(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|part| part.to_string()).collect()
+}
+
+/// Maps dotted key paths to the line that defines them, by scanning table
+/// headers and `key = value` lines. Continuation lines of multi-line arrays
+/// and strings are skipped.
+struct KeyLines {
+    lines: HashMap<Vec<String>, usize>,
+}
+
+impl KeyLines {
+    fn index(contents: &str) -> Self {
+        let mut lines = HashMap::new();
+        let mut table: Vec<String> = Vec::new();
+        let mut open_brackets = 0usize;
+        let mut in_multiline_string: Option<&str> = None;
+        for (index, raw_line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw_line.trim();
+            if let Some(delimiter) = in_multiline_string {
+                if line.contains(delimiter) {
+                    in_multiline_string = None;
+                }
+                continue;
+            }
+            if open_brackets > 0 {
+                open_brackets = bracket_depth(line, open_brackets);
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let header = line
+                    .trim_start_matches('[')
+                    .split(']')
+                    .next()
+                    .unwrap_or_default();
+                table = split_dotted_key(header);
+                lines.entry(table.clone()).or_insert(line_number);
+                continue;
+            }
+            let Some(equals) = find_unquoted(line, '=') else {
+                continue;
+            };
+            let mut key_path = table.clone();
+            key_path.extend(split_dotted_key(&line[..equals]));
+            lines.entry(key_path).or_insert(line_number);
+
+            let value = line[equals + 1..].trim_start();
+            for delimiter in ["\"\"\"", "'''"] {
+                if let Some(rest) = value.strip_prefix(delimiter) {
+                    if !rest.contains(delimiter) {
+                        in_multiline_string = Some(delimiter);
+                    }
+                }
+            }
+            if in_multiline_string.is_none() {
+                open_brackets = bracket_depth(value, 0);
+            }
+        }
+        Self { lines }
+    }
+
+    /// Line of `key_path`, falling back to its closest defined parent.
+    fn line_for(&self, key_path: &[String]) -> usize {
+        (1..=key_path.len())
+            .rev()
+            .find_map(|length| self.lines.get(&key_path[..length]).copied())
+            .unwrap_or(1)
+    }
+}
+
+fn split_dotted_key(raw: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for ch in raw.chars() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), ch) => current.push(ch),
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '.') => parts.push(std::mem::take(&mut current).trim().to_string()),
+            (None, ch) => current.push(ch),
+        }
+    }
+    parts.push(current.trim().to_string());
+    parts
+}
+
+fn find_unquoted(line: &str, target: char) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (index, ch) in line.char_indices() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '#') => return None,
+            (None, ch) if ch == target => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Open `[`/`{` count after `line`, ignoring brackets in strings and comments.
+fn bracket_depth(line: &str, mut depth: usize) -> usize {
+    let mut quote: Option<char> = None;
+    for ch in line.chars() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '#') => break,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(diagnostics: &'a [ConfigDiagnostic], key: &str) -> &'a ConfigDiagnostic {
+        diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.key.as_deref() == Some(key))
+            .unwrap_or_else(|| panic!("no diagnostic for {key}: {diagnostics:?}"))
+    }
+
+    #[test]
+    fn anchors_schema_errors_to_their_lines() {
+        let contents = "model = \"gpt-5\"\napproval_policy = \"sometimes\"\nnotify = [\n  \"say\",\n  1,\n]\nmystery = true\n\n[profiles.fast]\nmodel_provider = \"azure\"\nsandbox_mode = \"read-only\"\n\n[mcp_servers.docs]\ncommand = \"docs-mcp\"\nurl = \"http://localhost\"\n\n[features]\nsteer = \"yes\"\n";
+
+        let diagnostics = validate_config_toml(contents);
+
+        let approval = find(&diagnostics, "approval_policy");
+        assert_eq!(approval.severity, ConfigDiagnosticSeverity::Error);
+        assert_eq!(approval.line, 2);
+        assert!(approval.message.contains("on-request"));
+        assert_eq!(find(&diagnostics, "notify").line, 3);
+        let mystery = find(&diagnostics, "mystery");
+        assert_eq!(mystery.severity, ConfigDiagnosticSeverity::Warning);
+        assert_eq!(mystery.line, 7);
+        let provider = find(&diagnostics, "profiles.fast.model_provider");
+        assert_eq!(provider.severity, ConfigDiagnosticSeverity::Warning);
+        assert_eq!(provider.line, 10);
+        let server = find(&diagnostics, "mcp_servers.docs");
+        assert_eq!(server.severity, ConfigDiagnosticSeverity::Error);
+        assert_eq!(server.line, 13);
+        assert_eq!(find(&diagnostics, "features.steer").line, 18);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.key.as_deref() != Some("profiles.fast.sandbox_mode")));
+    }
+
+    #[test]
+    fn reports_parse_errors_with_position() {
+        let diagnostics = validate_config_toml("model = \"gpt-5\"\nsandbox_mode = \n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, ConfigDiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].line, 2);
+        assert!(diagnostics[0].column.is_some());
+        assert!(diagnostics[0].message.starts_with("Invalid TOML"));
+    }
+
+    #[test]
+    fn only_new_errors_block_a_write() {
+        let previous = "approval_policy = \"sometimes\"\n";
+        assert!(new_errors(
+            Some(previous),
+            "approval_policy = \"sometimes\"\nmodel = \"o3\"\n"
+        )
+        .is_empty());
+        let introduced = new_errors(
+            Some(previous),
+            "approval_policy = \"sometimes\"\nsandbox_mode = 3\n",
+        );
+        assert_eq!(introduced.len(), 1);
+        assert_eq!(introduced[0].key.as_deref(), Some("sandbox_mode"));
+        assert!(new_errors(None, "").is_empty());
+    }
+}
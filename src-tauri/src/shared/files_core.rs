@@ -7,6 +7,7 @@ use crate::codex::home as codex_home;
 use crate::files::io::TextFileResponse;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::shared::config_toml_core::{write_config_text, ConfigTarget};
 use crate::types::WorkspaceEntry;
 
 fn resolve_default_codex_home() -> Result<PathBuf, String> {
@@ -59,5 +60,12 @@ pub(crate) async fn file_write_core(
 ) -> Result<(), String> {
     let policy = policy_for(scope, kind)?;
     let root = resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?;
+    if matches!(kind, FileKind::Config) {
+        let target = match scope {
+            FileScope::Global => ConfigTarget::Global(root),
+            FileScope::Workspace => ConfigTarget::Workspace(root),
+        };
+        return write_config_text(&target, &content);
+    }
     write_with_policy(&root, policy, &content)
 }
@@ -2,6 +2,8 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::types::{GitHubListQuery, GitHubPullRequestReviewComment, GitHubPullRequestReviewEvent};
+
 pub(crate) const METHOD_GET_GIT_STATUS: &str = "get_git_status";
 pub(crate) const METHOD_INIT_GIT_REPO: &str = "init_git_repo";
 pub(crate) const METHOD_CREATE_GITHUB_REPO: &str = "create_github_repo";
@@ -20,6 +22,7 @@ pub(crate) const METHOD_GET_GIT_DIFFS: &str = "get_git_diffs";
 pub(crate) const METHOD_GET_GIT_LOG: &str = "get_git_log";
 pub(crate) const METHOD_GET_GIT_COMMIT_DIFF: &str = "get_git_commit_diff";
 pub(crate) const METHOD_GET_GIT_REMOTE: &str = "get_git_remote";
+pub(crate) const METHOD_GET_WORKSPACE_FORGE: &str = "get_workspace_forge";
 pub(crate) const METHOD_GET_GITHUB_ISSUES: &str = "get_github_issues";
 pub(crate) const METHOD_GET_GITHUB_PULL_REQUESTS: &str = "get_github_pull_requests";
 pub(crate) const METHOD_GET_GITHUB_PULL_REQUEST_DIFF: &str = "get_github_pull_request_diff";
@@ -28,6 +31,23 @@ pub(crate) const METHOD_CHECKOUT_GITHUB_PULL_REQUEST: &str = "checkout_github_pu
 pub(crate) const METHOD_LIST_GIT_BRANCHES: &str = "list_git_branches";
 pub(crate) const METHOD_CHECKOUT_GIT_BRANCH: &str = "checkout_git_branch";
 pub(crate) const METHOD_CREATE_GIT_BRANCH: &str = "create_git_branch";
+pub(crate) const METHOD_LIST_GIT_TAGS: &str = "list_git_tags";
+pub(crate) const METHOD_CREATE_GIT_TAG: &str = "create_git_tag";
+pub(crate) const METHOD_DELETE_GIT_TAG: &str = "delete_git_tag";
+pub(crate) const METHOD_PUSH_GIT_TAGS: &str = "push_git_tags";
+pub(crate) const METHOD_GENERATE_RELEASE_NOTES: &str = "generate_release_notes";
+pub(crate) const METHOD_CREATE_GITHUB_RELEASE: &str = "create_github_release";
+pub(crate) const METHOD_GENERATE_GITHUB_PULL_REQUEST_CONTENT: &str =
+    "generate_github_pull_request_content";
+pub(crate) const METHOD_CREATE_GITHUB_PULL_REQUEST: &str = "create_github_pull_request";
+pub(crate) const METHOD_UPDATE_GITHUB_PULL_REQUEST: &str = "update_github_pull_request";
+pub(crate) const METHOD_MARK_GITHUB_PULL_REQUEST_READY: &str = "mark_github_pull_request_ready";
+pub(crate) const METHOD_POST_GITHUB_PULL_REQUEST_REVIEW: &str = "post_github_pull_request_review";
+pub(crate) const METHOD_GET_GITHUB_CHECKS: &str = "get_github_checks";
+pub(crate) const METHOD_WATCH_GITHUB_CHECKS: &str = "watch_github_checks";
+pub(crate) const METHOD_UNWATCH_GITHUB_CHECKS: &str = "unwatch_github_checks";
+pub(crate) const METHOD_WATCH_GIT_STATUS: &str = "watch_git_status";
+pub(crate) const METHOD_UNWATCH_GIT_STATUS: &str = "unwatch_git_status";
 #[allow(dead_code)]
 pub(crate) const METHOD_GENERATE_COMMIT_MESSAGE: &str = "generate_commit_message";
 
@@ -134,3 +154,121 @@ pub(crate) struct GenerateCommitMessageRequest {
     pub(crate) workspace_id: String,
     pub(crate) commit_message_model_id: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateGitTagRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) name: String,
+    pub(crate) message: Option<String>,
+    pub(crate) target: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeleteGitTagRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) name: String,
+    pub(crate) remote: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PushGitTagsRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) name: Option<String>,
+    pub(crate) remote: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GenerateReleaseNotesRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) tag: String,
+    pub(crate) model_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateGitHubReleaseRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) tag: String,
+    pub(crate) title: Option<String>,
+    pub(crate) notes: Option<String>,
+    pub(crate) draft: Option<bool>,
+    pub(crate) model_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GenerateGitHubPullRequestContentRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) base: Option<String>,
+    pub(crate) model_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateGitHubPullRequestRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) title: Option<String>,
+    pub(crate) body: Option<String>,
+    pub(crate) base: Option<String>,
+    pub(crate) draft: Option<bool>,
+    pub(crate) reviewers: Option<Vec<String>>,
+    pub(crate) generate: Option<bool>,
+    pub(crate) model_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpdateGitHubPullRequestRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) pr_number: u64,
+    pub(crate) title: Option<String>,
+    pub(crate) body: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PostGitHubPullRequestReviewRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) pr_number: u64,
+    pub(crate) body: Option<String>,
+    pub(crate) event: Option<GitHubPullRequestReviewEvent>,
+    pub(crate) comments: Option<Vec<GitHubPullRequestReviewComment>>,
+    pub(crate) review_thread_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubListRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) query: Option<GitHubListQuery>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubChecksRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) git_ref: Option<String>,
+    pub(crate) pr_number: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WatchGitStatusRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) debounce_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WatchGitHubChecksRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) git_ref: Option<String>,
+    pub(crate) pr_number: Option<u64>,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) interval_secs: Option<u64>,
+}
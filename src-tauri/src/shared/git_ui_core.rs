@@ -1,24 +1,45 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use serde_json::Value;
 use tokio::sync::Mutex;
 
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::EventSink;
+
 use crate::types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, WorkspaceEntry,
+    AppSettings, ForgeInfo, GitCommitDiff, GitFileDiff, GitHubChecksResponse, GitHubIssue,
+    GitHubIssuesResponse, GitHubListQuery, GitHubPullRequest, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestReviewComment, GitHubPullRequestReviewEvent,
+    GitHubPullRequestReviewResult, GitHubPullRequestsResponse, GitHubRelease, GitLogResponse,
+    GitTagInfo, WorkspaceEntry,
 };
 
+#[path = "git_ui_core/checks.rs"]
+mod checks;
 #[path = "git_ui_core/commands.rs"]
 mod commands;
 #[path = "git_ui_core/context.rs"]
 mod context;
 #[path = "git_ui_core/diff.rs"]
 mod diff;
+#[path = "git_ui_core/forge.rs"]
+mod forge;
 #[path = "git_ui_core/github.rs"]
 mod github;
 #[path = "git_ui_core/log.rs"]
 mod log;
+#[path = "git_ui_core/reviews.rs"]
+mod reviews;
+#[path = "git_ui_core/tags.rs"]
+mod tags;
+#[path = "git_ui_core/watcher.rs"]
+mod watcher;
+
+pub(crate) use checks::GitHubChecksWatchers;
+pub(crate) use tags::ReleaseCommitRange;
+pub(crate) use watcher::GitStatusWatchers;
 
 #[cfg(test)]
 #[path = "git_ui_core/tests.rs"]
@@ -35,6 +56,27 @@ pub(crate) fn collect_workspace_diff_core(repo_root: &Path) -> Result<String, St
     diff::collect_workspace_diff(repo_root)
 }
 
+/// Watches the workspace tree, honoring `.gitignore`, and emits
+/// `git-status-changed` with the changed paths once edits settle for
+/// `debounce_ms`. Watching a workspace again replaces its earlier watcher.
+pub(crate) async fn watch_git_status_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    watchers: &GitStatusWatchers,
+    event_sink: impl EventSink,
+    workspace_id: String,
+    debounce_ms: Option<u64>,
+) -> Result<(), String> {
+    watcher::watch_git_status_inner(workspaces, watchers, event_sink, workspace_id, debounce_ms)
+        .await
+}
+
+pub(crate) async fn unwatch_git_status_core(
+    watchers: &GitStatusWatchers,
+    workspace_id: String,
+) -> bool {
+    watcher::unwatch_git_status_inner(watchers, workspace_id).await
+}
+
 pub(crate) async fn get_git_status_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -175,18 +217,38 @@ pub(crate) async fn sync_git_core(
     commands::sync_git_inner(workspaces, workspace_id).await
 }
 
+/// Forge serving the workspace's issues and pull requests, either from its
+/// settings override or detected from the remote URL.
+pub(crate) async fn get_workspace_forge_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<ForgeInfo, String> {
+    forge::get_workspace_forge_inner(workspaces, workspace_id).await
+}
+
 pub(crate) async fn get_github_issues_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    query: GitHubListQuery,
 ) -> Result<GitHubIssuesResponse, String> {
-    github::get_github_issues_inner(workspaces, workspace_id).await
+    forge::list_issues_inner(workspaces, workspace_id, query).await
+}
+
+/// A single issue with its comments, used to seed an issue agent's prompt.
+pub(crate) async fn get_github_issue_with_comments_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    issue_number: u64,
+) -> Result<(GitHubIssue, Vec<GitHubPullRequestComment>), String> {
+    forge::issue_with_comments_inner(workspaces, workspace_id, issue_number).await
 }
 
 pub(crate) async fn get_github_pull_requests_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    query: GitHubListQuery,
 ) -> Result<GitHubPullRequestsResponse, String> {
-    github::get_github_pull_requests_inner(workspaces, workspace_id).await
+    forge::list_pull_requests_inner(workspaces, workspace_id, query).await
 }
 
 pub(crate) async fn get_github_pull_request_diff_core(
@@ -194,7 +256,7 @@ pub(crate) async fn get_github_pull_request_diff_core(
     workspace_id: String,
     pr_number: u64,
 ) -> Result<Vec<GitHubPullRequestDiff>, String> {
-    github::get_github_pull_request_diff_inner(workspaces, workspace_id, pr_number).await
+    forge::pull_request_diff_inner(workspaces, workspace_id, pr_number).await
 }
 
 pub(crate) async fn get_github_pull_request_comments_core(
@@ -202,7 +264,75 @@ pub(crate) async fn get_github_pull_request_comments_core(
     workspace_id: String,
     pr_number: u64,
 ) -> Result<Vec<GitHubPullRequestComment>, String> {
-    github::get_github_pull_request_comments_inner(workspaces, workspace_id, pr_number).await
+    forge::pull_request_comments_inner(workspaces, workspace_id, pr_number).await
+}
+
+/// Posts a review to a pull request. `codex_review` is the rendered output of a
+/// completed Codex review turn; its findings become inline comments and its
+/// explanation fills the summary when `body` is empty.
+pub(crate) async fn post_github_pull_request_review_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    body: Option<String>,
+    event: GitHubPullRequestReviewEvent,
+    comments: Vec<GitHubPullRequestReviewComment>,
+    codex_review: Option<String>,
+) -> Result<GitHubPullRequestReviewResult, String> {
+    reviews::post_github_pull_request_review_inner(
+        workspaces,
+        workspace_id,
+        pr_number,
+        body,
+        event,
+        comments,
+        codex_review,
+    )
+    .await
+}
+
+/// Check runs for a pull request's head commit, or for `git_ref` (default `HEAD`).
+pub(crate) async fn get_github_checks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+) -> Result<GitHubChecksResponse, String> {
+    checks::get_github_checks_inner(workspaces, workspace_id, git_ref, pr_number).await
+}
+
+/// Polls checks in the background and emits `github-checks-finished` once they
+/// all complete. Watching the same target again replaces the earlier watcher.
+pub(crate) async fn watch_github_checks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    watchers: &GitHubChecksWatchers,
+    event_sink: impl EventSink,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    thread_id: Option<String>,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    checks::watch_github_checks_inner(
+        workspaces,
+        watchers,
+        event_sink,
+        workspace_id,
+        git_ref,
+        pr_number,
+        thread_id,
+        interval_secs,
+    )
+    .await
+}
+
+pub(crate) async fn unwatch_github_checks_core(
+    watchers: &GitHubChecksWatchers,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+) -> bool {
+    checks::unwatch_github_checks_inner(watchers, workspace_id, git_ref, pr_number).await
 }
 
 pub(crate) async fn checkout_github_pull_request_core(
@@ -210,7 +340,62 @@ pub(crate) async fn checkout_github_pull_request_core(
     workspace_id: String,
     pr_number: u64,
 ) -> Result<(), String> {
-    github::checkout_github_pull_request_inner(workspaces, workspace_id, pr_number).await
+    forge::checkout_pull_request_inner(workspaces, workspace_id, pr_number).await
+}
+
+pub(crate) async fn pull_request_head_ref_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<String, String> {
+    forge::pull_request_head_ref_inner(workspaces, workspace_id, pr_number).await
+}
+
+pub(crate) async fn get_github_pull_request_branch_diff_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    base: Option<String>,
+) -> Result<String, String> {
+    github::get_github_pull_request_branch_diff_inner(workspaces, workspace_id, base).await
+}
+
+pub(crate) async fn create_github_pull_request_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    title: String,
+    body: String,
+    base: Option<String>,
+    draft: bool,
+    reviewers: Vec<String>,
+) -> Result<GitHubPullRequest, String> {
+    github::create_github_pull_request_inner(
+        workspaces,
+        workspace_id,
+        title,
+        body,
+        base,
+        draft,
+        reviewers,
+    )
+    .await
+}
+
+pub(crate) async fn update_github_pull_request_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    title: Option<String>,
+    body: Option<String>,
+) -> Result<GitHubPullRequest, String> {
+    github::update_github_pull_request_inner(workspaces, workspace_id, pr_number, title, body).await
+}
+
+pub(crate) async fn mark_github_pull_request_ready_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubPullRequest, String> {
+    github::mark_github_pull_request_ready_inner(workspaces, workspace_id, pr_number).await
 }
 
 pub(crate) async fn list_git_branches_core(
@@ -235,3 +420,81 @@ pub(crate) async fn create_git_branch_core(
 ) -> Result<(), String> {
     commands::create_git_branch_inner(workspaces, workspace_id, name).await
 }
+
+pub(crate) async fn list_git_tags_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitTagInfo>, String> {
+    tags::list_git_tags_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn create_git_tag_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: String,
+    message: Option<String>,
+    target: Option<String>,
+) -> Result<(), String> {
+    tags::create_git_tag_inner(workspaces, workspace_id, name, message, target).await
+}
+
+pub(crate) async fn delete_git_tag_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+) -> Result<(), String> {
+    tags::delete_git_tag_inner(workspaces, workspace_id, name, remote).await
+}
+
+pub(crate) async fn push_git_tags_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: Option<String>,
+    remote: Option<String>,
+) -> Result<(), String> {
+    tags::push_git_tags_inner(workspaces, workspace_id, name, remote).await
+}
+
+pub(crate) async fn release_commit_range_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    tag: String,
+) -> Result<ReleaseCommitRange, String> {
+    tags::release_commit_range_inner(workspaces, workspace_id, tag).await
+}
+
+pub(crate) async fn release_notes_core<F>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    range: &ReleaseCommitRange,
+    notes: Option<String>,
+    model: Option<&str>,
+    on_hide_thread: F,
+) -> Result<String, String>
+where
+    F: Fn(&str, &str),
+{
+    tags::release_notes_inner(
+        sessions,
+        workspaces,
+        workspace_id,
+        range,
+        notes,
+        model,
+        on_hide_thread,
+    )
+    .await
+}
+
+pub(crate) async fn create_github_release_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    range: ReleaseCommitRange,
+    title: Option<String>,
+    notes: String,
+    draft: bool,
+) -> Result<GitHubRelease, String> {
+    tags::create_github_release_inner(workspaces, workspace_id, range, title, notes, draft).await
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::backend::events::{EventSink, GitHubChecksFinished};
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core;
+use crate::types::{GitHubCheckRun, GitHubChecksResponse, GitHubChecksState, WorkspaceEntry};
+
+use super::commands::run_gh_command;
+use super::context::workspace_entry_for_id;
+use super::github::github_repo_from_path;
+
+const FAILED_CONCLUSIONS: [&str; 3] = ["failure", "timed_out", "startup_failure"];
+const MAX_FAILED_LOGS: usize = 5;
+const LOG_EXCERPT_LINES: usize = 60;
+const LOG_EXCERPT_CHARS: usize = 6_000;
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 30;
+const MIN_WATCH_INTERVAL_SECS: u64 = 10;
+const WATCH_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Running check watchers, keyed by workspace and watched ref or pull request.
+pub(crate) type GitHubChecksWatchers = Mutex<HashMap<String, JoinHandle<()>>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ChecksTarget {
+    PullRequest(u64),
+    Ref(String),
+}
+
+impl ChecksTarget {
+    fn new(git_ref: Option<String>, pr_number: Option<u64>) -> Self {
+        match pr_number {
+            Some(number) => ChecksTarget::PullRequest(number),
+            None => ChecksTarget::Ref(
+                git_ref
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .unwrap_or_else(|| "HEAD".to_string()),
+            ),
+        }
+    }
+
+    fn watcher_key(&self, workspace_id: &str) -> String {
+        match self {
+            ChecksTarget::PullRequest(number) => format!("{workspace_id}:pr:{number}"),
+            ChecksTarget::Ref(git_ref) => format!("{workspace_id}:ref:{git_ref}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunsPage {
+    #[serde(default)]
+    check_runs: Vec<ApiCheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCheckRun {
+    id: u64,
+    name: String,
+    status: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+    #[serde(default)]
+    html_url: Option<String>,
+    #[serde(default)]
+    details_url: Option<String>,
+    #[serde(default)]
+    started_at: Option<String>,
+    #[serde(default)]
+    completed_at: Option<String>,
+    #[serde(default)]
+    app: Option<ApiCheckApp>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCheckApp {
+    #[serde(default)]
+    slug: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestHead {
+    head_ref_name: String,
+    head_ref_oid: String,
+}
+
+impl From<ApiCheckRun> for GitHubCheckRun {
+    fn from(run: ApiCheckRun) -> Self {
+        GitHubCheckRun {
+            id: run.id,
+            name: run.name,
+            status: run.status,
+            conclusion: run.conclusion,
+            url: run.html_url.or(run.details_url).unwrap_or_default(),
+            app: run.app.and_then(|app| app.slug),
+            started_at: run.started_at,
+            completed_at: run.completed_at,
+            log_excerpt: None,
+        }
+    }
+}
+
+fn is_failed_check(check: &GitHubCheckRun) -> bool {
+    check
+        .conclusion
+        .as_deref()
+        .is_some_and(|conclusion| FAILED_CONCLUSIONS.contains(&conclusion))
+}
+
+pub(super) fn checks_state(checks: &[GitHubCheckRun]) -> GitHubChecksState {
+    if checks.is_empty() {
+        GitHubChecksState::None
+    } else if checks.iter().any(|check| check.status != "completed") {
+        GitHubChecksState::Pending
+    } else if checks.iter().any(is_failed_check) {
+        GitHubChecksState::Failure
+    } else {
+        GitHubChecksState::Success
+    }
+}
+
+/// Keeps the tail of `gh run view --log-failed` output, dropping the job and
+/// step columns it prefixes every line with.
+pub(super) fn log_excerpt(log: &str) -> Option<String> {
+    let lines: Vec<&str> = log
+        .lines()
+        .map(|line| line.splitn(3, '\t').last().unwrap_or(line).trim_end())
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let tail = lines[lines.len().saturating_sub(LOG_EXCERPT_LINES)..].join("\n");
+    if tail.is_empty() {
+        return None;
+    }
+    let skip = tail.chars().count().saturating_sub(LOG_EXCERPT_CHARS);
+    Some(tail.chars().skip(skip).collect())
+}
+
+/// Message a thread can be sent when checks fail, listing each failed check with
+/// its log excerpt.
+pub(super) fn build_checks_follow_up(checks: &GitHubChecksResponse) -> Option<String> {
+    if checks.state != GitHubChecksState::Failure {
+        return None;
+    }
+    let short_sha: String = checks.sha.chars().take(7).collect();
+    let mut sections = vec![format!(
+        "CI failed for `{}` ({short_sha}). Failed checks:",
+        checks.git_ref
+    )];
+    for check in checks.checks.iter().filter(|check| is_failed_check(check)) {
+        let conclusion = check.conclusion.as_deref().unwrap_or("failure");
+        let mut section = format!("### {} ({conclusion})", check.name);
+        if !check.url.is_empty() {
+            section.push('\n');
+            section.push_str(&check.url);
+        }
+        if let Some(excerpt) = check.log_excerpt.as_deref() {
+            section.push_str(&format!("\n```\n{excerpt}\n```"));
+        }
+        sections.push(section);
+    }
+    sections.push("Investigate the failures and fix them.".to_string());
+    Some(sections.join("\n\n"))
+}
+
+async fn resolve_checks_commit(
+    repo_root: &PathBuf,
+    repo_name: &str,
+    target: &ChecksTarget,
+) -> Result<(String, String), String> {
+    match target {
+        ChecksTarget::PullRequest(number) => {
+            let number = number.to_string();
+            let (stdout, _) = run_gh_command(
+                repo_root,
+                &[
+                    "pr",
+                    "view",
+                    &number,
+                    "--repo",
+                    repo_name,
+                    "--json",
+                    "headRefName,headRefOid",
+                ],
+            )
+            .await?;
+            let head: PullRequestHead =
+                serde_json::from_str(stdout.trim()).map_err(|err| err.to_string())?;
+            Ok((head.head_ref_name, head.head_ref_oid))
+        }
+        ChecksTarget::Ref(git_ref) => {
+            let commit = format!("{git_ref}^{{commit}}");
+            if let Ok(sha) =
+                git_core::run_git_command(repo_root, &["rev-parse", "--verify", &commit]).await
+            {
+                let name = if git_ref == "HEAD" {
+                    git_core::run_git_command(repo_root, &["rev-parse", "--abbrev-ref", "HEAD"])
+                        .await
+                        .unwrap_or_else(|_| git_ref.clone())
+                } else {
+                    git_ref.clone()
+                };
+                return Ok((name, sha));
+            }
+            let endpoint = format!("repos/{repo_name}/commits/{git_ref}");
+            let (stdout, _) =
+                run_gh_command(repo_root, &["api", &endpoint, "--jq", ".sha"]).await?;
+            Ok((git_ref.clone(), stdout.trim().to_string()))
+        }
+    }
+}
+
+async fn failed_job_log(
+    repo_root: &Path,
+    repo_name: &str,
+    check: &GitHubCheckRun,
+) -> Option<String> {
+    if check.app.as_deref() != Some("github-actions") {
+        return None;
+    }
+    let job_id = check.id.to_string();
+    let (stdout, _) = run_gh_command(
+        repo_root,
+        &[
+            "run",
+            "view",
+            "--job",
+            &job_id,
+            "--log-failed",
+            "--repo",
+            repo_name,
+        ],
+    )
+    .await
+    .ok()?;
+    log_excerpt(&stdout)
+}
+
+async fn github_checks_for_repo(
+    repo_root: &PathBuf,
+    target: &ChecksTarget,
+) -> Result<GitHubChecksResponse, String> {
+    let repo_name = github_repo_from_path(repo_root)?;
+    let (git_ref, sha) = resolve_checks_commit(repo_root, &repo_name, target).await?;
+    let endpoint = format!("repos/{repo_name}/commits/{sha}/check-runs?per_page=100");
+    let (stdout, _) = run_gh_command(repo_root, &["api", &endpoint]).await?;
+    let page: CheckRunsPage = serde_json::from_str(stdout.trim()).map_err(|err| err.to_string())?;
+
+    let mut checks: Vec<GitHubCheckRun> = page
+        .check_runs
+        .into_iter()
+        .map(GitHubCheckRun::from)
+        .collect();
+    checks.sort_by(|a, b| a.name.cmp(&b.name));
+    let state = checks_state(&checks);
+    if state == GitHubChecksState::Failure {
+        for check in checks
+            .iter_mut()
+            .filter(|check| is_failed_check(check))
+            .take(MAX_FAILED_LOGS)
+        {
+            check.log_excerpt = failed_job_log(repo_root, &repo_name, check).await;
+        }
+    }
+
+    Ok(GitHubChecksResponse {
+        git_ref,
+        sha,
+        pr_number: match target {
+            ChecksTarget::PullRequest(number) => Some(*number),
+            ChecksTarget::Ref(_) => None,
+        },
+        state,
+        checks,
+    })
+}
+
+pub(super) async fn get_github_checks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+) -> Result<GitHubChecksResponse, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    github_checks_for_repo(&repo_root, &ChecksTarget::new(git_ref, pr_number)).await
+}
+
+async fn poll_github_checks(
+    event_sink: impl EventSink,
+    repo_root: PathBuf,
+    target: ChecksTarget,
+    workspace_id: String,
+    thread_id: Option<String>,
+    interval: Duration,
+) {
+    let started = Instant::now();
+    let mut latest = None;
+    loop {
+        // A failed poll is usually a transient gh or network error; keep going
+        // until the timeout rather than dropping the watch.
+        if let Ok(checks) = github_checks_for_repo(&repo_root, &target).await {
+            if checks.state.is_finished() {
+                latest = Some((checks, false));
+                break;
+            }
+            latest = Some((checks, true));
+        }
+        if started.elapsed() >= WATCH_TIMEOUT {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+    let Some((checks, timed_out)) = latest else {
+        return;
+    };
+    event_sink.emit_github_checks_finished(GitHubChecksFinished {
+        workspace_id,
+        thread_id,
+        timed_out,
+        follow_up: build_checks_follow_up(&checks),
+        checks,
+    });
+}
+
+pub(super) async fn watch_github_checks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    watchers: &GitHubChecksWatchers,
+    event_sink: impl EventSink,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    thread_id: Option<String>,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    github_repo_from_path(&repo_root)?;
+    let target = ChecksTarget::new(git_ref, pr_number);
+    let key = target.watcher_key(&workspace_id);
+    let interval = Duration::from_secs(
+        interval_secs
+            .unwrap_or(DEFAULT_WATCH_INTERVAL_SECS)
+            .max(MIN_WATCH_INTERVAL_SECS),
+    );
+
+    let handle = tokio::spawn(poll_github_checks(
+        event_sink,
+        repo_root,
+        target,
+        workspace_id,
+        thread_id,
+        interval,
+    ));
+    let mut watchers = watchers.lock().await;
+    watchers.retain(|_, handle| !handle.is_finished());
+    if let Some(previous) = watchers.insert(key, handle) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+pub(super) async fn unwatch_github_checks_inner(
+    watchers: &GitHubChecksWatchers,
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+) -> bool {
+    let key = ChecksTarget::new(git_ref, pr_number).watcher_key(&workspace_id);
+    let Some(handle) = watchers.lock().await.remove(&key) else {
+        return false;
+    };
+    let running = !handle.is_finished();
+    handle.abort();
+    running
+}
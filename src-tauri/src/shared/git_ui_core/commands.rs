@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
 
 use git2::{BranchType, Repository, Status, StatusOptions};
 use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
 use crate::git_utils::{
@@ -42,14 +44,48 @@ async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String>
     Err(detail.to_string())
 }
 
-async fn run_gh_command(repo_root: &Path, args: &[&str]) -> Result<(String, String), String> {
+pub(super) async fn run_gh_command(
+    repo_root: &Path,
+    args: &[&str],
+) -> Result<(String, String), String> {
     let output = tokio_command("gh")
         .args(args)
         .current_dir(repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
+    gh_output_result(output)
+}
 
+pub(super) async fn run_gh_command_with_input(
+    repo_root: &Path,
+    args: &[&str],
+    input: &[u8],
+) -> Result<(String, String), String> {
+    let mut child = tokio_command("gh")
+        .args(args)
+        .current_dir(repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .await
+            .map_err(|e| format!("Failed to write gh input: {e}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    gh_output_result(output)
+}
+
+fn gh_output_result(output: Output) -> Result<(String, String), String> {
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
     if output.status.success() {
@@ -98,7 +134,7 @@ fn count_effective_dir_entries(root: &Path) -> Result<usize, String> {
     Ok(count)
 }
 
-fn validate_branch_name(name: &str) -> Result<String, String> {
+pub(super) fn validate_branch_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err("Branch name is required.".to_string());
@@ -346,6 +382,34 @@ async fn push_with_upstream(repo_root: &Path) -> Result<(), String> {
     run_git_command(repo_root, &["push"]).await
 }
 
+/// Pushes the current branch so a pull request can be opened for it and
+/// returns the branch name on the remote.
+pub(super) async fn push_branch_for_pull_request(repo_root: &Path) -> Result<String, String> {
+    let branch = {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        let head = repo.head().map_err(|e| e.to_string())?;
+        if !head.is_branch() {
+            None
+        } else {
+            head.shorthand().map(str::to_string)
+        }
+    }
+    .ok_or_else(|| "Check out a branch before creating a pull request.".to_string())?;
+
+    // A branch created from `origin/main` tracks it; pushing there would
+    // open the pull request against its own base.
+    if let Some((remote, upstream_branch)) = upstream_remote_and_branch(repo_root)? {
+        if upstream_branch == branch {
+            let refspec = format!("HEAD:{upstream_branch}");
+            run_git_command(repo_root, &["push", remote.as_str(), refspec.as_str()]).await?;
+            return Ok(upstream_branch);
+        }
+    }
+    let refspec = format!("HEAD:{branch}");
+    run_git_command(repo_root, &["push", "-u", "origin", refspec.as_str()]).await?;
+    Ok(branch)
+}
+
 async fn fetch_with_default_remote(repo_root: &Path) -> Result<(), String> {
     let upstream = upstream_remote_and_branch(repo_root)?;
     if let Some((remote, _)) = upstream {
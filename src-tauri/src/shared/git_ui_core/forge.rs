@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core;
+use crate::types::{
+    ForgeInfo, ForgeKind, GitHubIssue, GitHubIssuesResponse, GitHubListQuery,
+    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry,
+};
+
+use super::commands::validate_branch_name;
+use super::context::workspace_entry_for_id;
+use super::github::{origin_remote_url, GitHubCliForge};
+
+#[path = "forge/gitea.rs"]
+mod gitea;
+#[path = "forge/gitlab.rs"]
+mod gitlab;
+#[path = "forge/http.rs"]
+mod http;
+
+pub(super) use gitea::GiteaForge;
+pub(super) use gitlab::GitLabForge;
+
+const GITLAB_TOKEN_ENV: &str = "GITLAB_TOKEN";
+const GITEA_TOKEN_ENV: &str = "GITEA_TOKEN";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Issue and pull request operations behind the Git panel. GitLab merge
+/// requests are reported as pull requests so every forge shares one shape.
+pub(super) trait Forge {
+    async fn list_issues(&self, query: &GitHubListQuery) -> Result<GitHubIssuesResponse, String>;
+    async fn issue(&self, number: u64) -> Result<GitHubIssue, String>;
+    async fn issue_comments(&self, number: u64) -> Result<Vec<GitHubPullRequestComment>, String>;
+    async fn list_pull_requests(
+        &self,
+        query: &GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String>;
+    async fn pull_request_diff(&self, number: u64) -> Result<Vec<GitHubPullRequestDiff>, String>;
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String>;
+    async fn checkout_pull_request(&self, number: u64) -> Result<(), String>;
+}
+
+/// Host and repository path parsed from a remote URL, with the web base the
+/// forge is reached at. SSH remotes are assumed to serve the web UI over HTTPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ForgeRemote {
+    pub(super) host: String,
+    pub(super) web_base: String,
+    pub(super) path: String,
+}
+
+pub(super) fn parse_forge_remote(remote_url: &str) -> Option<ForgeRemote> {
+    let trimmed = remote_url.trim();
+    let (web_scheme, authority, path) = match trimmed.split_once("://") {
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let web_scheme = match scheme {
+                "http" | "https" => Some(scheme),
+                _ => None,
+            };
+            (web_scheme, authority, path)
+        }
+        None => {
+            let (authority, path) = trimmed.split_once(':')?;
+            if authority.contains('/') {
+                return None;
+            }
+            (None, authority, path)
+        }
+    };
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    let host = authority
+        .split(':')
+        .next()
+        .unwrap_or(authority)
+        .to_lowercase();
+    let path = path
+        .trim_matches('/')
+        .trim_end_matches(".git")
+        .trim_end_matches('/')
+        .to_string();
+    if host.is_empty() || !path.contains('/') {
+        return None;
+    }
+    let web_base = match web_scheme {
+        Some(scheme) => format!("{scheme}://{authority}"),
+        None => format!("https://{host}"),
+    };
+    Some(ForgeRemote {
+        host,
+        web_base,
+        path,
+    })
+}
+
+/// Guesses the forge from the remote host. Self-hosted instances on other
+/// hosts need the workspace override.
+pub(super) fn detect_forge_kind(host: &str) -> ForgeKind {
+    let host = host.to_lowercase();
+    if host.contains("gitlab") {
+        ForgeKind::GitLab
+    } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+        ForgeKind::Gitea
+    } else {
+        ForgeKind::GitHub
+    }
+}
+
+pub(super) fn clean_filter(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub(super) fn clean_labels(labels: &[String]) -> Vec<String> {
+    labels
+        .iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect()
+}
+
+pub(super) fn list_page_size(query: &GitHubListQuery) -> u32 {
+    query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Page number encoded in a REST forge cursor; listings start at page 1.
+pub(super) fn page_from_cursor(query: &GitHubListQuery) -> Result<u32, String> {
+    match clean_filter(&query.cursor) {
+        Some(cursor) => cursor
+            .parse::<u32>()
+            .ok()
+            .filter(|page| *page > 0)
+            .ok_or_else(|| format!("Invalid page cursor: {cursor}")),
+        None => Ok(1),
+    }
+}
+
+/// Cursor for the page after `page`. Without a total, a full page is taken to
+/// mean more results may follow.
+pub(super) fn next_page_cursor(
+    page: u32,
+    page_size: u32,
+    returned: usize,
+    total: Option<usize>,
+) -> Option<String> {
+    let has_more = match total {
+        Some(total) => (page as usize) * (page_size as usize) < total,
+        None => returned >= page_size as usize,
+    };
+    has_more.then(|| (page + 1).to_string())
+}
+
+fn default_api_url(kind: ForgeKind, remote: &ForgeRemote) -> String {
+    match kind {
+        ForgeKind::GitHub if remote.host == "github.com" => "https://api.github.com".to_string(),
+        ForgeKind::GitHub => format!("{}/api/v3", remote.web_base),
+        ForgeKind::GitLab => format!("{}/api/v4", remote.web_base),
+        ForgeKind::Gitea => format!("{}/api/v1", remote.web_base),
+    }
+}
+
+fn forge_token(env_name: &str) -> Option<String> {
+    std::env::var(env_name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+struct ResolvedForge {
+    kind: ForgeKind,
+    overridden: bool,
+    remote: Option<ForgeRemote>,
+    api_url: Option<String>,
+    repo_root: PathBuf,
+}
+
+async fn resolve_forge(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<ResolvedForge, String> {
+    let entry = workspace_entry_for_id(workspaces, workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let remote = origin_remote_url(&repo_root)
+        .ok()
+        .and_then(|url| parse_forge_remote(&url));
+    let overridden = entry.settings.forge.is_some();
+    let kind = entry.settings.forge.unwrap_or_else(|| {
+        remote
+            .as_ref()
+            .map(|remote| detect_forge_kind(&remote.host))
+            .unwrap_or(ForgeKind::GitHub)
+    });
+    let api_url = entry
+        .settings
+        .forge_api_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| remote.as_ref().map(|remote| default_api_url(kind, remote)));
+    Ok(ResolvedForge {
+        kind,
+        overridden,
+        remote,
+        api_url,
+        repo_root,
+    })
+}
+
+/// The forge backend serving a workspace, picked from its settings override or
+/// its remote URL.
+pub(super) enum WorkspaceForge {
+    GitHub(GitHubCliForge),
+    GitLab(GitLabForge),
+    Gitea(GiteaForge),
+}
+
+impl ResolvedForge {
+    /// Remote and API base the REST backends need; only GitHub goes through `gh`.
+    fn rest_target(&self) -> Result<(ForgeRemote, String), String> {
+        let remote = self
+            .remote
+            .clone()
+            .ok_or_else(|| "Remote URL does not point at a forge repository.".to_string())?;
+        let api_url = self
+            .api_url
+            .clone()
+            .ok_or_else(|| "Forge API URL is not configured.".to_string())?;
+        Ok((remote, api_url))
+    }
+}
+
+impl WorkspaceForge {
+    fn from_resolved(resolved: ResolvedForge) -> Result<Self, String> {
+        match resolved.kind {
+            ForgeKind::GitHub => {
+                GitHubCliForge::new(resolved.repo_root).map(WorkspaceForge::GitHub)
+            }
+            ForgeKind::GitLab => {
+                let (remote, api_url) = resolved.rest_target()?;
+                GitLabForge::new(
+                    resolved.repo_root,
+                    &api_url,
+                    remote,
+                    forge_token(GITLAB_TOKEN_ENV),
+                )
+                .map(WorkspaceForge::GitLab)
+            }
+            ForgeKind::Gitea => {
+                let (remote, api_url) = resolved.rest_target()?;
+                GiteaForge::new(
+                    resolved.repo_root,
+                    &api_url,
+                    remote,
+                    forge_token(GITEA_TOKEN_ENV),
+                )
+                .map(WorkspaceForge::Gitea)
+            }
+        }
+    }
+}
+
+impl Forge for WorkspaceForge {
+    async fn list_issues(&self, query: &GitHubListQuery) -> Result<GitHubIssuesResponse, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.list_issues(query).await,
+            WorkspaceForge::GitLab(forge) => forge.list_issues(query).await,
+            WorkspaceForge::Gitea(forge) => forge.list_issues(query).await,
+        }
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssue, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.issue(number).await,
+            WorkspaceForge::GitLab(forge) => forge.issue(number).await,
+            WorkspaceForge::Gitea(forge) => forge.issue(number).await,
+        }
+    }
+
+    async fn issue_comments(&self, number: u64) -> Result<Vec<GitHubPullRequestComment>, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.issue_comments(number).await,
+            WorkspaceForge::GitLab(forge) => forge.issue_comments(number).await,
+            WorkspaceForge::Gitea(forge) => forge.issue_comments(number).await,
+        }
+    }
+
+    async fn list_pull_requests(
+        &self,
+        query: &GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.list_pull_requests(query).await,
+            WorkspaceForge::GitLab(forge) => forge.list_pull_requests(query).await,
+            WorkspaceForge::Gitea(forge) => forge.list_pull_requests(query).await,
+        }
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<Vec<GitHubPullRequestDiff>, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.pull_request_diff(number).await,
+            WorkspaceForge::GitLab(forge) => forge.pull_request_diff(number).await,
+            WorkspaceForge::Gitea(forge) => forge.pull_request_diff(number).await,
+        }
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.pull_request_comments(number).await,
+            WorkspaceForge::GitLab(forge) => forge.pull_request_comments(number).await,
+            WorkspaceForge::Gitea(forge) => forge.pull_request_comments(number).await,
+        }
+    }
+
+    async fn checkout_pull_request(&self, number: u64) -> Result<(), String> {
+        match self {
+            WorkspaceForge::GitHub(forge) => forge.checkout_pull_request(number).await,
+            WorkspaceForge::GitLab(forge) => forge.checkout_pull_request(number).await,
+            WorkspaceForge::Gitea(forge) => forge.checkout_pull_request(number).await,
+        }
+    }
+}
+
+async fn workspace_forge(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<WorkspaceForge, String> {
+    WorkspaceForge::from_resolved(resolve_forge(workspaces, workspace_id).await?)
+}
+
+pub(super) async fn list_issues_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    query: GitHubListQuery,
+) -> Result<GitHubIssuesResponse, String> {
+    workspace_forge(workspaces, &workspace_id)
+        .await?
+        .list_issues(&query)
+        .await
+}
+
+/// An issue together with its discussion.
+pub(super) async fn issue_with_comments_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    number: u64,
+) -> Result<(GitHubIssue, Vec<GitHubPullRequestComment>), String> {
+    let forge = workspace_forge(workspaces, &workspace_id).await?;
+    let issue = forge.issue(number).await?;
+    let comments = forge.issue_comments(number).await?;
+    Ok((issue, comments))
+}
+
+pub(super) async fn list_pull_requests_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    query: GitHubListQuery,
+) -> Result<GitHubPullRequestsResponse, String> {
+    workspace_forge(workspaces, &workspace_id)
+        .await?
+        .list_pull_requests(&query)
+        .await
+}
+
+pub(super) async fn pull_request_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    number: u64,
+) -> Result<Vec<GitHubPullRequestDiff>, String> {
+    workspace_forge(workspaces, &workspace_id)
+        .await?
+        .pull_request_diff(number)
+        .await
+}
+
+pub(super) async fn pull_request_comments_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    number: u64,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    workspace_forge(workspaces, &workspace_id)
+        .await?
+        .pull_request_comments(number)
+        .await
+}
+
+pub(super) async fn checkout_pull_request_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    number: u64,
+) -> Result<(), String> {
+    workspace_forge(workspaces, &workspace_id)
+        .await?
+        .checkout_pull_request(number)
+        .await
+}
+
+/// Remote ref a pull or merge request head is published under.
+pub(super) fn pull_request_head_ref(kind: ForgeKind, number: u64) -> String {
+    match kind {
+        ForgeKind::GitLab => format!("refs/merge-requests/{number}/head"),
+        ForgeKind::GitHub | ForgeKind::Gitea => format!("refs/pull/{number}/head"),
+    }
+}
+
+pub(super) async fn pull_request_head_ref_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    number: u64,
+) -> Result<String, String> {
+    let resolved = resolve_forge(workspaces, &workspace_id).await?;
+    Ok(pull_request_head_ref(resolved.kind, number))
+}
+
+pub(super) async fn get_workspace_forge_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<ForgeInfo, String> {
+    let resolved = resolve_forge(workspaces, &workspace_id).await?;
+    Ok(ForgeInfo {
+        kind: resolved.kind,
+        overridden: resolved.overridden,
+        host: resolved.remote.as_ref().map(|remote| remote.host.clone()),
+        repository: resolved.remote.map(|remote| remote.path),
+        api_url: resolved.api_url,
+    })
+}
+
+/// Fetches a pull or merge request head from `origin` and checks it out on a
+/// local branch named after its source branch, fast-forwarding an existing one.
+pub(super) async fn checkout_fetched_head(
+    repo_root: &PathBuf,
+    head_ref: &str,
+    branch: &str,
+) -> Result<(), String> {
+    let branch = validate_branch_name(branch)?;
+    git_core::run_git_command(repo_root, &["fetch", "origin", head_ref]).await?;
+    let local_ref = format!("refs/heads/{branch}");
+    let exists =
+        git_core::run_git_command(repo_root, &["rev-parse", "--verify", "--quiet", &local_ref])
+            .await
+            .is_ok();
+    if exists {
+        git_core::run_git_command(repo_root, &["checkout", &branch]).await?;
+        git_core::run_git_command(repo_root, &["merge", "--ff-only", "FETCH_HEAD"]).await?;
+    } else {
+        git_core::run_git_command(repo_root, &["checkout", "-b", &branch, "FETCH_HEAD"]).await?;
+    }
+    Ok(())
+}
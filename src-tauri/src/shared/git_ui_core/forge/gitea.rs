@@ -0,0 +1,421 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::types::{
+    ForgeKind, GitHubIssue, GitHubIssuesResponse, GitHubItemState, GitHubLabel, GitHubListQuery,
+    GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse,
+};
+
+use super::super::github::parse_pr_diff;
+use super::http::{encode_component, ForgeHttpClient};
+use super::{
+    checkout_fetched_head, clean_filter, clean_labels, list_page_size, next_page_cursor,
+    page_from_cursor, pull_request_head_ref, Forge, ForgeRemote,
+};
+
+const TOTAL_HEADER: &str = "x-total-count";
+const WIP_PREFIXES: [&str; 2] = ["WIP:", "[WIP]"];
+
+#[derive(Debug, Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaLabel {
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaMilestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    updated_at: String,
+    state: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    user: Option<GiteaUser>,
+    #[serde(default)]
+    labels: Vec<GiteaLabel>,
+    #[serde(default)]
+    assignees: Option<Vec<GiteaUser>>,
+    #[serde(default)]
+    comments: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaBranchRef {
+    #[serde(rename = "ref")]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaPullRequest {
+    number: u64,
+    title: String,
+    html_url: String,
+    updated_at: String,
+    created_at: String,
+    #[serde(default)]
+    body: Option<String>,
+    head: GiteaBranchRef,
+    base: GiteaBranchRef,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    user: Option<GiteaUser>,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    labels: Vec<GiteaLabel>,
+    #[serde(default)]
+    assignees: Option<Vec<GiteaUser>>,
+    #[serde(default)]
+    comments: u64,
+    #[serde(default)]
+    milestone: Option<GiteaMilestone>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaComment {
+    id: u64,
+    #[serde(default)]
+    body: String,
+    created_at: String,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    user: Option<GiteaUser>,
+}
+
+fn author(user: Option<GiteaUser>) -> Option<GitHubPullRequestAuthor> {
+    user.map(|user| GitHubPullRequestAuthor { login: user.login })
+}
+
+fn assignees(users: Option<Vec<GiteaUser>>) -> Vec<GitHubPullRequestAuthor> {
+    users
+        .unwrap_or_default()
+        .into_iter()
+        .map(|user| GitHubPullRequestAuthor { login: user.login })
+        .collect()
+}
+
+fn labels(labels: Vec<GiteaLabel>) -> Vec<GitHubLabel> {
+    labels
+        .into_iter()
+        .map(|label| GitHubLabel {
+            name: label.name,
+            color: label
+                .color
+                .filter(|color| !color.is_empty())
+                .map(|color| format!("#{}", color.trim_start_matches('#'))),
+        })
+        .collect()
+}
+
+fn github_issue(issue: GiteaIssue) -> GitHubIssue {
+    GitHubIssue {
+        number: issue.number,
+        title: issue.title,
+        url: issue.html_url,
+        updated_at: issue.updated_at,
+        state: issue.state,
+        body: issue.body.unwrap_or_default(),
+        author: author(issue.user),
+        labels: labels(issue.labels),
+        assignees: assignees(issue.assignees),
+        comment_count: issue.comments,
+        linked_pull_requests: Vec::new(),
+    }
+}
+
+fn api_state(state: GitHubItemState) -> &'static str {
+    match state {
+        GitHubItemState::Open => "open",
+        GitHubItemState::Closed | GitHubItemState::Merged => "closed",
+        GitHubItemState::All => "all",
+    }
+}
+
+fn issue_list_params(query: &GitHubListQuery) -> Result<String, String> {
+    if query.state == GitHubItemState::Merged {
+        return Err("Issues cannot be filtered by merged state.".to_string());
+    }
+    let mut params = vec![
+        format!("state={}", api_state(query.state)),
+        "type=issues".to_string(),
+        format!("limit={}", list_page_size(query)),
+        format!("page={}", page_from_cursor(query)?),
+    ];
+    let labels = clean_labels(&query.labels);
+    if !labels.is_empty() {
+        params.push(format!("labels={}", encode_component(&labels.join(","))));
+    }
+    let filters = [
+        ("assigned_by", &query.assignee),
+        ("created_by", &query.author),
+        ("milestones", &query.milestone),
+        ("q", &query.search),
+    ];
+    for (name, value) in filters {
+        if let Some(value) = clean_filter(value) {
+            let value = value.trim_start_matches('@');
+            params.push(format!("{name}={}", encode_component(value)));
+        }
+    }
+    Ok(params.join("&"))
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// The pulls endpoint only filters by state, so the remaining filters are
+/// checked against each fetched page.
+fn pull_matches(pull: &GiteaPullRequest, query: &GitHubListQuery) -> bool {
+    let merged_ok = match query.state {
+        GitHubItemState::Merged => pull.merged,
+        GitHubItemState::Closed => !pull.merged,
+        GitHubItemState::Open | GitHubItemState::All => true,
+    };
+    let labels_ok = clean_labels(&query.labels).iter().all(|wanted| {
+        pull.labels
+            .iter()
+            .any(|label| label.name.eq_ignore_ascii_case(wanted))
+    });
+    let login_matches = |user: &GiteaUser, wanted: &str| {
+        user.login
+            .eq_ignore_ascii_case(wanted.trim_start_matches('@'))
+    };
+    let assignee_ok = clean_filter(&query.assignee).map_or(true, |wanted| {
+        pull.assignees
+            .iter()
+            .flatten()
+            .any(|user| login_matches(user, &wanted))
+    });
+    let author_ok = clean_filter(&query.author).map_or(true, |wanted| {
+        pull.user
+            .as_ref()
+            .is_some_and(|user| login_matches(user, &wanted))
+    });
+    let milestone_ok = clean_filter(&query.milestone).map_or(true, |wanted| {
+        pull.milestone
+            .as_ref()
+            .is_some_and(|milestone| milestone.title.eq_ignore_ascii_case(&wanted))
+    });
+    let search_ok = clean_filter(&query.search).map_or(true, |wanted| {
+        contains_ignore_case(&pull.title, &wanted)
+            || contains_ignore_case(pull.body.as_deref().unwrap_or_default(), &wanted)
+    });
+    merged_ok && labels_ok && assignee_ok && author_ok && milestone_ok && search_ok
+}
+
+fn has_client_filters(query: &GitHubListQuery) -> bool {
+    matches!(
+        query.state,
+        GitHubItemState::Closed | GitHubItemState::Merged
+    ) || !clean_labels(&query.labels).is_empty()
+        || [
+            &query.assignee,
+            &query.author,
+            &query.milestone,
+            &query.search,
+        ]
+        .into_iter()
+        .any(|value| clean_filter(value).is_some())
+}
+
+/// Gitea (and Forgejo) backend over the REST API (v1). Older servers mark
+/// drafts only through a `WIP:` title prefix.
+pub(in super::super) struct GiteaForge {
+    repo_root: PathBuf,
+    remote: ForgeRemote,
+    client: ForgeHttpClient,
+}
+
+impl GiteaForge {
+    pub(in super::super) fn new(
+        repo_root: PathBuf,
+        api_url: &str,
+        remote: ForgeRemote,
+        token: Option<String>,
+    ) -> Result<Self, String> {
+        let auth = token.map(|token| ("authorization", format!("token {token}")));
+        let client = ForgeHttpClient::new(api_url, auth, TOTAL_HEADER)?;
+        Ok(Self {
+            repo_root,
+            remote,
+            client,
+        })
+    }
+
+    fn repo_path(&self, rest: &str) -> String {
+        let (owner, name) = self
+            .remote
+            .path
+            .rsplit_once('/')
+            .unwrap_or(("", self.remote.path.as_str()));
+        format!(
+            "repos/{}/{}/{rest}",
+            encode_component(owner),
+            encode_component(name)
+        )
+    }
+
+    async fn pull_request(&self, number: u64) -> Result<GiteaPullRequest, String> {
+        self.client
+            .get_json(&self.repo_path(&format!("pulls/{number}")))
+            .await
+    }
+}
+
+impl Forge for GiteaForge {
+    async fn list_issues(&self, query: &GitHubListQuery) -> Result<GitHubIssuesResponse, String> {
+        let params = issue_list_params(query)?;
+        let response = self
+            .client
+            .get(&self.repo_path(&format!("issues?{params}")))
+            .await?;
+        let issues: Vec<GiteaIssue> =
+            serde_json::from_str(&response.body).map_err(|err| err.to_string())?;
+        let next_cursor = next_page_cursor(
+            page_from_cursor(query)?,
+            list_page_size(query),
+            issues.len(),
+            response.total,
+        );
+        let total = response.total.unwrap_or(issues.len());
+        let issues = issues.into_iter().map(github_issue).collect();
+        Ok(GitHubIssuesResponse {
+            total,
+            issues,
+            next_cursor,
+        })
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssue, String> {
+        let issue: GiteaIssue = self
+            .client
+            .get_json(&self.repo_path(&format!("issues/{number}")))
+            .await?;
+        Ok(github_issue(issue))
+    }
+
+    /// With filters the pulls endpoint lacks, `total` counts the matches on the
+    /// fetched page rather than across the repository.
+    async fn list_pull_requests(
+        &self,
+        query: &GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        let page = page_from_cursor(query)?;
+        let page_size = list_page_size(query);
+        let response = self
+            .client
+            .get(&self.repo_path(&format!(
+                "pulls?state={}&sort=recentupdate&limit={page_size}&page={page}",
+                api_state(query.state)
+            )))
+            .await?;
+        let pulls: Vec<GiteaPullRequest> =
+            serde_json::from_str(&response.body).map_err(|err| err.to_string())?;
+        let next_cursor = next_page_cursor(page, page_size, pulls.len(), response.total);
+        let pulls: Vec<GiteaPullRequest> = pulls
+            .into_iter()
+            .filter(|pull| pull_matches(pull, query))
+            .collect();
+        let total = if has_client_filters(query) {
+            pulls.len()
+        } else {
+            response.total.unwrap_or(pulls.len())
+        };
+        let pull_requests = pulls
+            .into_iter()
+            .map(|pull| {
+                let is_draft = pull.draft
+                    || WIP_PREFIXES
+                        .iter()
+                        .any(|prefix| pull.title.starts_with(prefix));
+                GitHubPullRequest {
+                    number: pull.number,
+                    title: pull.title,
+                    url: pull.html_url,
+                    updated_at: pull.updated_at,
+                    created_at: pull.created_at,
+                    body: pull.body.unwrap_or_default(),
+                    head_ref_name: pull.head.name,
+                    base_ref_name: pull.base.name,
+                    is_draft,
+                    author: author(pull.user),
+                    state: if pull.merged {
+                        "merged".to_string()
+                    } else {
+                        pull.state
+                    },
+                    labels: labels(pull.labels),
+                    assignees: assignees(pull.assignees),
+                    comment_count: pull.comments,
+                    linked_issues: Vec::new(),
+                }
+            })
+            .collect();
+        Ok(GitHubPullRequestsResponse {
+            total,
+            pull_requests,
+            next_cursor,
+        })
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<Vec<GitHubPullRequestDiff>, String> {
+        let response = self
+            .client
+            .get(&self.repo_path(&format!("pulls/{number}.diff")))
+            .await?;
+        Ok(parse_pr_diff(&response.body))
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        self.issue_comments(number).await
+    }
+
+    async fn issue_comments(&self, number: u64) -> Result<Vec<GitHubPullRequestComment>, String> {
+        let comments: Vec<GiteaComment> = self
+            .client
+            .get_json(&self.repo_path(&format!("issues/{number}/comments")))
+            .await?;
+        Ok(comments
+            .into_iter()
+            .map(|comment| GitHubPullRequestComment {
+                id: comment.id,
+                body: comment.body,
+                created_at: comment.created_at,
+                url: comment.html_url,
+                author: author(comment.user),
+            })
+            .collect())
+    }
+
+    async fn checkout_pull_request(&self, number: u64) -> Result<(), String> {
+        let pull = self.pull_request(number).await?;
+        checkout_fetched_head(
+            &self.repo_root,
+            &pull_request_head_ref(ForgeKind::Gitea, number),
+            &pull.head.name,
+        )
+        .await
+    }
+}
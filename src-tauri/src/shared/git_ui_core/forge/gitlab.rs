@@ -0,0 +1,394 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::types::{
+    ForgeKind, GitHubIssue, GitHubIssuesResponse, GitHubItemState, GitHubLabel, GitHubListQuery,
+    GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse,
+};
+
+use super::super::github::parse_pr_diff;
+use super::http::{encode_component, ForgeHttpClient};
+use super::{
+    checkout_fetched_head, clean_filter, clean_labels, list_page_size, next_page_cursor,
+    page_from_cursor, pull_request_head_ref, Forge, ForgeRemote,
+};
+
+const TOTAL_HEADER: &str = "x-total";
+const TOKEN_HEADER: &str = "private-token";
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabIssue {
+    iid: u64,
+    title: String,
+    web_url: String,
+    updated_at: String,
+    state: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    author: Option<GitLabUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignees: Vec<GitLabUser>,
+    #[serde(default)]
+    user_notes_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    title: String,
+    web_url: String,
+    updated_at: String,
+    created_at: String,
+    #[serde(default)]
+    description: Option<String>,
+    source_branch: String,
+    target_branch: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    work_in_progress: bool,
+    #[serde(default)]
+    author: Option<GitLabUser>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignees: Vec<GitLabUser>,
+    #[serde(default)]
+    user_notes_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabDiff {
+    old_path: String,
+    new_path: String,
+    #[serde(default)]
+    new_file: bool,
+    #[serde(default)]
+    renamed_file: bool,
+    #[serde(default)]
+    deleted_file: bool,
+    #[serde(default)]
+    diff: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabNote {
+    id: u64,
+    #[serde(default)]
+    body: String,
+    created_at: String,
+    #[serde(default)]
+    system: bool,
+    #[serde(default)]
+    author: Option<GitLabUser>,
+}
+
+fn author(user: Option<GitLabUser>) -> Option<GitHubPullRequestAuthor> {
+    user.map(|user| GitHubPullRequestAuthor {
+        login: user.username,
+    })
+}
+
+fn assignees(users: Vec<GitLabUser>) -> Vec<GitHubPullRequestAuthor> {
+    users
+        .into_iter()
+        .map(|user| GitHubPullRequestAuthor {
+            login: user.username,
+        })
+        .collect()
+}
+
+fn labels(names: Vec<String>) -> Vec<GitHubLabel> {
+    names
+        .into_iter()
+        .map(|name| GitHubLabel { name, color: None })
+        .collect()
+}
+
+fn item_state(state: &str) -> String {
+    match state {
+        "opened" => "open".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn github_issue(issue: GitLabIssue) -> GitHubIssue {
+    GitHubIssue {
+        number: issue.iid,
+        title: issue.title,
+        url: issue.web_url,
+        updated_at: issue.updated_at,
+        state: item_state(&issue.state),
+        body: issue.description.unwrap_or_default(),
+        author: author(issue.author),
+        labels: labels(issue.labels),
+        assignees: assignees(issue.assignees),
+        comment_count: issue.user_notes_count,
+        linked_pull_requests: Vec::new(),
+    }
+}
+
+/// Query string for an issue or merge request listing. GitLab filters labels
+/// with AND semantics, matching the GitHub search qualifiers.
+fn gitlab_list_params(query: &GitHubListQuery, merge_requests: bool) -> Result<String, String> {
+    let state = match (query.state, merge_requests) {
+        (GitHubItemState::Open, _) => "opened",
+        (GitHubItemState::Closed, _) => "closed",
+        (GitHubItemState::Merged, true) => "merged",
+        (GitHubItemState::Merged, false) => {
+            return Err("Issues cannot be filtered by merged state.".to_string())
+        }
+        (GitHubItemState::All, _) => "all",
+    };
+    let mut params = vec![
+        format!("state={state}"),
+        "order_by=updated_at".to_string(),
+        format!("per_page={}", list_page_size(query)),
+        format!("page={}", page_from_cursor(query)?),
+    ];
+    let labels = clean_labels(&query.labels);
+    if !labels.is_empty() {
+        params.push(format!("labels={}", encode_component(&labels.join(","))));
+    }
+    let filters = [
+        ("assignee_username", &query.assignee),
+        ("author_username", &query.author),
+        ("milestone", &query.milestone),
+        ("search", &query.search),
+    ];
+    for (name, value) in filters {
+        if let Some(value) = clean_filter(value) {
+            let value = if name.ends_with("_username") {
+                value.trim_start_matches('@').to_string()
+            } else {
+                value
+            };
+            params.push(format!("{name}={}", encode_component(&value)));
+        }
+    }
+    Ok(params.join("&"))
+}
+
+/// Rebuilds the `git diff` headers GitLab strips from each file so the result
+/// parses like a `gh pr diff`.
+fn unified_diff(entries: &[GitLabDiff]) -> String {
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&format!(
+            "diff --git a/{} b/{}\n",
+            entry.old_path, entry.new_path
+        ));
+        let old_label = if entry.new_file {
+            text.push_str("new file mode 100644\n");
+            "/dev/null".to_string()
+        } else {
+            format!("a/{}", entry.old_path)
+        };
+        let new_label = if entry.deleted_file {
+            text.push_str("deleted file mode 100644\n");
+            "/dev/null".to_string()
+        } else {
+            format!("b/{}", entry.new_path)
+        };
+        if entry.renamed_file {
+            text.push_str(&format!(
+                "rename from {}\nrename to {}\n",
+                entry.old_path, entry.new_path
+            ));
+        }
+        if !entry.diff.is_empty() {
+            text.push_str(&format!("--- {old_label}\n+++ {new_label}\n"));
+            text.push_str(&entry.diff);
+            if !entry.diff.ends_with('\n') {
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+/// GitLab backend over the REST API (v4). Merge requests are addressed by
+/// their project-scoped `iid`.
+pub(in super::super) struct GitLabForge {
+    repo_root: PathBuf,
+    remote: ForgeRemote,
+    client: ForgeHttpClient,
+}
+
+impl GitLabForge {
+    pub(in super::super) fn new(
+        repo_root: PathBuf,
+        api_url: &str,
+        remote: ForgeRemote,
+        token: Option<String>,
+    ) -> Result<Self, String> {
+        let client = ForgeHttpClient::new(
+            api_url,
+            token.map(|token| (TOKEN_HEADER, token)),
+            TOTAL_HEADER,
+        )?;
+        Ok(Self {
+            repo_root,
+            remote,
+            client,
+        })
+    }
+
+    fn project_path(&self, rest: &str) -> String {
+        format!("projects/{}/{rest}", encode_component(&self.remote.path))
+    }
+
+    /// User comments on an issue or merge request; system notes such as label
+    /// changes are dropped.
+    async fn notes(
+        &self,
+        collection: &str,
+        iid: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        let notes: Vec<GitLabNote> = self
+            .client
+            .get_json(&self.project_path(&format!(
+                "{collection}/{iid}/notes?sort=asc&order_by=created_at&per_page=30"
+            )))
+            .await?;
+        let base_url = format!(
+            "{}/{}/-/{collection}/{iid}",
+            self.remote.web_base, self.remote.path
+        );
+        Ok(notes
+            .into_iter()
+            .filter(|note| !note.system)
+            .map(|note| GitHubPullRequestComment {
+                id: note.id,
+                body: note.body,
+                created_at: note.created_at,
+                url: format!("{base_url}#note_{}", note.id),
+                author: author(note.author),
+            })
+            .collect())
+    }
+
+    async fn merge_request(&self, iid: u64) -> Result<GitLabMergeRequest, String> {
+        self.client
+            .get_json(&self.project_path(&format!("merge_requests/{iid}")))
+            .await
+    }
+}
+
+impl Forge for GitLabForge {
+    async fn list_issues(&self, query: &GitHubListQuery) -> Result<GitHubIssuesResponse, String> {
+        let params = gitlab_list_params(query, false)?;
+        let response = self
+            .client
+            .get(&self.project_path(&format!("issues?{params}")))
+            .await?;
+        let issues: Vec<GitLabIssue> =
+            serde_json::from_str(&response.body).map_err(|err| err.to_string())?;
+        let next_cursor = next_page_cursor(
+            page_from_cursor(query)?,
+            list_page_size(query),
+            issues.len(),
+            response.total,
+        );
+        let total = response.total.unwrap_or(issues.len());
+        let issues = issues.into_iter().map(github_issue).collect();
+        Ok(GitHubIssuesResponse {
+            total,
+            issues,
+            next_cursor,
+        })
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssue, String> {
+        let issue: GitLabIssue = self
+            .client
+            .get_json(&self.project_path(&format!("issues/{number}")))
+            .await?;
+        Ok(github_issue(issue))
+    }
+
+    async fn issue_comments(&self, number: u64) -> Result<Vec<GitHubPullRequestComment>, String> {
+        self.notes("issues", number).await
+    }
+
+    async fn list_pull_requests(
+        &self,
+        query: &GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        let params = gitlab_list_params(query, true)?;
+        let response = self
+            .client
+            .get(&self.project_path(&format!("merge_requests?{params}")))
+            .await?;
+        let merge_requests: Vec<GitLabMergeRequest> =
+            serde_json::from_str(&response.body).map_err(|err| err.to_string())?;
+        let next_cursor = next_page_cursor(
+            page_from_cursor(query)?,
+            list_page_size(query),
+            merge_requests.len(),
+            response.total,
+        );
+        let total = response.total.unwrap_or(merge_requests.len());
+        let pull_requests = merge_requests
+            .into_iter()
+            .map(|request| GitHubPullRequest {
+                number: request.iid,
+                title: request.title,
+                url: request.web_url,
+                updated_at: request.updated_at,
+                created_at: request.created_at,
+                body: request.description.unwrap_or_default(),
+                head_ref_name: request.source_branch,
+                base_ref_name: request.target_branch,
+                is_draft: request.draft || request.work_in_progress,
+                author: author(request.author),
+                state: item_state(&request.state),
+                labels: labels(request.labels),
+                assignees: assignees(request.assignees),
+                comment_count: request.user_notes_count,
+                linked_issues: Vec::new(),
+            })
+            .collect();
+        Ok(GitHubPullRequestsResponse {
+            total,
+            pull_requests,
+            next_cursor,
+        })
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<Vec<GitHubPullRequestDiff>, String> {
+        let entries: Vec<GitLabDiff> = self
+            .client
+            .get_json(&self.project_path(&format!("merge_requests/{number}/diffs?per_page=100")))
+            .await?;
+        Ok(parse_pr_diff(&unified_diff(&entries)))
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        self.notes("merge_requests", number).await
+    }
+
+    async fn checkout_pull_request(&self, number: u64) -> Result<(), String> {
+        let request = self.merge_request(number).await?;
+        checkout_fetched_head(
+            &self.repo_root,
+            &pull_request_head_ref(ForgeKind::GitLab, number),
+            &request.source_branch,
+        )
+        .await
+    }
+}
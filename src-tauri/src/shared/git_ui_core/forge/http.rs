@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Body of a successful forge API response plus the pagination total some
+/// forges report in a header.
+pub(super) struct ForgeResponse {
+    pub(super) body: String,
+    pub(super) total: Option<usize>,
+}
+
+/// Minimal REST client for forge APIs: requests go to `api_base` and carry the
+/// forge's auth header when a token is configured.
+pub(super) struct ForgeHttpClient {
+    client: reqwest::Client,
+    api_base: String,
+    total_header: &'static str,
+}
+
+impl ForgeHttpClient {
+    pub(super) fn new(
+        api_base: &str,
+        auth: Option<(&'static str, String)>,
+        total_header: &'static str,
+    ) -> Result<Self, String> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            HeaderValue::from_static("application/json"),
+        );
+        if let Some((name, value)) = auth {
+            let mut value = HeaderValue::from_str(&value)
+                .map_err(|_| "Forge token contains invalid characters.".to_string())?;
+            value.set_sensitive(true);
+            headers.insert(HeaderName::from_static(name), value);
+        }
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(REQUEST_TIMEOUT)
+            .default_headers(headers)
+            .build()
+            .map_err(|err| format!("Failed to configure forge client: {err}"))?;
+        Ok(Self {
+            client,
+            api_base: api_base.trim_end_matches('/').to_string(),
+            total_header,
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.api_base, path.trim_start_matches('/'))
+    }
+
+    async fn finish(&self, response: reqwest::Response) -> Result<ForgeResponse, String> {
+        let status = response.status();
+        let total = response
+            .headers()
+            .get(self.total_header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("Failed to read forge response: {err}"))?;
+        if !status.is_success() {
+            let detail = forge_error_message(&body).unwrap_or_else(|| body.trim().to_string());
+            return Err(if detail.is_empty() {
+                format!("Forge request failed with HTTP {}.", status.as_u16())
+            } else {
+                format!(
+                    "Forge request failed with HTTP {}: {detail}",
+                    status.as_u16()
+                )
+            });
+        }
+        Ok(ForgeResponse { body, total })
+    }
+
+    pub(super) async fn get(&self, path: &str) -> Result<ForgeResponse, String> {
+        let response = self
+            .client
+            .get(self.url(path))
+            .send()
+            .await
+            .map_err(|err| format!("Forge request failed: {err}"))?;
+        self.finish(response).await
+    }
+
+    pub(super) async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let response = self.get(path).await?;
+        serde_json::from_str(&response.body).map_err(|err| err.to_string())
+    }
+}
+
+/// Pulls the human-readable message out of a GitLab (`message`/`error`) or
+/// Gitea (`message`) error body.
+fn forge_error_message(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let message = value.get("message").or_else(|| value.get("error"))?;
+    match message {
+        serde_json::Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Percent-encodes a value for use as a single URL path segment or query value.
+pub(super) fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
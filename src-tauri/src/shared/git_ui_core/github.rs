@@ -1,20 +1,35 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use git2::Repository;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
 use tokio::sync::Mutex;
 
 use crate::git_utils::{parse_github_repo, resolve_git_root};
+use crate::shared::git_core;
 use crate::shared::process_core::tokio_command;
 use crate::types::{
-    GitHubIssue, GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry,
+    GitHubIssue, GitHubIssuesResponse, GitHubItemState, GitHubLabel, GitHubLinkedItem,
+    GitHubListQuery, GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry, WorkspaceIssueLink,
 };
 use crate::utils::normalize_git_path;
 
+use super::commands::{push_branch_for_pull_request, run_gh_command};
 use super::context::workspace_entry_for_id;
+use super::forge::{clean_filter, clean_labels, list_page_size, Forge};
 
-fn github_repo_from_path(path: &Path) -> Result<String, String> {
+const PULL_REQUEST_JSON_FIELDS: &str = concat!(
+    "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author,",
+    "state,labels,assignees"
+);
+const ISSUE_JSON_FIELDS: &str = "number,title,url,updatedAt,state,body,author,labels,assignees";
+const MAX_PULL_REQUEST_DIFF_CHARS: usize = 120_000;
+
+/// URL of the `origin` remote, or of the first remote when there is no origin.
+pub(super) fn origin_remote_url(path: &Path) -> Result<String, String> {
     let repo = Repository::open(path).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
@@ -27,10 +42,15 @@ fn github_repo_from_path(path: &Path) -> Result<String, String> {
     }
     let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
     let remote_url = remote.url().ok_or("Remote has no URL configured.")?;
-    parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.".to_string())
+    Ok(remote_url.to_string())
+}
+
+pub(super) fn github_repo_from_path(path: &Path) -> Result<String, String> {
+    let remote_url = origin_remote_url(path)?;
+    parse_github_repo(&remote_url).ok_or("Remote is not a GitHub repository.".to_string())
 }
 
-fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
+pub(super) fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
     let mut entries = Vec::new();
     let mut current_lines: Vec<&str> = Vec::new();
     let mut current_old_path: Option<String> = None;
@@ -140,152 +160,449 @@ fn command_failure_detail(stdout: &[u8], stderr: &[u8], fallback: &str) -> Strin
     }
 }
 
-pub(super) async fn checkout_github_pull_request_inner(
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    workspace_id: String,
-    pr_number: u64,
-) -> Result<(), String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let pr_number_text = pr_number.to_string();
+const ISSUE_SEARCH_QUERY: &str = r#"query($searchQuery: String!, $first: Int!, $after: String) {
+  search(query: $searchQuery, type: ISSUE, first: $first, after: $after) {
+    issueCount
+    pageInfo { hasNextPage endCursor }
+    nodes {
+      ... on Issue {
+        number title url state body updatedAt
+        author { login }
+        labels(first: 20) { nodes { name color } }
+        assignees(first: 10) { nodes { login } }
+        comments { totalCount }
+        closedByPullRequestsReferences(first: 10, includeClosedPrs: true) {
+          nodes { number title url state }
+        }
+      }
+    }
+  }
+}"#;
+const PULL_REQUEST_SEARCH_QUERY: &str = r#"query($searchQuery: String!, $first: Int!, $after: String) {
+  search(query: $searchQuery, type: ISSUE, first: $first, after: $after) {
+    issueCount
+    pageInfo { hasNextPage endCursor }
+    nodes {
+      ... on PullRequest {
+        number title url state body updatedAt createdAt headRefName baseRefName isDraft
+        author { login }
+        labels(first: 20) { nodes { name color } }
+        assignees(first: 10) { nodes { login } }
+        comments { totalCount }
+        closingIssuesReferences(first: 10) { nodes { number title url state } }
+      }
+    }
+  }
+}"#;
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
 
-    let output = tokio_command("gh")
-        .args(["pr", "checkout", &pr_number_text])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
 
-    if !output.status.success() {
-        return Err(command_failure_detail(
-            &output.stdout,
-            &output.stderr,
-            "GitHub CLI command failed.",
-        ));
-    }
+#[derive(Debug, Deserialize)]
+struct SearchData {
+    search: SearchConnection,
+}
 
-    Ok(())
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchConnection {
+    issue_count: usize,
+    page_info: PageInfo,
+    #[serde(default)]
+    nodes: Vec<Value>,
 }
 
-pub(super) async fn get_github_issues_inner(
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    workspace_id: String,
-) -> Result<GitHubIssuesResponse, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    #[serde(default)]
+    end_cursor: Option<String>,
+}
 
-    let output = tokio_command("gh")
-        .args([
-            "issue",
-            "list",
-            "--repo",
-            &repo_name,
-            "--limit",
-            "50",
-            "--json",
-            "number,title,url,updatedAt",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct Nodes<T> {
+    #[serde(default)]
+    nodes: Vec<T>,
+}
 
-    if !output.status.success() {
-        return Err(command_failure_detail(
-            &output.stdout,
-            &output.stderr,
-            "GitHub CLI command failed.",
-        ));
+impl<T> Default for Nodes<T> {
+    fn default() -> Self {
+        Self { nodes: Vec::new() }
     }
+}
 
-    let issues: Vec<GitHubIssue> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    let search_query = format!("repo:{repo_name} is:issue is:open").replace(' ', "+");
-    let total = match tokio_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(issues.len()),
-        _ => issues.len(),
-    };
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TotalCount {
+    total_count: u64,
+}
 
-    Ok(GitHubIssuesResponse { total, issues })
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchIssue {
+    number: u64,
+    title: String,
+    url: String,
+    state: String,
+    #[serde(default)]
+    body: String,
+    updated_at: String,
+    #[serde(default)]
+    author: Option<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    labels: Nodes<GitHubLabel>,
+    #[serde(default)]
+    assignees: Nodes<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    comments: TotalCount,
+    #[serde(default)]
+    closed_by_pull_requests_references: Nodes<GitHubLinkedItem>,
 }
 
-pub(super) async fn get_github_pull_requests_inner(
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    workspace_id: String,
-) -> Result<GitHubPullRequestsResponse, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchPullRequest {
+    number: u64,
+    title: String,
+    url: String,
+    state: String,
+    #[serde(default)]
+    body: String,
+    updated_at: String,
+    created_at: String,
+    head_ref_name: String,
+    base_ref_name: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    author: Option<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    labels: Nodes<GitHubLabel>,
+    #[serde(default)]
+    assignees: Nodes<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    comments: TotalCount,
+    #[serde(default)]
+    closing_issues_references: Nodes<GitHubLinkedItem>,
+}
 
-    let output = tokio_command("gh")
-        .args([
-            "pr",
-            "list",
-            "--repo",
-            &repo_name,
-            "--state",
-            "open",
-            "--limit",
-            "50",
-            "--json",
-            "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+fn search_qualifier(name: &str, value: &str) -> String {
+    let value = value.replace('"', "");
+    if value.chars().any(char::is_whitespace) {
+        format!("{name}:\"{value}\"")
+    } else {
+        format!("{name}:{value}")
+    }
+}
 
-    if !output.status.success() {
-        return Err(command_failure_detail(
-            &output.stdout,
-            &output.stderr,
-            "GitHub CLI command failed.",
+/// Builds the GitHub search query for a listing: the repository, item type and
+/// state qualifiers, one `label:` per label, then free text.
+pub(super) fn github_search_query(
+    repo_name: &str,
+    pull_requests: bool,
+    query: &GitHubListQuery,
+) -> Result<String, String> {
+    let mut parts = vec![
+        format!("repo:{repo_name}"),
+        if pull_requests { "is:pr" } else { "is:issue" }.to_string(),
+    ];
+    match (query.state, pull_requests) {
+        (GitHubItemState::Open, _) => parts.push("is:open".to_string()),
+        (GitHubItemState::Closed, false) => parts.push("is:closed".to_string()),
+        (GitHubItemState::Closed, true) => parts.push("is:closed is:unmerged".to_string()),
+        (GitHubItemState::Merged, true) => parts.push("is:merged".to_string()),
+        (GitHubItemState::Merged, false) => {
+            return Err("Issues cannot be filtered by merged state.".to_string())
+        }
+        (GitHubItemState::All, _) => {}
+    }
+    for label in clean_labels(&query.labels) {
+        parts.push(search_qualifier("label", &label));
+    }
+    if let Some(assignee) = clean_filter(&query.assignee) {
+        parts.push(search_qualifier(
+            "assignee",
+            assignee.trim_start_matches('@'),
         ));
     }
+    if let Some(author) = clean_filter(&query.author) {
+        parts.push(search_qualifier("author", author.trim_start_matches('@')));
+    }
+    if let Some(milestone) = clean_filter(&query.milestone) {
+        parts.push(search_qualifier("milestone", &milestone));
+    }
+    if let Some(search) = clean_filter(&query.search) {
+        parts.push(search);
+    }
+    parts.push("sort:updated-desc".to_string());
+    Ok(parts.join(" "))
+}
 
-    let pull_requests: Vec<GitHubPullRequest> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+fn linked_items(items: Vec<GitHubLinkedItem>) -> Vec<GitHubLinkedItem> {
+    items
+        .into_iter()
+        .map(|item| GitHubLinkedItem {
+            state: item.state.to_lowercase(),
+            ..item
+        })
+        .collect()
+}
 
-    let search_query = format!("repo:{repo_name} is:pr is:open").replace(' ', "+");
-    let total = match tokio_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(pull_requests.len()),
-        _ => pull_requests.len(),
+fn labels(labels: Vec<GitHubLabel>) -> Vec<GitHubLabel> {
+    labels
+        .into_iter()
+        .map(|label| GitHubLabel {
+            color: label
+                .color
+                .map(|color| format!("#{}", color.trim_start_matches('#'))),
+            ..label
+        })
+        .collect()
+}
+
+fn parse_search<T: DeserializeOwned>(
+    stdout: &str,
+) -> Result<(usize, Option<String>, Vec<T>), String> {
+    let response: GraphQlResponse<SearchData> =
+        serde_json::from_str(stdout).map_err(|e| e.to_string())?;
+    let Some(data) = response.data else {
+        let message = response
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(if message.is_empty() {
+            "GitHub search returned no data.".to_string()
+        } else {
+            message
+        });
     };
+    let search = data.search;
+    let next_cursor = search
+        .page_info
+        .has_next_page
+        .then_some(search.page_info.end_cursor)
+        .flatten();
+    // Search can return other node types as empty objects; skip anything
+    // that does not parse as the requested type.
+    let nodes = search
+        .nodes
+        .into_iter()
+        .filter_map(|node| serde_json::from_value(node).ok())
+        .collect();
+    Ok((search.issue_count, next_cursor, nodes))
+}
+
+pub(super) fn parse_issue_search(stdout: &str) -> Result<GitHubIssuesResponse, String> {
+    let (total, next_cursor, nodes) = parse_search::<SearchIssue>(stdout)?;
+    let issues = nodes
+        .into_iter()
+        .map(|issue| GitHubIssue {
+            number: issue.number,
+            title: issue.title,
+            url: issue.url,
+            updated_at: issue.updated_at,
+            state: issue.state.to_lowercase(),
+            body: issue.body,
+            author: issue.author,
+            labels: labels(issue.labels.nodes),
+            assignees: issue.assignees.nodes,
+            comment_count: issue.comments.total_count,
+            linked_pull_requests: linked_items(issue.closed_by_pull_requests_references.nodes),
+        })
+        .collect();
+    Ok(GitHubIssuesResponse {
+        total,
+        issues,
+        next_cursor,
+    })
+}
 
+pub(super) fn parse_pull_request_search(
+    stdout: &str,
+) -> Result<GitHubPullRequestsResponse, String> {
+    let (total, next_cursor, nodes) = parse_search::<SearchPullRequest>(stdout)?;
+    let pull_requests = nodes
+        .into_iter()
+        .map(|pull| GitHubPullRequest {
+            number: pull.number,
+            title: pull.title,
+            url: pull.url,
+            updated_at: pull.updated_at,
+            created_at: pull.created_at,
+            body: pull.body,
+            head_ref_name: pull.head_ref_name,
+            base_ref_name: pull.base_ref_name,
+            is_draft: pull.is_draft,
+            author: pull.author,
+            state: pull.state.to_lowercase(),
+            labels: labels(pull.labels.nodes),
+            assignees: pull.assignees.nodes,
+            comment_count: pull.comments.total_count,
+            linked_issues: linked_items(pull.closing_issues_references.nodes),
+        })
+        .collect();
     Ok(GitHubPullRequestsResponse {
         total,
         pull_requests,
+        next_cursor,
     })
 }
 
+/// GitHub backend driven through the `gh` CLI, which owns authentication.
+pub(super) struct GitHubCliForge {
+    repo_root: PathBuf,
+    repo_name: String,
+}
+
+impl GitHubCliForge {
+    pub(super) fn new(repo_root: PathBuf) -> Result<Self, String> {
+        let repo_name = github_repo_from_path(&repo_root)?;
+        Ok(Self {
+            repo_root,
+            repo_name,
+        })
+    }
+
+    async fn run_gh(&self, args: &[&str]) -> Result<Vec<u8>, String> {
+        let output = tokio_command("gh")
+            .args(args)
+            .current_dir(&self.repo_root)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run gh: {e}"))?;
+
+        if !output.status.success() {
+            return Err(command_failure_detail(
+                &output.stdout,
+                &output.stderr,
+                "GitHub CLI command failed.",
+            ));
+        }
+        Ok(output.stdout)
+    }
+
+    async fn search(
+        &self,
+        graphql: &str,
+        search_query: &str,
+        query: &GitHubListQuery,
+    ) -> Result<String, String> {
+        let graphql_arg = format!("query={graphql}");
+        let first_arg = format!("first={}", list_page_size(query));
+        let search_arg = format!("searchQuery={search_query}");
+        let mut args = vec![
+            "api",
+            "graphql",
+            "-f",
+            graphql_arg.as_str(),
+            "-F",
+            first_arg.as_str(),
+            "-f",
+            search_arg.as_str(),
+        ];
+        let after_arg = clean_filter(&query.cursor).map(|cursor| format!("after={cursor}"));
+        if let Some(after_arg) = after_arg.as_deref() {
+            args.extend(["-f", after_arg]);
+        }
+        let stdout = self.run_gh(&args).await?;
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+}
+
+impl Forge for GitHubCliForge {
+    async fn list_issues(&self, query: &GitHubListQuery) -> Result<GitHubIssuesResponse, String> {
+        let search_query = github_search_query(&self.repo_name, false, query)?;
+        let stdout = self
+            .search(ISSUE_SEARCH_QUERY, &search_query, query)
+            .await?;
+        parse_issue_search(&stdout)
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssue, String> {
+        let stdout = self
+            .run_gh(&[
+                "issue",
+                "view",
+                &number.to_string(),
+                "--repo",
+                &self.repo_name,
+                "--json",
+                ISSUE_JSON_FIELDS,
+            ])
+            .await?;
+        let mut issue: GitHubIssue = serde_json::from_slice(&stdout).map_err(|e| e.to_string())?;
+        issue.state = issue.state.to_lowercase();
+        issue.labels = labels(issue.labels);
+        Ok(issue)
+    }
+
+    /// Pull requests share the issue comments endpoint, so this also backs
+    /// `pull_request_comments`.
+    async fn issue_comments(&self, number: u64) -> Result<Vec<GitHubPullRequestComment>, String> {
+        let comments_endpoint = format!(
+            "/repos/{}/issues/{number}/comments?per_page=30",
+            self.repo_name
+        );
+        let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
+        let stdout = self
+            .run_gh(&["api", &comments_endpoint, "--jq", jq_filter])
+            .await?;
+        serde_json::from_slice(&stdout).map_err(|e| e.to_string())
+    }
+
+    async fn list_pull_requests(
+        &self,
+        query: &GitHubListQuery,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        let search_query = github_search_query(&self.repo_name, true, query)?;
+        let stdout = self
+            .search(PULL_REQUEST_SEARCH_QUERY, &search_query, query)
+            .await?;
+        parse_pull_request_search(&stdout)
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<Vec<GitHubPullRequestDiff>, String> {
+        let stdout = self
+            .run_gh(&[
+                "pr",
+                "diff",
+                &number.to_string(),
+                "--repo",
+                &self.repo_name,
+                "--color",
+                "never",
+            ])
+            .await?;
+        Ok(parse_pr_diff(&String::from_utf8_lossy(&stdout)))
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        self.issue_comments(number).await
+    }
+
+    async fn checkout_pull_request(&self, number: u64) -> Result<(), String> {
+        self.run_gh(&["pr", "checkout", &number.to_string()])
+            .await
+            .map(|_| ())
+    }
+}
+
 pub(super) async fn get_github_pull_request_diff_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -293,64 +610,239 @@ pub(super) async fn get_github_pull_request_diff_inner(
 ) -> Result<Vec<GitHubPullRequestDiff>, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    GitHubCliForge::new(repo_root)?
+        .pull_request_diff(pr_number)
+        .await
+}
+
+fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn truncate_pull_request_diff(diff: String) -> String {
+    if diff.len() <= MAX_PULL_REQUEST_DIFF_CHARS {
+        return diff;
+    }
+    let mut end = MAX_PULL_REQUEST_DIFF_CHARS;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n\n[diff truncated]", &diff[..end])
+}
+
+/// Appends a closing reference to the issue a worktree was started from,
+/// unless the body already mentions that issue.
+pub(super) fn body_with_issue_reference(body: &str, issue: Option<&WorkspaceIssueLink>) -> String {
+    let Some(issue) = issue else {
+        return body.to_string();
+    };
+    let reference = format!("#{}", issue.number);
+    let mentioned = body.contains(&issue.url)
+        || body.match_indices(&reference).any(|(index, _)| {
+            !body[index + reference.len()..].starts_with(|ch: char| ch.is_ascii_digit())
+        });
+    if mentioned {
+        return body.to_string();
+    }
+    match body.trim_end() {
+        "" => format!("Closes {reference}"),
+        body => format!("{body}\n\nCloses {reference}"),
+    }
+}
+
+pub(super) fn gh_pr_create_args<'a>(
+    repo_name: &'a str,
+    head: &'a str,
+    title: &'a str,
+    body: &'a str,
+    base: Option<&'a str>,
+    draft: bool,
+    reviewers: &'a str,
+) -> Vec<&'a str> {
+    let mut args = vec![
+        "pr", "create", "--repo", repo_name, "--head", head, "--title", title, "--body", body,
+    ];
+    if let Some(base) = base {
+        args.extend(["--base", base]);
+    }
+    if draft {
+        args.push("--draft");
+    }
+    if !reviewers.is_empty() {
+        args.extend(["--reviewer", reviewers]);
+    }
+    args
+}
 
-    let output = tokio_command("gh")
-        .args([
+async fn view_github_pull_request(
+    repo_root: &Path,
+    repo_name: &str,
+    selector: &str,
+) -> Result<GitHubPullRequest, String> {
+    let (stdout, _) = run_gh_command(
+        repo_root,
+        &[
             "pr",
-            "diff",
-            &pr_number.to_string(),
+            "view",
+            selector,
             "--repo",
-            &repo_name,
-            "--color",
-            "never",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+            repo_name,
+            "--json",
+            PULL_REQUEST_JSON_FIELDS,
+        ],
+    )
+    .await?;
+    let mut pull_request: GitHubPullRequest =
+        serde_json::from_str(&stdout).map_err(|e| e.to_string())?;
+    pull_request.state = pull_request.state.to_lowercase();
+    pull_request.labels = labels(pull_request.labels);
+    Ok(pull_request)
+}
 
-    if !output.status.success() {
-        return Err(command_failure_detail(
-            &output.stdout,
-            &output.stderr,
-            "GitHub CLI command failed.",
-        ));
+async fn github_default_branch(repo_root: &Path, repo_name: &str) -> Result<String, String> {
+    let (stdout, _) = run_gh_command(
+        repo_root,
+        &[
+            "repo",
+            "view",
+            repo_name,
+            "--json",
+            "defaultBranchRef",
+            "--jq",
+            ".defaultBranchRef.name",
+        ],
+    )
+    .await?;
+    let branch = stdout.trim().to_string();
+    if branch.is_empty() {
+        return Err("Failed to determine the default branch.".to_string());
     }
+    Ok(branch)
+}
 
-    let diff_text = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_pr_diff(&diff_text))
+pub(super) async fn get_github_pull_request_branch_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    base: Option<String>,
+) -> Result<String, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let base = match normalize_optional_text(base) {
+        Some(base) => base,
+        None => {
+            let repo_name = github_repo_from_path(&repo_root)?;
+            github_default_branch(&repo_root, &repo_name).await?
+        }
+    };
+    let base_ref = if git_core::git_remote_branch_exists_local(&repo_root, "origin", &base).await? {
+        format!("origin/{base}")
+    } else {
+        base
+    };
+    let range = format!("{base_ref}...HEAD");
+    let diff = git_core::run_git_command_bytes(&repo_root, &["diff", "--no-color", &range]).await?;
+    Ok(truncate_pull_request_diff(
+        String::from_utf8_lossy(&diff).to_string(),
+    ))
 }
 
-pub(super) async fn get_github_pull_request_comments_inner(
+pub(super) async fn create_github_pull_request_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
-    pr_number: u64,
-) -> Result<Vec<GitHubPullRequestComment>, String> {
+    title: String,
+    body: String,
+    base: Option<String>,
+    draft: bool,
+    reviewers: Vec<String>,
+) -> Result<GitHubPullRequest, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Pull request title is required.".to_string());
+    }
+    let base = normalize_optional_text(base);
+    let reviewers = reviewers
+        .iter()
+        .map(|reviewer| reviewer.trim().trim_start_matches('@'))
+        .filter(|reviewer| !reviewer.is_empty())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let body = body_with_issue_reference(&body, entry.settings.issue_link.as_ref());
+
+    let head = push_branch_for_pull_request(&repo_root).await?;
+    let args = gh_pr_create_args(
+        &repo_name,
+        &head,
+        &title,
+        &body,
+        base.as_deref(),
+        draft,
+        &reviewers,
+    );
+    let (stdout, _) = run_gh_command(&repo_root, &args).await?;
+    let url = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with("http"))
+        .ok_or_else(|| "GitHub did not return a pull request URL.".to_string())?
+        .to_string();
+
+    view_github_pull_request(&repo_root, &repo_name, &url).await
+}
 
-    let comments_endpoint = format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
-    let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
-
-    let output = tokio_command("gh")
-        .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+pub(super) async fn update_github_pull_request_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    title: Option<String>,
+    body: Option<String>,
+) -> Result<GitHubPullRequest, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let title = normalize_optional_text(title);
+    if title.is_none() && body.is_none() {
+        return Err("Nothing to update.".to_string());
+    }
 
-    if !output.status.success() {
-        return Err(command_failure_detail(
-            &output.stdout,
-            &output.stderr,
-            "GitHub CLI command failed.",
-        ));
+    let pr_number_text = pr_number.to_string();
+    let mut args = vec![
+        "pr",
+        "edit",
+        pr_number_text.as_str(),
+        "--repo",
+        repo_name.as_str(),
+    ];
+    if let Some(title) = title.as_deref() {
+        args.extend(["--title", title]);
+    }
+    if let Some(body) = body.as_deref() {
+        args.extend(["--body", body]);
     }
+    run_gh_command(&repo_root, &args).await?;
 
-    let comments: Vec<GitHubPullRequestComment> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    view_github_pull_request(&repo_root, &repo_name, &pr_number_text).await
+}
+
+pub(super) async fn mark_github_pull_request_ready_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubPullRequest, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let pr_number_text = pr_number.to_string();
+    run_gh_command(
+        &repo_root,
+        &["pr", "ready", &pr_number_text, "--repo", &repo_name],
+    )
+    .await?;
 
-    Ok(comments)
+    view_github_pull_request(&repo_root, &repo_name, &pr_number_text).await
 }
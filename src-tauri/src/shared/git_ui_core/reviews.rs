@@ -0,0 +1,334 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::types::{
+    GitHubDiffSide, GitHubPullRequestDiff, GitHubPullRequestReviewComment,
+    GitHubPullRequestReviewEvent, GitHubPullRequestReviewResult, WorkspaceEntry,
+};
+use crate::utils::normalize_git_path;
+
+use super::commands::run_gh_command_with_input;
+use super::context::workspace_entry_for_id;
+use super::github::{get_github_pull_request_diff_inner, github_repo_from_path};
+
+const CODEX_REVIEW_HEADERS: [&str; 2] = ["Review comment:", "Full review comments:"];
+
+/// Summary and inline comments recovered from a rendered Codex review.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct CodexReviewFindings {
+    pub(super) summary: String,
+    pub(super) comments: Vec<GitHubPullRequestReviewComment>,
+}
+
+/// Lines GitHub accepts inline comments on, keyed by line number and mapped to
+/// the hunk they belong to. A multi-line comment must stay within one hunk.
+#[derive(Debug, Default)]
+struct DiffAnchors {
+    left: HashMap<u64, usize>,
+    right: HashMap<u64, usize>,
+}
+
+impl DiffAnchors {
+    fn side(&self, side: GitHubDiffSide) -> &HashMap<u64, usize> {
+        match side {
+            GitHubDiffSide::Left => &self.left,
+            GitHubDiffSide::Right => &self.right,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PostedReview {
+    id: u64,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    state: String,
+}
+
+fn parse_hunk_start(header: &str) -> Option<(u64, u64)> {
+    let ranges = header.strip_prefix("@@ ")?.split(" @@").next()?;
+    let mut parts = ranges.split_whitespace();
+    let old_start = parts.next()?.strip_prefix('-')?.split(',').next()?;
+    let new_start = parts.next()?.strip_prefix('+')?.split(',').next()?;
+    Some((old_start.parse().ok()?, new_start.parse().ok()?))
+}
+
+fn diff_anchors(diff: &str) -> DiffAnchors {
+    let mut anchors = DiffAnchors::default();
+    let mut hunk = 0usize;
+    let mut cursor: Option<(u64, u64)> = None;
+    for line in diff.lines() {
+        if line.starts_with("@@ ") {
+            cursor = parse_hunk_start(line);
+            hunk += 1;
+            continue;
+        }
+        let Some((old_line, new_line)) = cursor.as_mut() else {
+            continue;
+        };
+        if line.starts_with('\\') {
+            continue;
+        }
+        match line.chars().next() {
+            Some('+') => {
+                anchors.right.insert(*new_line, hunk);
+                *new_line += 1;
+            }
+            Some('-') => {
+                anchors.left.insert(*old_line, hunk);
+                *old_line += 1;
+            }
+            Some(' ') | None => {
+                anchors.left.insert(*old_line, hunk);
+                anchors.right.insert(*new_line, hunk);
+                *old_line += 1;
+                *new_line += 1;
+            }
+            _ => cursor = None,
+        }
+    }
+    anchors
+}
+
+/// Splits comments into those GitHub can attach to the diff and those it would
+/// reject. A start line outside the comment's hunk is dropped rather than the
+/// whole comment.
+pub(super) fn anchor_review_comments(
+    diffs: &[GitHubPullRequestDiff],
+    comments: Vec<GitHubPullRequestReviewComment>,
+) -> (
+    Vec<GitHubPullRequestReviewComment>,
+    Vec<GitHubPullRequestReviewComment>,
+) {
+    let anchors: HashMap<&str, DiffAnchors> = diffs
+        .iter()
+        .map(|entry| (entry.path.as_str(), diff_anchors(&entry.diff)))
+        .collect();
+    let mut anchored = Vec::new();
+    let mut unanchored = Vec::new();
+    for mut comment in comments {
+        comment.path = normalize_git_path(comment.path.trim());
+        let lines = anchors
+            .get(comment.path.as_str())
+            .map(|anchors| anchors.side(comment.side));
+        let Some(hunk) = lines.and_then(|lines| lines.get(&comment.line)) else {
+            unanchored.push(comment);
+            continue;
+        };
+        comment.start_line = comment.start_line.filter(|start| {
+            *start < comment.line && lines.and_then(|lines| lines.get(start)) == Some(hunk)
+        });
+        anchored.push(comment);
+    }
+    (anchored, unanchored)
+}
+
+pub(super) fn review_body_with_unanchored(
+    body: &str,
+    unanchored: &[GitHubPullRequestReviewComment],
+) -> String {
+    if unanchored.is_empty() {
+        return body.to_string();
+    }
+    let mut sections = Vec::new();
+    if !body.trim().is_empty() {
+        sections.push(body.trim().to_string());
+    }
+    let mut notes = vec!["**Comments outside the diff**".to_string(), String::new()];
+    for comment in unanchored {
+        let location = match comment.start_line {
+            Some(start) => format!("{}:{}-{}", comment.path, start, comment.line),
+            None => format!("{}:{}", comment.path, comment.line),
+        };
+        let mut lines = comment.body.trim().lines();
+        notes.push(format!(
+            "- `{location}` {}",
+            lines.next().unwrap_or_default()
+        ));
+        notes.extend(lines.map(|line| format!("  {line}")));
+    }
+    sections.push(notes.join("\n"));
+    sections.join("\n\n")
+}
+
+fn relative_review_path(path: &str, repo_root: &Path) -> String {
+    let path = Path::new(path.trim());
+    let relative = path.strip_prefix(repo_root).unwrap_or(path);
+    normalize_git_path(&relative.to_string_lossy())
+}
+
+fn parse_review_location(location: &str) -> Option<(&str, u64, u64)> {
+    let (path, range) = location.trim().rsplit_once(':')?;
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let line: u64 = range.trim().parse().ok()?;
+            (line, line)
+        }
+    };
+    if path.is_empty() || start == 0 {
+        return None;
+    }
+    Some((path, start.min(end), start.max(end)))
+}
+
+/// Recovers findings from the text Codex renders when a review turn completes:
+/// the overall explanation followed by `- <title> — <path>:<start>-<end>` items
+/// with indented bodies.
+pub(super) fn parse_codex_review_findings(review: &str, repo_root: &Path) -> CodexReviewFindings {
+    let mut summary_lines = Vec::new();
+    let mut findings: Vec<(GitHubPullRequestReviewComment, Vec<&str>)> = Vec::new();
+    let mut in_findings = false;
+    for line in review.lines() {
+        if CODEX_REVIEW_HEADERS.contains(&line.trim()) {
+            in_findings = true;
+            continue;
+        }
+        let finding = line
+            .strip_prefix("- ")
+            .and_then(|item| item.rsplit_once(" — "))
+            .and_then(|(title, location)| {
+                parse_review_location(location).map(|location| (title, location))
+            });
+        if let Some((title, (path, start, end))) = finding {
+            in_findings = true;
+            let comment = GitHubPullRequestReviewComment {
+                path: relative_review_path(path, repo_root),
+                line: end,
+                start_line: (start < end).then_some(start),
+                side: GitHubDiffSide::Right,
+                body: format!("**{}**", title.trim()),
+            };
+            findings.push((comment, Vec::new()));
+        } else if !in_findings {
+            summary_lines.push(line);
+        } else if let Some((_, body_lines)) = findings.last_mut() {
+            body_lines.push(line.strip_prefix("  ").unwrap_or(line));
+        }
+    }
+    let comments = findings
+        .into_iter()
+        .map(|(mut comment, body_lines)| {
+            let body = body_lines.join("\n");
+            if !body.trim().is_empty() {
+                comment.body = format!("{}\n\n{}", comment.body, body.trim());
+            }
+            comment
+        })
+        .collect();
+    CodexReviewFindings {
+        summary: summary_lines.join("\n").trim().to_string(),
+        comments,
+    }
+}
+
+pub(super) fn review_request_payload(
+    body: &str,
+    event: GitHubPullRequestReviewEvent,
+    comments: &[GitHubPullRequestReviewComment],
+) -> Value {
+    let comments: Vec<Value> = comments
+        .iter()
+        .map(|comment| {
+            let mut payload = json!({
+                "path": comment.path,
+                "line": comment.line,
+                "side": comment.side.as_api_value(),
+                "body": comment.body,
+            });
+            if let Some(start_line) = comment.start_line {
+                payload["start_line"] = json!(start_line);
+                payload["start_side"] = json!(comment.side.as_api_value());
+            }
+            payload
+        })
+        .collect();
+    json!({
+        "body": body,
+        "event": event.as_api_value(),
+        "comments": comments,
+    })
+}
+
+pub(super) async fn post_github_pull_request_review_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    body: Option<String>,
+    event: GitHubPullRequestReviewEvent,
+    comments: Vec<GitHubPullRequestReviewComment>,
+    codex_review: Option<String>,
+) -> Result<GitHubPullRequestReviewResult, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+
+    let mut body = body.unwrap_or_default().trim().to_string();
+    let mut comments: Vec<GitHubPullRequestReviewComment> = comments
+        .into_iter()
+        .filter(|comment| !comment.body.trim().is_empty() && comment.line > 0)
+        .collect();
+    if let Some(review) = codex_review.filter(|value| !value.trim().is_empty()) {
+        let findings = parse_codex_review_findings(&review, &repo_root);
+        if body.is_empty() {
+            body = findings.summary;
+        }
+        comments.extend(findings.comments);
+    }
+
+    let mut seen = HashSet::new();
+    comments.retain(|comment| {
+        seen.insert((
+            comment.path.clone(),
+            comment.line,
+            comment.side,
+            comment.body.clone(),
+        ))
+    });
+
+    let (anchored, unanchored) = if comments.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        let diffs = get_github_pull_request_diff_inner(workspaces, workspace_id, pr_number).await?;
+        anchor_review_comments(&diffs, comments)
+    };
+    let body = review_body_with_unanchored(&body, &unanchored);
+    if body.trim().is_empty() && event != GitHubPullRequestReviewEvent::Approve {
+        return Err("Add a review summary to comment or request changes.".to_string());
+    }
+
+    let payload = review_request_payload(&body, event, &anchored);
+    let input = serde_json::to_vec(&payload).map_err(|err| err.to_string())?;
+    let endpoint = format!("repos/{repo_name}/pulls/{pr_number}/reviews");
+    let (stdout, _) = run_gh_command_with_input(
+        &repo_root,
+        &[
+            "api",
+            "--method",
+            "POST",
+            &endpoint,
+            "--input",
+            "-",
+            "--jq",
+            "{id, url: .html_url, state}",
+        ],
+        &input,
+    )
+    .await?;
+    let posted: PostedReview =
+        serde_json::from_str(stdout.trim()).map_err(|err| err.to_string())?;
+
+    Ok(GitHubPullRequestReviewResult {
+        id: posted.id,
+        url: posted.url,
+        state: posted.state,
+        inline_comments: anchored.len(),
+        unanchored_comments: unanchored,
+    })
+}
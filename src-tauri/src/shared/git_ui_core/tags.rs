@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::git_utils::resolve_git_root;
+use crate::shared::{codex_aux_core, git_core};
+use crate::types::{GitHubRelease, GitTagInfo, WorkspaceEntry};
+
+use super::commands::{run_gh_command, validate_branch_name};
+use super::context::workspace_entry_for_id;
+use super::github::github_repo_from_path;
+
+const DEFAULT_TAG_REMOTE: &str = "origin";
+const MAX_RELEASE_COMMITS: &str = "200";
+
+/// Commits that make up a release: everything reachable from the tag but not
+/// from the tag before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReleaseCommitRange {
+    pub(crate) tag: String,
+    pub(crate) previous_tag: Option<String>,
+    pub(crate) commits: String,
+}
+
+pub(super) fn validate_tag_name(name: &str) -> Result<String, String> {
+    validate_branch_name(name).map_err(|error| error.replacen("Branch name", "Tag name", 1))
+}
+
+fn resolve_tag_remote(remote: Option<String>) -> String {
+    remote
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_TAG_REMOTE.to_string())
+}
+
+fn parse_tag_list(output: &str) -> Vec<GitTagInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let name = fields.next()?.trim();
+            let object_type = fields.next().unwrap_or("");
+            let object_sha = fields.next().unwrap_or("");
+            let peeled_sha = fields.next().unwrap_or("");
+            let subject = fields.next().unwrap_or("").trim();
+            let timestamp = fields.next().unwrap_or("").trim().parse().unwrap_or(0);
+            if name.is_empty() {
+                return None;
+            }
+            let annotated = object_type == "tag";
+            let sha = if annotated && !peeled_sha.is_empty() {
+                peeled_sha
+            } else {
+                object_sha
+            };
+            Some(GitTagInfo {
+                name: name.to_string(),
+                sha: sha.to_string(),
+                annotated,
+                message: (annotated && !subject.is_empty()).then(|| subject.to_string()),
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+async fn ensure_tag_exists(repo_root: &PathBuf, tag: &str) -> Result<(), String> {
+    let tag_ref = format!("refs/tags/{tag}");
+    git_core::run_git_command(repo_root, &["rev-parse", "--verify", "--quiet", &tag_ref])
+        .await
+        .map(|_| ())
+        .map_err(|_| format!("Tag `{tag}` not found."))
+}
+
+pub(super) async fn list_git_tags_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitTagInfo>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let output = git_core::run_git_command(
+        &repo_root,
+        &[
+            "for-each-ref",
+            "--sort=-creatordate",
+            "--format=%(refname:short)%00%(objecttype)%00%(objectname)%00%(*objectname)%00%(contents:subject)%00%(creatordate:unix)",
+            "refs/tags",
+        ],
+    )
+    .await?;
+    Ok(parse_tag_list(&output))
+}
+
+pub(super) async fn create_git_tag_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: String,
+    message: Option<String>,
+    target: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let name = validate_tag_name(&name)?;
+    let target = target
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    let message = message
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let mut args = vec!["tag"];
+    if let Some(message) = message.as_deref() {
+        args.extend(["-a", name.as_str(), "-m", message]);
+    } else {
+        args.push(name.as_str());
+    }
+    args.push(target.as_str());
+    git_core::run_git_command(&repo_root, &args)
+        .await
+        .map(|_| ())
+}
+
+pub(super) async fn delete_git_tag_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let name = validate_tag_name(&name)?;
+    ensure_tag_exists(&repo_root, &name).await?;
+    git_core::run_git_command(&repo_root, &["tag", "-d", &name]).await?;
+    if remote.is_some() {
+        let remote = resolve_tag_remote(remote);
+        let tag_ref = format!("refs/tags/{name}");
+        git_core::run_git_command(&repo_root, &["push", &remote, "--delete", &tag_ref]).await?;
+    }
+    Ok(())
+}
+
+pub(super) async fn push_git_tags_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    name: Option<String>,
+    remote: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let remote = resolve_tag_remote(remote);
+    let name = name.filter(|value| !value.trim().is_empty());
+    match name {
+        Some(name) => {
+            let name = validate_tag_name(&name)?;
+            ensure_tag_exists(&repo_root, &name).await?;
+            let tag_ref = format!("refs/tags/{name}");
+            git_core::run_git_command(&repo_root, &["push", &remote, &tag_ref]).await?;
+        }
+        None => {
+            git_core::run_git_command(&repo_root, &["push", &remote, "--tags"]).await?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) async fn release_commit_range_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    tag: String,
+) -> Result<ReleaseCommitRange, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let tag = validate_tag_name(&tag)?;
+    ensure_tag_exists(&repo_root, &tag).await?;
+
+    // `describe` fails when the tag has no parent or no earlier tag; the
+    // release then covers the whole history up to the tag.
+    let parent = format!("{tag}^");
+    let previous_tag =
+        git_core::run_git_command(&repo_root, &["describe", "--tags", "--abbrev=0", &parent])
+            .await
+            .ok()
+            .filter(|value| !value.is_empty());
+
+    let range = match previous_tag.as_deref() {
+        Some(previous) => format!("{previous}..{tag}"),
+        None => tag.clone(),
+    };
+    let commits = git_core::run_git_command(
+        &repo_root,
+        &[
+            "log",
+            "--no-merges",
+            "--max-count",
+            MAX_RELEASE_COMMITS,
+            "--format=- %s (%h)",
+            &range,
+        ],
+    )
+    .await?;
+
+    Ok(ReleaseCommitRange {
+        tag,
+        previous_tag,
+        commits,
+    })
+}
+
+pub(super) fn build_release_notes_prompt(
+    tag: &str,
+    previous_tag: Option<&str>,
+    commits: &str,
+) -> Result<String, String> {
+    if commits.trim().is_empty() {
+        return Err("No commits to generate release notes for".to_string());
+    }
+    let since = previous_tag
+        .map(|previous| format!("since {previous}"))
+        .unwrap_or_else(|| "since the start of the project".to_string());
+    Ok(format!(
+        "Write GitHub release notes for {tag}, covering the commits {since}.\n\
+Group related changes under short markdown headings such as Features, Fixes and Other. \
+Summarize each change in one line written for users, not for the developers who made it. \
+Skip commits that only touch CI, formatting or version bumps. \
+Only output the release notes in markdown, nothing else.\n\n\
+Commits:\n{commits}"
+    ))
+}
+
+/// Plain release notes listing the commits, used when Codex writes nothing.
+pub(super) fn commit_log_release_notes(range: &ReleaseCommitRange) -> String {
+    let heading = match range.previous_tag.as_deref() {
+        Some(previous) => format!("## Changes since {previous}"),
+        None => "## Changes".to_string(),
+    };
+    format!("{heading}\n\n{}", range.commits.trim())
+}
+
+/// Returns `notes` when given, otherwise asks Codex to write them and falls
+/// back to the commit log when Codex answers with nothing. Generation errors
+/// are returned so callers can tell the notes were not written.
+pub(super) async fn release_notes_inner<F>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    range: &ReleaseCommitRange,
+    notes: Option<String>,
+    model: Option<&str>,
+    on_hide_thread: F,
+) -> Result<String, String>
+where
+    F: Fn(&str, &str),
+{
+    if let Some(notes) = notes.filter(|value| !value.trim().is_empty()) {
+        return Ok(notes);
+    }
+    let prompt =
+        build_release_notes_prompt(&range.tag, range.previous_tag.as_deref(), &range.commits)?;
+    let generated = codex_aux_core::run_background_prompt_core(
+        sessions,
+        workspaces,
+        workspace_id,
+        prompt,
+        model,
+        None,
+        on_hide_thread,
+        "Timeout waiting for release notes generation",
+        "Unknown error during release notes generation",
+    )
+    .await?;
+    if generated.trim().is_empty() {
+        return Ok(commit_log_release_notes(range));
+    }
+    Ok(generated)
+}
+
+pub(super) async fn create_github_release_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    range: ReleaseCommitRange,
+    title: Option<String>,
+    notes: String,
+    draft: bool,
+) -> Result<GitHubRelease, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let title = title
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| range.tag.clone());
+
+    let mut args = vec![
+        "release",
+        "create",
+        range.tag.as_str(),
+        "--repo",
+        repo_name.as_str(),
+        "--verify-tag",
+        "--title",
+        title.as_str(),
+        "--notes",
+        notes.as_str(),
+    ];
+    if draft {
+        args.push("--draft");
+    }
+    let (stdout, _) = run_gh_command(&repo_root, &args).await?;
+    let url = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with("http"))
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(GitHubRelease {
+        tag_name: range.tag,
+        previous_tag: range.previous_tag,
+        url,
+        is_draft: draft,
+        notes,
+    })
+}
@@ -1,16 +1,29 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use git2::Repository;
 use serde_json::Value;
 use tokio::runtime::Runtime;
 use tokio::sync::Mutex;
 
-use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+use crate::shared::http_stub::{spawn_http_stub, StubResponse};
+use crate::types::{
+    AppSettings, ForgeKind, GitHubCheckRun, GitHubChecksResponse, GitHubChecksState,
+    GitHubDiffSide, GitHubItemState, GitHubListQuery, GitHubPullRequestDiff,
+    GitHubPullRequestReviewComment, GitHubPullRequestReviewEvent, WorkspaceEntry,
+    WorkspaceIssueLink, WorkspaceKind, WorkspaceSettings,
+};
 
+use super::checks;
 use super::commands;
 use super::diff;
+use super::forge::{self, Forge};
+use super::github;
+use super::reviews;
+use super::tags;
+use super::watcher;
 
 fn create_temp_repo() -> (PathBuf, Repository) {
     let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
@@ -435,3 +448,983 @@ fn collect_ignored_paths_with_git_handles_large_ignored_output() {
 
     assert_eq!(ignored_paths.len(), total);
 }
+
+fn commit_file(root: &Path, repo: &Repository, name: &str, message: &str) {
+    fs::write(root.join(name), format!("{name}\n")).expect("write file");
+    let mut index = repo.index().expect("repo index");
+    index.add_path(Path::new(name)).expect("add path");
+    index.write().expect("write index");
+    let tree_id = index.write_tree().expect("write tree");
+    let tree = repo.find_tree(tree_id).expect("find tree");
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents = parent.iter().collect::<Vec<_>>();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .expect("commit");
+}
+
+#[test]
+fn pull_request_push_ignores_upstream_with_another_branch_name() {
+    let (root, repo) = create_temp_repo();
+    commit_file(&root, &repo, "one.txt", "Add one");
+    let base = repo
+        .head()
+        .expect("head")
+        .shorthand()
+        .expect("base branch")
+        .to_string();
+    let remote_root =
+        std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+    let remote_repo = Repository::init_bare(&remote_root).expect("init bare remote");
+    let mut origin = repo
+        .remote("origin", &remote_root.to_string_lossy())
+        .expect("add origin");
+    let base_refspec = format!("refs/heads/{base}:refs/heads/{base}");
+    origin
+        .push(&[base_refspec.as_str()], None)
+        .expect("push base");
+    let base_commit = repo.head().expect("head").target().expect("head target");
+    repo.reference(
+        &format!("refs/remotes/origin/{base}"),
+        base_commit,
+        true,
+        "track origin",
+    )
+    .expect("create remote-tracking ref");
+
+    let head_commit = repo.find_commit(base_commit).expect("head commit");
+    let mut feature = repo
+        .branch("feature", &head_commit, false)
+        .expect("create feature branch");
+    feature
+        .set_upstream(Some(&format!("origin/{base}")))
+        .expect("track origin base");
+    repo.set_head("refs/heads/feature")
+        .expect("checkout feature");
+    commit_file(&root, &repo, "two.txt", "Add two");
+
+    let runtime = Runtime::new().expect("create tokio runtime");
+    let pushed = runtime
+        .block_on(commands::push_branch_for_pull_request(&root))
+        .expect("push branch");
+
+    assert_eq!(pushed, "feature");
+    let remote_feature = remote_repo
+        .find_reference("refs/heads/feature")
+        .expect("feature pushed")
+        .target();
+    assert_eq!(remote_feature, repo.head().expect("head").target());
+    let remote_base = remote_repo
+        .find_reference(&format!("refs/heads/{base}"))
+        .expect("base on remote")
+        .target();
+    assert_eq!(remote_base, Some(base_commit));
+}
+
+#[test]
+fn tags_create_list_and_release_range() {
+    let (root, repo) = create_temp_repo();
+    let mut config = repo.config().expect("repo config");
+    config.set_str("user.name", "Test").expect("set user.name");
+    config
+        .set_str("user.email", "test@example.com")
+        .expect("set user.email");
+    commit_file(&root, &repo, "one.txt", "Add one");
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    runtime
+        .block_on(tags::create_git_tag_inner(
+            &workspaces,
+            "w1".to_string(),
+            "v0.1.0".to_string(),
+            None,
+            None,
+        ))
+        .expect("create lightweight tag");
+    commit_file(&root, &repo, "two.txt", "Add two");
+    runtime
+        .block_on(tags::create_git_tag_inner(
+            &workspaces,
+            "w1".to_string(),
+            "v0.2.0".to_string(),
+            Some("Second release".to_string()),
+            None,
+        ))
+        .expect("create annotated tag");
+
+    let listed = runtime
+        .block_on(tags::list_git_tags_inner(&workspaces, "w1".to_string()))
+        .expect("list tags");
+    let head_sha = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .expect("head commit")
+        .id()
+        .to_string();
+    let annotated = listed
+        .iter()
+        .find(|tag| tag.name == "v0.2.0")
+        .expect("annotated tag listed");
+    assert!(annotated.annotated);
+    assert_eq!(annotated.sha, head_sha);
+    assert_eq!(annotated.message.as_deref(), Some("Second release"));
+    let lightweight = listed
+        .iter()
+        .find(|tag| tag.name == "v0.1.0")
+        .expect("lightweight tag listed");
+    assert!(!lightweight.annotated);
+    assert_eq!(lightweight.message, None);
+
+    let range = runtime
+        .block_on(tags::release_commit_range_inner(
+            &workspaces,
+            "w1".to_string(),
+            "v0.2.0".to_string(),
+        ))
+        .expect("release range");
+    assert_eq!(range.previous_tag.as_deref(), Some("v0.1.0"));
+    assert!(range.commits.contains("Add two"));
+    assert!(!range.commits.contains("Add one"));
+
+    runtime
+        .block_on(tags::delete_git_tag_inner(
+            &workspaces,
+            "w1".to_string(),
+            "v0.1.0".to_string(),
+            None,
+        ))
+        .expect("delete tag");
+    let listed = runtime
+        .block_on(tags::list_git_tags_inner(&workspaces, "w1".to_string()))
+        .expect("list tags after delete");
+    assert_eq!(
+        listed
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["v0.2.0"]
+    );
+}
+
+#[test]
+fn validate_tag_name_reports_tag_wording() {
+    assert_eq!(
+        tags::validate_tag_name("v1 .0"),
+        Err("Tag name cannot contain spaces.".to_string())
+    );
+}
+
+#[test]
+fn build_release_notes_prompt_names_range_and_commits() {
+    let prompt = tags::build_release_notes_prompt("v1.2.0", Some("v1.1.0"), "- Add tags (abc123)")
+        .expect("prompt");
+    assert!(prompt.contains("v1.2.0"));
+    assert!(prompt.contains("since v1.1.0"));
+    assert!(prompt.ends_with("- Add tags (abc123)"));
+    assert!(tags::build_release_notes_prompt("v1.2.0", None, "  ").is_err());
+}
+
+#[test]
+fn release_notes_keep_given_notes_and_report_generation_errors() {
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: std::env::temp_dir().to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let sessions = Mutex::new(HashMap::new());
+    let range = tags::ReleaseCommitRange {
+        tag: "v0.2.0".to_string(),
+        previous_tag: Some("v0.1.0".to_string()),
+        commits: "- Add two (abc123)".to_string(),
+    };
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let given = runtime
+        .block_on(tags::release_notes_inner(
+            &sessions,
+            &workspaces,
+            "w1".to_string(),
+            &range,
+            Some("Hand written".to_string()),
+            None,
+            |_, _| {},
+        ))
+        .expect("given notes");
+    assert_eq!(given, "Hand written");
+
+    let not_connected = runtime.block_on(tags::release_notes_inner(
+        &sessions,
+        &workspaces,
+        "w1".to_string(),
+        &range,
+        Some("  ".to_string()),
+        None,
+        |_, _| {},
+    ));
+    assert_eq!(not_connected, Err("workspace not connected".to_string()));
+
+    let empty = tags::ReleaseCommitRange {
+        commits: String::new(),
+        ..range
+    };
+    assert!(runtime
+        .block_on(tags::release_notes_inner(
+            &sessions,
+            &workspaces,
+            "w1".to_string(),
+            &empty,
+            None,
+            None,
+            |_, _| {},
+        ))
+        .is_err());
+}
+
+#[test]
+fn commit_log_release_notes_list_commits_under_a_heading() {
+    let range = tags::ReleaseCommitRange {
+        tag: "v0.2.0".to_string(),
+        previous_tag: Some("v0.1.0".to_string()),
+        commits: "- Add two (abc123)\n".to_string(),
+    };
+    assert_eq!(
+        tags::commit_log_release_notes(&range),
+        "## Changes since v0.1.0\n\n- Add two (abc123)"
+    );
+    let first = tags::ReleaseCommitRange {
+        previous_tag: None,
+        ..range
+    };
+    assert_eq!(
+        tags::commit_log_release_notes(&first),
+        "## Changes\n\n- Add two (abc123)"
+    );
+}
+
+#[test]
+fn gh_pr_create_args_include_optional_flags() {
+    assert_eq!(
+        github::gh_pr_create_args(
+            "owner/repo",
+            "feat/tags",
+            "Add tags",
+            "Body",
+            Some("main"),
+            true,
+            "alice,bob",
+        ),
+        vec![
+            "pr",
+            "create",
+            "--repo",
+            "owner/repo",
+            "--head",
+            "feat/tags",
+            "--title",
+            "Add tags",
+            "--body",
+            "Body",
+            "--base",
+            "main",
+            "--draft",
+            "--reviewer",
+            "alice,bob",
+        ]
+    );
+    assert_eq!(
+        github::gh_pr_create_args("owner/repo", "feat/tags", "Add tags", "", None, false, ""),
+        vec![
+            "pr",
+            "create",
+            "--repo",
+            "owner/repo",
+            "--head",
+            "feat/tags",
+            "--title",
+            "Add tags",
+            "--body",
+            "",
+        ]
+    );
+}
+
+#[test]
+fn body_with_issue_reference_closes_linked_issue_once() {
+    let link = WorkspaceIssueLink {
+        number: 12,
+        title: "Crash".to_string(),
+        url: "https://github.com/owner/repo/issues/12".to_string(),
+        branch: "feat/issue-12-crash".to_string(),
+        thread_id: "thread-1".to_string(),
+    };
+    assert_eq!(
+        github::body_with_issue_reference("Summary", None),
+        "Summary"
+    );
+    assert_eq!(
+        github::body_with_issue_reference("Summary\n", Some(&link)),
+        "Summary\n\nCloses #12"
+    );
+    assert_eq!(
+        github::body_with_issue_reference("", Some(&link)),
+        "Closes #12"
+    );
+    assert_eq!(
+        github::body_with_issue_reference("Fixes #12.", Some(&link)),
+        "Fixes #12."
+    );
+    assert_eq!(
+        github::body_with_issue_reference("See #123", Some(&link)),
+        "See #123\n\nCloses #12"
+    );
+}
+
+fn review_comment(
+    path: &str,
+    line: u64,
+    start_line: Option<u64>,
+) -> GitHubPullRequestReviewComment {
+    GitHubPullRequestReviewComment {
+        path: path.to_string(),
+        line,
+        start_line,
+        side: GitHubDiffSide::Right,
+        body: "Check this".to_string(),
+    }
+}
+
+#[test]
+fn anchors_comments_to_diff_hunks() {
+    let diffs = vec![GitHubPullRequestDiff {
+        path: "src/lib.rs".to_string(),
+        status: "M".to_string(),
+        diff: [
+            "diff --git a/src/lib.rs b/src/lib.rs",
+            "--- a/src/lib.rs",
+            "+++ b/src/lib.rs",
+            "@@ -10,3 +10,4 @@ fn main() {",
+            " let a = 1;",
+            "-let b = 2;",
+            "+let b = 3;",
+            "+let c = 4;",
+            " let d = 5;",
+            "@@ -40,2 +41,2 @@",
+            " one",
+            "+two",
+        ]
+        .join("\n"),
+    }];
+    let (anchored, unanchored) = reviews::anchor_review_comments(
+        &diffs,
+        vec![
+            review_comment("src/lib.rs", 12, Some(10)),
+            review_comment("src/lib.rs", 42, Some(12)),
+            review_comment("src/lib.rs", 30, None),
+            review_comment("src/other.rs", 1, None),
+        ],
+    );
+    assert_eq!(
+        anchored
+            .iter()
+            .map(|comment| (comment.line, comment.start_line))
+            .collect::<Vec<_>>(),
+        vec![(12, Some(10)), (42, None)]
+    );
+    assert_eq!(
+        unanchored
+            .iter()
+            .map(|comment| comment.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["src/lib.rs", "src/other.rs"]
+    );
+
+    let body = reviews::review_body_with_unanchored("Looks mostly good.", &unanchored);
+    assert!(body.starts_with("Looks mostly good.\n\n**Comments outside the diff**"));
+    assert!(body.contains("- `src/lib.rs:30` Check this"));
+}
+
+#[test]
+fn parses_codex_review_findings() {
+    let review = [
+        "The change breaks retries when the token expires.",
+        "",
+        "Full review comments:",
+        "",
+        "- [P1] Retry loop never refreshes the token — /repo/src/auth.rs:40-44",
+        "  The loop reuses the stale token.",
+        "  Refresh it before retrying.",
+        "",
+        "- [P3] Typo in log message — /repo/src/log.rs:7-7",
+        "  `recieved` should be `received`.",
+    ]
+    .join("\n");
+    let findings = reviews::parse_codex_review_findings(&review, Path::new("/repo"));
+    assert_eq!(
+        findings.summary,
+        "The change breaks retries when the token expires."
+    );
+    assert_eq!(findings.comments.len(), 2);
+    assert_eq!(findings.comments[0].path, "src/auth.rs");
+    assert_eq!(findings.comments[0].line, 44);
+    assert_eq!(findings.comments[0].start_line, Some(40));
+    assert_eq!(
+        findings.comments[0].body,
+        "**[P1] Retry loop never refreshes the token**\n\nThe loop reuses the stale token.\nRefresh it before retrying."
+    );
+    assert_eq!(findings.comments[1].path, "src/log.rs");
+    assert_eq!(findings.comments[1].start_line, None);
+}
+
+#[test]
+fn review_payload_uses_github_field_names() {
+    let payload = reviews::review_request_payload(
+        "Summary",
+        GitHubPullRequestReviewEvent::RequestChanges,
+        &[review_comment("src/lib.rs", 12, Some(10))],
+    );
+    assert_eq!(payload["event"], "REQUEST_CHANGES");
+    assert_eq!(payload["comments"][0]["side"], "RIGHT");
+    assert_eq!(payload["comments"][0]["start_line"], 10);
+    assert_eq!(payload["comments"][0]["start_side"], "RIGHT");
+}
+
+fn check_run(name: &str, status: &str, conclusion: Option<&str>) -> GitHubCheckRun {
+    GitHubCheckRun {
+        id: 1,
+        name: name.to_string(),
+        status: status.to_string(),
+        conclusion: conclusion.map(str::to_string),
+        url: format!("https://github.com/acme/app/actions/runs/1/job/{name}"),
+        app: Some("github-actions".to_string()),
+        started_at: None,
+        completed_at: None,
+        log_excerpt: None,
+    }
+}
+
+#[test]
+fn checks_state_waits_for_every_run() {
+    assert_eq!(checks::checks_state(&[]), GitHubChecksState::None);
+    assert_eq!(
+        checks::checks_state(&[
+            check_run("lint", "completed", Some("failure")),
+            check_run("test", "in_progress", None),
+        ]),
+        GitHubChecksState::Pending
+    );
+    assert_eq!(
+        checks::checks_state(&[
+            check_run("lint", "completed", Some("success")),
+            check_run("docs", "completed", Some("skipped")),
+        ]),
+        GitHubChecksState::Success
+    );
+    assert_eq!(
+        checks::checks_state(&[
+            check_run("lint", "completed", Some("success")),
+            check_run("test", "completed", Some("timed_out")),
+        ]),
+        GitHubChecksState::Failure
+    );
+}
+
+#[test]
+fn log_excerpt_keeps_log_tail_without_job_columns() {
+    let log = (1..=80)
+        .map(|index| format!("test\tRun tests\t2024-01-01T00:00:00Z line {index}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let excerpt = checks::log_excerpt(&log).expect("excerpt");
+    let lines: Vec<&str> = excerpt.lines().collect();
+    assert_eq!(lines.len(), 60);
+    assert_eq!(lines[0], "2024-01-01T00:00:00Z line 21");
+    assert_eq!(lines[59], "2024-01-01T00:00:00Z line 80");
+    assert_eq!(checks::log_excerpt("\n  \n"), None);
+}
+
+#[test]
+fn checks_follow_up_lists_failed_runs() {
+    let mut failed = check_run("test", "completed", Some("failure"));
+    failed.log_excerpt = Some("assertion failed".to_string());
+    let mut response = GitHubChecksResponse {
+        git_ref: "feature/login".to_string(),
+        sha: "0123456789abcdef".to_string(),
+        pr_number: None,
+        state: GitHubChecksState::Failure,
+        checks: vec![check_run("lint", "completed", Some("success")), failed],
+    };
+    let follow_up = checks::build_checks_follow_up(&response).expect("follow-up");
+    assert!(follow_up.starts_with("CI failed for `feature/login` (0123456)."));
+    assert!(follow_up.contains("### test (failure)"));
+    assert!(follow_up.contains("```\nassertion failed\n```"));
+    assert!(!follow_up.contains("### lint"));
+
+    response.state = GitHubChecksState::Success;
+    assert_eq!(checks::build_checks_follow_up(&response), None);
+}
+
+#[test]
+fn parse_forge_remote_handles_scp_ssh_and_http_urls() {
+    let scp = forge::parse_forge_remote("git@gitlab.com:group/sub/project.git").expect("scp");
+    assert_eq!(scp.host, "gitlab.com");
+    assert_eq!(scp.web_base, "https://gitlab.com");
+    assert_eq!(scp.path, "group/sub/project");
+
+    let ssh =
+        forge::parse_forge_remote("ssh://git@Gitea.Example.com:2222/team/app.git").expect("ssh");
+    assert_eq!(ssh.host, "gitea.example.com");
+    assert_eq!(ssh.web_base, "https://gitea.example.com");
+    assert_eq!(ssh.path, "team/app");
+
+    let http = forge::parse_forge_remote("http://localhost:3000/owner/repo/").expect("http");
+    assert_eq!(http.host, "localhost");
+    assert_eq!(http.web_base, "http://localhost:3000");
+    assert_eq!(http.path, "owner/repo");
+
+    assert!(forge::parse_forge_remote("/local/path/repo").is_none());
+    assert!(forge::parse_forge_remote("https://example.com/repo").is_none());
+}
+
+#[test]
+fn detect_forge_kind_uses_host_names() {
+    assert_eq!(forge::detect_forge_kind("gitlab.com"), ForgeKind::GitLab);
+    assert_eq!(
+        forge::detect_forge_kind("gitlab.internal.io"),
+        ForgeKind::GitLab
+    );
+    assert_eq!(forge::detect_forge_kind("codeberg.org"), ForgeKind::Gitea);
+    assert_eq!(
+        forge::detect_forge_kind("git.forgejo.dev"),
+        ForgeKind::Gitea
+    );
+    assert_eq!(forge::detect_forge_kind("github.com"), ForgeKind::GitHub);
+    assert_eq!(
+        forge::detect_forge_kind("git.example.com"),
+        ForgeKind::GitHub
+    );
+}
+
+/// Request path (including the query), JSON body and an optional extra header.
+type StubRoute = (
+    &'static str,
+    &'static str,
+    Option<(&'static str, &'static str)>,
+);
+
+/// Serves canned JSON bodies keyed by request path and records every request.
+fn spawn_stub_forge(
+    routes: Vec<StubRoute>,
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    spawn_http_stub(move |request| {
+        match routes.iter().find(|(route, _, _)| *route == request.path) {
+            Some((_, body, header)) => {
+                let response = StubResponse::json("200 OK", *body);
+                match header {
+                    Some((name, value)) => response.with_header(name, value),
+                    None => response,
+                }
+            }
+            None => StubResponse::json("404 Not Found", r#"{"message":"404 Not found"}"#),
+        }
+    })
+}
+
+#[test]
+fn gitlab_forge_maps_merge_requests_diffs_and_notes() {
+    let (base, requests) = spawn_stub_forge(vec![
+        (
+            "/api/v4/projects/group%2Fapp/merge_requests?state=opened&order_by=updated_at&per_page=20&page=2&labels=bug%2Cui&author_username=alice&search=login%20page",
+            r#"[{"iid":7,"title":"Add feature","web_url":"https://gitlab.example.com/group/app/-/merge_requests/7","updated_at":"2026-01-02T00:00:00Z","created_at":"2026-01-01T00:00:00Z","description":null,"source_branch":"feat/x","target_branch":"main","draft":true,"author":{"username":"alice"},"state":"opened","labels":["bug","ui"],"assignees":[{"username":"dan"}],"user_notes_count":3}]"#,
+            Some(("X-Total", "42")),
+        ),
+        (
+            "/api/v4/projects/group%2Fapp/merge_requests/7/diffs?per_page=100",
+            r#"[{"old_path":"src/a.rs","new_path":"src/a.rs","diff":"@@ -1 +1 @@\n-old\n+new\n"},{"old_path":"docs/new.md","new_path":"docs/new.md","new_file":true,"diff":"@@ -0,0 +1 @@\n+hello\n"}]"#,
+            None,
+        ),
+        (
+            "/api/v4/projects/group%2Fapp/merge_requests/7/notes?sort=asc&order_by=created_at&per_page=30",
+            r#"[{"id":1,"body":"added 1 commit","created_at":"2026-01-01T00:00:00Z","system":true},{"id":2,"body":"Looks good","created_at":"2026-01-02T00:00:00Z","author":{"username":"bob"}}]"#,
+            None,
+        ),
+    ]);
+    let remote = forge::parse_forge_remote("git@gitlab.example.com:group/app.git").expect("remote");
+    let gitlab = forge::GitLabForge::new(
+        PathBuf::from("."),
+        &format!("{base}/api/v4"),
+        remote,
+        Some("secret".to_string()),
+    )
+    .expect("gitlab forge");
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let query = GitHubListQuery {
+        labels: vec!["bug".to_string(), " ui ".to_string()],
+        author: Some("@alice".to_string()),
+        search: Some("login page".to_string()),
+        cursor: Some("2".to_string()),
+        limit: Some(20),
+        ..GitHubListQuery::default()
+    };
+    let pulls = runtime
+        .block_on(gitlab.list_pull_requests(&query))
+        .expect("list merge requests");
+    assert_eq!(pulls.total, 42);
+    assert_eq!(pulls.next_cursor.as_deref(), Some("3"));
+    let pull = &pulls.pull_requests[0];
+    assert_eq!(pull.state, "open");
+    assert_eq!(pull.labels.len(), 2);
+    assert_eq!(pull.assignees[0].login, "dan");
+    assert_eq!(pull.comment_count, 3);
+    assert_eq!(pull.number, 7);
+    assert_eq!(pull.head_ref_name, "feat/x");
+    assert_eq!(pull.base_ref_name, "main");
+    assert!(pull.is_draft);
+    assert_eq!(pull.body, "");
+    assert_eq!(
+        pull.author.as_ref().map(|a| a.login.as_str()),
+        Some("alice")
+    );
+
+    let diffs = runtime
+        .block_on(gitlab.pull_request_diff(7))
+        .expect("merge request diff");
+    assert_eq!(diffs.len(), 2);
+    assert_eq!(diffs[0].path, "src/a.rs");
+    assert_eq!(diffs[0].status, "M");
+    assert!(diffs[0].diff.contains("+new"));
+    assert_eq!(diffs[1].path, "docs/new.md");
+    assert_eq!(diffs[1].status, "A");
+
+    let comments = runtime
+        .block_on(gitlab.pull_request_comments(7))
+        .expect("merge request notes");
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].body, "Looks good");
+    assert_eq!(
+        comments[0].url,
+        "https://gitlab.example.com/group/app/-/merge_requests/7#note_2"
+    );
+
+    let error = runtime
+        .block_on(gitlab.list_issues(&GitHubListQuery::default()))
+        .expect_err("unknown route fails");
+    assert!(error.contains("HTTP 404"));
+    assert!(error.contains("404 Not found"));
+
+    let requests = requests.lock().expect("requests");
+    assert!(requests
+        .iter()
+        .all(|head| head.to_lowercase().contains("private-token: secret")));
+}
+
+#[test]
+fn gitlab_forge_fetches_issue_with_notes() {
+    let (base, _requests) = spawn_stub_forge(vec![
+        (
+            "/api/v4/projects/group%2Fapp/issues/5",
+            r#"{"iid":5,"title":"Crash on start","web_url":"https://gitlab.example.com/group/app/-/issues/5","updated_at":"2026-01-02T00:00:00Z","state":"opened","description":"Stack trace","labels":["bug"],"user_notes_count":2}"#,
+            None,
+        ),
+        (
+            "/api/v4/projects/group%2Fapp/issues/5/notes?sort=asc&order_by=created_at&per_page=30",
+            r#"[{"id":8,"body":"changed the description","created_at":"2026-01-01T00:00:00Z","system":true},{"id":9,"body":"Reproduced","created_at":"2026-01-02T00:00:00Z","author":{"username":"bob"}}]"#,
+            None,
+        ),
+    ]);
+    let remote = forge::parse_forge_remote("git@gitlab.example.com:group/app.git").expect("remote");
+    let gitlab =
+        forge::GitLabForge::new(PathBuf::from("."), &format!("{base}/api/v4"), remote, None)
+            .expect("gitlab forge");
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let issue = runtime.block_on(gitlab.issue(5)).expect("issue");
+    assert_eq!(issue.number, 5);
+    assert_eq!(issue.state, "open");
+    assert_eq!(issue.body, "Stack trace");
+    assert_eq!(issue.labels[0].name, "bug");
+
+    let comments = runtime
+        .block_on(gitlab.issue_comments(5))
+        .expect("issue notes");
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].body, "Reproduced");
+    assert_eq!(
+        comments[0].url,
+        "https://gitlab.example.com/group/app/-/issues/5#note_9"
+    );
+}
+
+#[test]
+fn gitea_forge_maps_pulls_and_raw_diff() {
+    let (base, requests) = spawn_stub_forge(vec![
+        (
+            "/api/v1/repos/owner/app/pulls?state=open&sort=recentupdate&limit=50&page=1",
+            r#"[{"number":3,"title":"WIP: Refactor","html_url":"https://codeberg.org/owner/app/pulls/3","updated_at":"2026-01-02T00:00:00Z","created_at":"2026-01-01T00:00:00Z","body":"Details","head":{"ref":"refactor"},"base":{"ref":"main"},"user":{"login":"carol"},"state":"open","labels":[{"name":"ui","color":"e11d21"}],"assignees":null,"comments":2}]"#,
+            Some(("X-Total-Count", "4")),
+        ),
+        (
+            "/api/v1/repos/owner/app/pulls?state=closed&sort=recentupdate&limit=50&page=1",
+            r#"[{"number":4,"title":"Merged work","html_url":"https://codeberg.org/owner/app/pulls/4","updated_at":"2026-01-02T00:00:00Z","created_at":"2026-01-01T00:00:00Z","head":{"ref":"done"},"base":{"ref":"main"},"state":"closed","merged":true},{"number":5,"title":"Abandoned","html_url":"https://codeberg.org/owner/app/pulls/5","updated_at":"2026-01-02T00:00:00Z","created_at":"2026-01-01T00:00:00Z","head":{"ref":"old"},"base":{"ref":"main"},"state":"closed","merged":false}]"#,
+            Some(("X-Total-Count", "2")),
+        ),
+        (
+            "/api/v1/repos/owner/app/pulls/3.diff",
+            "diff --git a/lib.rs b/lib.rs\n--- a/lib.rs\n+++ b/lib.rs\n@@ -1 +1 @@\n-a\n+b\n",
+            None,
+        ),
+        (
+            "/api/v1/repos/owner/app/issues?state=all&type=issues&limit=50&page=1&labels=bug&assigned_by=erin&milestones=v1.0",
+            r##"[{"number":9,"title":"Bug","html_url":"https://codeberg.org/owner/app/issues/9","updated_at":"2026-01-03T00:00:00Z","state":"closed","body":"Steps","labels":[{"name":"bug","color":"#ee0701"}],"assignees":[{"login":"erin"}],"comments":5}]"##,
+            None,
+        ),
+    ]);
+    let remote = forge::parse_forge_remote("https://codeberg.org/owner/app.git").expect("remote");
+    let gitea = forge::GiteaForge::new(
+        PathBuf::from("."),
+        &format!("{base}/api/v1"),
+        remote,
+        Some("tkn".to_string()),
+    )
+    .expect("gitea forge");
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let pulls = runtime
+        .block_on(gitea.list_pull_requests(&GitHubListQuery::default()))
+        .expect("list pulls");
+    assert_eq!(pulls.total, 4);
+    assert_eq!(pulls.next_cursor, None);
+    assert_eq!(pulls.pull_requests[0].head_ref_name, "refactor");
+    assert!(pulls.pull_requests[0].is_draft);
+    assert_eq!(pulls.pull_requests[0].comment_count, 2);
+    assert_eq!(
+        pulls.pull_requests[0].labels[0].color.as_deref(),
+        Some("#e11d21")
+    );
+
+    let merged = runtime
+        .block_on(gitea.list_pull_requests(&GitHubListQuery {
+            state: GitHubItemState::Merged,
+            ..GitHubListQuery::default()
+        }))
+        .expect("list merged pulls");
+    assert_eq!(merged.total, 1);
+    assert_eq!(merged.pull_requests[0].number, 4);
+    assert_eq!(merged.pull_requests[0].state, "merged");
+
+    let diffs = runtime.block_on(gitea.pull_request_diff(3)).expect("diff");
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].path, "lib.rs");
+
+    let issues = runtime
+        .block_on(gitea.list_issues(&GitHubListQuery {
+            state: GitHubItemState::All,
+            labels: vec!["bug".to_string()],
+            assignee: Some("erin".to_string()),
+            milestone: Some("v1.0".to_string()),
+            ..GitHubListQuery::default()
+        }))
+        .expect("issues");
+    assert_eq!(issues.total, 1);
+    let issue = &issues.issues[0];
+    assert_eq!(issue.number, 9);
+    assert_eq!(issue.state, "closed");
+    assert_eq!(issue.body, "Steps");
+    assert_eq!(issue.comment_count, 5);
+    assert_eq!(issue.assignees[0].login, "erin");
+    assert!(runtime
+        .block_on(gitea.list_issues(&GitHubListQuery {
+            state: GitHubItemState::Merged,
+            ..GitHubListQuery::default()
+        }))
+        .is_err());
+
+    let requests = requests.lock().expect("requests");
+    assert!(requests
+        .iter()
+        .all(|head| head.to_lowercase().contains("authorization: token tkn")));
+}
+
+#[test]
+fn github_search_query_combines_filters() {
+    let query = GitHubListQuery {
+        state: GitHubItemState::Closed,
+        labels: vec![
+            "bug".to_string(),
+            "needs triage".to_string(),
+            " ".to_string(),
+        ],
+        assignee: Some("@alice".to_string()),
+        author: Some("bob".to_string()),
+        search: Some("crash on start".to_string()),
+        milestone: Some("v2 \"beta\"".to_string()),
+        ..GitHubListQuery::default()
+    };
+    assert_eq!(
+        github::github_search_query("owner/repo", true, &query).expect("query"),
+        "repo:owner/repo is:pr is:closed is:unmerged label:bug label:\"needs triage\" \
+         assignee:alice author:bob milestone:\"v2 beta\" crash on start sort:updated-desc"
+    );
+    assert_eq!(
+        github::github_search_query("owner/repo", false, &GitHubListQuery::default())
+            .expect("default query"),
+        "repo:owner/repo is:issue is:open sort:updated-desc"
+    );
+    assert!(github::github_search_query(
+        "owner/repo",
+        false,
+        &GitHubListQuery {
+            state: GitHubItemState::Merged,
+            ..GitHubListQuery::default()
+        },
+    )
+    .is_err());
+}
+
+#[test]
+fn parses_github_issue_search_page() {
+    let stdout = r#"{"data":{"search":{"issueCount":73,"pageInfo":{"hasNextPage":true,"endCursor":"Y3Vyc29yOjUw"},"nodes":[{"number":12,"title":"Crash","url":"https://github.com/o/r/issues/12","state":"OPEN","body":"Trace","updatedAt":"2026-01-01T00:00:00Z","author":{"login":"alice"},"labels":{"nodes":[{"name":"bug","color":"d73a4a"}]},"assignees":{"nodes":[{"login":"bob"}]},"comments":{"totalCount":4},"closedByPullRequestsReferences":{"nodes":[{"number":15,"title":"Fix crash","url":"https://github.com/o/r/pull/15","state":"MERGED"}]}},{}]}}}"#;
+    let response = github::parse_issue_search(stdout).expect("parse issues");
+    assert_eq!(response.total, 73);
+    assert_eq!(response.next_cursor.as_deref(), Some("Y3Vyc29yOjUw"));
+    assert_eq!(response.issues.len(), 1);
+    let issue = &response.issues[0];
+    assert_eq!(issue.state, "open");
+    assert_eq!(issue.labels[0].color.as_deref(), Some("#d73a4a"));
+    assert_eq!(issue.assignees[0].login, "bob");
+    assert_eq!(issue.comment_count, 4);
+    assert_eq!(issue.linked_pull_requests[0].number, 15);
+    assert_eq!(issue.linked_pull_requests[0].state, "merged");
+}
+
+#[test]
+fn parses_github_pull_request_search_last_page() {
+    let stdout = r#"{"data":{"search":{"issueCount":1,"pageInfo":{"hasNextPage":false,"endCursor":"Y3Vyc29yOjE="},"nodes":[{"number":15,"title":"Fix crash","url":"https://github.com/o/r/pull/15","state":"OPEN","body":"","updatedAt":"2026-01-02T00:00:00Z","createdAt":"2026-01-01T00:00:00Z","headRefName":"fix/crash","baseRefName":"main","isDraft":false,"author":null,"labels":{"nodes":[]},"assignees":{"nodes":[]},"comments":{"totalCount":0},"closingIssuesReferences":{"nodes":[{"number":12,"title":"Crash","url":"https://github.com/o/r/issues/12","state":"OPEN"}]}}]}}}"#;
+    let response = github::parse_pull_request_search(stdout).expect("parse pull requests");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.next_cursor, None);
+    assert_eq!(response.pull_requests[0].head_ref_name, "fix/crash");
+    assert_eq!(response.pull_requests[0].linked_issues[0].number, 12);
+
+    let error = github::parse_pull_request_search(
+        r#"{"data":null,"errors":[{"message":"Something went wrong"}]}"#,
+    )
+    .expect_err("graphql errors surface");
+    assert_eq!(error, "Something went wrong");
+}
+
+#[test]
+fn next_page_cursor_uses_total_or_full_pages() {
+    assert_eq!(
+        forge::next_page_cursor(1, 50, 50, Some(120)),
+        Some("2".to_string())
+    );
+    assert_eq!(forge::next_page_cursor(3, 50, 20, Some(120)), None);
+    assert_eq!(
+        forge::next_page_cursor(1, 50, 50, None),
+        Some("2".to_string())
+    );
+    assert_eq!(forge::next_page_cursor(2, 50, 10, None), None);
+}
+
+#[test]
+fn git_status_filter_respects_gitignore_and_git_metadata() {
+    let (root, _repo) = create_temp_repo();
+    let root = root.canonicalize().expect("canonical root");
+    fs::write(root.join(".gitignore"), "target/\n*.log\n").expect("write gitignore");
+    fs::create_dir_all(root.join("src")).expect("create src");
+    fs::create_dir_all(root.join("target")).expect("create target");
+    fs::write(root.join("src/.gitignore"), "generated.rs\n").expect("write nested gitignore");
+
+    let mut filter = watcher::StatusPathFilter::new(&root).expect("filter");
+    assert_eq!(
+        filter.relevant(&root.join("src/lib.rs")).as_deref(),
+        Some("src/lib.rs")
+    );
+    assert_eq!(
+        filter.relevant(&root.join(".gitignore")).as_deref(),
+        Some(".gitignore")
+    );
+    assert_eq!(
+        filter.relevant(&root.join(".git/HEAD")).as_deref(),
+        Some(".git/HEAD")
+    );
+    assert_eq!(
+        filter.relevant(&root.join(".git/index")).as_deref(),
+        Some(".git/index")
+    );
+    assert_eq!(
+        filter
+            .relevant(&root.join(".git/refs/heads/main"))
+            .as_deref(),
+        Some(".git/refs/heads/main")
+    );
+    assert!(filter.relevant(&root.join("target/out.bin")).is_none());
+    assert!(filter.relevant(&root.join("debug.log")).is_none());
+    assert!(filter.relevant(&root.join("src/generated.rs")).is_none());
+    assert!(filter
+        .relevant(&root.join(".git/objects/ab/cdef"))
+        .is_none());
+    assert!(filter.relevant(&root.join(".git/index.lock")).is_none());
+    assert!(filter
+        .relevant(&root.join(".git/refs/heads/main.lock"))
+        .is_none());
+
+    fs::write(root.join(".gitignore"), "target/\n").expect("rewrite gitignore");
+    assert!(filter.relevant(&root.join(".gitignore")).is_some());
+    assert_eq!(
+        filter.relevant(&root.join("debug.log")).as_deref(),
+        Some("debug.log")
+    );
+}
+
+#[test]
+fn git_status_pending_changes_wait_for_quiet_period() {
+    let quiet = Duration::from_millis(500);
+    let start = Instant::now();
+    let mut pending = watcher::PendingChanges::default();
+    assert!(pending.deadline(quiet).is_none());
+
+    pending.add(Vec::new(), start);
+    assert!(pending.deadline(quiet).is_none());
+    pending.add(vec!["b.rs".to_string()], start);
+    pending.add(
+        vec!["a.rs".to_string(), "b.rs".to_string()],
+        start + Duration::from_millis(300),
+    );
+    assert_eq!(
+        pending.deadline(quiet),
+        Some(start + Duration::from_millis(800))
+    );
+
+    // A steady stream of changes is flushed after the maximum delay.
+    pending.add(
+        vec!["c.rs".to_string()],
+        start + Duration::from_millis(2_900),
+    );
+    assert_eq!(
+        pending.deadline(quiet),
+        Some(start + Duration::from_secs(3))
+    );
+
+    let (paths, truncated) = pending.take();
+    assert_eq!(paths, vec!["a.rs", "b.rs", "c.rs"]);
+    assert!(!truncated);
+    assert!(pending.deadline(quiet).is_none());
+}
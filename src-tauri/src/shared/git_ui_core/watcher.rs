@@ -0,0 +1,306 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use git2::Repository;
+use ignore::gitignore::{gitconfig_excludes_path, Gitignore, GitignoreBuilder};
+use notify_debouncer_full::notify::RecursiveMode;
+use notify_debouncer_full::{new_debouncer, DebounceEventResult};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::backend::events::{EventSink, GitStatusChanged};
+use crate::git_utils::resolve_git_root;
+use crate::types::WorkspaceEntry;
+use crate::utils::normalize_git_path;
+
+use super::context::workspace_entry_for_id;
+
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+const MIN_DEBOUNCE_MS: u64 = 200;
+/// How long the OS watcher batches raw events, such as the write and rename
+/// of an atomic save, before handing them over.
+const NOTIFY_BATCH: Duration = Duration::from_millis(100);
+/// Upper bound on how long a continuous stream of changes delays the event.
+const MAX_PENDING: Duration = Duration::from_secs(3);
+/// How often an idle watcher checks that the repository still exists.
+const LIVENESS_CHECK: Duration = Duration::from_secs(5);
+const MAX_REPORTED_PATHS: usize = 500;
+
+/// Running git status watchers, keyed by workspace id.
+pub(crate) type GitStatusWatchers = Mutex<HashMap<String, JoinHandle<()>>>;
+
+fn build_matcher(root: &Path, files: &[PathBuf]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for file in files.iter().filter(|file| file.is_file()) {
+        let _ = builder.add(file);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Decides which filesystem events can change `git status`: worktree files
+/// that are not ignored, plus the git metadata that records staging,
+/// checkouts and ref updates. Paths are reported repo-relative, with git
+/// metadata under `.git/`.
+pub(super) struct StatusPathFilter {
+    repo_root: PathBuf,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+    /// `.gitignore` matchers per directory, loaded on first use.
+    dir_ignores: HashMap<PathBuf, Gitignore>,
+    /// The global excludes file and `.git/info/exclude`.
+    repo_excludes: Gitignore,
+}
+
+impl StatusPathFilter {
+    pub(super) fn new(repo_root: &Path) -> Option<Self> {
+        let repo = Repository::open(repo_root).ok()?;
+        let repo_root = repo_root.canonicalize().ok()?;
+        let git_dir = repo.path().canonicalize().ok()?;
+        let common_dir = repo.commondir().canonicalize().ok()?;
+        let exclude_files = gitconfig_excludes_path()
+            .into_iter()
+            .chain([common_dir.join("info").join("exclude")])
+            .collect::<Vec<_>>();
+        Some(Self {
+            repo_excludes: build_matcher(&repo_root, &exclude_files),
+            repo_root,
+            git_dir,
+            common_dir,
+            dir_ignores: HashMap::new(),
+        })
+    }
+
+    /// Directories to watch: the worktree, plus the git directories when they
+    /// live outside it, as they do for linked worktrees.
+    pub(super) fn watch_roots(&self) -> Vec<(PathBuf, RecursiveMode)> {
+        let mut roots = vec![(self.repo_root.clone(), RecursiveMode::Recursive)];
+        if !self.git_dir.starts_with(&self.repo_root) {
+            roots.push((self.git_dir.clone(), RecursiveMode::NonRecursive));
+        }
+        if !self.common_dir.starts_with(&self.repo_root) {
+            roots.push((self.common_dir.clone(), RecursiveMode::NonRecursive));
+            roots.push((self.common_dir.join("refs"), RecursiveMode::Recursive));
+        }
+        roots
+    }
+
+    /// The reported name for a change at `path`, or `None` when the change
+    /// cannot affect the status.
+    pub(super) fn relevant(&mut self, path: &Path) -> Option<String> {
+        if let Some(name) = self.git_metadata_name(path) {
+            return Some(name);
+        }
+        let relative = path.strip_prefix(&self.repo_root).ok()?;
+        if relative.as_os_str().is_empty() || relative.starts_with(".git") {
+            return None;
+        }
+        if path.file_name().is_some_and(|name| name == ".gitignore") {
+            if let Some(dir) = path.parent() {
+                self.dir_ignores.remove(dir);
+            }
+        }
+        if self.is_ignored(path, path.is_dir()) {
+            return None;
+        }
+        Some(normalize_git_path(&relative.to_string_lossy()))
+    }
+
+    /// `HEAD` and `index` of the worktree's git dir, and `packed-refs` and
+    /// loose refs of the common dir. Lock files are skipped; git renames them
+    /// into place, which reports the final name.
+    fn git_metadata_name(&self, path: &Path) -> Option<String> {
+        if let Ok(relative) = path.strip_prefix(&self.git_dir) {
+            if relative == Path::new("HEAD") || relative == Path::new("index") {
+                return Some(format!(".git/{}", relative.to_string_lossy()));
+            }
+        }
+        let relative = path.strip_prefix(&self.common_dir).ok()?;
+        let is_lock = relative.extension().is_some_and(|ext| ext == "lock");
+        if relative == Path::new("packed-refs") || (relative.starts_with("refs") && !is_lock) {
+            return Some(normalize_git_path(&format!(
+                ".git/{}",
+                relative.to_string_lossy()
+            )));
+        }
+        None
+    }
+
+    /// Applies `.gitignore` files from the closest directory up to the repo
+    /// root, then the exclude files; the first decisive match wins.
+    fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            if !current.starts_with(&self.repo_root) {
+                break;
+            }
+            let matcher = self
+                .dir_ignores
+                .entry(current.to_path_buf())
+                .or_insert_with(|| build_matcher(current, &[current.join(".gitignore")]));
+            let matched = matcher.matched_path_or_any_parents(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+            if current == self.repo_root {
+                break;
+            }
+            dir = current.parent();
+        }
+        self.repo_excludes
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    }
+}
+
+/// Paths changed since the last event. They are due once no change has
+/// arrived for the quiet period, or `MAX_PENDING` after the first one while
+/// changes keep coming.
+#[derive(Default)]
+pub(super) struct PendingChanges {
+    paths: BTreeSet<String>,
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl PendingChanges {
+    pub(super) fn add(&mut self, paths: impl IntoIterator<Item = String>, now: Instant) {
+        let mut added = false;
+        for path in paths {
+            self.paths.insert(path);
+            added = true;
+        }
+        if added {
+            self.first.get_or_insert(now);
+            self.last = Some(now);
+        }
+    }
+
+    pub(super) fn deadline(&self, quiet: Duration) -> Option<Instant> {
+        Some((self.last? + quiet).min(self.first? + MAX_PENDING))
+    }
+
+    /// Sorted paths, capped at `MAX_REPORTED_PATHS`, and whether any were cut.
+    pub(super) fn take(&mut self) -> (Vec<String>, bool) {
+        let paths = std::mem::take(&mut self.paths);
+        self.first = None;
+        self.last = None;
+        let truncated = paths.len() > MAX_REPORTED_PATHS;
+        (
+            paths.into_iter().take(MAX_REPORTED_PATHS).collect(),
+            truncated,
+        )
+    }
+}
+
+/// Watches the repository with OS notifications and emits once the changes
+/// that can affect the status settle. Stops when the repository directory
+/// goes away or the watcher fails.
+async fn watch_git_status_events(
+    event_sink: impl EventSink,
+    repo_root: PathBuf,
+    workspace_id: String,
+    quiet: Duration,
+) {
+    let Some(mut filter) = StatusPathFilter::new(&repo_root) else {
+        return;
+    };
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let debouncer = new_debouncer(NOTIFY_BATCH, None, move |result: DebounceEventResult| {
+        if let Ok(events) = result {
+            let _ = sender.send(events);
+        }
+    });
+    let mut debouncer = match debouncer {
+        Ok(debouncer) => debouncer,
+        Err(err) => {
+            eprintln!("Failed to watch {}: {err}", repo_root.display());
+            return;
+        }
+    };
+    for (path, mode) in filter.watch_roots() {
+        if let Err(err) = debouncer.watch(&path, mode) {
+            eprintln!("Failed to watch {}: {err}", path.display());
+        }
+    }
+
+    let mut pending = PendingChanges::default();
+    loop {
+        let wait = pending
+            .deadline(quiet)
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+            .unwrap_or(LIVENESS_CHECK);
+        match tokio::time::timeout(wait, receiver.recv()).await {
+            Ok(Some(events)) => {
+                let paths = events
+                    .iter()
+                    .flat_map(|event| event.paths.iter())
+                    .filter_map(|path| filter.relevant(path))
+                    .collect::<Vec<_>>();
+                pending.add(paths, Instant::now());
+            }
+            Ok(None) => break,
+            Err(_) => {
+                if !repo_root.is_dir() {
+                    break;
+                }
+            }
+        }
+        if pending
+            .deadline(quiet)
+            .is_some_and(|deadline| deadline <= Instant::now())
+        {
+            let (paths, truncated) = pending.take();
+            event_sink.emit_git_status_changed(GitStatusChanged {
+                workspace_id: workspace_id.clone(),
+                paths,
+                truncated,
+            });
+        }
+    }
+    drop(debouncer);
+}
+
+pub(super) async fn watch_git_status_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    watchers: &GitStatusWatchers,
+    event_sink: impl EventSink,
+    workspace_id: String,
+    debounce_ms: Option<u64>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let quiet = Duration::from_millis(
+        debounce_ms
+            .unwrap_or(DEFAULT_DEBOUNCE_MS)
+            .max(MIN_DEBOUNCE_MS),
+    );
+
+    let handle = tokio::spawn(watch_git_status_events(
+        event_sink,
+        repo_root,
+        workspace_id.clone(),
+        quiet,
+    ));
+    let mut watchers = watchers.lock().await;
+    watchers.retain(|_, handle| !handle.is_finished());
+    if let Some(previous) = watchers.insert(workspace_id, handle) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+pub(super) async fn unwatch_git_status_inner(
+    watchers: &GitStatusWatchers,
+    workspace_id: String,
+) -> bool {
+    let Some(handle) = watchers.lock().await.remove(&workspace_id) else {
+        return false;
+    };
+    let running = !handle.is_finished();
+    handle.abort();
+    running
+}
@@ -0,0 +1,116 @@
+//! Loopback HTTP/1.1 server for tests that drive a real HTTP client.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+pub(crate) struct StubRequest {
+    /// Request line and headers, including the blank line.
+    pub(crate) head: String,
+    /// Request target as sent, with the query string.
+    pub(crate) path: String,
+    pub(crate) body: String,
+}
+
+pub(crate) struct StubResponse {
+    pub(crate) status: &'static str,
+    pub(crate) content_type: &'static str,
+    pub(crate) body: String,
+    /// Extra header lines, each ending in `\r\n`.
+    pub(crate) headers: String,
+}
+
+impl StubResponse {
+    pub(crate) fn new(status: &'static str, content_type: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+            headers: String::new(),
+        }
+    }
+
+    pub(crate) fn json(status: &'static str, body: impl Into<String>) -> Self {
+        Self::new(status, "application/json", body.into())
+    }
+
+    pub(crate) fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push_str(&format!("{name}: {value}\r\n"));
+        self
+    }
+}
+
+/// Serves every connection on `127.0.0.1` with `respond` and records each
+/// raw request (head and body). Returns `http://<addr>` without a trailing
+/// slash.
+pub(crate) fn spawn_http_stub<F>(respond: F) -> (String, Arc<Mutex<Vec<String>>>)
+where
+    F: Fn(&StubRequest) -> StubResponse + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind http stub");
+    let base = format!("http://{}", listener.local_addr().expect("stub addr"));
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                break;
+            };
+            let request = read_request(&mut stream);
+            recorded
+                .lock()
+                .expect("record request")
+                .push(format!("{}{}", request.head, request.body));
+            let response = respond(&request);
+            let raw = format!(
+                "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+                response.status,
+                response.content_type,
+                response.body.len(),
+                response.headers,
+                response.body
+            );
+            let _ = stream.write_all(raw.as_bytes());
+        }
+    });
+    (base, requests)
+}
+
+fn read_request(stream: &mut impl Read) -> StubRequest {
+    let mut raw = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let head_end = loop {
+        if let Some(index) = raw.windows(4).position(|window| window == b"\r\n\r\n") {
+            break index + 4;
+        }
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break raw.len(),
+            Ok(read) => raw.extend_from_slice(&buffer[..read]),
+        }
+    };
+    let head = String::from_utf8_lossy(&raw[..head_end]).to_string();
+    let length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    while raw.len() < head_end + length {
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => raw.extend_from_slice(&buffer[..read]),
+        }
+    }
+    let path = head
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+    StubRequest {
+        body: String::from_utf8_lossy(&raw[head_end..]).to_string(),
+        head,
+        path,
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::codex_aux_core::{sanitize_run_worktree_name, thread_id_from_start_response};
+use crate::shared::{codex_core, git_ui_core};
+use crate::storage::write_workspaces;
+use crate::types::{
+    default_issue_agent_prompt, AppSettings, GitHubIssue, GitHubPullRequestComment,
+    IssueAgentStart, WorkspaceEntry, WorkspaceInfo, WorkspaceIssueLink,
+};
+
+const MAX_BRANCH_TITLE_WORDS: usize = 6;
+
+/// Branch for an issue worktree, e.g. `feat/issue-42-crash-on-empty-config`.
+/// Only the first few words of the title are kept so branch names stay short.
+pub(crate) fn issue_branch_name(number: u64, title: &str) -> String {
+    let title = title
+        .split_whitespace()
+        .take(MAX_BRANCH_TITLE_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ");
+    sanitize_run_worktree_name(&format!("issue-{number} {}", title.replace('/', " ")))
+}
+
+fn format_issue_comments(comments: &[GitHubPullRequestComment]) -> String {
+    let comments = comments
+        .iter()
+        .filter(|comment| !comment.body.trim().is_empty())
+        .map(|comment| {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|author| author.login.as_str())
+                .unwrap_or("unknown");
+            format!(
+                "@{author} ({}):\n{}",
+                comment.created_at,
+                comment.body.trim()
+            )
+        })
+        .collect::<Vec<_>>();
+    if comments.is_empty() {
+        "(no comments)".to_string()
+    } else {
+        comments.join("\n\n")
+    }
+}
+
+/// Fills the issue agent template. Placeholders are substituted in a single
+/// pass, so braces inside the issue text are never expanded; unknown
+/// placeholders are left as written.
+pub(crate) fn build_issue_prompt(
+    template: &str,
+    issue: &GitHubIssue,
+    comments: &[GitHubPullRequestComment],
+    branch: &str,
+) -> String {
+    let labels = if issue.labels.is_empty() {
+        "none".to_string()
+    } else {
+        issue
+            .labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let body = match issue.body.trim() {
+        "" => "(no description)",
+        body => body,
+    };
+    let values = [
+        ("number", issue.number.to_string()),
+        ("title", issue.title.clone()),
+        ("url", issue.url.clone()),
+        ("branch", branch.to_string()),
+        ("labels", labels),
+        ("body", body.to_string()),
+        ("comments", format_issue_comments(comments)),
+    ];
+
+    let mut prompt = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        prompt.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                prompt.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                prompt.push('{');
+                rest = after;
+            }
+        }
+    }
+    prompt.push_str(rest);
+    prompt
+}
+
+/// Creates a worktree for an issue, starts a thread in it and sends the issue
+/// prompt. `add_worktree` receives the parent workspace id, branch and display
+/// name. The issue link is recorded on the worktree before the prompt is sent,
+/// so it survives a failed send.
+pub(crate) async fn start_issue_agent_core<FAddWorktree, FutAddWorktree>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    workspace_id: String,
+    issue_number: u64,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    add_worktree: FAddWorktree,
+) -> Result<IssueAgentStart, String>
+where
+    FAddWorktree: FnOnce(String, String, Option<String>) -> FutAddWorktree,
+    FutAddWorktree: Future<Output = Result<WorkspaceInfo, String>>,
+{
+    let parent_id = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        if entry.kind.is_worktree() {
+            entry
+                .parent_id
+                .clone()
+                .ok_or_else(|| "worktree parent not found".to_string())?
+        } else {
+            entry.id.clone()
+        }
+    };
+
+    let (issue, comments) =
+        git_ui_core::get_github_issue_with_comments_core(workspaces, workspace_id, issue_number)
+            .await?;
+    let branch = issue_branch_name(issue.number, &issue.title);
+    let name = format!("#{} {}", issue.number, issue.title.trim());
+    let mut workspace = add_worktree(parent_id, branch.clone(), Some(name)).await?;
+    let branch = workspace
+        .worktree
+        .as_ref()
+        .map(|worktree| worktree.branch.clone())
+        .unwrap_or(branch);
+
+    let thread_result =
+        codex_core::start_thread_core(sessions, workspaces, workspace.id.clone()).await?;
+    let thread_id = thread_id_from_start_response(&thread_result)?;
+
+    let link = WorkspaceIssueLink {
+        number: issue.number,
+        title: issue.title.clone(),
+        url: issue.url.clone(),
+        branch: branch.clone(),
+        thread_id: thread_id.clone(),
+    };
+    {
+        let mut workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get_mut(&workspace.id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        entry.settings.issue_link = Some(link.clone());
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(storage_path, &list)?;
+    }
+    workspace.settings.issue_link = Some(link);
+
+    let template = {
+        let settings = app_settings.lock().await;
+        if settings.issue_agent_prompt.trim().is_empty() {
+            default_issue_agent_prompt()
+        } else {
+            settings.issue_agent_prompt.clone()
+        }
+    };
+    let prompt = build_issue_prompt(&template, &issue, &comments, &branch);
+    codex_core::send_user_message_core(
+        sessions,
+        workspaces,
+        workspace.id.clone(),
+        thread_id.clone(),
+        prompt,
+        model,
+        effort,
+        access_mode,
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(IssueAgentStart {
+        workspace,
+        thread_id,
+        branch,
+        issue,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GitHubLabel, GitHubPullRequestAuthor};
+
+    fn issue() -> GitHubIssue {
+        GitHubIssue {
+            number: 42,
+            title: "Crash when config.toml is empty".to_string(),
+            url: "https://github.com/acme/app/issues/42".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            state: "open".to_string(),
+            body: "Steps: open an empty {comments} file.".to_string(),
+            author: None,
+            labels: vec![
+                GitHubLabel {
+                    name: "bug".to_string(),
+                    color: None,
+                },
+                GitHubLabel {
+                    name: "config".to_string(),
+                    color: None,
+                },
+            ],
+            assignees: Vec::new(),
+            comment_count: 1,
+            linked_pull_requests: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn issue_branch_name_keeps_number_and_short_title() {
+        assert_eq!(
+            issue_branch_name(42, "Crash when config.toml is empty"),
+            "feat/issue-42-crash-when-configtoml-is-empty"
+        );
+        assert_eq!(
+            issue_branch_name(
+                7,
+                "Support a/b testing of the new onboarding flow for teams"
+            ),
+            "feat/issue-7-support-a-b-testing-of-the-new"
+        );
+    }
+
+    #[test]
+    fn build_issue_prompt_fills_placeholders_once() {
+        let comments = vec![
+            GitHubPullRequestComment {
+                id: 1,
+                body: "Also happens on Linux.".to_string(),
+                created_at: "2026-01-02T00:00:00Z".to_string(),
+                url: String::new(),
+                author: Some(GitHubPullRequestAuthor {
+                    login: "octo".to_string(),
+                }),
+            },
+            GitHubPullRequestComment {
+                id: 2,
+                body: "   ".to_string(),
+                created_at: "2026-01-03T00:00:00Z".to_string(),
+                url: String::new(),
+                author: None,
+            },
+        ];
+        let prompt = build_issue_prompt(
+            "#{number} {title} [{labels}] on {branch}\n{body}\n{comments}\n{unknown}",
+            &issue(),
+            &comments,
+            "feat/issue-42-crash",
+        );
+        assert_eq!(
+            prompt,
+            "#42 Crash when config.toml is empty [bug, config] on feat/issue-42-crash\n\
+Steps: open an empty {comments} file.\n\
+@octo (2026-01-02T00:00:00Z):\nAlso happens on Linux.\n{unknown}"
+        );
+    }
+
+    #[test]
+    fn default_issue_prompt_uses_fallbacks_for_empty_issue() {
+        let mut issue = issue();
+        issue.body = String::new();
+        issue.labels.clear();
+        let prompt = build_issue_prompt(&default_issue_agent_prompt(), &issue, &[], "feat/x");
+        assert!(prompt.contains("Resolve issue #42: Crash when config.toml is empty"));
+        assert!(prompt.contains("Labels: none"));
+        assert!(prompt.contains("(no description)"));
+        assert!(prompt.contains("(no comments)"));
+        assert!(!prompt.contains('{'));
+    }
+}
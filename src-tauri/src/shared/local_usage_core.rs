@@ -9,7 +9,8 @@ use tokio::sync::Mutex;
 
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::types::{
-    LocalUsageDay, LocalUsageModel, LocalUsageSnapshot, LocalUsageTotals, WorkspaceEntry,
+    AppSettings, LocalUsageDay, LocalUsageModel, LocalUsageSnapshot, LocalUsageTotals,
+    WorkspaceEntry,
 };
 
 #[derive(Default, Clone, Copy)]
@@ -32,6 +33,7 @@ const MAX_ACTIVITY_GAP_MS: i64 = 2 * 60 * 1000;
 
 pub(crate) async fn local_usage_snapshot_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     days: Option<u32>,
     workspace_path: Option<String>,
 ) -> Result<LocalUsageSnapshot, String> {
@@ -44,9 +46,10 @@ pub(crate) async fn local_usage_snapshot_core(
             Some(PathBuf::from(trimmed))
         }
     });
+    let settings = app_settings.lock().await.clone();
     let sessions_roots = {
         let workspaces = workspaces.lock().await;
-        resolve_sessions_roots(&workspaces, workspace_path.as_deref())
+        resolve_sessions_roots(&workspaces, &settings, workspace_path.as_deref())
     };
     let snapshot = tokio::task::spawn_blocking(move || {
         scan_local_usage(days, workspace_path.as_deref(), &sessions_roots)
@@ -527,11 +530,12 @@ fn resolve_codex_sessions_root(codex_home_override: Option<PathBuf>) -> Option<P
 
 fn resolve_sessions_roots(
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     workspace_path: Option<&Path>,
 ) -> Vec<PathBuf> {
     if let Some(workspace_path) = workspace_path {
         let codex_home_override =
-            resolve_workspace_codex_home_for_path(workspaces, Some(workspace_path));
+            resolve_workspace_codex_home_for_path(workspaces, settings, Some(workspace_path));
         return resolve_codex_sessions_root(codex_home_override)
             .into_iter()
             .collect();
@@ -551,7 +555,8 @@ fn resolve_sessions_roots(
             .parent_id
             .as_ref()
             .and_then(|parent_id| workspaces.get(parent_id));
-        let Some(codex_home) = resolve_workspace_codex_home(entry, parent_entry) else {
+        let Some(codex_home) = resolve_workspace_codex_home(entry, parent_entry, Some(settings))
+        else {
             continue;
         };
         if let Some(root) = resolve_codex_sessions_root(Some(codex_home)) {
@@ -566,6 +571,7 @@ fn resolve_sessions_roots(
 
 fn resolve_workspace_codex_home_for_path(
     workspaces: &HashMap<String, crate::types::WorkspaceEntry>,
+    settings: &AppSettings,
     workspace_path: Option<&Path>,
 ) -> Option<PathBuf> {
     let workspace_path = workspace_path?;
@@ -582,7 +588,7 @@ fn resolve_workspace_codex_home_for_path(
         .as_ref()
         .and_then(|parent_id| workspaces.get(parent_id));
 
-    resolve_workspace_codex_home(entry, parent_entry)
+    resolve_workspace_codex_home(entry, parent_entry, Some(settings))
 }
 
 fn day_dir_for_key(root: &Path, day_key: &str) -> PathBuf {
@@ -831,10 +837,41 @@ mod tests {
         workspaces.insert(entry_a.id.clone(), entry_a.clone());
         workspaces.insert(entry_b.id.clone(), entry_b.clone());
 
-        let roots = resolve_sessions_roots(&workspaces, None);
+        let roots = resolve_sessions_roots(&workspaces, &AppSettings::default(), None);
         let expected = resolve_codex_sessions_root(None)
             .map(|root| vec![root])
             .unwrap_or_default();
         assert_eq!(roots, expected);
     }
+
+    #[test]
+    fn resolve_sessions_roots_includes_codex_home_profiles() {
+        let mut settings = AppSettings::default();
+        settings.codex_home_profiles = vec![crate::types::CodexHomeProfile {
+            id: "client".to_string(),
+            name: "Client".to_string(),
+            path: "/tmp/codex-client".to_string(),
+        }];
+        let mut entry = WorkspaceEntry {
+            id: "a".to_string(),
+            name: "A".to_string(),
+            path: "/tmp/project-a".to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        entry.settings.codex_home_profile_id = Some("client".to_string());
+        let workspaces = HashMap::from([(entry.id.clone(), entry)]);
+
+        let roots = resolve_sessions_roots(
+            &workspaces,
+            &settings,
+            Some(Path::new("/tmp/project-a/src")),
+        );
+        assert_eq!(roots, vec![PathBuf::from("/tmp/codex-client/sessions")]);
+
+        let roots = resolve_sessions_roots(&workspaces, &settings, None);
+        assert!(roots.contains(&PathBuf::from("/tmp/codex-client/sessions")));
+    }
 }
@@ -1,10 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::path::PathBuf;
 use toml_edit::{Array, Document, Item, Table, Value as TomlValue};
 
-use crate::codex::home as codex_home;
-use crate::shared::config_toml_core;
+use crate::shared::config_toml_core::{self, ConfigTarget};
 
 const MCP_SERVERS_KEY: &str = "mcp_servers";
 
@@ -42,6 +40,8 @@ pub(crate) struct CreateMcpServerInput {
     #[serde(default)]
     pub headers: BTreeMap<String, String>,
     pub additional_toml: Option<String>,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -59,23 +59,25 @@ pub(crate) struct UpdateMcpServerInput {
     #[serde(default)]
     pub headers: BTreeMap<String, String>,
     pub additional_toml: Option<String>,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct DeleteMcpServerInput {
     pub name: String,
+    pub workspace_id: Option<String>,
 }
 
-pub(crate) fn get_mcp_settings_core() -> Result<McpSettingsDto, String> {
-    let codex_home = resolve_codex_home()?;
-    let config_path = codex_home.join("config.toml");
+pub(crate) fn get_mcp_settings_core(target: &ConfigTarget) -> Result<McpSettingsDto, String> {
+    let config_path = target.config_path();
     let config_path_string = config_path
         .to_str()
-        .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())?
+        .ok_or_else(|| "Unable to resolve config.toml path".to_string())?
         .to_string();
 
-    let (_, document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let (_, document) = config_toml_core::load_config_document(target)?;
     let mut servers = collect_servers(&document);
     servers.sort_by(|left, right| left.name.cmp(&right.name));
 
@@ -85,7 +87,10 @@ pub(crate) fn get_mcp_settings_core() -> Result<McpSettingsDto, String> {
     })
 }
 
-pub(crate) fn create_mcp_server_core(input: CreateMcpServerInput) -> Result<McpSettingsDto, String> {
+pub(crate) fn create_mcp_server_core(
+    target: &ConfigTarget,
+    input: CreateMcpServerInput,
+) -> Result<McpSettingsDto, String> {
     let name = normalize_server_name(&input.name)?;
     validate_connection_shape(
         input.command.as_deref(),
@@ -94,8 +99,7 @@ pub(crate) fn create_mcp_server_core(input: CreateMcpServerInput) -> Result<McpS
         input.env.clone(),
     )?;
 
-    let codex_home = resolve_codex_home()?;
-    let (_, mut document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
     let mcp_servers = config_toml_core::ensure_table(&mut document, MCP_SERVERS_KEY)?;
     if mcp_servers.get(&name).is_some() {
         return Err(format!("MCP server '{name}' already exists"));
@@ -113,11 +117,14 @@ pub(crate) fn create_mcp_server_core(input: CreateMcpServerInput) -> Result<McpS
     })?;
     mcp_servers[&name] = Item::Table(server_table);
 
-    config_toml_core::persist_global_config_document(&codex_home, &document)?;
-    get_mcp_settings_core()
+    config_toml_core::persist_config_document(target, &document)?;
+    get_mcp_settings_core(target)
 }
 
-pub(crate) fn update_mcp_server_core(input: UpdateMcpServerInput) -> Result<McpSettingsDto, String> {
+pub(crate) fn update_mcp_server_core(
+    target: &ConfigTarget,
+    input: UpdateMcpServerInput,
+) -> Result<McpSettingsDto, String> {
     let original_name = normalize_server_name(&input.original_name)?;
     let name = normalize_server_name(&input.name)?;
     validate_connection_shape(
@@ -127,8 +134,7 @@ pub(crate) fn update_mcp_server_core(input: UpdateMcpServerInput) -> Result<McpS
         input.env.clone(),
     )?;
 
-    let codex_home = resolve_codex_home()?;
-    let (_, mut document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
     let mcp_servers = config_toml_core::ensure_table(&mut document, MCP_SERVERS_KEY)?;
 
     if name != original_name && mcp_servers.get(&name).is_some() {
@@ -155,27 +161,25 @@ pub(crate) fn update_mcp_server_core(input: UpdateMcpServerInput) -> Result<McpS
     })?;
     mcp_servers[&name] = Item::Table(server_table);
 
-    config_toml_core::persist_global_config_document(&codex_home, &document)?;
-    get_mcp_settings_core()
+    config_toml_core::persist_config_document(target, &document)?;
+    get_mcp_settings_core(target)
 }
 
-pub(crate) fn delete_mcp_server_core(input: DeleteMcpServerInput) -> Result<McpSettingsDto, String> {
+pub(crate) fn delete_mcp_server_core(
+    target: &ConfigTarget,
+    input: DeleteMcpServerInput,
+) -> Result<McpSettingsDto, String> {
     let name = normalize_server_name(&input.name)?;
 
-    let codex_home = resolve_codex_home()?;
-    let (_, mut document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
     let mcp_servers = config_toml_core::ensure_table(&mut document, MCP_SERVERS_KEY)?;
 
     if mcp_servers.remove(&name).is_none() {
         return Err(format!("MCP server '{name}' not found"));
     }
 
-    config_toml_core::persist_global_config_document(&codex_home, &document)?;
-    get_mcp_settings_core()
-}
-
-fn resolve_codex_home() -> Result<PathBuf, String> {
-    codex_home::resolve_default_codex_home().ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
+    config_toml_core::persist_config_document(target, &document)?;
+    get_mcp_settings_core(target)
 }
 
 fn normalize_server_name(raw: &str) -> Result<String, String> {
@@ -268,7 +272,7 @@ fn read_string_array(item: Option<&Item>) -> Vec<String> {
         .collect()
 }
 
-fn read_string_map(item: Option<&Item>) -> BTreeMap<String, String> {
+pub(crate) fn read_string_map(item: Option<&Item>) -> BTreeMap<String, String> {
     let Some(table_like) = item.and_then(Item::as_table_like) else {
         return BTreeMap::new();
     };
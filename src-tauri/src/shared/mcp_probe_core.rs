@@ -0,0 +1,930 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::backend::app_server::build_codex_command_with_bin;
+use crate::shared::config_toml_core;
+use crate::shared::mcp_config_core::{read_string_map, McpServerDto};
+use crate::shared::process_core::kill_child_process_tree;
+
+const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_STDERR_BYTES: usize = 16 * 1024;
+const MAX_LIST_PAGES: usize = 20;
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpProbeTool {
+    pub name: String,
+    pub description: Option<String>,
+    pub input_schema: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpProbeResource {
+    pub uri: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpProbePrompt {
+    pub name: String,
+    pub description: Option<String>,
+    pub arguments: Vec<Value>,
+}
+
+/// Milliseconds spent on each step; a step the server does not advertise (or
+/// never reached) stays `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpProbeLatency {
+    pub initialize_ms: Option<u64>,
+    pub tools_ms: Option<u64>,
+    pub resources_ms: Option<u64>,
+    pub prompts_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpProbeResult {
+    pub ok: bool,
+    pub transport: String,
+    pub protocol_version: Option<String>,
+    pub server_info: Option<Value>,
+    pub tools: Vec<McpProbeTool>,
+    pub resources: Vec<McpProbeResource>,
+    pub prompts: Vec<McpProbePrompt>,
+    pub latency: McpProbeLatency,
+    pub error: Option<String>,
+    /// Tail of the server's stderr, only kept when the probe failed.
+    pub stderr: Option<String>,
+}
+
+/// Starts (or connects to) the server described by `server`, runs the MCP
+/// initialize handshake and lists its tools, resources and prompts. Failures
+/// after validation are reported in the result rather than as an error.
+pub(crate) async fn test_mcp_server_core(server: McpServerDto) -> Result<McpProbeResult, String> {
+    probe_mcp_server(server, PROBE_TIMEOUT, |name| std::env::var(name).ok()).await
+}
+
+/// Server options Codex reads from the extra TOML keys of a server table.
+#[derive(Debug, Default, PartialEq)]
+struct McpLaunchOptions {
+    cwd: Option<String>,
+    bearer_token_env_var: Option<String>,
+    http_headers: BTreeMap<String, String>,
+    env_http_headers: BTreeMap<String, String>,
+}
+
+impl McpLaunchOptions {
+    fn parse(additional_toml: &str) -> Result<Self, String> {
+        let document = config_toml_core::parse_document(additional_toml)
+            .map_err(|err| format!("Failed to parse additional TOML: {err}"))?;
+        let string = |key: &str| {
+            document
+                .get(key)
+                .and_then(|item| item.as_str())
+                .and_then(|value| non_empty(Some(value)))
+        };
+        Ok(Self {
+            cwd: string("cwd"),
+            bearer_token_env_var: string("bearer_token_env_var"),
+            http_headers: read_string_map(document.get("http_headers")),
+            env_http_headers: read_string_map(document.get("env_http_headers")),
+        })
+    }
+}
+
+async fn probe_mcp_server<F>(
+    server: McpServerDto,
+    timeout: Duration,
+    env: F,
+) -> Result<McpProbeResult, String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let command = non_empty(server.command.as_deref());
+    let url = non_empty(server.url.as_deref());
+    let transport_kind = match (&command, &url) {
+        (Some(_), _) => "stdio",
+        (None, Some(_)) => "http",
+        (None, None) => return Err("Set either command or url for the MCP server".to_string()),
+    };
+    let options = McpLaunchOptions::parse(&server.additional_toml)?;
+
+    let started = Instant::now();
+    let mut result = McpProbeResult {
+        ok: false,
+        transport: transport_kind.to_string(),
+        protocol_version: None,
+        server_info: None,
+        tools: Vec::new(),
+        resources: Vec::new(),
+        prompts: Vec::new(),
+        latency: McpProbeLatency::default(),
+        error: None,
+        stderr: None,
+    };
+
+    let transport = match (command, url) {
+        (Some(command), _) => {
+            StdioTransport::spawn(&command, &server, &options).map(McpTransport::Stdio)
+        }
+        (None, Some(url)) => {
+            HttpTransport::new(&url, &server, &options, env).map(McpTransport::Http)
+        }
+        (None, None) => unreachable!("validated above"),
+    };
+    let mut client = match transport {
+        Ok(transport) => McpClient {
+            transport,
+            next_id: 1,
+        },
+        Err(err) => {
+            result.error = Some(err);
+            result.latency.total_ms = elapsed_ms(started);
+            return Ok(result);
+        }
+    };
+
+    let error = match tokio::time::timeout(timeout, run_probe(&mut client, &mut result)).await {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err),
+        Err(_) => Some(format!(
+            "MCP server did not respond within {}s",
+            timeout.as_secs()
+        )),
+    };
+    let stderr = client.transport.shutdown().await;
+
+    result.latency.total_ms = elapsed_ms(started);
+    result.ok = error.is_none();
+    if error.is_some() {
+        result.stderr = stderr.filter(|value| !value.trim().is_empty());
+    }
+    result.error = error;
+    Ok(result)
+}
+
+async fn run_probe(client: &mut McpClient, result: &mut McpProbeResult) -> Result<(), String> {
+    let started = Instant::now();
+    let initialize = client
+        .request(
+            "initialize",
+            json!({
+                "protocolVersion": MCP_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "codex-monitor",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        )
+        .await?;
+    result.latency.initialize_ms = Some(elapsed_ms(started));
+    result.protocol_version = initialize
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    result.server_info = initialize.get("serverInfo").cloned();
+    if let McpTransport::Http(transport) = &mut client.transport {
+        transport.protocol_version = result.protocol_version.clone();
+    }
+    client.notify("notifications/initialized").await?;
+
+    let capabilities = initialize
+        .get("capabilities")
+        .cloned()
+        .unwrap_or(Value::Null);
+    if capabilities.get("tools").is_some() {
+        let started = Instant::now();
+        let items = client.list_all("tools/list", "tools").await?;
+        result.tools = items.iter().filter_map(parse_tool).collect();
+        result.latency.tools_ms = Some(elapsed_ms(started));
+    }
+    if capabilities.get("resources").is_some() {
+        let started = Instant::now();
+        let items = client.list_all("resources/list", "resources").await?;
+        result.resources = items.iter().filter_map(parse_resource).collect();
+        result.latency.resources_ms = Some(elapsed_ms(started));
+    }
+    if capabilities.get("prompts").is_some() {
+        let started = Instant::now();
+        let items = client.list_all("prompts/list", "prompts").await?;
+        result.prompts = items.iter().filter_map(parse_prompt).collect();
+        result.latency.prompts_ms = Some(elapsed_ms(started));
+    }
+    Ok(())
+}
+
+struct McpClient {
+    transport: McpTransport,
+    next_id: u64,
+}
+
+impl McpClient {
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let response = match &mut self.transport {
+            McpTransport::Stdio(transport) => transport.request(id, &message).await?,
+            McpTransport::Http(transport) => transport.request(id, &message).await?,
+        };
+        if let Some(error) = response.get("error") {
+            let detail = error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string());
+            return Err(format!("{method} failed: {detail}"));
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    async fn notify(&mut self, method: &str) -> Result<(), String> {
+        let message = json!({ "jsonrpc": "2.0", "method": method });
+        match &mut self.transport {
+            McpTransport::Stdio(transport) => transport.write_message(&message).await,
+            McpTransport::Http(transport) => transport.post(&message).await.map(|_| ()),
+        }
+    }
+
+    /// Follows `nextCursor` until the server stops paginating.
+    async fn list_all(&mut self, method: &str, key: &str) -> Result<Vec<Value>, String> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let page = self.request(method, params).await?;
+            if let Some(entries) = page.get(key).and_then(Value::as_array) {
+                items.extend(entries.iter().cloned());
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+enum McpTransport {
+    Stdio(StdioTransport),
+    Http(HttpTransport),
+}
+
+impl McpTransport {
+    /// Stops the server (or ends the HTTP session) and returns captured stderr.
+    async fn shutdown(self) -> Option<String> {
+        match self {
+            McpTransport::Stdio(transport) => Some(transport.shutdown().await),
+            McpTransport::Http(transport) => {
+                transport.close().await;
+                None
+            }
+        }
+    }
+}
+
+struct StdioTransport {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+    stderr_task: JoinHandle<()>,
+}
+
+impl StdioTransport {
+    fn spawn(
+        program: &str,
+        server: &McpServerDto,
+        options: &McpLaunchOptions,
+    ) -> Result<Self, String> {
+        let mut command =
+            build_codex_command_with_bin(Some(program.to_string()), None, server.args.clone())?;
+        command.envs(&server.env);
+        if let Some(cwd) = &options.cwd {
+            command.current_dir(cwd);
+        }
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+        command.kill_on_drop(true);
+
+        let mut child = command
+            .spawn()
+            .map_err(|err| format!("Failed to start MCP server `{program}`: {err}"))?;
+        let stdin = child.stdin.take().ok_or("missing stdin")?;
+        let stdout = child.stdout.take().ok_or("missing stdout")?;
+        let mut stderr_pipe = child.stderr.take().ok_or("missing stderr")?;
+
+        let stderr = Arc::new(Mutex::new(Vec::new()));
+        let captured = Arc::clone(&stderr);
+        let stderr_task = tokio::spawn(async move {
+            let mut buffer = [0u8; 4096];
+            loop {
+                match stderr_pipe.read(&mut buffer).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => {
+                        let mut captured = captured.lock().await;
+                        captured.extend_from_slice(&buffer[..read]);
+                        if captured.len() > MAX_STDERR_BYTES {
+                            let excess = captured.len() - MAX_STDERR_BYTES;
+                            captured.drain(..excess);
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            stderr,
+            stderr_task,
+        })
+    }
+
+    async fn write_message(&mut self, message: &Value) -> Result<(), String> {
+        let mut line = serde_json::to_string(message).map_err(|err| err.to_string())?;
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|err| format!("Failed to write to MCP server: {err}"))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|err| format!("Failed to write to MCP server: {err}"))
+    }
+
+    async fn request(&mut self, id: u64, message: &Value) -> Result<Value, String> {
+        let method = message["method"].as_str().unwrap_or("request");
+        if let Err(err) = self.write_message(message).await {
+            return Err(self.exited_error(method).await.unwrap_or(err));
+        }
+        loop {
+            let line = self
+                .stdout
+                .next_line()
+                .await
+                .map_err(|err| format!("Failed to read from MCP server: {err}"))?;
+            let Some(line) = line else {
+                return Err(self.exited_error(method).await.unwrap_or_else(|| {
+                    format!("MCP server closed stdout before answering {method}")
+                }));
+            };
+            let Ok(incoming) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if is_response_to(&incoming, id) {
+                return Ok(incoming);
+            }
+            if let Some(reply) = reply_to_server_request(&incoming) {
+                self.write_message(&reply).await?;
+            }
+        }
+    }
+
+    /// Describes an early exit; a server that died closes its pipes, so broken
+    /// writes and EOF on stdout usually mean it is already gone.
+    async fn exited_error(&mut self, method: &str) -> Option<String> {
+        let status = tokio::time::timeout(Duration::from_secs(1), self.child.wait())
+            .await
+            .ok()?
+            .ok()?;
+        Some(format!(
+            "MCP server exited before answering {method} ({status})"
+        ))
+    }
+
+    async fn shutdown(mut self) -> String {
+        drop(self.stdin);
+        kill_child_process_tree(&mut self.child).await;
+        let _ = tokio::time::timeout(Duration::from_millis(500), &mut self.stderr_task).await;
+        let captured = self.stderr.lock().await;
+        String::from_utf8_lossy(&captured).trim().to_string()
+    }
+}
+
+struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+}
+
+impl HttpTransport {
+    fn new<F>(
+        url: &str,
+        server: &McpServerDto,
+        options: &McpLaunchOptions,
+        env: F,
+    ) -> Result<Self, String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut pairs: Vec<(String, String)> = server
+            .headers
+            .iter()
+            .chain(&options.http_headers)
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        // Like Codex, headers whose variable is unset are skipped.
+        for (name, var) in &options.env_http_headers {
+            if let Some(value) = env(var).filter(|value| !value.trim().is_empty()) {
+                pairs.push((name.clone(), value));
+            }
+        }
+        if let Some(var) = &options.bearer_token_env_var {
+            let token = env(var)
+                .filter(|value| !value.trim().is_empty())
+                .ok_or_else(|| format!("Environment variable {var} is not set"))?;
+            pairs.push(("Authorization".to_string(), format!("Bearer {token}")));
+        }
+        let mut headers = HeaderMap::new();
+        for (name, value) in &pairs {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid header name `{name}`"))?;
+            let mut value = HeaderValue::from_str(value)
+                .map_err(|_| format!("Header `{name}` contains invalid characters"))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .default_headers(headers)
+            .build()
+            .map_err(|err| format!("Failed to configure MCP client: {err}"))?;
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            session_id: None,
+            protocol_version: None,
+        })
+    }
+
+    /// Posts one JSON-RPC message and returns the raw body and content type.
+    async fn post(&mut self, message: &Value) -> Result<(String, String), String> {
+        let body = serde_json::to_string(message).map_err(|err| err.to_string())?;
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(
+                reqwest::header::ACCEPT,
+                "application/json, text/event-stream",
+            )
+            .body(body);
+        if let Some(session_id) = &self.session_id {
+            request = request.header(SESSION_ID_HEADER, session_id);
+        }
+        if let Some(version) = &self.protocol_version {
+            request = request.header(PROTOCOL_VERSION_HEADER, version);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|err| format!("Failed to reach MCP server: {err}"))?;
+
+        let status = response.status();
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("Failed to read MCP server response: {err}"))?;
+        if !status.is_success() {
+            let detail = body.trim();
+            return Err(if detail.is_empty() {
+                format!("MCP server returned HTTP {}", status.as_u16())
+            } else {
+                format!("MCP server returned HTTP {}: {detail}", status.as_u16())
+            });
+        }
+        Ok((body, content_type))
+    }
+
+    async fn request(&mut self, id: u64, message: &Value) -> Result<Value, String> {
+        let (body, content_type) = self.post(message).await?;
+        let messages = if content_type.starts_with("text/event-stream") {
+            parse_sse_messages(&body)
+        } else {
+            match serde_json::from_str::<Value>(&body) {
+                Ok(Value::Array(batch)) => batch,
+                Ok(value) => vec![value],
+                Err(err) => return Err(format!("Invalid MCP server response: {err}")),
+            }
+        };
+        messages
+            .into_iter()
+            .find(|incoming| is_response_to(incoming, id))
+            .ok_or_else(|| {
+                format!(
+                    "MCP server sent no response to {}",
+                    message["method"].as_str().unwrap_or("request")
+                )
+            })
+    }
+
+    async fn close(self) {
+        let Some(session_id) = self.session_id else {
+            return;
+        };
+        let _ = self
+            .client
+            .delete(&self.url)
+            .header(SESSION_ID_HEADER, session_id)
+            .timeout(Duration::from_secs(2))
+            .send()
+            .await;
+    }
+}
+
+/// JSON payloads of the `data:` fields in a server-sent event stream.
+fn parse_sse_messages(body: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut data = String::new();
+    for line in body.lines().chain(std::iter::once("")) {
+        if line.is_empty() {
+            if !data.is_empty() {
+                if let Ok(value) = serde_json::from_str(&data) {
+                    messages.push(value);
+                }
+                data.clear();
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+    messages
+}
+
+fn is_response_to(message: &Value, id: u64) -> bool {
+    message.get("method").is_none() && message.get("id").and_then(Value::as_u64) == Some(id)
+}
+
+/// Answers pings and declines any other server-initiated request so a
+/// well-behaved server does not stall waiting for the client.
+fn reply_to_server_request(message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message.get("method")?.as_str()?;
+    Some(if method == "ping" {
+        json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+    } else {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": format!("Method not supported: {method}") },
+        })
+    })
+}
+
+fn parse_tool(value: &Value) -> Option<McpProbeTool> {
+    Some(McpProbeTool {
+        name: value.get("name")?.as_str()?.to_string(),
+        description: string_field(value, "description"),
+        input_schema: value.get("inputSchema").cloned().unwrap_or(Value::Null),
+    })
+}
+
+fn parse_resource(value: &Value) -> Option<McpProbeResource> {
+    Some(McpProbeResource {
+        uri: value.get("uri")?.as_str()?.to_string(),
+        name: string_field(value, "name"),
+        description: string_field(value, "description"),
+        mime_type: string_field(value, "mimeType"),
+    })
+}
+
+fn parse_prompt(value: &Value) -> Option<McpProbePrompt> {
+    Some(McpProbePrompt {
+        name: value.get("name")?.as_str()?.to_string(),
+        description: string_field(value, "description"),
+        arguments: value
+            .get("arguments")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default(),
+    })
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::http_stub::{spawn_http_stub, StubResponse};
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    fn server(command: Option<&str>, args: Vec<String>, url: Option<&str>) -> McpServerDto {
+        McpServerDto {
+            name: "fake".to_string(),
+            enabled: Some(true),
+            command: command.map(str::to_string),
+            args,
+            env: BTreeMap::new(),
+            url: url.map(str::to_string),
+            headers: BTreeMap::new(),
+            additional_toml: String::new(),
+        }
+    }
+
+    fn write_script(body: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-mcp-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("server.sh");
+        std::fs::write(&path, body).expect("write fake server");
+        path
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "STUB_MCP_TOKEN" => Some("token-123".to_string()),
+            _ => None,
+        }
+    }
+
+    fn probe(server: McpServerDto) -> McpProbeResult {
+        tokio::runtime::Runtime::new()
+            .expect("runtime")
+            .block_on(probe_mcp_server(server, Duration::from_secs(10), env))
+            .expect("probe")
+    }
+
+    /// Request ids are sequential: initialize=1, tools=2, resources=3, prompts=4.
+    const FAKE_STDIO_SERVER: &str = r#"
+echo "fake server starting" >&2
+while IFS= read -r line; do
+  case "$line" in
+    *'"method":"initialize"'*)
+      printf '%s\n' '{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info"}}'
+      printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"fake","version":"1.0"},"capabilities":{"tools":{},"resources":{},"prompts":{}}}}' ;;
+    *'"method":"tools/list"'*)
+      printf '%s\n' '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo text","inputSchema":{"type":"object","properties":{"text":{"type":"string"}}}}]}}' ;;
+    *'"method":"resources/list"'*)
+      printf '%s\n' '{"jsonrpc":"2.0","id":3,"result":{"resources":[{"uri":"file:///readme","name":"readme","mimeType":"text/markdown"}]}}' ;;
+    *'"method":"prompts/list"'*)
+      printf '%s\n' '{"jsonrpc":"2.0","id":4,"result":{"prompts":[{"name":"review","arguments":[{"name":"path","required":true}]}]}}' ;;
+  esac
+done
+"#;
+
+    #[cfg(unix)]
+    #[test]
+    fn stdio_probe_lists_tools_resources_and_prompts() {
+        let script = write_script(FAKE_STDIO_SERVER);
+        let result = probe(server(
+            Some("sh"),
+            vec![script.to_string_lossy().to_string()],
+            None,
+        ));
+
+        assert!(result.ok, "probe failed: {:?}", result.error);
+        assert_eq!(result.transport, "stdio");
+        assert_eq!(result.protocol_version.as_deref(), Some("2025-06-18"));
+        assert_eq!(
+            result.server_info,
+            Some(json!({"name": "fake", "version": "1.0"}))
+        );
+        assert_eq!(result.tools.len(), 1);
+        assert_eq!(result.tools[0].name, "echo");
+        assert_eq!(result.tools[0].input_schema["type"], "object");
+        assert_eq!(result.resources[0].uri, "file:///readme");
+        assert_eq!(
+            result.resources[0].mime_type.as_deref(),
+            Some("text/markdown")
+        );
+        assert_eq!(result.prompts[0].name, "review");
+        assert_eq!(result.prompts[0].arguments.len(), 1);
+        assert!(result.latency.initialize_ms.is_some());
+        assert!(result.latency.prompts_ms.is_some());
+        assert_eq!(result.stderr, None);
+        let _ = std::fs::remove_dir_all(script.parent().expect("script dir"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stdio_probe_applies_env_and_cwd() {
+        let script = write_script(&format!(
+            "if [ \"$FAKE_MCP_TOKEN\" != secret ] || [ ! -f marker ]; then\n  echo 'missing token or cwd' >&2\n  exit 2\nfi\n{FAKE_STDIO_SERVER}"
+        ));
+        let workdir = script.parent().expect("script dir").join("work");
+        std::fs::create_dir_all(&workdir).expect("create workdir");
+        std::fs::write(workdir.join("marker"), "").expect("write marker");
+        let mut config = server(Some("sh"), vec![script.to_string_lossy().to_string()], None);
+
+        let result = probe(config.clone());
+        assert!(!result.ok);
+        assert_eq!(result.stderr.as_deref(), Some("missing token or cwd"));
+
+        config
+            .env
+            .insert("FAKE_MCP_TOKEN".to_string(), "secret".to_string());
+        config.additional_toml = format!("cwd = {:?}", workdir.to_string_lossy());
+        let result = probe(config);
+        assert!(result.ok, "probe failed: {:?}", result.error);
+        assert_eq!(result.tools[0].name, "echo");
+        let _ = std::fs::remove_dir_all(script.parent().expect("script dir"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stdio_probe_returns_stderr_when_the_server_exits() {
+        let script = write_script("echo 'error: GITHUB_TOKEN is not set' >&2\nexit 3\n");
+        let result = probe(server(
+            Some("sh"),
+            vec![script.to_string_lossy().to_string()],
+            None,
+        ));
+
+        assert!(!result.ok);
+        let error = result.error.expect("error");
+        assert!(
+            error.contains("exited before answering initialize"),
+            "{error}"
+        );
+        assert_eq!(
+            result.stderr.as_deref(),
+            Some("error: GITHUB_TOKEN is not set")
+        );
+        assert_eq!(result.latency.initialize_ms, None);
+        let _ = std::fs::remove_dir_all(script.parent().expect("script dir"));
+    }
+
+    #[test]
+    fn missing_command_fails_without_spawning() {
+        let result = probe(server(
+            Some("codex-monitor-missing-mcp-server"),
+            Vec::new(),
+            None,
+        ));
+        assert!(!result.ok);
+        assert!(result
+            .error
+            .expect("error")
+            .contains("Failed to start MCP server"));
+
+        let err = tokio::runtime::Runtime::new()
+            .expect("runtime")
+            .block_on(probe_mcp_server(
+                server(None, Vec::new(), Some(" ")),
+                Duration::from_secs(1),
+                env,
+            ))
+            .expect_err("empty server should be rejected");
+        assert_eq!(err, "Set either command or url for the MCP server");
+    }
+
+    /// Streamable HTTP stub: replies by JSON-RPC method, issues a session id on
+    /// initialize and answers `tools/list` as an event stream.
+    fn spawn_stub_mcp_http() -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        let (base, requests) = spawn_http_stub(|request| {
+            let message: Value = serde_json::from_str(&request.body).unwrap_or(Value::Null);
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            match message.get("method").and_then(Value::as_str) {
+                Some("initialize") => StubResponse::json(
+                    "200 OK",
+                    json!({"jsonrpc":"2.0","id":id,"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"stub"},"capabilities":{"tools":{}}}}).to_string(),
+                )
+                .with_header("Mcp-Session-Id", "session-42"),
+                Some("tools/list") => StubResponse::new(
+                    "200 OK",
+                    "text/event-stream",
+                    format!(
+                        "event: message\ndata: {}\n\n",
+                        json!({"jsonrpc":"2.0","id":id,"result":{"tools":[{"name":"search","inputSchema":{"type":"object"}}]}})
+                    ),
+                ),
+                Some(_) => StubResponse::json("202 Accepted", ""),
+                None => StubResponse::json("200 OK", ""),
+            }
+        });
+        (format!("{base}/mcp"), requests)
+    }
+
+    #[test]
+    fn http_probe_handles_sessions_and_event_streams() {
+        let (url, requests) = spawn_stub_mcp_http();
+        let mut config = server(None, Vec::new(), Some(&url));
+        config
+            .headers
+            .insert("Authorization".to_string(), "Bearer secret".to_string());
+
+        let result = probe(config);
+
+        assert!(result.ok, "probe failed: {:?}", result.error);
+        assert_eq!(result.transport, "http");
+        assert_eq!(result.tools.len(), 1);
+        assert_eq!(result.tools[0].name, "search");
+        assert!(result.resources.is_empty());
+        assert_eq!(result.latency.resources_ms, None);
+
+        let requests = requests.lock().expect("requests");
+        assert!(requests[0].contains("\"method\":\"initialize\""));
+        assert!(requests[0]
+            .to_ascii_lowercase()
+            .contains("authorization: bearer secret"));
+        assert!(requests[1].contains("notifications/initialized"));
+        assert!(requests[2]
+            .to_ascii_lowercase()
+            .contains("mcp-session-id: session-42"));
+        assert!(requests[2]
+            .to_ascii_lowercase()
+            .contains("mcp-protocol-version: 2025-06-18"));
+    }
+
+    #[test]
+    fn http_probe_sends_bearer_token_and_extra_headers() {
+        let (url, requests) = spawn_stub_mcp_http();
+        let mut config = server(None, Vec::new(), Some(&url));
+        config.additional_toml = [
+            "bearer_token_env_var = \"STUB_MCP_TOKEN\"",
+            "http_headers = { \"X-Team\" = \"core\" }",
+            "env_http_headers = { \"X-Unset\" = \"UNSET_VAR\" }",
+        ]
+        .join("\n");
+
+        let result = probe(config.clone());
+
+        assert!(result.ok, "probe failed: {:?}", result.error);
+        let head = requests.lock().expect("requests")[0].to_ascii_lowercase();
+        assert!(head.contains("authorization: bearer token-123"));
+        assert!(head.contains("x-team: core"));
+        assert!(!head.contains("x-unset"));
+
+        config.additional_toml = "bearer_token_env_var = \"UNSET_VAR\"".to_string();
+        let result = probe(config);
+        assert!(!result.ok);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Environment variable UNSET_VAR is not set")
+        );
+    }
+
+    #[test]
+    fn sse_parser_joins_multi_line_data() {
+        let body =
+            "event: message\ndata: {\"id\":1,\ndata: \"result\":{}}\n\n: comment\ndata: not json\n";
+        assert_eq!(
+            parse_sse_messages(body),
+            vec![json!({"id": 1, "result": {}})]
+        );
+    }
+}
@@ -1,19 +1,36 @@
 pub(crate) mod account;
+pub(crate) mod agent_templates_core;
 pub(crate) mod agents_config_core;
 pub(crate) mod codex_aux_core;
 pub(crate) mod codex_core;
+pub(crate) mod codex_home_core;
 pub(crate) mod codex_update_core;
+pub(crate) mod config_history_core;
+pub(crate) mod config_profiles_core;
 pub(crate) mod config_toml_core;
+pub(crate) mod config_validation_core;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
 pub(crate) mod git_rpc;
 pub(crate) mod git_ui_core;
+#[cfg(test)]
+pub(crate) mod http_stub;
+pub(crate) mod issue_agent_core;
 pub(crate) mod local_usage_core;
 pub(crate) mod mcp_config_core;
+pub(crate) mod mcp_probe_core;
+pub(crate) mod model_provider_probe_core;
+pub(crate) mod model_providers_core;
 pub(crate) mod thread_usage_core;
 pub(crate) mod process_core;
+pub(crate) mod project_config_core;
+pub(crate) mod prompt_repo_core;
 pub(crate) mod prompts_core;
 pub(crate) mod settings_core;
+pub(crate) mod settings_sync_core;
+pub(crate) mod skills_core;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub(crate) mod workspace_rpc;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
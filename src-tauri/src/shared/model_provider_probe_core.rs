@@ -0,0 +1,280 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use crate::shared::model_providers_core::ModelProviderDto;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_ERROR_BODY_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelProviderProbeResult {
+    pub ok: bool,
+    /// Models endpoint that was requested, without query params.
+    pub url: String,
+    pub status: Option<u16>,
+    pub models: Vec<String>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Requests `<base_url>/models` with the provider's auth, headers and query
+/// params. Failures after validation are reported in the result rather than
+/// as an error.
+pub(crate) async fn test_model_provider_core(
+    provider: ModelProviderDto,
+) -> Result<ModelProviderProbeResult, String> {
+    probe_model_provider(provider, PROBE_TIMEOUT, |name| std::env::var(name).ok()).await
+}
+
+async fn probe_model_provider(
+    provider: ModelProviderDto,
+    timeout: Duration,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<ModelProviderProbeResult, String> {
+    let base_url = provider
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| "base_url is required for a model provider".to_string())?;
+    let url = format!("{}/models", base_url.trim_end_matches('/'));
+
+    let started = Instant::now();
+    let mut result = ModelProviderProbeResult {
+        ok: false,
+        url: url.clone(),
+        status: None,
+        models: Vec::new(),
+        latency_ms: 0,
+        error: None,
+    };
+
+    let outcome = match build_headers(&provider, &env) {
+        Ok(headers) => request_models(&url, &provider, headers, timeout, &mut result).await,
+        Err(err) => Err(err),
+    };
+    result.latency_ms = started.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+    result.ok = outcome.is_ok();
+    result.error = outcome.err();
+    Ok(result)
+}
+
+fn build_headers(
+    provider: &ModelProviderDto,
+    env: &impl Fn(&str) -> Option<String>,
+) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    if let Some(env_key) = provider
+        .env_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let api_key = env(env_key)
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| format!("Environment variable {env_key} is not set"))?;
+        headers.insert(
+            AUTHORIZATION,
+            sensitive_value("Authorization", &format!("Bearer {api_key}"))?,
+        );
+    }
+    for (name, value) in &provider.http_headers {
+        headers.insert(header_name(name)?, sensitive_value(name, value)?);
+    }
+    for (name, env_var) in &provider.env_http_headers {
+        // Codex skips headers whose variable is unset, so the probe does too.
+        if let Some(value) = env(env_var.trim()).filter(|value| !value.trim().is_empty()) {
+            headers.insert(header_name(name)?, sensitive_value(name, &value)?);
+        }
+    }
+    Ok(headers)
+}
+
+fn header_name(name: &str) -> Result<HeaderName, String> {
+    HeaderName::from_bytes(name.trim().as_bytes())
+        .map_err(|_| format!("Invalid header name `{name}`"))
+}
+
+fn sensitive_value(name: &str, value: &str) -> Result<HeaderValue, String> {
+    let mut value = HeaderValue::from_str(value)
+        .map_err(|_| format!("Header `{name}` contains invalid characters"))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+async fn request_models(
+    url: &str,
+    provider: &ModelProviderDto,
+    headers: HeaderMap,
+    timeout: Duration,
+    result: &mut ModelProviderProbeResult,
+) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(timeout)
+        .default_headers(headers)
+        .build()
+        .map_err(|err| format!("Failed to configure provider client: {err}"))?;
+    let response = client
+        .get(url)
+        .query(&provider.query_params)
+        .send()
+        .await
+        .map_err(|err| {
+            if err.is_timeout() {
+                format!("Provider did not respond within {}s", timeout.as_secs())
+            } else {
+                format!("Failed to reach provider: {err}")
+            }
+        })?;
+    let status = response.status();
+    result.status = Some(status.as_u16());
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("Failed to read provider response: {err}"))?;
+    if !status.is_success() {
+        let detail: String = body.trim().chars().take(MAX_ERROR_BODY_CHARS).collect();
+        return Err(if detail.is_empty() {
+            format!("Provider returned HTTP {status}")
+        } else {
+            format!("Provider returned HTTP {status}: {detail}")
+        });
+    }
+    let payload: Value = serde_json::from_str(&body)
+        .map_err(|err| format!("Provider returned invalid JSON: {err}"))?;
+    result.models = parse_model_ids(&payload)
+        .ok_or_else(|| "Provider response has no `data` or `models` list".to_string())?;
+    Ok(())
+}
+
+/// Accepts the OpenAI shape (`data[].id`) and Ollama-style `models[].name`.
+fn parse_model_ids(payload: &Value) -> Option<Vec<String>> {
+    let entries = payload
+        .get("data")
+        .or_else(|| payload.get("models"))
+        .and_then(Value::as_array)?;
+    let mut ids: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .get("id")
+                .or_else(|| entry.get("name"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Some(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::http_stub::{spawn_http_stub, StubResponse};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    fn provider(base_url: &str) -> ModelProviderDto {
+        ModelProviderDto {
+            id: "stub".to_string(),
+            name: None,
+            base_url: Some(base_url.to_string()),
+            env_key: Some("STUB_API_KEY".to_string()),
+            wire_api: None,
+            http_headers: BTreeMap::from([("X-Team".to_string(), "core".to_string())]),
+            env_http_headers: BTreeMap::from([(
+                "OpenAI-Organization".to_string(),
+                "STUB_ORG".to_string(),
+            )]),
+            query_params: BTreeMap::from([(
+                "api-version".to_string(),
+                "2025-04-01-preview".to_string(),
+            )]),
+        }
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "STUB_API_KEY" => Some("sk-test".to_string()),
+            _ => None,
+        }
+    }
+
+    fn probe(provider: ModelProviderDto) -> ModelProviderProbeResult {
+        tokio::runtime::Runtime::new()
+            .expect("runtime")
+            .block_on(probe_model_provider(provider, Duration::from_secs(10), env))
+            .expect("probe")
+    }
+
+    /// Answers every request with `status` and `body`; returns the `/v1/`
+    /// base URL and the recorded requests.
+    fn spawn_stub_provider(
+        status: &'static str,
+        body: String,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let (base, requests) = spawn_http_stub(move |_| StubResponse::json(status, body.clone()));
+        (format!("{base}/v1/"), requests)
+    }
+
+    #[test]
+    fn probe_lists_models_with_provider_auth_and_params() {
+        let (base_url, requests) = spawn_stub_provider(
+            "200 OK",
+            json!({"object":"list","data":[{"id":"gpt-5"},{"id":"gpt-5-mini"}]}).to_string(),
+        );
+
+        let result = probe(provider(&base_url));
+
+        assert!(result.ok, "probe failed: {:?}", result.error);
+        assert_eq!(result.status, Some(200));
+        assert_eq!(result.models, vec!["gpt-5", "gpt-5-mini"]);
+        assert!(result.url.ends_with("/v1/models"));
+        let requests = requests.lock().expect("requests");
+        let head = requests[0].to_ascii_lowercase();
+        assert!(head.starts_with("get /v1/models?api-version=2025-04-01-preview "));
+        assert!(head.contains("authorization: bearer sk-test"));
+        assert!(head.contains("x-team: core"));
+        assert!(!head.contains("openai-organization"));
+    }
+
+    #[test]
+    fn probe_reports_http_errors_and_missing_keys() {
+        let (base_url, _) = spawn_stub_provider(
+            "401 Unauthorized",
+            json!({"error":{"message":"bad key"}}).to_string(),
+        );
+        let result = probe(provider(&base_url));
+        assert!(!result.ok);
+        assert_eq!(result.status, Some(401));
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("bad key"));
+
+        let mut missing_key = provider(&base_url);
+        missing_key.env_key = Some("UNSET_KEY".to_string());
+        let result = probe(missing_key);
+        assert_eq!(result.status, None);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Environment variable UNSET_KEY is not set")
+        );
+    }
+
+    #[test]
+    fn parses_openai_and_ollama_model_lists() {
+        assert_eq!(
+            parse_model_ids(&json!({"models":[{"name":"llama3"},{"name":"qwen"}]})),
+            Some(vec!["llama3".to_string(), "qwen".to_string()])
+        );
+        assert_eq!(parse_model_ids(&json!({"object":"list"})), None);
+    }
+}
@@ -0,0 +1,472 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use toml_edit::{Document, Item, Table, Value as TomlValue};
+
+use crate::shared::config_toml_core::{self, ConfigTarget};
+
+const MODEL_PROVIDERS_KEY: &str = "model_providers";
+const DEFAULT_PROVIDER_KEY: &str = "model_provider";
+/// Providers built into Codex; `[model_providers.<id>]` cannot override them.
+pub(crate) const RESERVED_PROVIDER_IDS: &[&str] = &["openai", "oss"];
+pub(crate) const WIRE_APIS: &[&str] = &["responses", "chat"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelProviderDto {
+    pub id: String,
+    /// Display name (`name` in config.toml).
+    pub name: Option<String>,
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key sent as a bearer token.
+    pub env_key: Option<String>,
+    pub wire_api: Option<String>,
+    #[serde(default)]
+    pub http_headers: BTreeMap<String, String>,
+    /// Header name -> environment variable holding its value.
+    #[serde(default)]
+    pub env_http_headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub query_params: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelProvidersDto {
+    pub config_path: String,
+    pub default_provider: Option<String>,
+    pub providers: Vec<ModelProviderDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateModelProviderInput {
+    #[serde(flatten)]
+    pub provider: ModelProviderDto,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpdateModelProviderInput {
+    pub original_id: String,
+    #[serde(flatten)]
+    pub provider: ModelProviderDto,
+    /// Edits the workspace's `.codex/config.toml` instead of the global one.
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeleteModelProviderInput {
+    pub id: String,
+    pub workspace_id: Option<String>,
+}
+
+pub(crate) fn get_model_providers_core(target: &ConfigTarget) -> Result<ModelProvidersDto, String> {
+    let config_path = target.config_path();
+    let config_path_string = config_path
+        .to_str()
+        .ok_or_else(|| "Unable to resolve config.toml path".to_string())?
+        .to_string();
+
+    let (_, document) = config_toml_core::load_config_document(target)?;
+    let mut providers = collect_providers(&document);
+    providers.sort_by(|left, right| left.id.cmp(&right.id));
+
+    Ok(ModelProvidersDto {
+        config_path: config_path_string,
+        default_provider: config_toml_core::read_top_level_string(&document, DEFAULT_PROVIDER_KEY),
+        providers,
+    })
+}
+
+pub(crate) fn create_model_provider_core(
+    target: &ConfigTarget,
+    input: CreateModelProviderInput,
+) -> Result<ModelProvidersDto, String> {
+    let id = normalize_provider_id(&input.provider.id)?;
+    validate_provider(&id, &input.provider)?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let providers = config_toml_core::ensure_table(&mut document, MODEL_PROVIDERS_KEY)?;
+    if providers.get(&id).is_some() {
+        return Err(format!("Model provider '{id}' already exists"));
+    }
+    providers[&id] = Item::Table(build_provider_table(input.provider, None));
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_model_providers_core(target)
+}
+
+pub(crate) fn update_model_provider_core(
+    target: &ConfigTarget,
+    input: UpdateModelProviderInput,
+) -> Result<ModelProvidersDto, String> {
+    let original_id = normalize_provider_id(&input.original_id)?;
+    let id = normalize_provider_id(&input.provider.id)?;
+    validate_provider(&id, &input.provider)?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let providers = config_toml_core::ensure_table(&mut document, MODEL_PROVIDERS_KEY)?;
+    if id != original_id && providers.get(&id).is_some() {
+        return Err(format!("Model provider '{id}' already exists"));
+    }
+    let Some(existing_item) = providers.remove(&original_id) else {
+        return Err(format!("Model provider '{original_id}' not found"));
+    };
+    let existing = existing_item
+        .as_table_like()
+        .ok_or_else(|| format!("Model provider '{original_id}' must be a table"))?;
+    let unknown = collect_unknown_fields(existing);
+    providers[&id] = Item::Table(build_provider_table(input.provider, Some(unknown)));
+
+    if id != original_id {
+        rename_provider_references(&mut document, &original_id, Some(&id));
+    }
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_model_providers_core(target)
+}
+
+pub(crate) fn delete_model_provider_core(
+    target: &ConfigTarget,
+    input: DeleteModelProviderInput,
+) -> Result<ModelProvidersDto, String> {
+    let id = normalize_provider_id(&input.id)?;
+
+    let (_, mut document) = config_toml_core::load_config_document(target)?;
+    let providers = config_toml_core::ensure_table(&mut document, MODEL_PROVIDERS_KEY)?;
+    if providers.remove(&id).is_none() {
+        return Err(format!("Model provider '{id}' not found"));
+    }
+    rename_provider_references(&mut document, &id, None);
+
+    config_toml_core::persist_config_document(target, &document)?;
+    get_model_providers_core(target)
+}
+
+pub(crate) fn normalize_provider_id(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err("Model provider id is required".to_string());
+    }
+    if trimmed.len() > 64 {
+        return Err("Model provider id must be 64 characters or fewer".to_string());
+    }
+    if trimmed
+        .chars()
+        .any(|ch| !ch.is_ascii_alphanumeric() && ch != '_' && ch != '-' && ch != '.')
+    {
+        return Err("Model provider id must use only letters, digits, '.', '_' or '-'".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+fn validate_provider(id: &str, provider: &ModelProviderDto) -> Result<(), String> {
+    if RESERVED_PROVIDER_IDS.contains(&id) {
+        return Err(format!(
+            "'{id}' is a built-in provider and cannot be redefined"
+        ));
+    }
+    let Some(base_url) = normalize_optional_string(provider.base_url.clone()) else {
+        return Err("base_url is required for a model provider".to_string());
+    };
+    let parsed =
+        reqwest::Url::parse(&base_url).map_err(|err| format!("Invalid base_url: {err}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("base_url must be an http(s) URL".to_string());
+    }
+    if let Some(wire_api) = normalize_optional_string(provider.wire_api.clone()) {
+        if !WIRE_APIS.contains(&wire_api.as_str()) {
+            return Err(format!("wire_api must be one of {}", WIRE_APIS.join(", ")));
+        }
+    }
+    if let Some(env_key) = normalize_optional_string(provider.env_key.clone()) {
+        validate_env_var_name(&env_key)?;
+    }
+    for env_var in provider.env_http_headers.values() {
+        validate_env_var_name(env_var.trim())?;
+    }
+    Ok(())
+}
+
+fn validate_env_var_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.starts_with(|ch: char| ch.is_ascii_digit())
+        || name
+            .chars()
+            .any(|ch| !ch.is_ascii_alphanumeric() && ch != '_')
+    {
+        return Err(format!("'{name}' is not a valid environment variable name"));
+    }
+    Ok(())
+}
+
+/// Points the top-level `model_provider` and any profile using `from` at `to`,
+/// or drops those keys when the provider is deleted.
+fn rename_provider_references(document: &mut Document, from: &str, to: Option<&str>) {
+    if config_toml_core::read_top_level_string(document, DEFAULT_PROVIDER_KEY).as_deref()
+        == Some(from)
+    {
+        config_toml_core::set_top_level_string(document, DEFAULT_PROVIDER_KEY, to);
+    }
+    let Some(profiles) = document
+        .get_mut("profiles")
+        .and_then(Item::as_table_like_mut)
+    else {
+        return;
+    };
+    for (_, profile) in profiles.iter_mut() {
+        let Some(profile) = profile.as_table_like_mut() else {
+            continue;
+        };
+        if profile.get(DEFAULT_PROVIDER_KEY).and_then(Item::as_str) != Some(from) {
+            continue;
+        }
+        match to {
+            Some(to) => {
+                profile.insert(DEFAULT_PROVIDER_KEY, Item::Value(TomlValue::from(to)));
+            }
+            None => {
+                profile.remove(DEFAULT_PROVIDER_KEY);
+            }
+        }
+    }
+}
+
+fn collect_providers(document: &Document) -> Vec<ModelProviderDto> {
+    let Some(table) = document
+        .get(MODEL_PROVIDERS_KEY)
+        .and_then(Item::as_table_like)
+    else {
+        return Vec::new();
+    };
+
+    let mut providers = Vec::new();
+    for (id, item) in table.iter() {
+        let Some(provider) = item.as_table_like() else {
+            continue;
+        };
+        providers.push(ModelProviderDto {
+            id: id.to_string(),
+            name: read_trimmed_string(provider.get("name")),
+            base_url: read_trimmed_string(provider.get("base_url")),
+            env_key: read_trimmed_string(provider.get("env_key")),
+            wire_api: read_trimmed_string(provider.get("wire_api")),
+            http_headers: read_string_map(provider.get("http_headers")),
+            env_http_headers: read_string_map(provider.get("env_http_headers")),
+            query_params: read_string_map(provider.get("query_params")),
+        });
+    }
+    providers
+}
+
+fn build_provider_table(provider: ModelProviderDto, existing_unknown: Option<Table>) -> Table {
+    let mut table = Table::new();
+    let strings = [
+        ("name", provider.name),
+        ("base_url", provider.base_url),
+        ("env_key", provider.env_key),
+        ("wire_api", provider.wire_api),
+    ];
+    for (key, value) in strings {
+        if let Some(value) = normalize_optional_string(value) {
+            table[key] = Item::Value(TomlValue::from(value));
+        }
+    }
+    let maps = [
+        ("http_headers", provider.http_headers),
+        ("env_http_headers", provider.env_http_headers),
+        ("query_params", provider.query_params),
+    ];
+    for (key, values) in maps {
+        if !values.is_empty() {
+            table[key] = Item::Table(build_string_table(values));
+        }
+    }
+    // Keys without a form field (retry limits, timeouts, ...) survive edits.
+    for (key, item) in existing_unknown.unwrap_or_default().iter() {
+        table[key] = item.clone();
+    }
+    table
+}
+
+fn read_trimmed_string(item: Option<&Item>) -> Option<String> {
+    let value = item.and_then(Item::as_str)?;
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn read_string_map(item: Option<&Item>) -> BTreeMap<String, String> {
+    let Some(table_like) = item.and_then(Item::as_table_like) else {
+        return BTreeMap::new();
+    };
+    table_like
+        .iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value.as_str()?.to_string())))
+        .collect()
+}
+
+fn build_string_table(values: BTreeMap<String, String>) -> Table {
+    let mut table = Table::new();
+    for (key, value) in values {
+        let trimmed_key = key.trim();
+        if trimmed_key.is_empty() {
+            continue;
+        }
+        table[trimmed_key] = Item::Value(TomlValue::from(value));
+    }
+    table
+}
+
+fn collect_unknown_fields(table_like: &dyn toml_edit::TableLike) -> Table {
+    let mut table = Table::new();
+    for (key, item) in table_like.iter() {
+        if is_known_provider_key(key) {
+            continue;
+        }
+        table[key] = item.clone();
+    }
+    table
+}
+
+fn is_known_provider_key(key: &str) -> bool {
+    matches!(
+        key,
+        "name"
+            | "base_url"
+            | "env_key"
+            | "wire_api"
+            | "http_headers"
+            | "env_http_headers"
+            | "query_params"
+    )
+}
+
+fn normalize_optional_string(value: Option<String>) -> Option<String> {
+    let text = value?;
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+    use std::fs;
+
+    fn provider(id: &str, base_url: &str) -> ModelProviderDto {
+        ModelProviderDto {
+            id: id.to_string(),
+            name: Some("Azure".to_string()),
+            base_url: Some(base_url.to_string()),
+            env_key: Some("AZURE_OPENAI_API_KEY".to_string()),
+            wire_api: Some("responses".to_string()),
+            http_headers: BTreeMap::new(),
+            env_http_headers: BTreeMap::new(),
+            query_params: BTreeMap::from([(
+                "api-version".to_string(),
+                "2025-04-01-preview".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn providers_round_trip_and_follow_renames() {
+        let home = temp_dir("providers");
+        fs::write(
+            home.join("config.toml"),
+            "model_provider = \"azure\"\n\n[model_providers.azure]\nbase_url = \"https://old.example.com/openai\"\nrequest_max_retries = 4\n\n[profiles.deep]\nmodel_provider = \"azure\"\n",
+        )
+        .expect("write config");
+        let target = ConfigTarget::Global(home.clone());
+
+        let updated = update_model_provider_core(
+            &target,
+            UpdateModelProviderInput {
+                original_id: "azure".to_string(),
+                provider: provider("azure-eu", "https://eu.example.com/openai"),
+                workspace_id: None,
+            },
+        )
+        .expect("update");
+        assert_eq!(updated.default_provider.as_deref(), Some("azure-eu"));
+        assert_eq!(updated.providers.len(), 1);
+        assert_eq!(
+            updated.providers[0]
+                .query_params
+                .get("api-version")
+                .map(String::as_str),
+            Some("2025-04-01-preview")
+        );
+        let contents = fs::read_to_string(home.join("config.toml")).expect("read config");
+        assert!(contents.contains("request_max_retries = 4"));
+        assert!(contents.contains("[profiles.deep]\nmodel_provider = \"azure-eu\""));
+
+        create_model_provider_core(
+            &target,
+            CreateModelProviderInput {
+                provider: ModelProviderDto {
+                    wire_api: Some("chat".to_string()),
+                    env_key: None,
+                    ..provider("ollama", "http://localhost:11434/v1")
+                },
+                workspace_id: None,
+            },
+        )
+        .expect("create");
+        let deleted = delete_model_provider_core(
+            &target,
+            DeleteModelProviderInput {
+                id: "azure-eu".to_string(),
+                workspace_id: None,
+            },
+        )
+        .expect("delete");
+        assert_eq!(deleted.default_provider, None);
+        assert_eq!(deleted.providers.len(), 1);
+        assert_eq!(deleted.providers[0].id, "ollama");
+        let contents = fs::read_to_string(home.join("config.toml")).expect("read config");
+        assert!(!contents.contains("azure-eu"));
+    }
+
+    #[test]
+    fn rejects_invalid_providers() {
+        let target = ConfigTarget::Global(temp_dir("providers"));
+        let create = |provider: ModelProviderDto| {
+            create_model_provider_core(
+                &target,
+                CreateModelProviderInput {
+                    provider,
+                    workspace_id: None,
+                },
+            )
+        };
+
+        let err = create(provider("openai", "https://api.example.com")).expect_err("reserved");
+        assert!(err.contains("built-in"));
+        let err = create(provider("local", "ftp://example.com")).expect_err("scheme");
+        assert!(err.contains("http(s)"));
+        let err = create(ModelProviderDto {
+            wire_api: Some("grpc".to_string()),
+            ..provider("local", "http://localhost:8080/v1")
+        })
+        .expect_err("wire api");
+        assert!(err.contains("wire_api"));
+        let err = create(ModelProviderDto {
+            env_key: Some("MY KEY".to_string()),
+            ..provider("local", "http://localhost:8080/v1")
+        })
+        .expect_err("env key");
+        assert!(err.contains("environment variable"));
+    }
+}
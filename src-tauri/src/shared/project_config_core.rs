@@ -0,0 +1,481 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use ignore::WalkBuilder;
+use tokio::sync::Mutex;
+use toml_edit::{Item, Table, Value as TomlValue};
+
+use crate::codex::home as codex_home;
+use crate::files::io::{read_text_file_within, write_text_file_within, TextFileResponse};
+use crate::files::policy::FileScope;
+use crate::shared::codex_core;
+use crate::shared::config_toml_core::{self, ConfigTarget};
+use crate::shared::files_core::resolve_root_core;
+use crate::types::{AppSettings, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+const AGENTS_FILENAMES: [&str; 2] = ["AGENTS.md", "AGENTS.override.md"];
+const MAX_AGENTS_FILES: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceAgentsFile {
+    /// Path relative to the workspace root, `/`-separated.
+    pub path: String,
+    /// Folder the instructions apply to; empty for the workspace root.
+    pub directory: String,
+    pub is_override: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ConfigLayerSource {
+    Global,
+    Project,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectiveConfigLayer {
+    pub source: ConfigLayerSource,
+    pub path: String,
+    pub exists: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectiveConfigEntry {
+    /// Dotted TOML key, e.g. `mcp_servers.github.command`.
+    pub key: String,
+    pub value: Value,
+    pub source: ConfigLayerSource,
+    /// Lower layers that also set this key, in load order.
+    pub overridden: Vec<ConfigLayerSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectiveConfig {
+    pub layers: Vec<EffectiveConfigLayer>,
+    pub entries: Vec<EffectiveConfigEntry>,
+}
+
+/// Global `config.toml` when no workspace is given, otherwise the workspace's
+/// `.codex/config.toml`.
+pub(crate) async fn resolve_config_target_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: Option<&str>,
+) -> Result<ConfigTarget, String> {
+    match workspace_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(workspace_id) => {
+            resolve_root_core(workspaces, FileScope::Workspace, Some(workspace_id))
+                .await
+                .map(ConfigTarget::Workspace)
+        }
+        None => codex_home::resolve_default_codex_home()
+            .map(ConfigTarget::Global)
+            .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string()),
+    }
+}
+
+pub(crate) async fn list_workspace_agents_files_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<WorkspaceAgentsFile>, String> {
+    let root = resolve_root_core(workspaces, FileScope::Workspace, Some(&workspace_id)).await?;
+    tokio::task::spawn_blocking(move || collect_agents_files(&root))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub(crate) async fn read_workspace_agents_file_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+) -> Result<TextFileResponse, String> {
+    let relative = validate_agents_path(&path)?;
+    let root = resolve_root_core(workspaces, FileScope::Workspace, Some(&workspace_id)).await?;
+    read_text_file_within(
+        &root,
+        &relative.to_string_lossy(),
+        false,
+        "workspace root",
+        "AGENTS.md",
+        false,
+    )
+}
+
+pub(crate) async fn write_workspace_agents_file_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    content: String,
+) -> Result<(), String> {
+    let relative = validate_agents_path(&path)?;
+    let root = resolve_root_core(workspaces, FileScope::Workspace, Some(&workspace_id)).await?;
+    if let Some(parent) = relative.parent() {
+        if !root.join(parent).is_dir() {
+            return Err(format!(
+                "Folder `{}` does not exist in the workspace",
+                normalize_git_path(&parent.to_string_lossy())
+            ));
+        }
+    }
+    write_text_file_within(
+        &root,
+        &relative.to_string_lossy(),
+        &content,
+        false,
+        "workspace root",
+        "AGENTS.md",
+        false,
+    )
+}
+
+/// Merges the workspace's Codex home `config.toml` with its repo-local
+/// `.codex/config.toml`, recording which layer each key comes from.
+pub(crate) async fn get_effective_config_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<EffectiveConfig, String> {
+    let codex_home =
+        codex_core::resolve_codex_home_for_workspace_core(workspaces, app_settings, &workspace_id)
+            .await?;
+    let root = resolve_root_core(workspaces, FileScope::Workspace, Some(&workspace_id)).await?;
+    Ok(merge_config_layers(&[
+        (ConfigLayerSource::Global, ConfigTarget::Global(codex_home)),
+        (ConfigLayerSource::Project, ConfigTarget::Workspace(root)),
+    ]))
+}
+
+fn merge_config_layers(layers: &[(ConfigLayerSource, ConfigTarget)]) -> EffectiveConfig {
+    let mut infos = Vec::new();
+    let mut merged: BTreeMap<String, EffectiveConfigEntry> = BTreeMap::new();
+    for (source, target) in layers {
+        let mut info = EffectiveConfigLayer {
+            source: *source,
+            path: target.config_path().to_string_lossy().to_string(),
+            exists: false,
+            error: None,
+        };
+        let document = match config_toml_core::load_config_document(target) {
+            Ok((exists, document)) => {
+                info.exists = exists;
+                document
+            }
+            Err(err) => {
+                info.error = Some(err);
+                infos.push(info);
+                continue;
+            }
+        };
+        infos.push(info);
+
+        let mut leaves = Vec::new();
+        flatten_json("", &table_to_json(document.as_table()), &mut leaves);
+        for (key, value) in leaves {
+            match merged.get_mut(&key) {
+                Some(entry) => {
+                    entry.overridden.push(entry.source);
+                    entry.source = *source;
+                    entry.value = value;
+                }
+                None => {
+                    merged.insert(
+                        key.clone(),
+                        EffectiveConfigEntry {
+                            key,
+                            value,
+                            source: *source,
+                            overridden: Vec::new(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+    EffectiveConfig {
+        layers: infos,
+        entries: merged.into_values().collect(),
+    }
+}
+
+/// Tables merge key by key; anything else (including arrays) is a leaf.
+fn flatten_json(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                flatten_json(&join_key(prefix, key), child, out);
+            }
+        }
+        _ if prefix.is_empty() => {}
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    let key = if bare {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    };
+    if prefix.is_empty() {
+        key
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn table_to_json(table: &Table) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .map(|(key, item)| (key.to_string(), item_to_json(item)))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+fn item_to_json(item: &Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => toml_value_to_json(value),
+        Item::Table(table) => table_to_json(table),
+        Item::ArrayOfTables(array) => Value::Array(array.iter().map(table_to_json).collect()),
+    }
+}
+
+fn toml_value_to_json(value: &TomlValue) -> Value {
+    match value {
+        TomlValue::String(value) => Value::String(value.value().clone()),
+        TomlValue::Integer(value) => Value::from(*value.value()),
+        TomlValue::Float(value) => serde_json::Number::from_f64(*value.value())
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        TomlValue::Boolean(value) => Value::Bool(*value.value()),
+        TomlValue::Datetime(value) => Value::String(value.value().to_string()),
+        TomlValue::Array(values) => Value::Array(values.iter().map(toml_value_to_json).collect()),
+        TomlValue::InlineTable(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_string(), toml_value_to_json(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+fn validate_agents_path(path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path.trim());
+    if relative.as_os_str().is_empty() {
+        return Err("AGENTS.md path is required".to_string());
+    }
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err("Invalid AGENTS.md path".to_string());
+    }
+    let is_agents_file = relative
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| AGENTS_FILENAMES.contains(&name));
+    if !is_agents_file {
+        return Err("Only AGENTS.md or AGENTS.override.md files can be edited".to_string());
+    }
+    Ok(relative.to_path_buf())
+}
+
+fn collect_agents_files(root: &Path) -> Vec<WorkspaceAgentsFile> {
+    let walker = WalkBuilder::new(root)
+        .hidden(true)
+        .follow_links(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|ft| ft.is_dir())
+                || !matches!(
+                    entry.file_name().to_string_lossy().as_ref(),
+                    "node_modules" | "target" | "dist"
+                )
+        })
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !AGENTS_FILENAMES.contains(&name.as_str()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        files.push(WorkspaceAgentsFile {
+            path: normalize_git_path(&relative.to_string_lossy()),
+            directory: relative
+                .parent()
+                .map(|parent| normalize_git_path(&parent.to_string_lossy()))
+                .unwrap_or_default(),
+            is_override: name != AGENTS_FILENAMES[0],
+            size: entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+        });
+        if files.len() >= MAX_AGENTS_FILES {
+            break;
+        }
+    }
+    files.sort_by(|left, right| {
+        let depth = |file: &WorkspaceAgentsFile| {
+            file.directory
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .count()
+        };
+        depth(left)
+            .cmp(&depth(right))
+            .then_with(|| left.path.cmp(&right.path))
+    });
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn effective_config_tracks_layer_provenance() {
+        let home = temp_dir("effective-home");
+        let workspace = temp_dir("effective-workspace");
+        fs::write(
+            home.join("config.toml"),
+            "model = \"gpt-5\"\napproval_policy = \"on-request\"\n\n[mcp_servers.github]\ncommand = \"gh-mcp\"\nenv = { TOKEN = \"global\" }\n",
+        )
+        .expect("write global config");
+        fs::create_dir_all(workspace.join(".codex")).expect("create .codex");
+        fs::write(
+            workspace.join(".codex").join("config.toml"),
+            "model = \"o3\"\n\n[mcp_servers.github]\nenv = { TOKEN = \"project\" }\n\n[mcp_servers.\"docs.search\"]\nurl = \"http://localhost:9000/mcp\"\nargs = []\n",
+        )
+        .expect("write project config");
+
+        let config = merge_config_layers(&[
+            (
+                ConfigLayerSource::Global,
+                ConfigTarget::Global(home.clone()),
+            ),
+            (
+                ConfigLayerSource::Project,
+                ConfigTarget::Workspace(workspace.clone()),
+            ),
+        ]);
+
+        assert!(config.layers.iter().all(|layer| layer.exists));
+        let entry = |key: &str| {
+            config
+                .entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .unwrap_or_else(|| panic!("missing {key}"))
+        };
+        assert_eq!(entry("model").value, json!("o3"));
+        assert_eq!(entry("model").source, ConfigLayerSource::Project);
+        assert_eq!(entry("model").overridden, vec![ConfigLayerSource::Global]);
+        assert_eq!(entry("approval_policy").source, ConfigLayerSource::Global);
+        assert!(entry("approval_policy").overridden.is_empty());
+        assert_eq!(
+            entry("mcp_servers.github.command").source,
+            ConfigLayerSource::Global
+        );
+        assert_eq!(
+            entry("mcp_servers.github.env.TOKEN").value,
+            json!("project")
+        );
+        assert_eq!(
+            entry("mcp_servers.\"docs.search\".url").source,
+            ConfigLayerSource::Project
+        );
+        assert_eq!(entry("mcp_servers.\"docs.search\".args").value, json!([]));
+
+        let _ = fs::remove_dir_all(&home);
+        let _ = fs::remove_dir_all(&workspace);
+    }
+
+    #[test]
+    fn effective_config_reports_broken_layers() {
+        let home = temp_dir("effective-broken-home");
+        let workspace = temp_dir("effective-broken-workspace");
+        fs::create_dir_all(workspace.join(".codex")).expect("create .codex");
+        fs::write(workspace.join(".codex").join("config.toml"), "model = \n")
+            .expect("write project config");
+
+        let config = merge_config_layers(&[
+            (
+                ConfigLayerSource::Global,
+                ConfigTarget::Global(home.clone()),
+            ),
+            (
+                ConfigLayerSource::Project,
+                ConfigTarget::Workspace(workspace.clone()),
+            ),
+        ]);
+
+        assert!(!config.layers[0].exists);
+        assert!(config.layers[0].error.is_none());
+        assert!(config.layers[1].error.is_some());
+        assert!(config.entries.is_empty());
+
+        let _ = fs::remove_dir_all(&home);
+        let _ = fs::remove_dir_all(&workspace);
+    }
+
+    #[test]
+    fn agents_paths_stay_inside_the_workspace() {
+        assert!(validate_agents_path("AGENTS.md").is_ok());
+        assert!(validate_agents_path("packages/api/AGENTS.override.md").is_ok());
+        assert!(validate_agents_path("../AGENTS.md").is_err());
+        assert!(validate_agents_path("/etc/AGENTS.md").is_err());
+        assert!(validate_agents_path("packages/README.md").is_err());
+        assert!(validate_agents_path(" ").is_err());
+    }
+
+    #[test]
+    fn nested_agents_files_are_listed_by_depth() {
+        let root = temp_dir("nested-agents");
+        for dir in ["", "packages/api", "packages", "node_modules/dep"] {
+            fs::create_dir_all(root.join(dir)).expect("create dir");
+            fs::write(root.join(dir).join("AGENTS.md"), "rules").expect("write agents");
+        }
+        fs::write(root.join("packages").join("AGENTS.override.md"), "override")
+            .expect("write override");
+
+        let files = collect_agents_files(&root);
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "AGENTS.md",
+                "packages/AGENTS.md",
+                "packages/AGENTS.override.md",
+                "packages/api/AGENTS.md",
+            ]
+        );
+        assert_eq!(files[0].directory, "");
+        assert!(files[2].is_override);
+        assert_eq!(files[3].directory, "packages/api");
+        assert_eq!(files[3].size, 5);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,314 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::shared::config_history_core::file_key;
+use crate::shared::git_core::{run_git_command, run_git_command_bytes};
+
+const HISTORY_DIR: &str = "prompt-history";
+const TEAM_DIR: &str = "team-prompts";
+/// History commits are made by the app, so they must not depend on the
+/// user's git identity or signing setup.
+const HISTORY_GIT_CONFIG: [&str; 6] = [
+    "-c",
+    "user.name=Codex Monitor",
+    "-c",
+    "user.email=codex-monitor@localhost",
+    "-c",
+    "commit.gpgsign=false",
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptVersion {
+    pub(crate) commit: String,
+    pub(crate) message: String,
+    /// Commit time in seconds since the epoch.
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPromptRepo {
+    pub(crate) url: Option<String>,
+    pub(crate) path: String,
+    pub(crate) head: Option<String>,
+}
+
+/// Path of `prompt_path` inside the history repo: one folder per prompt
+/// directory, named by a hash of that directory, holding the prompt file.
+fn history_key(prompt_path: &Path) -> Result<String, String> {
+    let parent = prompt_path
+        .parent()
+        .ok_or_else(|| "Unable to resolve prompt directory.".to_string())?;
+    let file_name = prompt_path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .ok_or_else(|| "Invalid prompt path.".to_string())?;
+    Ok(format!("{}/{file_name}", file_key(parent)))
+}
+
+async fn ensure_repo(root: &PathBuf) -> Result<(), String> {
+    if root.join(".git").exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(root)
+        .map_err(|err| format!("Failed to create {}: {err}", root.display()))?;
+    run_git_command(root, &["init", "-q"]).await.map(|_| ())
+}
+
+/// Commits the prompt's current contents, or its removal when `content` is
+/// `None`, to the history repo. Unchanged prompts add no commit.
+pub(crate) async fn record_prompt_version(
+    data_dir: &Path,
+    prompt_path: &Path,
+    content: Option<&str>,
+    message: &str,
+) -> Result<(), String> {
+    let root = data_dir.join(HISTORY_DIR);
+    let key = history_key(prompt_path)?;
+    let file = root.join(&key);
+    match content {
+        Some(content) => {
+            ensure_repo(&root).await?;
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+            }
+            fs::write(&file, content).map_err(|err| err.to_string())?;
+        }
+        None => {
+            if !file.exists() {
+                return Ok(());
+            }
+            fs::remove_file(&file).map_err(|err| err.to_string())?;
+        }
+    }
+    run_git_command(&root, &["add", "-A", "--", &key]).await?;
+    let status = run_git_command(&root, &["status", "--porcelain", "--", &key]).await?;
+    if status.is_empty() {
+        return Ok(());
+    }
+    let mut args: Vec<&str> = HISTORY_GIT_CONFIG.to_vec();
+    args.extend(["commit", "-q", "-m", message, "--", key.as_str()]);
+    run_git_command(&root, &args).await.map(|_| ())
+}
+
+/// Versions of a prompt, newest first.
+pub(crate) async fn list_prompt_versions(
+    data_dir: &Path,
+    prompt_path: &Path,
+) -> Result<Vec<PromptVersion>, String> {
+    let root = data_dir.join(HISTORY_DIR);
+    if !root.join(".git").exists() {
+        return Ok(Vec::new());
+    }
+    let key = history_key(prompt_path)?;
+    let output =
+        match run_git_command(&root, &["log", "--format=%H%x1f%ct%x1f%s", "--", &key]).await {
+            Ok(output) => output,
+            // A repo without commits has no history yet.
+            Err(_) => return Ok(Vec::new()),
+        };
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\u{1f}');
+            let commit = parts.next()?.to_string();
+            let timestamp = parts.next()?.parse().ok()?;
+            let message = parts.next().unwrap_or_default().to_string();
+            Some(PromptVersion {
+                commit,
+                message,
+                timestamp,
+            })
+        })
+        .collect())
+}
+
+/// Contents of a prompt as of `commit`.
+pub(crate) async fn read_prompt_version(
+    data_dir: &Path,
+    prompt_path: &Path,
+    commit: &str,
+) -> Result<String, String> {
+    let commit = commit.trim();
+    if commit.len() < 4 || commit.len() > 40 || !commit.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err("Invalid prompt version.".to_string());
+    }
+    let root = data_dir.join(HISTORY_DIR);
+    let key = history_key(prompt_path)?;
+    let spec = format!("{commit}:{key}");
+    let bytes = run_git_command_bytes(&root, &["show", &spec])
+        .await
+        .map_err(|_| "Prompt version not found.".to_string())?;
+    String::from_utf8(bytes).map_err(|_| "Prompt version is not valid UTF-8.".to_string())
+}
+
+pub(crate) fn team_repo_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(TEAM_DIR)
+}
+
+/// Folder whose markdown files are listed as team prompts: `prompts/` when
+/// the repository has one, its root otherwise. `None` until a repo is cloned.
+pub(crate) fn team_prompts_dir(data_dir: &Path) -> Option<PathBuf> {
+    let root = team_repo_dir(data_dir);
+    if !root.join(".git").exists() {
+        return None;
+    }
+    let nested = root.join("prompts");
+    Some(if nested.is_dir() { nested } else { root })
+}
+
+pub(crate) async fn team_prompt_repo_core(data_dir: &Path) -> TeamPromptRepo {
+    let root = team_repo_dir(data_dir);
+    let (url, head) = if root.join(".git").exists() {
+        (
+            run_git_command(&root, &["remote", "get-url", "origin"])
+                .await
+                .ok(),
+            run_git_command(&root, &["rev-parse", "--short", "HEAD"])
+                .await
+                .ok(),
+        )
+    } else {
+        (None, None)
+    };
+    TeamPromptRepo {
+        url,
+        path: root.to_string_lossy().to_string(),
+        head,
+    }
+}
+
+/// Points the team prompt source at `url`, cloning it into the app data dir.
+/// An empty URL removes the clone; the current URL pulls instead.
+pub(crate) async fn set_team_prompt_repo_core(
+    data_dir: &Path,
+    url: Option<String>,
+) -> Result<TeamPromptRepo, String> {
+    let url = url
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if url.as_deref().is_some_and(|value| value.starts_with('-')) {
+        return Err("Invalid team prompt repository URL.".to_string());
+    }
+    let root = team_repo_dir(data_dir);
+    let current = team_prompt_repo_core(data_dir).await;
+    if url.is_some() && current.url == url {
+        return sync_team_prompt_repo_core(data_dir).await;
+    }
+    if root.exists() {
+        fs::remove_dir_all(&root).map_err(|err| format!("Failed to remove team prompts: {err}"))?;
+    }
+    if let Some(url) = url {
+        fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+        run_git_command(
+            &data_dir.to_path_buf(),
+            &["clone", "-q", "--depth", "1", "--", &url, TEAM_DIR],
+        )
+        .await
+        .map_err(|err| format!("Failed to clone team prompts: {err}"))?;
+    }
+    Ok(team_prompt_repo_core(data_dir).await)
+}
+
+pub(crate) async fn sync_team_prompt_repo_core(data_dir: &Path) -> Result<TeamPromptRepo, String> {
+    let root = team_repo_dir(data_dir);
+    if !root.join(".git").exists() {
+        return Err("No team prompt repository is configured.".to_string());
+    }
+    run_git_command(&root, &["pull", "-q", "--ff-only"])
+        .await
+        .map_err(|err| format!("Failed to update team prompts: {err}"))?;
+    Ok(team_prompt_repo_core(data_dir).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::{run_async, temp_dir};
+
+    #[test]
+    fn records_and_reads_prompt_versions() {
+        run_async(async {
+            let data_dir = temp_dir("prompt-history");
+            let prompt = data_dir.join("prompts").join("review.md");
+
+            record_prompt_version(&data_dir, &prompt, Some("v1\n"), "Create review")
+                .await
+                .expect("record v1");
+            record_prompt_version(&data_dir, &prompt, Some("v1\n"), "Update review")
+                .await
+                .expect("record unchanged");
+            record_prompt_version(&data_dir, &prompt, Some("v2\n"), "Update review")
+                .await
+                .expect("record v2");
+
+            let versions = list_prompt_versions(&data_dir, &prompt)
+                .await
+                .expect("list");
+            assert_eq!(versions.len(), 2);
+            assert_eq!(versions[0].message, "Update review");
+            let first = read_prompt_version(&data_dir, &prompt, &versions[1].commit)
+                .await
+                .expect("read v1");
+            assert_eq!(first, "v1\n");
+            assert!(read_prompt_version(&data_dir, &prompt, "HEAD~1")
+                .await
+                .is_err());
+
+            let other = data_dir.join("other").join("review.md");
+            assert!(list_prompt_versions(&data_dir, &other)
+                .await
+                .expect("list other")
+                .is_empty());
+        });
+    }
+
+    #[test]
+    fn team_repo_clones_and_lists_prompts_folder() {
+        run_async(async {
+            let upstream = temp_dir("team-prompts-upstream");
+            fs::create_dir_all(upstream.join("prompts")).expect("create prompts");
+            fs::write(
+                upstream.join("prompts").join("triage.md"),
+                "Triage $TICKET\n",
+            )
+            .expect("write prompt");
+            run_git_command(&upstream, &["init", "-q"])
+                .await
+                .expect("init");
+            run_git_command(&upstream, &["add", "-A"])
+                .await
+                .expect("add");
+            let mut args = HISTORY_GIT_CONFIG.to_vec();
+            args.extend(["commit", "-q", "-m", "Add triage"]);
+            run_git_command(&upstream, &args).await.expect("commit");
+
+            let data_dir = temp_dir("team-prompts");
+            assert!(team_prompts_dir(&data_dir).is_none());
+            let url = upstream.to_string_lossy().to_string();
+            let repo = set_team_prompt_repo_core(&data_dir, Some(url.clone()))
+                .await
+                .expect("clone");
+            assert_eq!(repo.url.as_deref(), Some(url.as_str()));
+            assert!(repo.head.is_some());
+            assert_eq!(
+                team_prompts_dir(&data_dir),
+                Some(team_repo_dir(&data_dir).join("prompts"))
+            );
+
+            let repo = set_team_prompt_repo_core(&data_dir, None)
+                .await
+                .expect("remove");
+            assert!(repo.url.is_none());
+            assert!(!team_repo_dir(&data_dir).exists());
+            assert!(
+                set_team_prompt_repo_core(&data_dir, Some("--upload-pack=x".to_string()))
+                    .await
+                    .is_err()
+            );
+        });
+    }
+}
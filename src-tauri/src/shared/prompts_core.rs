@@ -1,12 +1,13 @@
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
 use tokio::task;
 
-use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
-use crate::types::WorkspaceEntry;
+use crate::codex::home::resolve_workspace_codex_home;
+use crate::shared::prompt_repo_core::{self, PromptVersion, TeamPromptRepo};
+use crate::types::{AppSettings, WorkspaceEntry};
 
 #[derive(Serialize, Clone)]
 pub(crate) struct CustomPromptEntry {
@@ -18,24 +19,51 @@ pub(crate) struct CustomPromptEntry {
     pub(crate) content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) variables: Vec<PromptVariable>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PromptVariableKind {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    Enum,
+}
+
+/// A typed `$NAME` placeholder declared in the frontmatter as
+/// `variable: "NAME: type = default"`. Values are filled in at send time.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct PromptVariable {
+    pub(crate) name: String,
+    #[serde(rename = "type", default)]
+    pub(crate) kind: PromptVariableKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) default: Option<String>,
 }
 
 fn resolve_codex_home_for_workspace(
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     entry: &WorkspaceEntry,
 ) -> Option<PathBuf> {
     let parent_entry = entry
         .parent_id
         .as_ref()
         .and_then(|parent_id| workspaces.get(parent_id));
-    resolve_workspace_codex_home(entry, parent_entry).or_else(resolve_default_codex_home)
+    resolve_workspace_codex_home(entry, parent_entry, Some(settings))
 }
 
 fn default_prompts_dir_for_workspace(
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     entry: &WorkspaceEntry,
 ) -> Option<PathBuf> {
-    resolve_codex_home_for_workspace(workspaces, entry).map(|home| home.join("prompts"))
+    resolve_codex_home_for_workspace(workspaces, settings, entry).map(|home| home.join("prompts"))
 }
 
 fn require_workspace_entry(
@@ -63,17 +91,18 @@ fn workspace_prompts_dir(settings_path: &Path, entry: &WorkspaceEntry) -> Result
 fn prompt_roots_for_workspace(
     settings_path: &Path,
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     entry: &WorkspaceEntry,
 ) -> Result<Vec<PathBuf>, String> {
     let mut roots = Vec::new();
     roots.push(workspace_prompts_dir(settings_path, entry)?);
-    if let Some(global_dir) = default_prompts_dir_for_workspace(workspaces, entry) {
+    if let Some(global_dir) = default_prompts_dir_for_workspace(workspaces, settings, entry) {
         roots.push(global_dir);
     }
     Ok(roots)
 }
 
-fn ensure_path_within_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String> {
+pub(crate) fn ensure_path_within_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String> {
     let canonical_path = path
         .canonicalize()
         .map_err(|_| "Invalid prompt path.".to_string())?;
@@ -87,6 +116,41 @@ fn ensure_path_within_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String
     Err("Prompt path is not within allowed directories.".to_string())
 }
 
+fn ensure_not_team_prompt(settings_path: &Path, path: &Path) -> Result<(), String> {
+    let team_dir = prompt_repo_core::team_repo_dir(&app_data_dir(settings_path)?);
+    let (Ok(canonical_path), Ok(canonical_team)) = (path.canonicalize(), team_dir.canonicalize())
+    else {
+        return Ok(());
+    };
+    if canonical_path.starts_with(canonical_team) {
+        return Err("Team prompts are read-only.".to_string());
+    }
+    Ok(())
+}
+
+/// History is best effort: a prompt that was saved stays saved even when
+/// git is unavailable.
+async fn record_history(settings_path: &Path, path: &Path, content: Option<&str>, message: String) {
+    let Ok(data_dir) = app_data_dir(settings_path) else {
+        return;
+    };
+    if let Err(err) =
+        prompt_repo_core::record_prompt_version(&data_dir, path, content, &message).await
+    {
+        eprintln!(
+            "Failed to record prompt history for {}: {err}",
+            path.display()
+        );
+    }
+}
+
+fn prompt_name_from_path(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("")
+        .to_string()
+}
+
 #[cfg(unix)]
 fn is_cross_device_error(err: &std::io::Error) -> bool {
     err.raw_os_error() == Some(libc::EXDEV)
@@ -108,18 +172,109 @@ fn move_file(src: &Path, dest: &Path) -> Result<(), String> {
     }
 }
 
-fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String) {
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_uppercase())
+        && chars.all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_')
+}
+
+fn validate_variable(variable: &PromptVariable) -> Result<(), String> {
+    let name = variable.name.as_str();
+    if !is_variable_name(name) || name == "ARGUMENTS" {
+        return Err(format!(
+            "Invalid variable name `{name}`: use uppercase letters, digits and `_`."
+        ));
+    }
+    if variable.kind == PromptVariableKind::Enum && variable.options.is_empty() {
+        return Err(format!("Variable `{name}` needs at least one option."));
+    }
+    let Some(default) = variable.default.as_deref() else {
+        return Ok(());
+    };
+    let valid = match variable.kind {
+        PromptVariableKind::String => true,
+        PromptVariableKind::Number => default.trim().parse::<f64>().is_ok(),
+        PromptVariableKind::Boolean => matches!(default.trim(), "true" | "false"),
+        PromptVariableKind::Enum => variable.options.iter().any(|option| option == default),
+    };
+    if !valid {
+        return Err(format!(
+            "Default `{default}` does not match the type of variable `{name}`."
+        ));
+    }
+    Ok(())
+}
+
+fn validate_variables(variables: &[PromptVariable]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for variable in variables {
+        validate_variable(variable)?;
+        if !seen.insert(variable.name.as_str()) {
+            return Err(format!("Variable `{}` is declared twice.", variable.name));
+        }
+    }
+    Ok(())
+}
+
+fn parse_variable_spec(raw: &str) -> Result<PromptVariable, String> {
+    let (name, rest) = raw.split_once(':').unwrap_or((raw, ""));
+    let (kind_raw, default) = match rest.split_once('=') {
+        Some((kind, default)) => (kind.trim(), Some(default.trim().to_string())),
+        None => (rest.trim(), None),
+    };
+    let (kind, options) = match kind_raw.to_ascii_lowercase().as_str() {
+        "" | "string" => (PromptVariableKind::String, Vec::new()),
+        "number" => (PromptVariableKind::Number, Vec::new()),
+        "boolean" | "bool" => (PromptVariableKind::Boolean, Vec::new()),
+        lowered if lowered.starts_with("enum(") && lowered.ends_with(')') => {
+            let options = kind_raw[5..kind_raw.len() - 1]
+                .split('|')
+                .map(str::trim)
+                .filter(|option| !option.is_empty())
+                .map(str::to_string)
+                .collect();
+            (PromptVariableKind::Enum, options)
+        }
+        _ => return Err(format!("Unknown variable type `{kind_raw}`.")),
+    };
+    let variable = PromptVariable {
+        name: name.trim().to_string(),
+        kind,
+        options,
+        default,
+    };
+    validate_variable(&variable)?;
+    Ok(variable)
+}
+
+fn format_variable_spec(variable: &PromptVariable) -> String {
+    let kind = match variable.kind {
+        PromptVariableKind::String => "string".to_string(),
+        PromptVariableKind::Number => "number".to_string(),
+        PromptVariableKind::Boolean => "boolean".to_string(),
+        PromptVariableKind::Enum => format!("enum({})", variable.options.join("|")),
+    };
+    match variable.default.as_deref() {
+        Some(default) => format!("{}: {kind} = {default}", variable.name),
+        None => format!("{}: {kind}", variable.name),
+    }
+}
+
+fn parse_frontmatter(
+    content: &str,
+) -> (Option<String>, Option<String>, Vec<PromptVariable>, String) {
     let mut segments = content.split_inclusive('\n');
     let Some(first_segment) = segments.next() else {
-        return (None, None, String::new());
+        return (None, None, Vec::new(), String::new());
     };
     let first_line = first_segment.trim_end_matches(['\r', '\n']);
     if first_line.trim() != "---" {
-        return (None, None, content.to_string());
+        return (None, None, Vec::new(), content.to_string());
     }
 
     let mut description: Option<String> = None;
     let mut argument_hint: Option<String> = None;
+    let mut variables: Vec<PromptVariable> = Vec::new();
     let mut frontmatter_closed = false;
     let mut consumed = first_segment.len();
 
@@ -151,6 +306,12 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
             match key.trim().to_ascii_lowercase().as_str() {
                 "description" => description = Some(val),
                 "argument-hint" | "argument_hint" => argument_hint = Some(val),
+                "variable" => {
+                    // Malformed declarations are skipped; the body still works.
+                    if let Ok(variable) = parse_variable_spec(&val) {
+                        variables.push(variable);
+                    }
+                }
                 _ => {}
             }
         }
@@ -159,7 +320,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     }
 
     if !frontmatter_closed {
-        return (None, None, content.to_string());
+        return (None, None, Vec::new(), content.to_string());
     }
 
     let body = if consumed >= content.len() {
@@ -167,12 +328,13 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     } else {
         content[consumed..].to_string()
     };
-    (description, argument_hint, body)
+    (description, argument_hint, variables, body)
 }
 
 fn build_prompt_contents(
     description: Option<String>,
     argument_hint: Option<String>,
+    variables: &[PromptVariable],
     content: String,
 ) -> String {
     let has_meta = description
@@ -180,7 +342,8 @@ fn build_prompt_contents(
         .is_some_and(|value| !value.trim().is_empty())
         || argument_hint
             .as_ref()
-            .is_some_and(|value| !value.trim().is_empty());
+            .is_some_and(|value| !value.trim().is_empty())
+        || !variables.is_empty();
     if !has_meta {
         return content;
     }
@@ -203,6 +366,12 @@ fn build_prompt_contents(
             ));
         }
     }
+    for variable in variables {
+        output.push_str(&format!(
+            "variable: \"{}\"\n",
+            format_variable_spec(variable).replace('"', "\\\"")
+        ));
+    }
     output.push_str("---\n");
     output.push_str(&content);
     output
@@ -254,7 +423,7 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
             Ok(content) => content,
             Err(_) => continue,
         };
-        let (description, argument_hint, body) = parse_frontmatter(&content);
+        let (description, argument_hint, variables, body) = parse_frontmatter(&content);
         out.push(CustomPromptEntry {
             name,
             path: path.to_string_lossy().to_string(),
@@ -262,6 +431,7 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
             argument_hint,
             content: body,
             scope: scope.map(|value| value.to_string()),
+            variables,
         });
     }
 
@@ -271,9 +441,14 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
 
 pub(crate) async fn prompts_list_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     settings_path: &Path,
     workspace_id: String,
 ) -> Result<Vec<CustomPromptEntry>, String> {
+    let settings = app_settings.lock().await.clone();
+    let team_dir = app_data_dir(settings_path)
+        .ok()
+        .and_then(|data_dir| prompt_repo_core::team_prompts_dir(&data_dir));
     let (workspace_dir, global_dir) = {
         let workspaces = workspaces.lock().await;
         let entry = workspaces.get(&workspace_id).cloned();
@@ -282,7 +457,7 @@ pub(crate) async fn prompts_list_core(
             .and_then(|entry| workspace_prompts_dir(settings_path, entry).ok());
         let global_dir = entry
             .as_ref()
-            .and_then(|entry| default_prompts_dir_for_workspace(&workspaces, entry));
+            .and_then(|entry| default_prompts_dir_for_workspace(&workspaces, &settings, entry));
         (workspace_dir, global_dir)
     };
 
@@ -296,6 +471,9 @@ pub(crate) async fn prompts_list_core(
             let _ = fs::create_dir_all(&dir);
             out.extend(discover_prompts_in(&dir, Some("global")));
         }
+        if let Some(dir) = team_dir {
+            out.extend(discover_prompts_in(&dir, Some("team")));
+        }
         out
     })
     .await
@@ -318,11 +496,13 @@ pub(crate) async fn prompts_workspace_dir_core(
 
 pub(crate) async fn prompts_global_dir_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     workspace_id: String,
 ) -> Result<String, String> {
+    let settings = app_settings.lock().await.clone();
     let workspaces = workspaces.lock().await;
     let entry = require_workspace_entry(&workspaces, &workspace_id)?;
-    let dir = default_prompts_dir_for_workspace(&workspaces, &entry)
+    let dir = default_prompts_dir_for_workspace(&workspaces, &settings, &entry)
         .ok_or("Unable to resolve CODEX_HOME".to_string())?;
     fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
     Ok(dir.to_string_lossy().to_string())
@@ -330,15 +510,20 @@ pub(crate) async fn prompts_global_dir_core(
 
 pub(crate) async fn prompts_create_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     settings_path: &Path,
     workspace_id: String,
     scope: String,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
+    variables: Option<Vec<PromptVariable>>,
     content: String,
 ) -> Result<CustomPromptEntry, String> {
+    let settings = app_settings.lock().await.clone();
     let name = sanitize_prompt_name(&name)?;
+    let variables = variables.unwrap_or_default();
+    validate_variables(&variables)?;
     let (target_dir, resolved_scope) = {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
@@ -348,7 +533,7 @@ pub(crate) async fn prompts_create_core(
                 (dir, "workspace")
             }
             "global" => {
-                let dir = default_prompts_dir_for_workspace(&workspaces, &entry)
+                let dir = default_prompts_dir_for_workspace(&workspaces, &settings, &entry)
                     .ok_or("Unable to resolve CODEX_HOME".to_string())?;
                 (dir, "global")
             }
@@ -362,8 +547,14 @@ pub(crate) async fn prompts_create_core(
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
-    let body = build_prompt_contents(description.clone(), argument_hint.clone(), content.clone());
-    fs::write(&path, body).map_err(|err| err.to_string())?;
+    let body = build_prompt_contents(
+        description.clone(),
+        argument_hint.clone(),
+        &variables,
+        content.clone(),
+    );
+    fs::write(&path, &body).map_err(|err| err.to_string())?;
+    record_history(settings_path, &path, Some(&body), format!("Create {name}")).await;
     Ok(CustomPromptEntry {
         name,
         path: path.to_string_lossy().to_string(),
@@ -371,28 +562,33 @@ pub(crate) async fn prompts_create_core(
         argument_hint,
         content,
         scope: Some(resolved_scope.to_string()),
+        variables,
     })
 }
 
 pub(crate) async fn prompts_update_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     settings_path: &Path,
     workspace_id: String,
     path: String,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
+    variables: Option<Vec<PromptVariable>>,
     content: String,
 ) -> Result<CustomPromptEntry, String> {
+    let settings = app_settings.lock().await.clone();
     let name = sanitize_prompt_name(&name)?;
     let target_path = PathBuf::from(&path);
     if !target_path.exists() {
         return Err("Prompt not found.".to_string());
     }
+    ensure_not_team_prompt(settings_path, &target_path)?;
     {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
-        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &settings, &entry)?;
         ensure_path_within_roots(&target_path, &roots)?;
     }
     let dir = target_path
@@ -402,11 +598,40 @@ pub(crate) async fn prompts_update_core(
     if next_path != target_path && next_path.exists() {
         return Err("Prompt with that name already exists.".to_string());
     }
-    let body = build_prompt_contents(description.clone(), argument_hint.clone(), content.clone());
-    fs::write(&next_path, body).map_err(|err| err.to_string())?;
+    // Callers that do not edit variables keep the ones already declared.
+    let variables = match variables {
+        Some(variables) => variables,
+        None => {
+            let existing = fs::read_to_string(&target_path).unwrap_or_default();
+            parse_frontmatter(&existing).2
+        }
+    };
+    validate_variables(&variables)?;
+    let body = build_prompt_contents(
+        description.clone(),
+        argument_hint.clone(),
+        &variables,
+        content.clone(),
+    );
+    fs::write(&next_path, &body).map_err(|err| err.to_string())?;
     if next_path != target_path {
         fs::remove_file(&target_path).map_err(|err| err.to_string())?;
+        let previous_name = prompt_name_from_path(&target_path);
+        record_history(
+            settings_path,
+            &target_path,
+            None,
+            format!("Rename {previous_name} to {name}"),
+        )
+        .await;
     }
+    record_history(
+        settings_path,
+        &next_path,
+        Some(&body),
+        format!("Update {name}"),
+    )
+    .await;
     let scope = {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
@@ -424,43 +649,53 @@ pub(crate) async fn prompts_update_core(
         argument_hint,
         content,
         scope,
+        variables,
     })
 }
 
 pub(crate) async fn prompts_delete_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     settings_path: &Path,
     workspace_id: String,
     path: String,
 ) -> Result<(), String> {
+    let settings = app_settings.lock().await.clone();
     let target = PathBuf::from(path);
     if !target.exists() {
         return Ok(());
     }
+    ensure_not_team_prompt(settings_path, &target)?;
     {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
-        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &settings, &entry)?;
         ensure_path_within_roots(&target, &roots)?;
     }
-    fs::remove_file(&target).map_err(|err| err.to_string())
+    fs::remove_file(&target).map_err(|err| err.to_string())?;
+    let name = prompt_name_from_path(&target);
+    record_history(settings_path, &target, None, format!("Delete {name}")).await;
+    Ok(())
 }
 
 pub(crate) async fn prompts_move_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     settings_path: &Path,
     workspace_id: String,
     path: String,
     scope: String,
 ) -> Result<CustomPromptEntry, String> {
+    let settings = app_settings.lock().await.clone();
     let target_path = PathBuf::from(&path);
     if !target_path.exists() {
         return Err("Prompt not found.".to_string());
     }
+    ensure_not_team_prompt(settings_path, &target_path)?;
     let roots = {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
-        prompt_roots_for_workspace(settings_path, &workspaces, &entry)?
+        prompt_roots_for_workspace(settings_path, &workspaces, &settings, &entry)?
     };
     ensure_path_within_roots(&target_path, &roots)?;
     let file_name = target_path
@@ -472,7 +707,7 @@ pub(crate) async fn prompts_move_core(
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
         match scope.as_str() {
             "workspace" => workspace_prompts_dir(settings_path, &entry)?,
-            "global" => default_prompts_dir_for_workspace(&workspaces, &entry)
+            "global" => default_prompts_dir_for_workspace(&workspaces, &settings, &entry)
                 .ok_or("Unable to resolve CODEX_HOME".to_string())?,
             _ => return Err("Invalid scope.".to_string()),
         }
@@ -489,12 +724,11 @@ pub(crate) async fn prompts_move_core(
     }
     move_file(&target_path, &next_path)?;
     let content = fs::read_to_string(&next_path).unwrap_or_default();
-    let (description, argument_hint, body) = parse_frontmatter(&content);
-    let name = next_path
-        .file_stem()
-        .and_then(|value| value.to_str())
-        .unwrap_or("")
-        .to_string();
+    let (description, argument_hint, variables, body) = parse_frontmatter(&content);
+    let name = prompt_name_from_path(&next_path);
+    let message = format!("Move {name} to {scope}");
+    record_history(settings_path, &target_path, None, message.clone()).await;
+    record_history(settings_path, &next_path, Some(&content), message).await;
     Ok(CustomPromptEntry {
         name,
         path: next_path.to_string_lossy().to_string(),
@@ -502,5 +736,196 @@ pub(crate) async fn prompts_move_core(
         argument_hint,
         content: body,
         scope: Some(scope),
+        variables,
     })
 }
+
+/// Checks the prompt's folder rather than the file, so the history of a
+/// deleted prompt stays reachable.
+async fn ensure_prompt_dir_allowed(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings: &AppSettings,
+    settings_path: &Path,
+    workspace_id: &str,
+    path: &Path,
+) -> Result<WorkspaceEntry, String> {
+    let dir = path
+        .parent()
+        .ok_or("Unable to resolve prompt directory.".to_string())?;
+    let workspaces = workspaces.lock().await;
+    let entry = require_workspace_entry(&workspaces, workspace_id)?;
+    let roots = prompt_roots_for_workspace(settings_path, &workspaces, settings, &entry)?;
+    ensure_path_within_roots(dir, &roots)?;
+    Ok(entry)
+}
+
+pub(crate) async fn prompts_history_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+) -> Result<Vec<PromptVersion>, String> {
+    let settings = app_settings.lock().await.clone();
+    let target_path = PathBuf::from(&path);
+    ensure_prompt_dir_allowed(
+        workspaces,
+        &settings,
+        settings_path,
+        &workspace_id,
+        &target_path,
+    )
+    .await?;
+    let data_dir = app_data_dir(settings_path)?;
+    prompt_repo_core::list_prompt_versions(&data_dir, &target_path).await
+}
+
+pub(crate) async fn prompts_restore_version_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+    commit: String,
+) -> Result<CustomPromptEntry, String> {
+    let settings = app_settings.lock().await.clone();
+    let target_path = PathBuf::from(&path);
+    let entry = ensure_prompt_dir_allowed(
+        workspaces,
+        &settings,
+        settings_path,
+        &workspace_id,
+        &target_path,
+    )
+    .await?;
+    let data_dir = app_data_dir(settings_path)?;
+    let content = prompt_repo_core::read_prompt_version(&data_dir, &target_path, &commit).await?;
+    fs::write(&target_path, &content).map_err(|err| err.to_string())?;
+    let name = prompt_name_from_path(&target_path);
+    let short = commit.trim().chars().take(7).collect::<String>();
+    record_history(
+        settings_path,
+        &target_path,
+        Some(&content),
+        format!("Restore {name} to {short}"),
+    )
+    .await;
+    let (description, argument_hint, variables, body) = parse_frontmatter(&content);
+    let scope = if target_path.starts_with(workspace_prompts_dir(settings_path, &entry)?) {
+        "workspace"
+    } else {
+        "global"
+    };
+    Ok(CustomPromptEntry {
+        name,
+        path: target_path.to_string_lossy().to_string(),
+        description,
+        argument_hint,
+        content: body,
+        scope: Some(scope.to_string()),
+        variables,
+    })
+}
+
+pub(crate) async fn prompts_team_repo_core(settings_path: &Path) -> Result<TeamPromptRepo, String> {
+    let data_dir = app_data_dir(settings_path)?;
+    Ok(prompt_repo_core::team_prompt_repo_core(&data_dir).await)
+}
+
+pub(crate) async fn prompts_team_repo_set_core(
+    settings_path: &Path,
+    url: Option<String>,
+) -> Result<TeamPromptRepo, String> {
+    let data_dir = app_data_dir(settings_path)?;
+    prompt_repo_core::set_team_prompt_repo_core(&data_dir, url).await
+}
+
+pub(crate) async fn prompts_team_repo_sync_core(
+    settings_path: &Path,
+) -> Result<TeamPromptRepo, String> {
+    let data_dir = app_data_dir(settings_path)?;
+    prompt_repo_core::sync_team_prompt_repo_core(&data_dir).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typed_variables_from_frontmatter() {
+        let content = "---\ndescription: \"Triage\"\nvariable: \"TICKET: string\"\nvariable: \"PRIORITY: enum(low|high) = low\"\nvariable: \"COUNT: number = 3\"\nvariable: \"bad name\"\n---\nTriage $TICKET\n";
+        let (description, _, variables, body) = parse_frontmatter(content);
+
+        assert_eq!(description.as_deref(), Some("Triage"));
+        assert_eq!(body, "Triage $TICKET\n");
+        assert_eq!(variables.len(), 3);
+        assert_eq!(variables[0].kind, PromptVariableKind::String);
+        assert_eq!(variables[0].default, None);
+        assert_eq!(variables[1].kind, PromptVariableKind::Enum);
+        assert_eq!(variables[1].options, vec!["low", "high"]);
+        assert_eq!(variables[1].default.as_deref(), Some("low"));
+        assert_eq!(variables[2].kind, PromptVariableKind::Number);
+    }
+
+    #[test]
+    fn variables_round_trip_through_prompt_contents() {
+        let variables = vec![
+            PromptVariable {
+                name: "DRY_RUN".to_string(),
+                kind: PromptVariableKind::Boolean,
+                options: Vec::new(),
+                default: Some("false".to_string()),
+            },
+            PromptVariable {
+                name: "MODE".to_string(),
+                kind: PromptVariableKind::Enum,
+                options: vec!["fast".to_string(), "safe".to_string()],
+                default: None,
+            },
+        ];
+        let contents = build_prompt_contents(None, None, &variables, "Run $MODE\n".to_string());
+        assert_eq!(
+            contents,
+            "---\nvariable: \"DRY_RUN: boolean = false\"\nvariable: \"MODE: enum(fast|safe)\"\n---\nRun $MODE\n"
+        );
+        let (_, _, parsed, body) = parse_frontmatter(&contents);
+        assert_eq!(parsed, variables);
+        assert_eq!(body, "Run $MODE\n");
+    }
+
+    #[test]
+    fn rejects_invalid_variable_declarations() {
+        let variable = |name: &str, kind, options: &[&str], default: Option<&str>| PromptVariable {
+            name: name.to_string(),
+            kind,
+            options: options.iter().map(|value| value.to_string()).collect(),
+            default: default.map(str::to_string),
+        };
+        assert!(
+            validate_variables(&[variable("lower", PromptVariableKind::String, &[], None)])
+                .is_err()
+        );
+        assert!(
+            validate_variables(&[variable("N", PromptVariableKind::Number, &[], Some("x"))])
+                .is_err()
+        );
+        assert!(validate_variables(&[variable("E", PromptVariableKind::Enum, &[], None)]).is_err());
+        assert!(
+            validate_variables(&[variable("E", PromptVariableKind::Enum, &["a"], Some("b"))])
+                .is_err()
+        );
+        assert!(validate_variables(&[
+            variable("A", PromptVariableKind::String, &[], None),
+            variable("A", PromptVariableKind::String, &[], None),
+        ])
+        .is_err());
+        assert!(parse_variable_spec("X: color").is_err());
+        assert!(validate_variables(&[variable(
+            "B",
+            PromptVariableKind::Boolean,
+            &[],
+            Some("true")
+        )])
+        .is_ok());
+    }
+}
@@ -0,0 +1,623 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tokio::sync::Mutex;
+use toml_edit::Document;
+
+use crate::codex::home::resolve_default_codex_home;
+use crate::shared::config_profiles_core::REASONING_EFFORTS;
+use crate::shared::config_toml_core;
+use crate::types::{AppSettings, SettingsSyncMode};
+
+const PERSONALITIES: &[&str] = &["friendly", "pragmatic"];
+
+/// How an `AppSettings` field is stored in `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SyncValue {
+    /// A boolean under `[features]`; the config key is `features.<name>`.
+    FeatureFlag,
+    /// A top-level string restricted to `allowed`. A missing or unknown value
+    /// reads as `default`, and an unknown app value removes the key.
+    Choice {
+        allowed: &'static [&'static str],
+        default: Option<&'static str>,
+    },
+    /// A free-form top-level string.
+    Text,
+    /// The app access mode, stored as `sandbox_mode` plus `approval_policy`.
+    AccessMode,
+}
+
+/// Which sync modes write the field to `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SyncScope {
+    /// Written in every mode; read back only in bidirectional mode.
+    Always,
+    /// Only read and written in bidirectional mode.
+    Bidirectional,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SettingsConfigMapping {
+    /// Serialized `AppSettings` field name.
+    pub(crate) setting: &'static str,
+    pub(crate) config_key: &'static str,
+    pub(crate) value: SyncValue,
+    pub(crate) scope: SyncScope,
+}
+
+pub(crate) const SETTINGS_CONFIG_MAPPINGS: &[SettingsConfigMapping] = &[
+    SettingsConfigMapping {
+        setting: "collaborationModesEnabled",
+        config_key: "features.collaboration_modes",
+        value: SyncValue::FeatureFlag,
+        scope: SyncScope::Always,
+    },
+    SettingsConfigMapping {
+        setting: "steerEnabled",
+        config_key: "features.steer",
+        value: SyncValue::FeatureFlag,
+        scope: SyncScope::Always,
+    },
+    SettingsConfigMapping {
+        setting: "unifiedExecEnabled",
+        config_key: "features.unified_exec",
+        value: SyncValue::FeatureFlag,
+        scope: SyncScope::Always,
+    },
+    SettingsConfigMapping {
+        setting: "experimentalAppsEnabled",
+        config_key: "features.apps",
+        value: SyncValue::FeatureFlag,
+        scope: SyncScope::Always,
+    },
+    SettingsConfigMapping {
+        setting: "personality",
+        config_key: "personality",
+        value: SyncValue::Choice {
+            allowed: PERSONALITIES,
+            default: Some("friendly"),
+        },
+        scope: SyncScope::Always,
+    },
+    SettingsConfigMapping {
+        setting: "lastComposerModelId",
+        config_key: "model",
+        value: SyncValue::Text,
+        scope: SyncScope::Bidirectional,
+    },
+    SettingsConfigMapping {
+        setting: "lastComposerReasoningEffort",
+        config_key: "model_reasoning_effort",
+        value: SyncValue::Choice {
+            allowed: REASONING_EFFORTS,
+            default: None,
+        },
+        scope: SyncScope::Bidirectional,
+    },
+    SettingsConfigMapping {
+        setting: "defaultAccessMode",
+        config_key: "sandbox_mode",
+        value: SyncValue::AccessMode,
+        scope: SyncScope::Bidirectional,
+    },
+];
+
+/// App access modes paired with the `sandbox_mode` and `approval_policy`
+/// they are written as.
+const ACCESS_MODES: &[(&str, &str, &str)] = &[
+    ("read-only", "read-only", "on-request"),
+    ("current", "workspace-write", "on-request"),
+    ("full-access", "danger-full-access", "never"),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsSyncConflict {
+    pub(crate) setting: String,
+    pub(crate) config_key: String,
+    pub(crate) app_value: Value,
+    pub(crate) config_value: Value,
+}
+
+/// Outcome of the most recent settings/config.toml sync.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsSyncReport {
+    pub(crate) checked_at: i64,
+    /// `config.toml` changed on disk since the app last read or wrote it.
+    pub(crate) external_edit: bool,
+    /// Settings that took their value from `config.toml`.
+    pub(crate) adopted: Vec<String>,
+    /// Settings changed in both places; the app value was kept.
+    pub(crate) conflicts: Vec<SettingsSyncConflict>,
+    pub(crate) errors: Vec<String>,
+}
+
+/// What `config.toml` held for each mapped setting after the last sync, and
+/// its mtime then, so external edits can be told apart from app edits.
+#[derive(Debug, Default)]
+pub(crate) struct SettingsSyncState {
+    config_mtime: Option<SystemTime>,
+    baseline: HashMap<&'static str, Value>,
+    last_report: SettingsSyncReport,
+}
+
+fn feature_name(config_key: &str) -> &str {
+    config_key.strip_prefix("features.").unwrap_or(config_key)
+}
+
+fn normalize_choice(allowed: &[&'static str], raw: &str) -> Option<&'static str> {
+    let normalized = raw.trim().to_ascii_lowercase();
+    allowed
+        .iter()
+        .copied()
+        .find(|candidate| *candidate == normalized)
+}
+
+/// The app-side value `config.toml` holds for `mapping`, or `None` when the
+/// file has no opinion.
+pub(crate) fn read_mapping(document: &Document, mapping: &SettingsConfigMapping) -> Option<Value> {
+    match mapping.value {
+        SyncValue::FeatureFlag => {
+            config_toml_core::read_feature_flag(document, feature_name(mapping.config_key))
+                .map(Value::Bool)
+        }
+        SyncValue::Choice { allowed, default } => {
+            config_toml_core::read_top_level_string(document, mapping.config_key)
+                .as_deref()
+                .and_then(|raw| normalize_choice(allowed, raw))
+                .or(default)
+                .map(|value| Value::String(value.to_string()))
+        }
+        SyncValue::Text => {
+            config_toml_core::read_top_level_string(document, mapping.config_key).map(Value::String)
+        }
+        SyncValue::AccessMode => {
+            let sandbox = config_toml_core::read_top_level_string(document, mapping.config_key)?;
+            ACCESS_MODES
+                .iter()
+                .find(|(_, sandbox_mode, _)| *sandbox_mode == sandbox)
+                .map(|(mode, _, _)| Value::String(mode.to_string()))
+        }
+    }
+}
+
+/// Writes the app value for `mapping` into `document`. `null` leaves the
+/// config untouched.
+pub(crate) fn write_mapping(
+    document: &mut Document,
+    mapping: &SettingsConfigMapping,
+    app_value: &Value,
+) -> Result<(), String> {
+    if app_value.is_null() {
+        return Ok(());
+    }
+    match mapping.value {
+        SyncValue::FeatureFlag => {
+            let enabled = app_value
+                .as_bool()
+                .ok_or_else(|| format!("`{}` must be a boolean", mapping.setting))?;
+            config_toml_core::set_feature_flag(document, feature_name(mapping.config_key), enabled)
+        }
+        SyncValue::Choice { allowed, .. } => {
+            let normalized = app_value
+                .as_str()
+                .and_then(|raw| normalize_choice(allowed, raw));
+            config_toml_core::set_top_level_string(document, mapping.config_key, normalized);
+            Ok(())
+        }
+        SyncValue::Text => {
+            config_toml_core::set_top_level_string(
+                document,
+                mapping.config_key,
+                app_value.as_str(),
+            );
+            Ok(())
+        }
+        SyncValue::AccessMode => {
+            let mode = app_value.as_str().unwrap_or_default();
+            let (_, sandbox_mode, approval_policy) = ACCESS_MODES
+                .iter()
+                .find(|(candidate, _, _)| *candidate == mode)
+                .ok_or_else(|| format!("Unknown access mode `{mode}`"))?;
+            config_toml_core::set_top_level_string(
+                document,
+                mapping.config_key,
+                Some(*sandbox_mode),
+            );
+            config_toml_core::set_top_level_string(
+                document,
+                "approval_policy",
+                Some(*approval_policy),
+            );
+            Ok(())
+        }
+    }
+}
+
+fn is_synced(mapping: &SettingsConfigMapping, bidirectional: bool) -> bool {
+    bidirectional || mapping.scope == SyncScope::Always
+}
+
+fn read_baseline(document: &Document) -> HashMap<&'static str, Value> {
+    SETTINGS_CONFIG_MAPPINGS
+        .iter()
+        .filter_map(|mapping| read_mapping(document, mapping).map(|value| (mapping.setting, value)))
+        .collect()
+}
+
+/// Merges config values into `next` and writes `next` back into `document`.
+///
+/// A setting the app left unchanged adopts the config value. A setting changed
+/// in both places since `baseline` is reported as a conflict and the app value
+/// wins. Config-side changes only count when `config_edited` is set.
+fn reconcile_document(
+    document: &mut Document,
+    previous: &Map<String, Value>,
+    next: &mut Map<String, Value>,
+    baseline: &HashMap<&'static str, Value>,
+    bidirectional: bool,
+    config_edited: bool,
+) -> SettingsSyncReport {
+    let mut report = SettingsSyncReport::default();
+    for mapping in SETTINGS_CONFIG_MAPPINGS {
+        if !is_synced(mapping, bidirectional) {
+            continue;
+        }
+        let app_value = next.get(mapping.setting).cloned().unwrap_or(Value::Null);
+        if bidirectional {
+            if let Some(config_value) = read_mapping(document, mapping) {
+                let previous_value = previous.get(mapping.setting).unwrap_or(&Value::Null);
+                let known_value = baseline.get(mapping.setting).unwrap_or(previous_value);
+                let app_changed = &app_value != previous_value;
+                let config_changed = config_edited && &config_value != known_value;
+                if config_value != app_value {
+                    if !app_changed {
+                        next.insert(mapping.setting.to_string(), config_value);
+                        report.adopted.push(mapping.setting.to_string());
+                        continue;
+                    }
+                    if config_changed {
+                        report.conflicts.push(SettingsSyncConflict {
+                            setting: mapping.setting.to_string(),
+                            config_key: mapping.config_key.to_string(),
+                            app_value: app_value.clone(),
+                            config_value,
+                        });
+                    }
+                }
+            }
+        }
+        if let Err(err) = write_mapping(document, mapping, &app_value) {
+            report.errors.push(format!("{}: {err}", mapping.config_key));
+        }
+    }
+    report
+}
+
+fn config_mtime(codex_home: &Path) -> Option<SystemTime> {
+    std::fs::metadata(codex_home.join("config.toml"))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn settings_to_map(settings: &AppSettings) -> Result<Map<String, Value>, String> {
+    match serde_json::to_value(settings).map_err(|err| err.to_string())? {
+        Value::Object(map) => Ok(map),
+        _ => Err("App settings must serialize to an object".to_string()),
+    }
+}
+
+/// Overlays `config.toml` values onto `settings` in bidirectional mode and
+/// records an external edit when the file changed since the last sync.
+pub(crate) async fn overlay_config_settings(
+    settings: AppSettings,
+    sync_state: &Mutex<SettingsSyncState>,
+) -> AppSettings {
+    if !matches!(settings.sync_mode, SettingsSyncMode::Bidirectional) {
+        return settings;
+    }
+    let Some(codex_home) = resolve_default_codex_home() else {
+        return settings;
+    };
+    let mut state = sync_state.lock().await;
+    let mut report = SettingsSyncReport {
+        checked_at: now_millis(),
+        ..SettingsSyncReport::default()
+    };
+    let document = match config_toml_core::load_global_config_document(&codex_home) {
+        Ok((_, document)) => document,
+        Err(err) => {
+            report.errors.push(err);
+            state.last_report = report;
+            return settings;
+        }
+    };
+    let mtime = config_mtime(&codex_home);
+    report.external_edit = state.config_mtime.is_some() && state.config_mtime != mtime;
+
+    let mut map = match settings_to_map(&settings) {
+        Ok(map) => map,
+        Err(err) => {
+            report.errors.push(err);
+            state.last_report = report;
+            return settings;
+        }
+    };
+    for mapping in SETTINGS_CONFIG_MAPPINGS {
+        if let Some(config_value) = read_mapping(&document, mapping) {
+            if map.get(mapping.setting) != Some(&config_value) {
+                report.adopted.push(mapping.setting.to_string());
+                map.insert(mapping.setting.to_string(), config_value);
+            }
+        }
+    }
+    state.baseline = read_baseline(&document);
+    state.config_mtime = mtime;
+    state.last_report = report;
+    serde_json::from_value(Value::Object(map)).unwrap_or(settings)
+}
+
+/// Reconciles `next` with `config.toml` and writes the managed fields back.
+/// Write failures are recorded in the sync report instead of failing the
+/// settings update.
+pub(crate) async fn sync_settings_to_config(
+    previous: &AppSettings,
+    next: AppSettings,
+    sync_state: &Mutex<SettingsSyncState>,
+) -> Result<AppSettings, String> {
+    let Some(codex_home) = resolve_default_codex_home() else {
+        return Ok(next);
+    };
+    let bidirectional = matches!(next.sync_mode, SettingsSyncMode::Bidirectional);
+    let mut state = sync_state.lock().await;
+    let mut report = SettingsSyncReport {
+        checked_at: now_millis(),
+        ..SettingsSyncReport::default()
+    };
+    let mut document = match config_toml_core::load_global_config_document(&codex_home) {
+        Ok((_, document)) => document,
+        Err(err) => {
+            report.errors.push(err);
+            state.last_report = report;
+            return Ok(next);
+        }
+    };
+    let original = document.to_string();
+    let mtime = config_mtime(&codex_home);
+    let external_edit = state.config_mtime.is_some() && state.config_mtime != mtime;
+    let config_edited = external_edit || state.baseline.is_empty();
+
+    let previous_map = settings_to_map(previous)?;
+    let mut next_map = settings_to_map(&next)?;
+    let reconciled = reconcile_document(
+        &mut document,
+        &previous_map,
+        &mut next_map,
+        &state.baseline,
+        bidirectional,
+        config_edited,
+    );
+    report.external_edit = external_edit;
+    report.adopted = reconciled.adopted;
+    report.conflicts = reconciled.conflicts;
+    report.errors = reconciled.errors;
+
+    if document.to_string() != original {
+        if let Err(err) = config_toml_core::persist_global_config_document(&codex_home, &document) {
+            report.errors.push(err);
+        }
+    }
+    state.baseline = read_baseline(&document);
+    state.config_mtime = config_mtime(&codex_home);
+    state.last_report = report;
+    serde_json::from_value(Value::Object(next_map)).map_err(|err| err.to_string())
+}
+
+pub(crate) async fn settings_sync_report_core(
+    sync_state: &Mutex<SettingsSyncState>,
+) -> SettingsSyncReport {
+    sync_state.lock().await.last_report.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping(setting: &str) -> &'static SettingsConfigMapping {
+        SETTINGS_CONFIG_MAPPINGS
+            .iter()
+            .find(|mapping| mapping.setting == setting)
+            .expect("mapping")
+    }
+
+    fn parse(contents: &str) -> Document {
+        config_toml_core::parse_document(contents).expect("parse")
+    }
+
+    fn write(contents: &str, setting: &str, app_value: Value) -> String {
+        let mut document = parse(contents);
+        write_mapping(&mut document, mapping(setting), &app_value).expect("write");
+        document.to_string()
+    }
+
+    #[test]
+    fn feature_flag_mappings_round_trip_under_features() {
+        for (setting, feature) in [
+            ("collaborationModesEnabled", "collaboration_modes"),
+            ("steerEnabled", "steer"),
+            ("unifiedExecEnabled", "unified_exec"),
+            ("experimentalAppsEnabled", "apps"),
+        ] {
+            let written = write("", setting, json!(false));
+            assert_eq!(
+                config_toml_core::read_feature_flag(&parse(&written), feature),
+                Some(false)
+            );
+            assert_eq!(
+                read_mapping(&parse(&written), mapping(setting)),
+                Some(json!(false))
+            );
+            assert_eq!(read_mapping(&parse(""), mapping(setting)), None);
+        }
+    }
+
+    #[test]
+    fn personality_mapping_normalizes_and_defaults() {
+        let personality = mapping("personality");
+        assert_eq!(
+            read_mapping(&parse("personality = \"PRAGMATIC\"\n"), personality),
+            Some(json!("pragmatic"))
+        );
+        assert_eq!(
+            read_mapping(&parse("personality = \"unknown\"\n"), personality),
+            Some(json!("friendly"))
+        );
+        assert_eq!(
+            write("", "personality", json!("Pragmatic")),
+            "personality = \"pragmatic\"\n"
+        );
+        let cleared = write(
+            "personality = \"pragmatic\"\n",
+            "personality",
+            json!("unknown"),
+        );
+        assert_eq!(
+            config_toml_core::read_top_level_string(&parse(&cleared), "personality"),
+            None
+        );
+    }
+
+    #[test]
+    fn model_mapping_round_trips_and_skips_null() {
+        let model = mapping("lastComposerModelId");
+        assert_eq!(read_mapping(&parse(""), model), None);
+        let written = write("", "lastComposerModelId", json!("gpt-5"));
+        assert_eq!(written, "model = \"gpt-5\"\n");
+        assert_eq!(read_mapping(&parse(&written), model), Some(json!("gpt-5")));
+        assert_eq!(
+            write("model = \"o3\"\n", "lastComposerModelId", Value::Null),
+            "model = \"o3\"\n"
+        );
+    }
+
+    #[test]
+    fn reasoning_effort_mapping_only_accepts_known_efforts() {
+        let effort = mapping("lastComposerReasoningEffort");
+        assert_eq!(
+            read_mapping(&parse("model_reasoning_effort = \"high\"\n"), effort),
+            Some(json!("high"))
+        );
+        assert_eq!(
+            read_mapping(&parse("model_reasoning_effort = \"extreme\"\n"), effort),
+            None
+        );
+        assert_eq!(
+            write("", "lastComposerReasoningEffort", json!("low")),
+            "model_reasoning_effort = \"low\"\n"
+        );
+    }
+
+    #[test]
+    fn access_mode_mapping_writes_sandbox_and_approval() {
+        let access = mapping("defaultAccessMode");
+        assert_eq!(
+            write("", "defaultAccessMode", json!("full-access")),
+            "sandbox_mode = \"danger-full-access\"\napproval_policy = \"never\"\n"
+        );
+        assert_eq!(
+            write("", "defaultAccessMode", json!("current")),
+            "sandbox_mode = \"workspace-write\"\napproval_policy = \"on-request\"\n"
+        );
+        assert_eq!(
+            read_mapping(&parse("sandbox_mode = \"read-only\"\n"), access),
+            Some(json!("read-only"))
+        );
+        assert_eq!(
+            read_mapping(&parse("sandbox_mode = \"yolo\"\n"), access),
+            None
+        );
+        let mut document = parse("");
+        assert!(write_mapping(&mut document, access, &json!("bogus")).is_err());
+    }
+
+    #[test]
+    fn reconcile_adopts_external_edits_and_reports_conflicts() {
+        let mut document = parse("model = \"o3\"\npersonality = \"pragmatic\"\n");
+        let baseline: HashMap<&'static str, Value> = [
+            ("lastComposerModelId", json!("gpt-5")),
+            ("personality", json!("friendly")),
+        ]
+        .into_iter()
+        .collect();
+        let previous: Map<String, Value> = [
+            ("lastComposerModelId".to_string(), json!("gpt-5")),
+            ("personality".to_string(), json!("friendly")),
+        ]
+        .into_iter()
+        .collect();
+        let mut next = previous.clone();
+        next.insert("lastComposerModelId".to_string(), json!("gpt-5-mini"));
+
+        let report = reconcile_document(&mut document, &previous, &mut next, &baseline, true, true);
+
+        assert_eq!(report.adopted, vec!["personality".to_string()]);
+        assert_eq!(
+            report.conflicts,
+            vec![SettingsSyncConflict {
+                setting: "lastComposerModelId".to_string(),
+                config_key: "model".to_string(),
+                app_value: json!("gpt-5-mini"),
+                config_value: json!("o3"),
+            }]
+        );
+        assert_eq!(next.get("personality"), Some(&json!("pragmatic")));
+        assert_eq!(
+            config_toml_core::read_top_level_string(&document, "model").as_deref(),
+            Some("gpt-5-mini")
+        );
+    }
+
+    #[test]
+    fn app_authoritative_mode_skips_bidirectional_mappings() {
+        let mut document = parse("model = \"o3\"\n");
+        let previous = Map::new();
+        let mut next: Map<String, Value> = [
+            ("lastComposerModelId".to_string(), json!("gpt-5")),
+            ("steerEnabled".to_string(), json!(true)),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = reconcile_document(
+            &mut document,
+            &previous,
+            &mut next,
+            &HashMap::new(),
+            false,
+            true,
+        );
+
+        assert!(report.adopted.is_empty() && report.conflicts.is_empty());
+        assert_eq!(
+            config_toml_core::read_top_level_string(&document, "model").as_deref(),
+            Some("o3")
+        );
+        assert_eq!(
+            config_toml_core::read_feature_flag(&document, "steer"),
+            Some(true)
+        );
+    }
+}
@@ -0,0 +1,886 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::codex::home::resolve_workspace_codex_home;
+use crate::shared::git_core::run_git_command;
+use crate::shared::process_core::tokio_command;
+use crate::shared::prompts_core::ensure_path_within_roots;
+use crate::types::{AppSettings, WorkspaceEntry};
+
+const SKILL_FILE: &str = "SKILL.md";
+/// Codex only discovers folders holding a `SKILL.md`, so a disabled skill
+/// keeps its folder and renames the file.
+const DISABLED_SKILL_FILE: &str = "SKILL.md.disabled";
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+/// Upper bound on the files copied for a single installed skill.
+const MAX_SKILL_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SkillEntry {
+    /// Folder name, which is also the name Codex invokes the skill by.
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) path: String,
+    pub(crate) scope: String,
+    pub(crate) enabled: bool,
+    pub(crate) content: String,
+    /// Frontmatter problems that would keep Codex from loading the skill.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum SkillSource {
+    Directory {
+        path: String,
+    },
+    Git {
+        url: String,
+        /// Folder inside the repository holding the skill, for repositories
+        /// that ship several.
+        #[serde(default)]
+        subdir: Option<String>,
+    },
+    Zip {
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstallSkillInput {
+    pub(crate) scope: String,
+    pub(crate) source: SkillSource,
+    /// Replaces an installed skill with the same name.
+    #[serde(default)]
+    pub(crate) overwrite: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SkillManifest {
+    pub(crate) name: String,
+    pub(crate) description: String,
+}
+
+struct SkillRoots {
+    /// New workspace skills go to the first folder.
+    workspace: Vec<PathBuf>,
+    global: Option<PathBuf>,
+}
+
+impl SkillRoots {
+    fn for_scope(&self, scope: &str) -> Result<PathBuf, String> {
+        match scope {
+            "workspace" => self
+                .workspace
+                .first()
+                .cloned()
+                .ok_or_else(|| "Unable to resolve the workspace skills folder".to_string()),
+            "global" => self
+                .global
+                .clone()
+                .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string()),
+            _ => Err("Invalid scope.".to_string()),
+        }
+    }
+
+    fn all(&self) -> Vec<PathBuf> {
+        self.workspace
+            .iter()
+            .cloned()
+            .chain(self.global.clone())
+            .collect()
+    }
+
+    fn scope_of(&self, skill_dir: &Path) -> &'static str {
+        let within = |root: &PathBuf| {
+            root.canonicalize()
+                .is_ok_and(|root| skill_dir.starts_with(root))
+        };
+        if self.workspace.iter().any(within) {
+            "workspace"
+        } else {
+            "global"
+        }
+    }
+}
+
+async fn skill_roots(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: &str,
+) -> Result<SkillRoots, String> {
+    let settings = app_settings.lock().await.clone();
+    let workspaces = workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or_else(|| "workspace not found".to_string())?;
+    let parent_entry = entry
+        .parent_id
+        .as_ref()
+        .and_then(|parent_id| workspaces.get(parent_id));
+    Ok(SkillRoots {
+        workspace: workspace_skill_roots(Path::new(&entry.path)),
+        global: resolve_workspace_codex_home(entry, parent_entry, Some(&settings))
+            .map(|home| home.join("skills")),
+    })
+}
+
+/// Project skill folders: the one `skills_list_core` passes to Codex, then
+/// `.codex/skills`.
+fn workspace_skill_roots(workspace_path: &Path) -> Vec<PathBuf> {
+    vec![
+        workspace_path.join(".agents").join("skills"),
+        workspace_path.join(".codex").join("skills"),
+    ]
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner.to_string();
+        }
+    }
+    value.to_string()
+}
+
+/// Top-level `key: value` pairs of the frontmatter, or `None` when the file
+/// has no closed `---` block. Indented lines are folded into the previous
+/// value, which covers `>`/`|` block scalars.
+fn parse_frontmatter(content: &str) -> Option<Vec<(String, String)>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in lines {
+        if line.trim() == "---" {
+            return Some(fields);
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = fields.last_mut() {
+                let text = line.trim();
+                if !text.is_empty() {
+                    if !value.is_empty() {
+                        value.push(' ');
+                    }
+                    value.push_str(text);
+                }
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            let value = if matches!(value, "|" | ">" | "|-" | ">-") {
+                ""
+            } else {
+                value
+            };
+            fields.push((key.trim().to_string(), unquote(value)));
+        }
+    }
+    None
+}
+
+fn validate_skill_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!("Skill name must be 1-{MAX_NAME_LEN} characters."));
+    }
+    let valid = name
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--");
+    if !valid {
+        return Err(format!(
+            "Skill name `{name}` must use lowercase letters, digits and single hyphens."
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the frontmatter Codex requires: a kebab-case `name`, matching the
+/// skill folder when `dir_name` is given, and a `description`.
+pub(crate) fn validate_skill_md(
+    content: &str,
+    dir_name: Option<&str>,
+) -> Result<SkillManifest, Vec<String>> {
+    let Some(fields) = parse_frontmatter(content) else {
+        return Err(vec![
+            "SKILL.md must start with a `---` frontmatter block.".to_string()
+        ]);
+    };
+    let field = |key: &str| {
+        fields
+            .iter()
+            .find(|(field_key, _)| field_key == key)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let mut issues = Vec::new();
+    let name = field("name");
+    match name {
+        None => issues.push("Frontmatter is missing `name`.".to_string()),
+        Some(name) => {
+            if let Err(err) = validate_skill_name(name) {
+                issues.push(err);
+            }
+            if let Some(dir_name) = dir_name.filter(|dir_name| *dir_name != name) {
+                issues.push(format!(
+                    "Skill name `{name}` must match its folder `{dir_name}`."
+                ));
+            }
+        }
+    }
+    let description = field("description");
+    match description {
+        None => issues.push("Frontmatter is missing `description`.".to_string()),
+        Some(description) if description.chars().count() > MAX_DESCRIPTION_LEN => issues.push(
+            format!("Description must be at most {MAX_DESCRIPTION_LEN} characters."),
+        ),
+        Some(_) => {}
+    }
+    match (name, description) {
+        (Some(name), Some(description)) if issues.is_empty() => Ok(SkillManifest {
+            name: name.to_string(),
+            description: description.to_string(),
+        }),
+        _ => Err(issues),
+    }
+}
+
+fn invalid_skill_md(issues: Vec<String>) -> String {
+    format!("SKILL.md is invalid: {}", issues.join(" "))
+}
+
+fn skill_file(skill_dir: &Path) -> Option<(PathBuf, bool)> {
+    let enabled = skill_dir.join(SKILL_FILE);
+    if enabled.is_file() {
+        return Some((enabled, true));
+    }
+    let disabled = skill_dir.join(DISABLED_SKILL_FILE);
+    disabled.is_file().then_some((disabled, false))
+}
+
+fn read_skill(skill_dir: &Path, scope: &str) -> Option<SkillEntry> {
+    let (file, enabled) = skill_file(skill_dir)?;
+    let name = skill_dir.file_name()?.to_str()?.to_string();
+    let content = fs::read_to_string(file).ok()?;
+    let (description, issues) = match validate_skill_md(&content, Some(name.as_str())) {
+        Ok(manifest) => (Some(manifest.description), Vec::new()),
+        Err(issues) => {
+            let description = parse_frontmatter(&content).and_then(|fields| {
+                fields
+                    .into_iter()
+                    .find(|(key, value)| key == "description" && !value.is_empty())
+                    .map(|(_, value)| value)
+            });
+            (description, issues)
+        }
+    };
+    Some(SkillEntry {
+        name,
+        description,
+        path: skill_dir.to_string_lossy().to_string(),
+        scope: scope.to_string(),
+        enabled,
+        content,
+        issues,
+    })
+}
+
+fn list_skills_in(root: &Path, scope: &str) -> Vec<SkillEntry> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut out: Vec<SkillEntry> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && !path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.'))
+        })
+        .filter_map(|path| read_skill(&path, scope))
+        .collect();
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+/// Canonical skill folder for `path`, which must be a direct child of one of
+/// the skill roots.
+fn resolve_skill_dir(path: &str, roots: &SkillRoots) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+    let all_roots = roots.all();
+    ensure_path_within_roots(&path, &all_roots)
+        .map_err(|_| "Skill path is not within the skill folders.".to_string())?;
+    let canonical = path.canonicalize().map_err(|err| err.to_string())?;
+    let is_skill_dir = all_roots.iter().any(|root| {
+        root.canonicalize()
+            .is_ok_and(|root| canonical.parent() == Some(root.as_path()))
+    });
+    if !is_skill_dir || skill_file(&canonical).is_none() {
+        return Err("Path is not a skill folder.".to_string());
+    }
+    Ok(canonical)
+}
+
+fn create_skill_in(root: &Path, scope: &str, content: &str) -> Result<SkillEntry, String> {
+    let manifest = validate_skill_md(content, None).map_err(invalid_skill_md)?;
+    let skill_dir = root.join(&manifest.name);
+    if skill_dir.exists() {
+        return Err(format!("Skill `{}` already exists.", manifest.name));
+    }
+    fs::create_dir_all(&skill_dir).map_err(|err| err.to_string())?;
+    fs::write(skill_dir.join(SKILL_FILE), content).map_err(|err| err.to_string())?;
+    read_skill(&skill_dir, scope).ok_or_else(|| "Failed to read skill.".to_string())
+}
+
+fn update_skill_in(skill_dir: &Path, scope: &str, content: &str) -> Result<SkillEntry, String> {
+    let dir_name = skill_dir.file_name().and_then(|name| name.to_str());
+    validate_skill_md(content, dir_name).map_err(invalid_skill_md)?;
+    let (file, _) = skill_file(skill_dir).ok_or_else(|| "Skill not found.".to_string())?;
+    fs::write(file, content).map_err(|err| err.to_string())?;
+    read_skill(skill_dir, scope).ok_or_else(|| "Failed to read skill.".to_string())
+}
+
+fn set_skill_enabled_in(
+    skill_dir: &Path,
+    scope: &str,
+    enabled: bool,
+) -> Result<SkillEntry, String> {
+    let (file, currently_enabled) =
+        skill_file(skill_dir).ok_or_else(|| "Skill not found.".to_string())?;
+    if currently_enabled != enabled {
+        let target = skill_dir.join(if enabled {
+            SKILL_FILE
+        } else {
+            DISABLED_SKILL_FILE
+        });
+        fs::rename(file, target).map_err(|err| err.to_string())?;
+    }
+    read_skill(skill_dir, scope).ok_or_else(|| "Failed to read skill.".to_string())
+}
+
+/// The folder holding `SKILL.md`: `base` itself, or its only child folder
+/// that has one, as archives usually wrap their contents in a folder.
+fn locate_skill_dir(base: &Path) -> Result<PathBuf, String> {
+    if base.join(SKILL_FILE).is_file() {
+        return Ok(base.to_path_buf());
+    }
+    let candidates: Vec<PathBuf> = fs::read_dir(base)
+        .map_err(|err| err.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join(SKILL_FILE).is_file())
+        .collect();
+    match candidates.as_slice() {
+        [only] => Ok(only.clone()),
+        [] => Err("No SKILL.md found in the skill source.".to_string()),
+        _ => Err("The skill source contains several skills; pick one folder.".to_string()),
+    }
+}
+
+/// Copies a skill folder, skipping symlinks and `.git` so nothing outside
+/// the source ends up installed.
+fn copy_skill_dir(source: &Path, target: &Path, budget: &mut u64) -> Result<(), String> {
+    fs::create_dir_all(target).map_err(|err| err.to_string())?;
+    for entry in fs::read_dir(source).map_err(|err| err.to_string())? {
+        let entry = entry.map_err(|err| err.to_string())?;
+        let file_type = entry.file_type().map_err(|err| err.to_string())?;
+        if file_type.is_symlink() || entry.file_name() == ".git" {
+            continue;
+        }
+        let destination = target.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_skill_dir(&entry.path(), &destination, budget)?;
+        } else if file_type.is_file() {
+            let size = entry.metadata().map_err(|err| err.to_string())?.len();
+            *budget = budget
+                .checked_sub(size)
+                .ok_or_else(|| "Skill is too large to install.".to_string())?;
+            fs::copy(entry.path(), &destination).map_err(|err| err.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// No archive crate is bundled, so zips are unpacked with the system tool:
+/// `unzip` on macOS and Linux, bsdtar on Windows. Both refuse entries that
+/// would escape `dest`.
+async fn extract_zip(archive: &Path, dest: &Path) -> Result<(), String> {
+    if !archive.is_file() {
+        return Err("Skill archive not found.".to_string());
+    }
+    fs::create_dir_all(dest).map_err(|err| err.to_string())?;
+    let mut command = if cfg!(windows) {
+        let mut command = tokio_command("tar");
+        command.arg("-xf").arg(archive).arg("-C").arg(dest);
+        command
+    } else {
+        let mut command = tokio_command("unzip");
+        command.arg("-q").arg("-o").arg(archive).arg("-d").arg(dest);
+        command
+    };
+    let output = command
+        .output()
+        .await
+        .map_err(|err| format!("Failed to extract skill archive: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to extract skill archive: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+async fn stage_skill_source(source: &SkillSource, staging: &Path) -> Result<PathBuf, String> {
+    match source {
+        SkillSource::Directory { path } => {
+            let path = PathBuf::from(path);
+            if !path.is_dir() {
+                return Err("Skill folder not found.".to_string());
+            }
+            Ok(path)
+        }
+        SkillSource::Git { url, subdir } => {
+            let url = url.trim();
+            if url.is_empty() || url.starts_with('-') {
+                return Err("Invalid skill repository URL.".to_string());
+            }
+            fs::create_dir_all(staging).map_err(|err| err.to_string())?;
+            run_git_command(
+                &staging.to_path_buf(),
+                &["clone", "-q", "--depth", "1", "--", url, "repo"],
+            )
+            .await
+            .map_err(|err| format!("Failed to clone skill repository: {err}"))?;
+            let repo = staging.join("repo");
+            match subdir
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+            {
+                Some(subdir) => {
+                    let nested = repo.join(subdir);
+                    ensure_path_within_roots(&nested, &[repo])
+                        .map_err(|_| "Skill folder is not within the repository.".to_string())?;
+                    Ok(nested)
+                }
+                None => Ok(repo),
+            }
+        }
+        SkillSource::Zip { path } => {
+            let dest = staging.join("archive");
+            extract_zip(Path::new(path), &dest).await?;
+            Ok(dest)
+        }
+    }
+}
+
+async fn install_staged_skill(
+    root: &Path,
+    scope: &str,
+    source: &SkillSource,
+    overwrite: bool,
+    staging: &Path,
+) -> Result<SkillEntry, String> {
+    let base = stage_skill_source(source, staging).await?;
+    let skill_dir = locate_skill_dir(&base)?;
+    let content = fs::read_to_string(skill_dir.join(SKILL_FILE)).map_err(|err| err.to_string())?;
+    let manifest = validate_skill_md(&content, None).map_err(invalid_skill_md)?;
+    let target = root.join(&manifest.name);
+    if target.exists() {
+        let same_folder = match (skill_dir.canonicalize(), target.canonicalize()) {
+            (Ok(source), Ok(target)) => source.starts_with(target),
+            _ => false,
+        };
+        if same_folder {
+            return Err(format!(
+                "Skill `{}` is already installed there.",
+                manifest.name
+            ));
+        }
+        if !overwrite {
+            return Err(format!("Skill `{}` is already installed.", manifest.name));
+        }
+    }
+
+    // Copy next to the target first so a failed copy leaves the installed
+    // skill untouched; dot folders are not listed as skills.
+    let incoming = root.join(format!(".{}.installing-{}", manifest.name, Uuid::new_v4()));
+    let mut budget = MAX_SKILL_BYTES;
+    let copied = copy_skill_dir(&skill_dir, &incoming, &mut budget).and_then(|()| {
+        // Symlinks are not copied, so a linked SKILL.md is missing here.
+        if incoming.join(SKILL_FILE).is_file() {
+            Ok(())
+        } else {
+            Err(format!("{SKILL_FILE} must be a regular file."))
+        }
+    });
+    if let Err(err) = copied {
+        let _ = fs::remove_dir_all(&incoming);
+        return Err(err);
+    }
+    if let Err(err) = swap_in_skill_dir(&incoming, &target) {
+        let _ = fs::remove_dir_all(&incoming);
+        return Err(err);
+    }
+    read_skill(&target, scope).ok_or_else(|| "Failed to read skill.".to_string())
+}
+
+/// Renames `incoming` to `target`, replacing an existing folder only once the
+/// new one is in place.
+fn swap_in_skill_dir(incoming: &Path, target: &Path) -> Result<(), String> {
+    if !target.exists() {
+        return fs::rename(incoming, target).map_err(|err| err.to_string());
+    }
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let previous = target.with_file_name(format!(".{file_name}.previous-{}", Uuid::new_v4()));
+    fs::rename(target, &previous).map_err(|err| err.to_string())?;
+    if let Err(err) = fs::rename(incoming, target) {
+        let _ = fs::rename(&previous, target);
+        return Err(err.to_string());
+    }
+    let _ = fs::remove_dir_all(&previous);
+    Ok(())
+}
+
+async fn install_skill_in(
+    root: &Path,
+    scope: &str,
+    source: &SkillSource,
+    overwrite: bool,
+) -> Result<SkillEntry, String> {
+    let staging =
+        std::env::temp_dir().join(format!("codex-monitor-skill-install-{}", Uuid::new_v4()));
+    let result = install_staged_skill(root, scope, source, overwrite, &staging).await;
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Skills on disk in the workspace and global scopes, including disabled
+/// ones and ones whose SKILL.md fails validation.
+pub(crate) async fn skills_local_list_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<Vec<SkillEntry>, String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let mut out = Vec::new();
+    for root in &roots.workspace {
+        out.extend(list_skills_in(root, "workspace"));
+    }
+    if let Some(global) = &roots.global {
+        out.extend(list_skills_in(global, "global"));
+    }
+    Ok(out)
+}
+
+pub(crate) async fn skills_create_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    scope: String,
+    content: String,
+) -> Result<SkillEntry, String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let root = roots.for_scope(&scope)?;
+    create_skill_in(&root, &scope, &content)
+}
+
+pub(crate) async fn skills_update_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    path: String,
+    content: String,
+) -> Result<SkillEntry, String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let skill_dir = resolve_skill_dir(&path, &roots)?;
+    update_skill_in(&skill_dir, roots.scope_of(&skill_dir), &content)
+}
+
+pub(crate) async fn skills_set_enabled_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    path: String,
+    enabled: bool,
+) -> Result<SkillEntry, String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let skill_dir = resolve_skill_dir(&path, &roots)?;
+    set_skill_enabled_in(&skill_dir, roots.scope_of(&skill_dir), enabled)
+}
+
+pub(crate) async fn skills_delete_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    path: String,
+) -> Result<(), String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let skill_dir = resolve_skill_dir(&path, &roots)?;
+    fs::remove_dir_all(&skill_dir).map_err(|err| format!("Failed to remove skill: {err}"))
+}
+
+pub(crate) async fn skills_install_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    input: InstallSkillInput,
+) -> Result<SkillEntry, String> {
+    let roots = skill_roots(workspaces, app_settings, &workspace_id).await?;
+    let root = roots.for_scope(&input.scope)?;
+    fs::create_dir_all(&root).map_err(|err| err.to_string())?;
+    install_skill_in(&root, &input.scope, &input.source, input.overwrite).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::{run_async, temp_dir};
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn skill_md(name: &str) -> String {
+        format!("---\nname: {name}\ndescription: Reviews {name} changes.\n---\n\n# {name}\n")
+    }
+
+    #[test]
+    fn validates_skill_frontmatter() {
+        let manifest =
+            validate_skill_md(&skill_md("code-review"), Some("code-review")).expect("valid skill");
+        assert_eq!(manifest.name, "code-review");
+        assert_eq!(manifest.description, "Reviews code-review changes.");
+
+        let folded = "---\nname: notes\ndescription: >\n  Takes notes\n  as you go.\n---\n";
+        assert_eq!(
+            validate_skill_md(folded, None)
+                .expect("folded description")
+                .description,
+            "Takes notes as you go."
+        );
+
+        assert!(validate_skill_md("# No frontmatter\n", None).is_err());
+        assert_eq!(
+            validate_skill_md("---\nname: Bad_Name\n---\n", None)
+                .expect_err("invalid")
+                .len(),
+            2
+        );
+        assert!(validate_skill_md(&skill_md("code-review"), Some("review")).is_err());
+    }
+
+    #[test]
+    fn creates_disables_and_updates_skills() {
+        let root = temp_dir("skills");
+        let skill = create_skill_in(&root, "workspace", &skill_md("lint")).expect("create");
+        assert!(skill.enabled);
+        assert!(create_skill_in(&root, "workspace", &skill_md("lint")).is_err());
+
+        let skill_dir = PathBuf::from(&skill.path);
+        let disabled = set_skill_enabled_in(&skill_dir, "workspace", false).expect("disable");
+        assert!(!disabled.enabled);
+        assert!(!skill_dir.join(SKILL_FILE).exists());
+
+        assert!(update_skill_in(&skill_dir, "workspace", &skill_md("other")).is_err());
+        let updated = update_skill_in(
+            &skill_dir,
+            "workspace",
+            "---\nname: lint\ndescription: Runs the linters.\n---\n",
+        )
+        .expect("update");
+        assert_eq!(updated.description.as_deref(), Some("Runs the linters."));
+        assert!(!updated.enabled);
+
+        fs::create_dir_all(root.join("broken")).expect("create broken");
+        fs::write(root.join("broken").join(SKILL_FILE), "no frontmatter").expect("write");
+        let listed = list_skills_in(&root, "workspace");
+        assert_eq!(listed.len(), 2);
+        assert!(!listed[0].issues.is_empty());
+        assert_eq!(listed[1].name, "lint");
+    }
+
+    #[test]
+    fn only_manages_folders_directly_under_skill_roots() {
+        let root = temp_dir("skills-roots");
+        let outside = temp_dir("skills-outside");
+        create_skill_in(&root, "workspace", &skill_md("lint")).expect("create");
+        fs::create_dir_all(outside.join("lint")).expect("create outside");
+        fs::write(outside.join("lint").join(SKILL_FILE), skill_md("lint")).expect("write");
+        let roots = SkillRoots {
+            workspace: vec![root.clone()],
+            global: None,
+        };
+
+        assert!(resolve_skill_dir(&root.join("lint").to_string_lossy(), &roots).is_ok());
+        assert!(resolve_skill_dir(&root.to_string_lossy(), &roots).is_err());
+        assert!(resolve_skill_dir(&outside.join("lint").to_string_lossy(), &roots).is_err());
+        assert!(
+            resolve_skill_dir(&root.join("lint").join("..").to_string_lossy(), &roots).is_err()
+        );
+    }
+
+    #[test]
+    fn lists_and_manages_skills_under_codex_skills() {
+        run_async(async {
+            let workspace_dir = temp_dir("skills-codex-dir");
+            let codex_skills = workspace_dir.join(".codex").join("skills");
+            create_skill_in(&codex_skills, "workspace", &skill_md("triage")).expect("create");
+            let entry = WorkspaceEntry {
+                id: "ws".to_string(),
+                name: "ws".to_string(),
+                path: workspace_dir.to_string_lossy().to_string(),
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            };
+            let workspaces = Mutex::new(HashMap::from([("ws".to_string(), entry)]));
+            let app_settings = Mutex::new(AppSettings::default());
+
+            let listed = skills_local_list_core(&workspaces, &app_settings, "ws".to_string())
+                .await
+                .expect("list");
+            let skill = listed
+                .iter()
+                .find(|skill| skill.scope == "workspace")
+                .expect("workspace skill");
+            assert_eq!(skill.name, "triage");
+
+            let disabled = skills_set_enabled_core(
+                &workspaces,
+                &app_settings,
+                "ws".to_string(),
+                skill.path.clone(),
+                false,
+            )
+            .await
+            .expect("disable");
+            assert!(!disabled.enabled);
+            assert_eq!(disabled.scope, "workspace");
+            assert!(codex_skills
+                .join("triage")
+                .join(DISABLED_SKILL_FILE)
+                .exists());
+        });
+    }
+
+    #[test]
+    fn installs_skill_from_git_subdir() {
+        run_async(async {
+            let upstream = temp_dir("skills-upstream");
+            let nested = upstream.join("skills").join("deploy");
+            fs::create_dir_all(nested.join("scripts")).expect("create skill");
+            fs::write(nested.join(SKILL_FILE), skill_md("deploy")).expect("write skill");
+            fs::write(nested.join("scripts").join("run.sh"), "echo deploy\n")
+                .expect("write script");
+            run_git_command(&upstream, &["init", "-q"])
+                .await
+                .expect("init");
+            run_git_command(&upstream, &["add", "-A"])
+                .await
+                .expect("add");
+            run_git_command(
+                &upstream,
+                &[
+                    "-c",
+                    "user.name=Test",
+                    "-c",
+                    "user.email=test@localhost",
+                    "-c",
+                    "commit.gpgsign=false",
+                    "commit",
+                    "-q",
+                    "-m",
+                    "Add deploy",
+                ],
+            )
+            .await
+            .expect("commit");
+
+            let root = temp_dir("skills-install");
+            let source = |subdir: &str| SkillSource::Git {
+                url: upstream.to_string_lossy().to_string(),
+                subdir: Some(subdir.to_string()),
+            };
+            let skill = install_skill_in(&root, "global", &source("skills/deploy"), false)
+                .await
+                .expect("install");
+            assert_eq!(skill.name, "deploy");
+            assert!(root.join("deploy").join("scripts").join("run.sh").is_file());
+            assert!(!root.join("deploy").join(".git").exists());
+
+            assert!(
+                install_skill_in(&root, "global", &source("skills/deploy"), false)
+                    .await
+                    .is_err()
+            );
+            assert!(
+                install_skill_in(&root, "global", &source("skills/deploy"), true)
+                    .await
+                    .is_ok()
+            );
+            assert!(install_skill_in(&root, "global", &source("../.."), false)
+                .await
+                .is_err());
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_overwrite_keeps_installed_skill() {
+        run_async(async {
+            let root = temp_dir("skills-overwrite");
+            let source_dir = temp_dir("skills-overwrite-source");
+            let skill_dir = source_dir.join("notes");
+            fs::create_dir_all(&skill_dir).expect("create skill");
+            fs::write(skill_dir.join(SKILL_FILE), skill_md("notes")).expect("write skill");
+            let source = SkillSource::Directory {
+                path: skill_dir.to_string_lossy().to_string(),
+            };
+            install_skill_in(&root, "global", &source, false)
+                .await
+                .expect("install");
+
+            let linked = source_dir.join("linked.md");
+            fs::write(&linked, skill_md("notes")).expect("write linked");
+            fs::remove_file(skill_dir.join(SKILL_FILE)).expect("remove skill file");
+            std::os::unix::fs::symlink(&linked, skill_dir.join(SKILL_FILE)).expect("symlink");
+            let err = install_skill_in(&root, "global", &source, true)
+                .await
+                .expect_err("linked SKILL.md");
+            assert_eq!(err, format!("{SKILL_FILE} must be a regular file."));
+
+            assert_eq!(
+                fs::read_to_string(root.join("notes").join(SKILL_FILE)).expect("installed"),
+                skill_md("notes")
+            );
+            let names: Vec<_> = fs::read_dir(&root)
+                .expect("read root")
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name())
+                .collect();
+            assert_eq!(names, vec![std::ffi::OsString::from("notes")]);
+        });
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the unit tests under `shared/`.
+
+use std::future::Future;
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+/// Creates an empty `codex-monitor-{prefix}-{uuid}` folder in the system
+/// temp dir.
+pub(crate) fn temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("codex-monitor-{prefix}-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+/// Drives `future` to completion on a fresh runtime; tokio's test macro is
+/// not enabled for this crate.
+pub(crate) fn run_async<F: Future<Output = ()>>(future: F) {
+    tokio::runtime::Runtime::new()
+        .expect("runtime")
+        .block_on(future);
+}
@@ -11,7 +11,8 @@ use tokio::sync::Mutex;
 
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::types::{
-    LocalThreadUsageSnapshot, ThreadTokenUsageBreakdown, ThreadTokenUsageSnapshot, WorkspaceEntry,
+    AppSettings, LocalThreadUsageSnapshot, ThreadTokenUsageBreakdown, ThreadTokenUsageSnapshot,
+    WorkspaceEntry,
 };
 
 #[derive(Default, Clone, Copy)]
@@ -157,6 +158,7 @@ struct IndexedSessionFile {
 
 pub(crate) async fn local_thread_usage_snapshot_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
     thread_ids: Vec<String>,
     workspace_path: Option<String>,
 ) -> Result<LocalThreadUsageSnapshot, String> {
@@ -178,9 +180,10 @@ pub(crate) async fn local_thread_usage_snapshot_core(
         }
     });
 
+    let settings = app_settings.lock().await.clone();
     let sessions_roots = {
         let workspaces = workspaces.lock().await;
-        resolve_sessions_roots(&workspaces, workspace_path.as_deref())
+        resolve_sessions_roots(&workspaces, &settings, workspace_path.as_deref())
     };
 
     let usage_by_thread = tokio::task::spawn_blocking(move || {
@@ -649,11 +652,12 @@ fn resolve_codex_sessions_root(codex_home_override: Option<PathBuf>) -> Option<P
 
 fn resolve_sessions_roots(
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     workspace_path: Option<&Path>,
 ) -> Vec<PathBuf> {
     if let Some(workspace_path) = workspace_path {
         let codex_home_override =
-            resolve_workspace_codex_home_for_path(workspaces, Some(workspace_path));
+            resolve_workspace_codex_home_for_path(workspaces, settings, Some(workspace_path));
         return resolve_codex_sessions_root(codex_home_override)
             .into_iter()
             .collect();
@@ -673,7 +677,8 @@ fn resolve_sessions_roots(
             .parent_id
             .as_ref()
             .and_then(|parent_id| workspaces.get(parent_id));
-        let Some(codex_home) = resolve_workspace_codex_home(entry, parent_entry) else {
+        let Some(codex_home) = resolve_workspace_codex_home(entry, parent_entry, Some(settings))
+        else {
             continue;
         };
         if let Some(root) = resolve_codex_sessions_root(Some(codex_home)) {
@@ -688,6 +693,7 @@ fn resolve_sessions_roots(
 
 fn resolve_workspace_codex_home_for_path(
     workspaces: &HashMap<String, WorkspaceEntry>,
+    settings: &AppSettings,
     workspace_path: Option<&Path>,
 ) -> Option<PathBuf> {
     let workspace_path = workspace_path?;
@@ -704,7 +710,7 @@ fn resolve_workspace_codex_home_for_path(
         .as_ref()
         .and_then(|parent_id| workspaces.get(parent_id));
 
-    resolve_workspace_codex_home(entry, parent_entry)
+    resolve_workspace_codex_home(entry, parent_entry, Some(settings))
 }
 
 #[cfg(test)]
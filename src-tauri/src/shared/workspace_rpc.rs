@@ -2,7 +2,9 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::types::WorkspaceSettings;
+use crate::types::{
+    CodexHomeScope, WorkspaceCheckoutOptions, WorkspaceSettings, WorktreeApplyStrategy,
+};
 
 #[allow(dead_code)]
 pub(crate) fn to_params<T: Serialize>(request: &T) -> Result<Value, String> {
@@ -21,6 +23,14 @@ pub(crate) struct ReadWorkspaceFileRequest {
     pub(crate) path: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WriteWorkspaceAgentsFileRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) path: String,
+    pub(crate) content: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct SetWorkspaceRuntimeCodexArgsRequest {
@@ -43,6 +53,8 @@ pub(crate) struct AddWorkspaceFromGitUrlRequest {
     pub(crate) url: String,
     pub(crate) destination_path: String,
     pub(crate) target_folder_name: Option<String>,
+    #[serde(default)]
+    pub(crate) checkout: Option<WorkspaceCheckoutOptions>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,6 +64,32 @@ pub(crate) struct AddWorktreeRequest {
     pub(crate) branch: String,
     pub(crate) name: Option<String>,
     pub(crate) copy_agents_md: bool,
+    #[serde(default)]
+    pub(crate) base_ref: Option<String>,
+    #[serde(default)]
+    pub(crate) detach: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartIssueAgentRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) issue_number: u64,
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) access_mode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApplyWorktreeChangesRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) strategy: Option<WorktreeApplyStrategy>,
+    #[serde(default)]
+    pub(crate) dry_run: Option<bool>,
+    #[serde(default)]
+    pub(crate) commit_message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,6 +98,52 @@ pub(crate) struct WorkspaceIdRequest {
     pub(crate) workspace_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleRequest {
+    pub(crate) root: String,
+    pub(crate) path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateCodexHomeProfileRequest {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SwitchCodexHomeProfileRequest {
+    pub(crate) scope: CodexHomeScope,
+    pub(crate) target_id: String,
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeleteCodexHomeProfileRequest {
+    pub(crate) profile_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeGcApplyRequest {
+    pub(crate) ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) allow_dirty: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeSetupRunRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) timeout_secs: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct IdRequest {
     pub(crate) id: String,
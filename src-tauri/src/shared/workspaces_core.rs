@@ -1,16 +1,21 @@
+mod bundle;
 mod connect;
 mod crud_persistence;
+mod gc;
 mod git_orchestration;
 mod helpers;
 mod io;
 mod runtime_codex_args;
+mod setup_script;
 mod worktree;
 
-pub(crate) use connect::connect_workspace_core;
+pub(crate) use bundle::{export_workspaces_core, import_workspaces_core};
+pub(crate) use connect::{connect_workspace_core, reconnect_workspaces_with_changed_runtime};
 pub(crate) use crud_persistence::{
     add_clone_core, add_workspace_core, add_workspace_from_git_url_core, remove_workspace_core,
     update_workspace_settings_core,
 };
+pub(crate) use gc::{worktree_gc_apply_core, worktree_gc_report_core};
 pub(crate) use git_orchestration::{apply_worktree_changes_core, run_git_command_unit};
 pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core};
 pub(crate) use io::{
@@ -20,6 +25,10 @@ pub(crate) use io::{
 pub(crate) use runtime_codex_args::{
     set_workspace_runtime_codex_args_core, WorkspaceRuntimeCodexArgsResult,
 };
+pub(crate) use setup_script::{
+    cancel_worktree_setup_core, start_worktree_setup_core, worktree_setup_log_core,
+    WorktreeSetupRuns,
+};
 pub(crate) use worktree::{
     add_worktree_core, remove_worktree_core, rename_worktree_core, rename_worktree_upstream_core,
     worktree_setup_mark_ran_core, worktree_setup_status_core,
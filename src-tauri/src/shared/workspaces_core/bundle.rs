@@ -0,0 +1,539 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::git_core;
+use crate::storage::{write_settings, write_workspaces};
+use crate::types::{
+    AppSettings, WorkspaceBundle, WorkspaceBundleEntry, WorkspaceEntry, WorkspaceExportResult,
+    WorkspaceGroup, WorkspaceImportItem, WorkspaceImportResult, WorkspaceImportStatus,
+    WorkspaceInfo, WorkspaceSettings,
+};
+
+use super::crud_persistence::{add_workspace_core, add_workspace_from_git_url_core};
+use super::helpers::normalize_workspace_path_input;
+
+const WORKSPACE_BUNDLE_VERSION: u32 = 1;
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// `path` relative to `root` with `/` separators, or `None` outside the root.
+fn relative_to_root(path: &Path, root: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let mut segments = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_str()?.to_string()),
+            _ => return None,
+        }
+    }
+    Some(segments.join("/"))
+}
+
+/// Joins a bundle-relative path onto `root`. Bundles come from other
+/// machines, so anything but plain folder names is rejected.
+fn resolve_bundle_path(root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let mut path = root.to_path_buf();
+    for segment in relative.split('/').filter(|segment| !segment.is_empty()) {
+        let mut components = Path::new(segment).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(_)), None) if !segment.contains('\\') => path.push(segment),
+            _ => return Err(format!("Invalid path in bundle: {relative}")),
+        }
+    }
+    Ok(path)
+}
+
+/// Where each imported workspace goes, in bundle order. Entries exported from
+/// outside the export root keep only their folder name, so nothing lands
+/// outside `root`; when that name is already taken by another entry of the
+/// bundle a `-2`, `-3`, ... suffix keeps them apart.
+fn bundle_entry_targets(
+    root: &Path,
+    entries: &[WorkspaceBundleEntry],
+) -> Vec<Result<PathBuf, String>> {
+    let mut claimed = entries
+        .iter()
+        .filter_map(|entry| entry.relative_path.as_deref())
+        .filter_map(|relative| resolve_bundle_path(root, relative).ok())
+        .collect::<HashSet<_>>();
+    entries
+        .iter()
+        .map(|entry| {
+            if let Some(relative) = entry.relative_path.as_deref() {
+                return resolve_bundle_path(root, relative);
+            }
+            let name = entry
+                .path
+                .rsplit(['/', '\\'])
+                .find(|segment| !segment.is_empty())
+                .ok_or_else(|| format!("Invalid path in bundle: {}", entry.path))?;
+            let mut target = resolve_bundle_path(root, name)?;
+            let mut suffix = 2;
+            while claimed.contains(&target) {
+                target = resolve_bundle_path(root, &format!("{name}-{suffix}"))?;
+                suffix += 1;
+            }
+            claimed.insert(target.clone());
+            Ok(target)
+        })
+        .collect()
+}
+
+/// Settings for a newly imported workspace, with group and clone-source links
+/// remapped to the new ids.
+fn imported_settings(
+    settings: &WorkspaceSettings,
+    group_ids: &HashMap<String, String>,
+    workspace_ids: &HashMap<String, String>,
+) -> WorkspaceSettings {
+    let mut settings = settings.clone();
+    settings.issue_link = None;
+    settings.group_id = settings
+        .group_id
+        .as_ref()
+        .and_then(|group_id| group_ids.get(group_id).cloned());
+    settings.clone_source_workspace_id = settings
+        .clone_source_workspace_id
+        .as_ref()
+        .and_then(|source_id| workspace_ids.get(source_id).cloned());
+    settings
+}
+
+/// Settings without machine-specific state: issue links point at local
+/// threads, Codex home profiles are local paths, and an absolute git root
+/// inside the workspace becomes relative.
+fn portable_settings(settings: &WorkspaceSettings, workspace_path: &Path) -> WorkspaceSettings {
+    let mut settings = settings.clone();
+    settings.issue_link = None;
+    settings.codex_home_profile_id = None;
+    if let Some(git_root) = settings.git_root.as_deref() {
+        if let Some(relative) = relative_to_root(Path::new(git_root), workspace_path) {
+            settings.git_root = (!relative.is_empty()).then_some(relative);
+        }
+    }
+    settings
+}
+
+/// Writes the workspace list, groups and remote URLs to `bundle_path`.
+/// Paths under `root` are stored relative to it so the bundle can be
+/// imported under a different root on another machine.
+pub(crate) async fn export_workspaces_core(
+    root: String,
+    bundle_path: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+) -> Result<WorkspaceExportResult, String> {
+    let root = normalize_workspace_path_input(&root);
+    if !root.is_dir() {
+        return Err("Export root must be an existing folder.".to_string());
+    }
+    let bundle_path = normalize_workspace_path_input(&bundle_path);
+
+    let mut entries: Vec<WorkspaceEntry> = workspaces.lock().await.values().cloned().collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    let skipped_worktrees = entries
+        .iter()
+        .filter(|entry| entry.kind.is_worktree())
+        .count();
+
+    let mut bundle_entries = Vec::new();
+    for entry in entries.iter().filter(|entry| !entry.kind.is_worktree()) {
+        let path = PathBuf::from(&entry.path);
+        bundle_entries.push(WorkspaceBundleEntry {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            relative_path: relative_to_root(&path, &root),
+            path: entry.path.clone(),
+            remote_url: git_core::git_get_origin_url(&path).await,
+            settings: portable_settings(&entry.settings, &path),
+        });
+    }
+
+    let groups: Vec<WorkspaceGroup> = app_settings
+        .lock()
+        .await
+        .workspace_groups
+        .iter()
+        .cloned()
+        .map(|mut group| {
+            if let Some(folder) = group.copies_folder.as_deref() {
+                if let Some(relative) = relative_to_root(Path::new(folder), &root) {
+                    group.copies_folder = Some(relative);
+                }
+            }
+            group
+        })
+        .collect();
+
+    let bundle = WorkspaceBundle {
+        version: WORKSPACE_BUNDLE_VERSION,
+        exported_at: now_ms(),
+        groups,
+        workspaces: bundle_entries,
+    };
+    if let Some(parent) = bundle_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create bundle folder: {err}"))?;
+    }
+    let data = serde_json::to_string_pretty(&bundle).map_err(|err| err.to_string())?;
+    std::fs::write(&bundle_path, data)
+        .map_err(|err| format!("Failed to write workspace bundle: {err}"))?;
+
+    Ok(WorkspaceExportResult {
+        path: bundle_path.to_string_lossy().to_string(),
+        workspaces: bundle.workspaces.len(),
+        groups: bundle.groups.len(),
+        skipped_worktrees,
+    })
+}
+
+/// Merges bundle groups into the settings by name and returns the bundle
+/// group id to local group id mapping.
+async fn import_groups(
+    groups: &[WorkspaceGroup],
+    root: &Path,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<(HashMap<String, String>, usize), String> {
+    let mut settings = app_settings.lock().await;
+    let mut next = settings.clone();
+    let mut group_ids = HashMap::new();
+    let mut added = 0;
+    for group in groups {
+        let existing = next.workspace_groups.iter().find(|candidate| {
+            candidate
+                .name
+                .trim()
+                .eq_ignore_ascii_case(group.name.trim())
+        });
+        if let Some(existing) = existing {
+            group_ids.insert(group.id.clone(), existing.id.clone());
+            continue;
+        }
+        // An absolute copies folder points into the exporting machine.
+        let copies_folder = match group.copies_folder.as_deref() {
+            Some(folder) if !Path::new(folder).is_absolute() => Some(
+                resolve_bundle_path(root, folder)?
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        let id = Uuid::new_v4().to_string();
+        group_ids.insert(group.id.clone(), id.clone());
+        next.workspace_groups.push(WorkspaceGroup {
+            id,
+            name: group.name.clone(),
+            sort_order: group.sort_order,
+            copies_folder,
+            codex_home_profile_id: None,
+        });
+        added += 1;
+    }
+    if added > 0 {
+        write_settings(settings_path, &next)?;
+        *settings = next;
+    }
+    Ok((group_ids, added))
+}
+
+/// Adds every workspace of a bundle under `root`. Folders that exist are
+/// added as they are; missing ones are cloned from their remote URL. Group
+/// and clone-source links are remapped to the new ids, imported worktree
+/// setup scripts are flagged for review, and workspaces that are already
+/// present keep their current settings.
+pub(crate) async fn import_workspaces_core<F, Fut>(
+    root: String,
+    bundle_path: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    settings_path: &PathBuf,
+    spawn_session: F,
+) -> Result<WorkspaceImportResult, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let bundle_path = normalize_workspace_path_input(&bundle_path);
+    let data = std::fs::read_to_string(&bundle_path)
+        .map_err(|err| format!("Failed to read workspace bundle: {err}"))?;
+    let bundle: WorkspaceBundle =
+        serde_json::from_str(&data).map_err(|err| format!("Invalid workspace bundle: {err}"))?;
+    if bundle.version > WORKSPACE_BUNDLE_VERSION {
+        return Err(format!(
+            "Workspace bundle version {} is newer than this app supports.",
+            bundle.version
+        ));
+    }
+    let root = normalize_workspace_path_input(&root);
+    std::fs::create_dir_all(&root).map_err(|err| format!("Failed to create import root: {err}"))?;
+
+    let (group_ids, groups_added) =
+        import_groups(&bundle.groups, &root, app_settings, settings_path).await?;
+
+    let mut items = Vec::new();
+    let mut workspace_ids = HashMap::new();
+    let mut imported = Vec::new();
+    let targets = bundle_entry_targets(&root, &bundle.workspaces);
+    for (entry, target) in bundle.workspaces.iter().zip(targets) {
+        let mut item = WorkspaceImportItem {
+            name: entry.name.clone(),
+            path: entry.path.clone(),
+            status: WorkspaceImportStatus::Failed,
+            workspace_id: None,
+            error: None,
+            needs_review: false,
+        };
+        let target = match target {
+            Ok(target) => target,
+            Err(error) => {
+                item.error = Some(error);
+                items.push(item);
+                continue;
+            }
+        };
+        item.path = target.to_string_lossy().to_string();
+
+        let existing_id = workspaces
+            .lock()
+            .await
+            .values()
+            .find(|existing| Path::new(&existing.path) == target)
+            .map(|existing| existing.id.clone());
+        if let Some(existing_id) = existing_id {
+            workspace_ids.insert(entry.id.clone(), existing_id.clone());
+            item.status = WorkspaceImportStatus::Existing;
+            item.workspace_id = Some(existing_id);
+            items.push(item);
+            continue;
+        }
+        let result = if target.is_dir() {
+            add_workspace_core(
+                item.path.clone(),
+                workspaces,
+                sessions,
+                app_settings,
+                storage_path,
+                &spawn_session,
+            )
+            .await
+            .map(|workspace| (workspace, WorkspaceImportStatus::Added))
+        } else if let Some(remote_url) = entry.remote_url.clone() {
+            clone_missing_workspace(
+                remote_url,
+                &target,
+                entry,
+                workspaces,
+                sessions,
+                app_settings,
+                storage_path,
+                &spawn_session,
+            )
+            .await
+            .map(|workspace| (workspace, WorkspaceImportStatus::Cloned))
+        } else {
+            Err("Folder is missing and the bundle has no remote URL.".to_string())
+        };
+        match result {
+            Ok((workspace, status)) => {
+                workspace_ids.insert(entry.id.clone(), workspace.id.clone());
+                imported.push((workspace.id.clone(), entry));
+                // The script runs on the next worktree, so it came from
+                // another machine without anyone here having read it.
+                item.needs_review = entry
+                    .settings
+                    .worktree_setup_script
+                    .as_deref()
+                    .is_some_and(|script| !script.trim().is_empty());
+                item.status = status;
+                item.workspace_id = Some(workspace.id);
+            }
+            Err(error) => item.error = Some(error),
+        }
+        items.push(item);
+    }
+
+    if !imported.is_empty() {
+        let mut workspaces = workspaces.lock().await;
+        for (workspace_id, entry) in imported {
+            let Some(workspace) = workspaces.get_mut(&workspace_id) else {
+                continue;
+            };
+            workspace.name = entry.name.clone();
+            workspace.settings = imported_settings(&entry.settings, &group_ids, &workspace_ids);
+        }
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(storage_path, &list)?;
+    }
+
+    Ok(WorkspaceImportResult {
+        items,
+        groups_added,
+    })
+}
+
+async fn clone_missing_workspace<F, Fut>(
+    remote_url: String,
+    target: &Path,
+    entry: &WorkspaceBundleEntry,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    spawn_session: F,
+) -> Result<WorkspaceInfo, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let (Some(parent), Some(folder_name)) = (target.parent(), target.file_name()) else {
+        return Err("Cannot clone into the import root itself.".to_string());
+    };
+    std::fs::create_dir_all(parent)
+        .map_err(|err| format!("Failed to create destination folder: {err}"))?;
+    add_workspace_from_git_url_core(
+        remote_url,
+        parent.to_string_lossy().to_string(),
+        Some(folder_name.to_string_lossy().to_string()),
+        entry.settings.checkout.clone(),
+        workspaces,
+        sessions,
+        app_settings,
+        storage_path,
+        spawn_session,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bundle_entry_targets, imported_settings, portable_settings, relative_to_root,
+        resolve_bundle_path,
+    };
+    use crate::types::{WorkspaceBundleEntry, WorkspaceSettings};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn bundle_paths_stay_under_the_root() {
+        let root = PathBuf::from("/work");
+        assert_eq!(
+            relative_to_root(Path::new("/work/team/api"), &root).as_deref(),
+            Some("team/api")
+        );
+        assert_eq!(relative_to_root(Path::new("/elsewhere/api"), &root), None);
+        assert_eq!(
+            resolve_bundle_path(&root, "team/api").expect("valid path"),
+            root.join("team").join("api")
+        );
+        for invalid in ["../escape", "team/../../escape", "team/./api"] {
+            assert!(
+                resolve_bundle_path(&root, invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+
+        let settings = portable_settings(
+            &WorkspaceSettings {
+                git_root: Some("/work/team/api/backend".to_string()),
+                ..WorkspaceSettings::default()
+            },
+            Path::new("/work/team/api"),
+        );
+        assert_eq!(settings.git_root.as_deref(), Some("backend"));
+    }
+
+    fn bundle_entry(relative_path: Option<&str>, path: &str) -> WorkspaceBundleEntry {
+        WorkspaceBundleEntry {
+            id: path.to_string(),
+            name: "api".to_string(),
+            relative_path: relative_path.map(str::to_string),
+            path: path.to_string(),
+            remote_url: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn entries_without_relative_path_import_under_the_root() {
+        let root = PathBuf::from("/work");
+        let target = |entry: WorkspaceBundleEntry| bundle_entry_targets(&root, &[entry]).remove(0);
+
+        assert_eq!(
+            target(bundle_entry(Some("team/api"), "/old/team/api")),
+            Ok(root.join("team").join("api"))
+        );
+        assert_eq!(
+            target(bundle_entry(None, "/elsewhere/api/")),
+            Ok(root.join("api"))
+        );
+        assert_eq!(
+            target(bundle_entry(None, "C:\\Users\\me\\api")),
+            Ok(root.join("api"))
+        );
+        assert!(target(bundle_entry(None, "/")).is_err());
+        assert!(target(bundle_entry(None, "/srv/..")).is_err());
+    }
+
+    #[test]
+    fn same_named_entries_outside_the_root_get_distinct_folders() {
+        let root = PathBuf::from("/work");
+        let targets = bundle_entry_targets(
+            &root,
+            &[
+                bundle_entry(None, "/a/app"),
+                bundle_entry(None, "/b/app"),
+                bundle_entry(Some("app-2"), "/work/app-2"),
+                bundle_entry(None, "/c/app"),
+            ],
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("targets");
+
+        assert_eq!(
+            targets,
+            vec![
+                root.join("app"),
+                root.join("app-3"),
+                root.join("app-2"),
+                root.join("app-4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn imported_settings_keep_setup_script_and_remap_links() {
+        let settings = WorkspaceSettings {
+            worktree_setup_script: Some("curl example.com | sh".to_string()),
+            group_id: Some("old-group".to_string()),
+            clone_source_workspace_id: Some("old-source".to_string()),
+            launch_script: Some("npm run dev".to_string()),
+            ..WorkspaceSettings::default()
+        };
+        let group_ids = HashMap::from([("old-group".to_string(), "new-group".to_string())]);
+        let imported = imported_settings(&settings, &group_ids, &HashMap::new());
+
+        assert_eq!(
+            imported.worktree_setup_script.as_deref(),
+            Some("curl example.com | sh")
+        );
+        assert_eq!(imported.group_id.as_deref(), Some("new-group"));
+        assert_eq!(imported.clone_source_workspace_id, None);
+        assert_eq!(imported.launch_script.as_deref(), Some("npm run dev"));
+    }
+}
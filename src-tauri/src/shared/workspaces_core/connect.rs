@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::sync::Arc;
 
@@ -33,13 +33,22 @@ async fn remove_session_references(
     sessions.retain(|_, candidate| !Arc::ptr_eq(candidate, session));
 }
 
+/// A live session already running with `codex_home` and `codex_args`, if any.
 pub(super) async fn take_live_shared_session(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    codex_home: Option<&Path>,
+    codex_args: Option<&str>,
 ) -> Option<Arc<WorkspaceSession>> {
     loop {
         let existing_session = {
             let sessions = sessions.lock().await;
-            sessions.values().next().cloned()
+            sessions
+                .values()
+                .find(|session| {
+                    session.codex_home.as_deref() == codex_home
+                        && session.codex_args.as_deref() == codex_args
+                })
+                .cloned()
         };
         let Some(existing_session) = existing_session else {
             return None;
@@ -73,7 +82,17 @@ where
         }
         remove_session_references(sessions, &existing_for_entry).await;
     }
-    if let Some(existing_session) = take_live_shared_session(sessions).await {
+    let (default_bin, codex_args, codex_home) = {
+        let settings = app_settings.lock().await;
+        (
+            settings.codex_bin.clone(),
+            resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
+            resolve_workspace_codex_home(&entry, parent_entry.as_ref(), Some(&settings)),
+        )
+    };
+    if let Some(existing_session) =
+        take_live_shared_session(sessions, codex_home.as_deref(), codex_args.as_deref()).await
+    {
         existing_session
             .register_workspace_with_path(&entry.id, Some(&entry.path))
             .await;
@@ -83,14 +102,6 @@ where
             .insert(entry.id.clone(), existing_session);
         return Ok(());
     }
-    let (default_bin, codex_args) = {
-        let settings = app_settings.lock().await;
-        (
-            settings.codex_bin.clone(),
-            resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
-        )
-    };
-    let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
     let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
     session
         .register_workspace_with_path(&entry.id, Some(&entry.path))
@@ -99,6 +110,56 @@ where
     Ok(())
 }
 
+/// Moves connected workspaces whose Codex home or args no longer match their
+/// session's onto a matching session. Threads running on the old session are
+/// stopped for those workspaces. Returns the moved ids.
+pub(crate) async fn reconnect_workspaces_with_changed_runtime<F, Fut>(
+    workspace_ids: &[String],
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    spawn_session: F,
+) -> Result<Vec<String>, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let mut reconnected = Vec::new();
+    for workspace_id in workspace_ids {
+        let Ok((entry, parent_entry)) = resolve_entry_and_parent(workspaces, workspace_id).await
+        else {
+            continue;
+        };
+        let Some(current) = sessions.lock().await.get(workspace_id).cloned() else {
+            continue;
+        };
+        let (codex_args, codex_home) = {
+            let settings = app_settings.lock().await;
+            (
+                resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
+                resolve_workspace_codex_home(&entry, parent_entry.as_ref(), Some(&settings)),
+            )
+        };
+        if current.codex_home == codex_home && current.codex_args == codex_args {
+            continue;
+        }
+        {
+            let _spawn_guard = workspace_session_spawn_lock().lock().await;
+            kill_session_by_id(sessions, workspace_id).await;
+        }
+        connect_workspace_core(
+            workspace_id.clone(),
+            workspaces,
+            sessions,
+            app_settings,
+            &spawn_session,
+        )
+        .await?;
+        reconnected.push(workspace_id.clone());
+    }
+    Ok(reconnected)
+}
+
 pub(super) async fn kill_session_by_id(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     id: &str,
@@ -135,7 +196,7 @@ mod tests {
     use tokio::process::Command;
     use tokio::sync::Mutex;
 
-    use crate::types::{WorkspaceKind, WorkspaceSettings};
+    use crate::types::{CodexHomeProfile, WorkspaceKind, WorkspaceSettings};
 
     fn make_workspace_entry(id: &str) -> WorkspaceEntry {
         WorkspaceEntry {
@@ -150,6 +211,10 @@ mod tests {
     }
 
     fn make_session(_entry: WorkspaceEntry) -> Arc<WorkspaceSession> {
+        make_session_with_home(None)
+    }
+
+    fn make_session_with_home(codex_home: Option<PathBuf>) -> Arc<WorkspaceSession> {
         let mut cmd = if cfg!(windows) {
             let mut cmd = Command::new("cmd");
             cmd.args(["/C", "more"]);
@@ -169,6 +234,7 @@ mod tests {
 
         Arc::new(WorkspaceSession {
             codex_args: None,
+            codex_home,
             child: Mutex::new(child),
             stdin: Mutex::new(stdin),
             pending: Mutex::new(HashMap::new()),
@@ -250,4 +316,72 @@ mod tests {
             kill_session_by_id(&sessions, &entry.id).await;
         });
     }
+
+    #[test]
+    fn connect_workspace_shares_sessions_only_within_a_codex_home() {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let mut client = make_workspace_entry("ws-client");
+            client.settings.codex_home_profile_id = Some("client".to_string());
+            let mut client_two = make_workspace_entry("ws-client-2");
+            client_two.settings.codex_home_profile_id = Some("client".to_string());
+            let mut internal = make_workspace_entry("ws-internal");
+            internal.settings.codex_home_profile_id = Some("internal".to_string());
+            let workspaces = Mutex::new(HashMap::from([
+                (client.id.clone(), client.clone()),
+                (client_two.id.clone(), client_two.clone()),
+                (internal.id.clone(), internal.clone()),
+            ]));
+            let client_session = make_session_with_home(Some(PathBuf::from("/tmp/codex-client")));
+            let sessions = Mutex::new(HashMap::from([(
+                client.id.clone(),
+                Arc::clone(&client_session),
+            )]));
+            let mut settings = AppSettings::default();
+            settings.codex_home_profiles = ["client", "internal"]
+                .into_iter()
+                .map(|id| CodexHomeProfile {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    path: format!("/tmp/codex-{id}"),
+                })
+                .collect();
+            let app_settings = Mutex::new(settings);
+            let spawned_homes = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+            for workspace_id in [&client_two.id, &internal.id] {
+                let spawned_homes = Arc::clone(&spawned_homes);
+                connect_workspace_core(
+                    workspace_id.clone(),
+                    &workspaces,
+                    &sessions,
+                    &app_settings,
+                    move |_entry, _default_bin, _codex_args, codex_home: Option<PathBuf>| {
+                        let spawned_homes = Arc::clone(&spawned_homes);
+                        async move {
+                            spawned_homes
+                                .lock()
+                                .expect("lock spawned homes")
+                                .push(codex_home.clone());
+                            Ok(make_session_with_home(codex_home))
+                        }
+                    },
+                )
+                .await
+                .expect("connect workspace");
+            }
+
+            assert_eq!(
+                *spawned_homes.lock().expect("lock spawned homes"),
+                vec![Some(PathBuf::from("/tmp/codex-internal"))]
+            );
+            {
+                let sessions = sessions.lock().await;
+                assert!(Arc::ptr_eq(&sessions[&client_two.id], &client_session));
+                assert!(!Arc::ptr_eq(&sessions[&internal.id], &client_session));
+            }
+            kill_session_by_id(&sessions, &client.id).await;
+            kill_session_by_id(&sessions, &client_two.id).await;
+            kill_session_by_id(&sessions, &internal.id).await;
+        });
+    }
 }
@@ -12,10 +12,19 @@ use crate::codex::home::resolve_workspace_codex_home;
 use crate::shared::process_core::kill_child_process_tree;
 use crate::shared::{git_core, worktree_core};
 use crate::storage::write_workspaces;
-use crate::types::{AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings};
-
-use super::connect::{kill_session_by_id, take_live_shared_session, workspace_session_spawn_lock};
-use super::helpers::{normalize_setup_script, normalize_workspace_path_input};
+use crate::types::{
+    AppSettings, WorkspaceCheckoutOptions, WorkspaceEntry, WorkspaceInfo, WorkspaceKind,
+    WorkspaceSettings,
+};
+
+use super::connect::{
+    kill_session_by_id, reconnect_workspaces_with_changed_runtime, take_live_shared_session,
+    workspace_session_spawn_lock,
+};
+use super::helpers::{
+    checkout_sparse_paths, normalize_checkout_options, normalize_setup_script,
+    normalize_workspace_path_input, sparse_checkout_set_args,
+};
 
 pub(crate) async fn add_workspace_core<F, Fut>(
     path: String,
@@ -51,18 +60,19 @@ where
     };
 
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
-    let existing_session = take_live_shared_session(sessions).await;
+    let (default_bin, codex_args, codex_home) = {
+        let settings = app_settings.lock().await;
+        (
+            settings.codex_bin.clone(),
+            resolve_workspace_codex_args(&entry, None, Some(&settings)),
+            resolve_workspace_codex_home(&entry, None, Some(&settings)),
+        )
+    };
+    let existing_session =
+        take_live_shared_session(sessions, codex_home.as_deref(), codex_args.as_deref()).await;
     let (session, spawned_new_session) = if let Some(existing_session) = existing_session {
         (existing_session, false)
     } else {
-        let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
-            (
-                settings.codex_bin.clone(),
-                resolve_workspace_codex_args(&entry, None, Some(&settings)),
-            )
-        };
-        let codex_home = resolve_workspace_codex_home(&entry, None);
         (
             spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?,
             true,
@@ -155,15 +165,28 @@ where
         worktree_core::build_clone_destination_path(&copies_folder_path, &copy_name);
     let destination_path_string = destination_path.to_string_lossy().to_string();
 
-    if let Err(error) = git_core::run_git_command(
-        &copies_folder_path,
-        &["clone", &source_entry.path, &destination_path_string],
-    )
-    .await
-    {
+    // Local clones hardlink objects, so only the sparse paths are applied;
+    // depth and filter are kept for clones of this clone's remote.
+    let checkout = source_entry.settings.checkout.clone();
+    let sparse_paths = checkout_sparse_paths(checkout.as_ref());
+    let mut clone_args = vec!["clone"];
+    if !sparse_paths.is_empty() {
+        clone_args.push("--sparse");
+    }
+    clone_args.extend([source_entry.path.as_str(), destination_path_string.as_str()]);
+    if let Err(error) = git_core::run_git_command(&copies_folder_path, &clone_args).await {
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
+    if !sparse_paths.is_empty() {
+        if let Err(error) =
+            git_core::run_git_command(&destination_path, &sparse_checkout_set_args(&sparse_paths))
+                .await
+        {
+            let _ = tokio::fs::remove_dir_all(&destination_path).await;
+            return Err(error);
+        }
+    }
 
     if let Some(origin_url) = git_core::git_get_origin_url(&PathBuf::from(&source_entry.path)).await
     {
@@ -196,23 +219,29 @@ where
         settings: WorkspaceSettings {
             group_id: inherited_group_id,
             clone_source_workspace_id,
+            forge: source_entry.settings.forge,
+            forge_api_url: source_entry.settings.forge_api_url.clone(),
+            checkout,
+            codex_home_profile_id: source_entry.settings.codex_home_profile_id.clone(),
+            active_profile: source_entry.settings.active_profile.clone(),
             ..WorkspaceSettings::default()
         },
     };
 
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
-    let existing_session = take_live_shared_session(sessions).await;
+    let (default_bin, codex_args, codex_home) = {
+        let settings = app_settings.lock().await;
+        (
+            settings.codex_bin.clone(),
+            resolve_workspace_codex_args(&entry, None, Some(&settings)),
+            resolve_workspace_codex_home(&entry, None, Some(&settings)),
+        )
+    };
+    let existing_session =
+        take_live_shared_session(sessions, codex_home.as_deref(), codex_args.as_deref()).await;
     let (session, spawned_new_session) = if let Some(existing_session) = existing_session {
         (existing_session, false)
     } else {
-        let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
-            (
-                settings.codex_bin.clone(),
-                resolve_workspace_codex_args(&entry, None, Some(&settings)),
-            )
-        };
-        let codex_home = resolve_workspace_codex_home(&entry, None);
         match spawn_session(entry.clone(), default_bin, codex_args, codex_home).await {
             Ok(session) => (session, true),
             Err(error) => {
@@ -298,6 +327,7 @@ pub(crate) async fn add_workspace_from_git_url_core<F, Fut>(
     url: String,
     destination_path: String,
     target_folder_name: Option<String>,
+    checkout: Option<WorkspaceCheckoutOptions>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     app_settings: &Mutex<AppSettings>,
@@ -320,6 +350,7 @@ where
     if !destination_parent.is_dir() {
         return Err("Destination folder must be an existing directory.".to_string());
     }
+    let checkout = normalize_checkout_options(checkout)?;
 
     let folder_name = target_folder_name
         .as_deref()
@@ -342,12 +373,34 @@ where
     }
 
     let clone_path_string = clone_path.to_string_lossy().to_string();
-    if let Err(error) =
-        git_core::run_git_command(&destination_parent, &["clone", &url, &clone_path_string]).await
-    {
+    let sparse_paths = checkout_sparse_paths(checkout.as_ref());
+    let depth_arg = checkout
+        .as_ref()
+        .and_then(|checkout| checkout.depth)
+        .map(|depth| format!("--depth={depth}"));
+    let filter_arg = checkout
+        .as_ref()
+        .and_then(|checkout| checkout.filter.as_deref())
+        .map(|filter| format!("--filter={filter}"));
+    let mut clone_args = vec!["clone"];
+    clone_args.extend(depth_arg.as_deref());
+    clone_args.extend(filter_arg.as_deref());
+    if !sparse_paths.is_empty() {
+        clone_args.push("--sparse");
+    }
+    clone_args.extend(["--", url.as_str(), clone_path_string.as_str()]);
+    if let Err(error) = git_core::run_git_command(&destination_parent, &clone_args).await {
         let _ = tokio::fs::remove_dir_all(&clone_path).await;
         return Err(error);
     }
+    if !sparse_paths.is_empty() {
+        if let Err(error) =
+            git_core::run_git_command(&clone_path, &sparse_checkout_set_args(&sparse_paths)).await
+        {
+            let _ = tokio::fs::remove_dir_all(&clone_path).await;
+            return Err(error);
+        }
+    }
 
     let workspace_name = clone_path
         .file_name()
@@ -361,22 +414,26 @@ where
         kind: WorkspaceKind::Main,
         parent_id: None,
         worktree: None,
-        settings: WorkspaceSettings::default(),
+        settings: WorkspaceSettings {
+            checkout,
+            ..WorkspaceSettings::default()
+        },
     };
 
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
-    let existing_session = take_live_shared_session(sessions).await;
+    let (default_bin, codex_args, codex_home) = {
+        let settings = app_settings.lock().await;
+        (
+            settings.codex_bin.clone(),
+            resolve_workspace_codex_args(&entry, None, Some(&settings)),
+            resolve_workspace_codex_home(&entry, None, Some(&settings)),
+        )
+    };
+    let existing_session =
+        take_live_shared_session(sessions, codex_home.as_deref(), codex_args.as_deref()).await;
     let (session, spawned_new_session) = if let Some(existing_session) = existing_session {
         (existing_session, false)
     } else {
-        let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
-            (
-                settings.codex_bin.clone(),
-                resolve_workspace_codex_args(&entry, None, Some(&settings)),
-            )
-        };
-        let codex_home = resolve_workspace_codex_home(&entry, None);
         match spawn_session(entry.clone(), default_bin, codex_args, codex_home).await {
             Ok(session) => (session, true),
             Err(error) => {
@@ -538,10 +595,10 @@ pub(crate) async fn update_workspace_settings_core<FApplySettings, FSpawn, FutSp
     mut settings: WorkspaceSettings,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    _app_settings: &Mutex<AppSettings>,
+    app_settings: &Mutex<AppSettings>,
     storage_path: &PathBuf,
     apply_settings_update: FApplySettings,
-    _spawn_session: FSpawn,
+    spawn_session: FSpawn,
 ) -> Result<WorkspaceInfo, String>
 where
     FApplySettings: Fn(
@@ -553,14 +610,16 @@ where
     FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
     settings.worktree_setup_script = normalize_setup_script(settings.worktree_setup_script);
+    settings.checkout = normalize_checkout_options(settings.checkout)?;
 
-    let (entry_snapshot, previous_worktree_setup_script, child_entries) = {
+    let (entry_snapshot, previous_worktree_setup_script, previous_runtime, child_entries) = {
         let mut workspaces = workspaces.lock().await;
         let previous_entry = workspaces
             .get(&id)
             .cloned()
             .ok_or_else(|| "workspace not found".to_string())?;
         let previous_worktree_setup_script = previous_entry.settings.worktree_setup_script.clone();
+        let previous_runtime = runtime_settings(&previous_entry.settings);
         let entry_snapshot = apply_settings_update(&mut workspaces, &id, settings)?;
         let child_entries = workspaces
             .values()
@@ -570,6 +629,7 @@ where
         (
             entry_snapshot,
             previous_worktree_setup_script,
+            previous_runtime,
             child_entries,
         )
     };
@@ -598,6 +658,19 @@ where
         workspaces.values().cloned().collect()
     };
     write_workspaces(storage_path, &list)?;
+    if previous_runtime != runtime_settings(&entry_snapshot.settings) {
+        let affected = std::iter::once(entry_snapshot.id.clone())
+            .chain(child_entries.iter().map(|child| child.id.clone()))
+            .collect::<Vec<_>>();
+        reconnect_workspaces_with_changed_runtime(
+            &affected,
+            workspaces,
+            sessions,
+            app_settings,
+            spawn_session,
+        )
+        .await?;
+    }
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
@@ -610,6 +683,14 @@ where
     })
 }
 
+/// Settings that change how the workspace's app-server is started.
+fn runtime_settings(settings: &WorkspaceSettings) -> (Option<String>, Option<String>) {
+    (
+        settings.codex_home_profile_id.clone(),
+        settings.active_profile.clone(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::{default_repo_name_from_url, validate_target_folder_name};
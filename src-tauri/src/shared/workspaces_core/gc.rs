@@ -0,0 +1,677 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use git2::{Oid, Repository, StatusOptions};
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::types::{
+    AppSettings, WorkspaceEntry, WorktreeGcApplyResult, WorktreeGcItem, WorktreeGcItemKind,
+    WorktreeGcReport, WorktreeGcSkipped,
+};
+
+use super::helpers::{
+    worktree_setup_log_path, worktree_setup_marker_path, worktree_setup_run_path,
+};
+use super::worktree::{remove_worktree_core, remove_worktree_dir};
+
+/// Merged worktrees younger than this are not recommended for removal; a
+/// fresh worktree without commits is trivially "merged".
+const MERGED_GRACE_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Default)]
+struct DirUsage {
+    size_bytes: u64,
+    newest: Option<SystemTime>,
+}
+
+/// Total file size and newest modification time below `path`, without
+/// following symlinks.
+fn dir_usage(path: &Path) -> DirUsage {
+    let mut usage = DirUsage::default();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            usage.size_bytes += metadata.len();
+            if let Ok(modified) = metadata.modified() {
+                usage.newest = Some(usage.newest.map_or(modified, |newest| newest.max(modified)));
+            }
+        }
+    }
+    usage
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn idle_secs(
+    now: SystemTime,
+    newest_file: Option<SystemTime>,
+    commit_time: Option<i64>,
+) -> Option<u64> {
+    let newest_file = newest_file
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64);
+    let last_activity = newest_file.into_iter().chain(commit_time).max()?;
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    Some((now - last_activity).max(0) as u64)
+}
+
+#[derive(Default)]
+struct RepoState {
+    tip: Option<Oid>,
+    branch: Option<String>,
+    commit_time: Option<i64>,
+    upstream_gone: bool,
+    dirty: bool,
+}
+
+fn upstream_gone(repo: &Repository, refname: &str) -> bool {
+    let Ok(upstream) = repo.branch_upstream_name(refname) else {
+        return false;
+    };
+    upstream
+        .as_str()
+        .is_some_and(|name| repo.find_reference(name).is_err())
+}
+
+fn inspect_repo(path: &Path) -> Option<RepoState> {
+    let repo = Repository::open(path).ok()?;
+    let mut state = RepoState::default();
+    if let Ok(head) = repo.head() {
+        state.tip = head.target();
+        if head.is_branch() {
+            state.branch = head.shorthand().map(str::to_string);
+            state.upstream_gone = head
+                .name()
+                .is_some_and(|refname| upstream_gone(&repo, refname));
+        }
+    }
+    state.commit_time = state
+        .tip
+        .and_then(|oid| repo.find_commit(oid).ok())
+        .map(|commit| commit.time().seconds());
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+    // A status that can't be read is treated as dirty so it is never
+    // recommended for removal.
+    state.dirty = repo
+        .statuses(Some(&mut options))
+        .map(|statuses| !statuses.is_empty())
+        .unwrap_or(true);
+    Some(state)
+}
+
+/// Like `inspect_repo`, but a directory that isn't a readable repository
+/// counts as dirty for the same reason an unreadable status does.
+fn inspect_repo_or_dirty(path: &Path) -> RepoState {
+    inspect_repo(path).unwrap_or(RepoState {
+        dirty: true,
+        ..RepoState::default()
+    })
+}
+
+/// Whether `tip` is contained in the parent's HEAD or `origin/HEAD`.
+fn merged_into_parent(parent_path: &Path, tip: Option<Oid>) -> bool {
+    let (Some(tip), Ok(parent)) = (tip, Repository::open(parent_path)) else {
+        return false;
+    };
+    let head = parent.head().ok().and_then(|head| head.target());
+    let origin_head = parent
+        .find_reference("refs/remotes/origin/HEAD")
+        .and_then(|reference| reference.resolve())
+        .ok()
+        .and_then(|reference| reference.target());
+    [head, origin_head]
+        .into_iter()
+        .flatten()
+        .any(|base| base == tip || parent.graph_descendant_of(base, tip).unwrap_or(false))
+}
+
+/// Whether a clone's commits all exist elsewhere: no stash, and every branch
+/// commit on a remote (the equivalent of an empty
+/// `git rev-list --branches --not --remotes`). Repositories that can't be
+/// read count as holding local commits.
+fn clone_commits_are_pushed(path: &Path) -> bool {
+    let Ok(repo) = Repository::open(path) else {
+        return false;
+    };
+    if repo.find_reference("refs/stash").is_ok() {
+        return false;
+    }
+    let Ok(mut walk) = repo.revwalk() else {
+        return false;
+    };
+    if walk.push_glob("refs/heads/*").is_err() || walk.hide_glob("refs/remotes/*").is_err() {
+        return false;
+    }
+    walk.next().is_none()
+}
+
+fn origin_url(path: &Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(str::to_string)
+}
+
+fn subdirectories(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut dirs = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs
+}
+
+struct KnownPaths(Vec<PathBuf>);
+
+impl KnownPaths {
+    /// A path is in use when it is a workspace or contains one.
+    fn in_use(&self, path: &Path) -> bool {
+        let path = normalize_path(path);
+        self.0.iter().any(|known| known.starts_with(&path))
+    }
+}
+
+fn directory_item(
+    id: String,
+    kind: WorktreeGcItemKind,
+    path: &Path,
+    parent: Option<&WorkspaceEntry>,
+    now: SystemTime,
+) -> WorktreeGcItem {
+    let usage = dir_usage(path);
+    let repo = inspect_repo_or_dirty(path);
+    let merged = parent.is_some_and(|parent| merged_into_parent(Path::new(&parent.path), repo.tip));
+    WorktreeGcItem {
+        id,
+        kind,
+        path: path.to_string_lossy().to_string(),
+        workspace_id: None,
+        parent_id: parent.map(|parent| parent.id.clone()),
+        branch: repo.branch,
+        merged,
+        upstream_gone: repo.upstream_gone,
+        missing: false,
+        dirty: repo.dirty,
+        idle_secs: idle_secs(now, usage.newest, repo.commit_time),
+        size_bytes: usage.size_bytes,
+        recommended: !repo.dirty,
+    }
+}
+
+fn worktree_item(
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+    now: SystemTime,
+) -> WorktreeGcItem {
+    let path = Path::new(&entry.path);
+    let missing = !path.is_dir();
+    let (usage, repo) = if missing {
+        // Nothing is left on disk to lose.
+        (DirUsage::default(), RepoState::default())
+    } else {
+        (dir_usage(path), inspect_repo_or_dirty(path))
+    };
+    let merged = parent.is_some_and(|parent| merged_into_parent(Path::new(&parent.path), repo.tip));
+    let idle = idle_secs(now, usage.newest, repo.commit_time);
+    let recommended = !repo.dirty
+        && (missing
+            || repo.upstream_gone
+            || (merged && idle.is_some_and(|idle| idle >= MERGED_GRACE_SECS)));
+    WorktreeGcItem {
+        id: format!("worktree:{}", entry.id),
+        kind: WorktreeGcItemKind::Worktree,
+        path: entry.path.clone(),
+        workspace_id: Some(entry.id.clone()),
+        parent_id: entry.parent_id.clone(),
+        branch: repo.branch.or_else(|| {
+            entry
+                .worktree
+                .as_ref()
+                .map(|worktree| worktree.branch.clone())
+        }),
+        merged,
+        upstream_gone: repo.upstream_gone,
+        missing,
+        dirty: repo.dirty,
+        idle_secs: idle,
+        size_bytes: usage.size_bytes,
+        recommended,
+    }
+}
+
+fn stale_metadata_items(parent: &WorkspaceEntry) -> Vec<WorktreeGcItem> {
+    let Ok(repo) = Repository::open(&parent.path) else {
+        return Vec::new();
+    };
+    let Ok(names) = repo.worktrees() else {
+        return Vec::new();
+    };
+    names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let worktree = repo.find_worktree(name).ok()?;
+            if worktree.validate().is_ok() {
+                return None;
+            }
+            Some(WorktreeGcItem {
+                id: format!("stale:{}:{name}", parent.id),
+                kind: WorktreeGcItemKind::StaleWorktreeMetadata,
+                path: worktree.path().to_string_lossy().to_string(),
+                workspace_id: None,
+                parent_id: Some(parent.id.clone()),
+                branch: None,
+                merged: false,
+                upstream_gone: false,
+                missing: true,
+                dirty: false,
+                idle_secs: None,
+                size_bytes: 0,
+                recommended: true,
+            })
+        })
+        .collect()
+}
+
+/// Orphaned clones must have an `origin` matching a tracked project, so
+/// unrelated repositories that share a copies folder are never reported.
+fn orphaned_clone_items(
+    entries: &[WorkspaceEntry],
+    copies_folders: &[PathBuf],
+    known: &KnownPaths,
+    now: SystemTime,
+) -> Vec<WorktreeGcItem> {
+    let tracked_origins = entries
+        .iter()
+        .filter(|entry| !entry.kind.is_worktree())
+        .flat_map(|entry| [origin_url(Path::new(&entry.path)), Some(entry.path.clone())])
+        .flatten()
+        .collect::<HashSet<_>>();
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    for folder in copies_folders {
+        for dir in subdirectories(folder) {
+            if !seen.insert(normalize_path(&dir)) || known.in_use(&dir) {
+                continue;
+            }
+            if !dir.join(".git").exists() {
+                continue;
+            }
+            let Some(origin) = origin_url(&dir) else {
+                continue;
+            };
+            if !tracked_origins.contains(&origin) {
+                continue;
+            }
+            let mut item = directory_item(
+                format!("clone:{}", dir.to_string_lossy()),
+                WorktreeGcItemKind::OrphanedClone,
+                &dir,
+                None,
+                now,
+            );
+            // A clone has no parent to be merged into; it is only safe to
+            // delete when none of its commits or stashes would be lost.
+            item.recommended = item.recommended && clone_commits_are_pushed(&dir);
+            items.push(item);
+        }
+    }
+    items
+}
+
+fn build_gc_report(
+    entries: Vec<WorkspaceEntry>,
+    copies_folders: Vec<PathBuf>,
+    data_dir: PathBuf,
+) -> WorktreeGcReport {
+    let now = SystemTime::now();
+    let by_id = entries
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry))
+        .collect::<HashMap<_, _>>();
+    let known = KnownPaths(
+        entries
+            .iter()
+            .map(|entry| normalize_path(Path::new(&entry.path)))
+            .collect(),
+    );
+
+    let mut items = entries
+        .iter()
+        .filter(|entry| entry.kind.is_worktree())
+        .map(|entry| {
+            let parent = entry
+                .parent_id
+                .as_deref()
+                .and_then(|parent_id| by_id.get(parent_id).copied());
+            worktree_item(entry, parent, now)
+        })
+        .collect::<Vec<_>>();
+
+    for parent_dir in subdirectories(&data_dir.join("worktrees")) {
+        let parent_id = parent_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let parent = by_id.get(parent_id.as_str()).copied();
+        for dir in subdirectories(&parent_dir) {
+            if known.in_use(&dir) {
+                continue;
+            }
+            let mut item = directory_item(
+                format!("orphan:{}", dir.to_string_lossy()),
+                WorktreeGcItemKind::OrphanedWorktreeDir,
+                &dir,
+                parent,
+                now,
+            );
+            item.parent_id = Some(parent_id.clone());
+            items.push(item);
+        }
+    }
+
+    for entry in entries.iter().filter(|entry| !entry.kind.is_worktree()) {
+        items.extend(stale_metadata_items(entry));
+    }
+    items.extend(orphaned_clone_items(&entries, &copies_folders, &known, now));
+
+    let total_bytes = items.iter().map(|item| item.size_bytes).sum();
+    let reclaimable_bytes = items
+        .iter()
+        .filter(|item| item.recommended)
+        .map(|item| item.size_bytes)
+        .sum();
+    WorktreeGcReport {
+        items,
+        total_bytes,
+        reclaimable_bytes,
+    }
+}
+
+/// Lists worktrees and leftovers that can be cleaned up: known worktrees with
+/// merge/upstream state, orphaned directories under the app's worktrees
+/// folder and the configured copies folders, and stale `git worktree`
+/// metadata. Sizes and idle ages are included for every item.
+pub(crate) async fn worktree_gc_report_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    data_dir: &PathBuf,
+) -> Result<WorktreeGcReport, String> {
+    let entries = workspaces
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    let copies_folders = app_settings
+        .lock()
+        .await
+        .workspace_groups
+        .iter()
+        .filter_map(|group| group.copies_folder.as_deref())
+        .filter(|folder| !folder.trim().is_empty())
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    let data_dir = data_dir.clone();
+    tokio::task::spawn_blocking(move || build_gc_report(entries, copies_folders, data_dir))
+        .await
+        .map_err(|err| format!("Failed to scan worktrees: {err}"))
+}
+
+fn remove_worktree_setup_files(data_dir: &PathBuf, workspace_id: &str) {
+    for path in [
+        worktree_setup_marker_path(data_dir, workspace_id),
+        worktree_setup_log_path(data_dir, workspace_id),
+        worktree_setup_run_path(data_dir, workspace_id),
+    ] {
+        if let Err(err) = std::fs::remove_file(&path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Failed to remove {}: {err}", path.display());
+            }
+        }
+    }
+}
+
+/// Removes the selected report items. The report is rebuilt first, so only
+/// items it still lists are touched. Worktrees go through
+/// `remove_worktree_core` and orphaned worktree directories through the same
+/// `git worktree remove` path; dirty items are skipped unless `allow_dirty`.
+pub(crate) async fn worktree_gc_apply_core<FRunGit, FutRunGit, FIsMissing, FRemoveDirAll>(
+    ids: Vec<String>,
+    allow_dirty: bool,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    data_dir: &PathBuf,
+    storage_path: &PathBuf,
+    run_git_command: FRunGit,
+    is_missing_worktree_error: FIsMissing,
+    remove_dir_all: FRemoveDirAll,
+) -> Result<WorktreeGcApplyResult, String>
+where
+    FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
+    FutRunGit: Future<Output = Result<(), String>>,
+    FIsMissing: Fn(&str) -> bool,
+    FRemoveDirAll: Fn(&PathBuf) -> Result<(), String>,
+{
+    let report = worktree_gc_report_core(workspaces, app_settings, data_dir).await?;
+    let items = report
+        .items
+        .into_iter()
+        .map(|item| (item.id.clone(), item))
+        .collect::<HashMap<_, _>>();
+    let mut result = WorktreeGcApplyResult::default();
+    let mut pruned_parents = HashSet::new();
+    let mut seen = HashSet::new();
+
+    for id in ids {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        let Some(item) = items.get(&id) else {
+            result.skipped.push(WorktreeGcSkipped {
+                id,
+                reason: "No longer reported as removable.".to_string(),
+            });
+            continue;
+        };
+        if item.dirty && !allow_dirty {
+            result.skipped.push(WorktreeGcSkipped {
+                id,
+                reason: "Has uncommitted changes.".to_string(),
+            });
+            continue;
+        }
+
+        let path = PathBuf::from(&item.path);
+        if item.kind == WorktreeGcItemKind::OrphanedClone && !clone_commits_are_pushed(&path) {
+            result.skipped.push(WorktreeGcSkipped {
+                id,
+                reason: "Has commits or stashes that are not on a remote.".to_string(),
+            });
+            continue;
+        }
+        let parent_path = {
+            let workspaces = workspaces.lock().await;
+            item.parent_id
+                .as_ref()
+                .and_then(|parent_id| workspaces.get(parent_id))
+                .map(|parent| PathBuf::from(&parent.path))
+        };
+        let outcome = match item.kind {
+            WorktreeGcItemKind::Worktree => {
+                let workspace_id = item.workspace_id.clone().unwrap_or_default();
+                let removed = remove_worktree_core(
+                    workspace_id.clone(),
+                    workspaces,
+                    sessions,
+                    storage_path,
+                    &run_git_command,
+                    &is_missing_worktree_error,
+                    &remove_dir_all,
+                )
+                .await;
+                if removed.is_ok() {
+                    remove_worktree_setup_files(data_dir, &workspace_id);
+                }
+                removed
+            }
+            WorktreeGcItemKind::OrphanedWorktreeDir => match parent_path {
+                Some(parent_path) if parent_path.is_dir() => {
+                    remove_worktree_dir(
+                        &parent_path,
+                        &path,
+                        &run_git_command,
+                        &is_missing_worktree_error,
+                        &remove_dir_all,
+                    )
+                    .await
+                }
+                _ => remove_dir_all(&path),
+            },
+            WorktreeGcItemKind::OrphanedClone => remove_dir_all(&path),
+            WorktreeGcItemKind::StaleWorktreeMetadata => match parent_path {
+                Some(parent_path) => {
+                    if pruned_parents.insert(parent_path.clone()) {
+                        run_git_command(&parent_path, &["worktree", "prune", "--expire", "now"])
+                            .await
+                    } else {
+                        Ok(())
+                    }
+                }
+                None => Err("worktree parent not found".to_string()),
+            },
+        };
+        match outcome {
+            Ok(()) => {
+                result.freed_bytes += item.size_bytes;
+                result.removed.push(id);
+            }
+            Err(reason) => result.skipped.push(WorktreeGcSkipped { id, reason }),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_support::temp_dir;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn commit_file(repo: &Repository, root: &Path, name: &str) {
+        std::fs::write(root.join(name), name).expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new(name)).expect("add path");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents = parent.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &signature, &signature, name, &tree, &parents)
+            .expect("commit");
+    }
+
+    #[test]
+    fn orphaned_clone_with_unpushed_commit_is_not_recommended() {
+        let upstream = temp_dir("gc-upstream");
+        let upstream_repo = Repository::init(&upstream).expect("init upstream");
+        commit_file(&upstream_repo, &upstream, "README.md");
+        let upstream_url = upstream.to_string_lossy().to_string();
+
+        let copies = temp_dir("gc-copies");
+        Repository::clone(&upstream_url, copies.join("pushed")).expect("clone pushed");
+        let local = Repository::clone(&upstream_url, copies.join("local")).expect("clone local");
+        commit_file(&local, &copies.join("local"), "local.txt");
+
+        let entries = vec![WorkspaceEntry {
+            id: "main".to_string(),
+            name: "main".to_string(),
+            path: upstream_url,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }];
+        let report = build_gc_report(entries, vec![copies.clone()], temp_dir("gc-data"));
+        let clone_item = |name: &str| {
+            report
+                .items
+                .iter()
+                .find(|item| item.path == copies.join(name).to_string_lossy())
+                .expect("clone item")
+        };
+
+        assert!(clone_item("pushed").recommended);
+        let local_item = clone_item("local");
+        assert!(!local_item.dirty);
+        assert!(!local_item.recommended);
+    }
+
+    #[test]
+    fn unreadable_orphan_dir_is_dirty_and_missing_worktree_is_recommended() {
+        let data_dir = temp_dir("gc-data");
+        let orphan = data_dir.join("worktrees").join("main").join("leftover");
+        std::fs::create_dir_all(&orphan).expect("create orphan dir");
+        std::fs::write(orphan.join("notes.txt"), "keep me").expect("write orphan file");
+
+        let entries = vec![WorkspaceEntry {
+            id: "gone".to_string(),
+            name: "gone".to_string(),
+            path: data_dir.join("missing").to_string_lossy().to_string(),
+            kind: WorkspaceKind::Worktree,
+            parent_id: Some("main".to_string()),
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }];
+        let report = build_gc_report(entries, Vec::new(), data_dir.clone());
+
+        let orphan_item = report
+            .items
+            .iter()
+            .find(|item| item.kind == WorktreeGcItemKind::OrphanedWorktreeDir)
+            .expect("orphan item");
+        assert!(orphan_item.dirty);
+        assert!(!orphan_item.recommended);
+        let missing_item = report
+            .items
+            .iter()
+            .find(|item| item.kind == WorktreeGcItemKind::Worktree)
+            .expect("worktree item");
+        assert!(missing_item.missing);
+        assert!(missing_item.recommended);
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn idle_secs_uses_latest_activity() {
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(10_000);
+        let file = Some(UNIX_EPOCH + std::time::Duration::from_secs(9_000));
+        assert_eq!(idle_secs(now, file, Some(9_500)), Some(500));
+        assert_eq!(idle_secs(now, file, None), Some(1_000));
+        assert_eq!(idle_secs(now, None, Some(11_000)), Some(0));
+        assert_eq!(idle_secs(now, None, None), None);
+    }
+}
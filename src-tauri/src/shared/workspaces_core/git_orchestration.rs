@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
-use std::process::Stdio;
+use std::process::{Output, Stdio};
 
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
@@ -9,7 +9,9 @@ use tokio::sync::Mutex;
 use crate::git_utils::resolve_git_root;
 use crate::shared::process_core::tokio_command;
 use crate::shared::{git_core, worktree_core};
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    WorkspaceEntry, WorktreeApplyOptions, WorktreeApplyResult, WorktreeApplyStrategy,
+};
 
 pub(crate) fn run_git_command_unit<F, Fut>(
     repo_path: &PathBuf,
@@ -32,10 +34,13 @@ where
     }
 }
 
+const PARENT_DIRTY_ERROR: &str = "Your current branch has uncommitted changes. Please commit, stash, or discard them before applying worktree changes.";
+
 pub(crate) async fn apply_worktree_changes_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
-) -> Result<(), String> {
+    options: WorktreeApplyOptions,
+) -> Result<WorktreeApplyResult, String> {
     let (entry, parent) = {
         let workspaces = workspaces.lock().await;
         let entry = workspaces
@@ -56,38 +61,128 @@ pub(crate) async fn apply_worktree_changes_core(
         (entry, parent)
     };
 
-    apply_worktree_changes_inner_core(&entry, &parent).await
+    apply_worktree_changes_inner_core(&entry, &parent, &options).await
 }
 
 pub(super) async fn apply_worktree_changes_inner_core(
     entry: &WorkspaceEntry,
     parent: &WorkspaceEntry,
-) -> Result<(), String> {
+    options: &WorktreeApplyOptions,
+) -> Result<WorktreeApplyResult, String> {
     let worktree_root = resolve_git_root(entry)?;
     let parent_root = resolve_git_root(parent)?;
 
-    let parent_status =
-        git_core::run_git_command_bytes(&parent_root, &["status", "--porcelain"]).await?;
-    if !String::from_utf8_lossy(&parent_status).trim().is_empty() {
+    if options.strategy.is_commit_based() {
+        let branch = entry
+            .worktree
+            .as_ref()
+            .map(|worktree| worktree.branch.trim().to_string())
+            .filter(|branch| !branch.is_empty())
+            .ok_or_else(|| "Worktree branch not found.".to_string())?;
+        return apply_worktree_commits(&worktree_root, &parent_root, &branch, options).await;
+    }
+    apply_worktree_patch(&worktree_root, &parent_root, options).await
+}
+
+async fn is_clean(repo_root: &PathBuf) -> Result<bool, String> {
+    let status = git_core::run_git_command_bytes(repo_root, &["status", "--porcelain"]).await?;
+    Ok(String::from_utf8_lossy(&status).trim().is_empty())
+}
+
+async fn apply_worktree_patch(
+    worktree_root: &PathBuf,
+    parent_root: &PathBuf,
+    options: &WorktreeApplyOptions,
+) -> Result<WorktreeApplyResult, String> {
+    let strategy = options.strategy;
+    if strategy == WorktreeApplyStrategy::Patch && !is_clean(parent_root).await? {
+        return Err(PARENT_DIRTY_ERROR.to_string());
+    }
+
+    let patch = build_worktree_patch(worktree_root).await?;
+    if String::from_utf8_lossy(&patch).trim().is_empty() {
+        return Err("No changes to apply.".to_string());
+    }
+
+    let mut result = WorktreeApplyResult {
+        strategy,
+        dry_run: options.dry_run,
+        applied: false,
+        conflicts: Vec::new(),
+        commits: Vec::new(),
+        commit_message: None,
+    };
+
+    if options.dry_run {
+        let output = run_git_with_input(parent_root, &["apply", "--check", "-"], &patch).await?;
+        if !output.status.success() {
+            result.conflicts = parse_apply_check_conflicts(&command_detail(&output));
+        }
+        return Ok(result);
+    }
+
+    if strategy == WorktreeApplyStrategy::ThreeWayPatch {
+        // A plain apply is atomic, so only fall back to a 3-way merge when it is rejected.
+        let direct =
+            run_git_with_input(parent_root, &["apply", "--whitespace=nowarn", "-"], &patch).await?;
+        if direct.status.success() {
+            result.applied = true;
+            return Ok(result);
+        }
+    }
+
+    let output = run_git_with_input(
+        parent_root,
+        &["apply", "--3way", "--whitespace=nowarn", "-"],
+        &patch,
+    )
+    .await?;
+
+    if output.status.success() {
+        result.applied = true;
+        return Ok(result);
+    }
+
+    let detail = command_detail(&output);
+    if detail.is_empty() {
+        return Err("Git apply failed.".to_string());
+    }
+
+    if detail.contains("Applied patch to") {
+        if detail.contains("with conflicts") {
+            if strategy == WorktreeApplyStrategy::ThreeWayPatch {
+                result.applied = true;
+                result.conflicts = parse_unmerged_paths(&detail);
+                return Ok(result);
+            }
+            return Err(
+                "Applied with conflicts. Resolve conflicts in the parent repo before retrying."
+                    .to_string(),
+            );
+        }
         return Err(
-            "Your current branch has uncommitted changes. Please commit, stash, or discard them before applying worktree changes."
+            "Patch applied partially. Resolve changes in the parent repo before retrying."
                 .to_string(),
         );
     }
 
+    Err(detail)
+}
+
+async fn build_worktree_patch(worktree_root: &PathBuf) -> Result<Vec<u8>, String> {
     let mut patch: Vec<u8> = Vec::new();
     let staged_patch = git_core::run_git_diff(
-        &worktree_root,
+        worktree_root,
         &["diff", "--binary", "--no-color", "--cached"],
     )
     .await?;
     patch.extend_from_slice(&staged_patch);
     let unstaged_patch =
-        git_core::run_git_diff(&worktree_root, &["diff", "--binary", "--no-color"]).await?;
+        git_core::run_git_diff(worktree_root, &["diff", "--binary", "--no-color"]).await?;
     patch.extend_from_slice(&unstaged_patch);
 
     let untracked_output = git_core::run_git_command_bytes(
-        &worktree_root,
+        worktree_root,
         &["ls-files", "--others", "--exclude-standard", "-z"],
     )
     .await?;
@@ -97,7 +192,7 @@ pub(super) async fn apply_worktree_changes_inner_core(
         }
         let path = String::from_utf8_lossy(raw_path).to_string();
         let diff = git_core::run_git_diff(
-            &worktree_root,
+            worktree_root,
             &[
                 "diff",
                 "--binary",
@@ -111,16 +206,254 @@ pub(super) async fn apply_worktree_changes_inner_core(
         .await?;
         patch.extend_from_slice(&diff);
     }
+    Ok(patch)
+}
 
-    if String::from_utf8_lossy(&patch).trim().is_empty() {
-        return Err("No changes to apply.".to_string());
+async fn apply_worktree_commits(
+    worktree_root: &PathBuf,
+    parent_root: &PathBuf,
+    branch: &str,
+    options: &WorktreeApplyOptions,
+) -> Result<WorktreeApplyResult, String> {
+    let strategy = options.strategy;
+    if !is_clean(worktree_root).await? {
+        return Err(
+            "The worktree has uncommitted changes. Commit them first, or use the patch strategy to include them."
+                .to_string(),
+        );
+    }
+    if !is_clean(parent_root).await? {
+        return Err(PARENT_DIRTY_ERROR.to_string());
+    }
+    if !git_core::git_branch_exists(parent_root, branch).await? {
+        return Err(format!("Worktree branch `{branch}` not found."));
     }
 
+    let range = format!("HEAD..{branch}");
+    let commits = git_core::run_git_command(parent_root, &["rev-list", "--reverse", &range])
+        .await?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if commits.is_empty() {
+        return Err("No commits to apply.".to_string());
+    }
+
+    let custom_message = options
+        .commit_message
+        .as_ref()
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    let commit_message = match strategy {
+        WorktreeApplyStrategy::Squash => match custom_message {
+            Some(message) => Some(message),
+            None => {
+                let subjects = git_core::run_git_command(
+                    parent_root,
+                    &["log", "--reverse", "--format=%s", &range],
+                )
+                .await?;
+                Some(build_squash_commit_message(branch, &subjects))
+            }
+        },
+        WorktreeApplyStrategy::Merge => custom_message,
+        _ => None,
+    };
+
+    let mut result = WorktreeApplyResult {
+        strategy,
+        dry_run: options.dry_run,
+        applied: false,
+        conflicts: Vec::new(),
+        commits,
+        commit_message,
+    };
+
+    if options.dry_run {
+        // merge-tree never touches the index or working tree. Cherry-picks are
+        // approximated by the merge of the branch tip, which covers the same changes.
+        let output = run_git_output(
+            parent_root,
+            &[
+                "merge-tree",
+                "--write-tree",
+                "--name-only",
+                "--no-messages",
+                "HEAD",
+                branch,
+            ],
+        )
+        .await?;
+        match output.status.code() {
+            Some(0) => {}
+            Some(1) => {
+                result.conflicts = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .skip(1)
+                    .map(|line| line.trim())
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.to_string())
+                    .collect();
+            }
+            _ => return Err(command_failure(&output)),
+        }
+        return Ok(result);
+    }
+
+    let (args, abort_args): (Vec<&str>, &[&str]) = match strategy {
+        WorktreeApplyStrategy::Merge => {
+            let mut args = vec!["merge", "--no-ff", "--no-edit"];
+            if let Some(message) = result.commit_message.as_deref() {
+                args.push("-m");
+                args.push(message);
+            }
+            args.push(branch);
+            (args, &["merge", "--abort"][..])
+        }
+        WorktreeApplyStrategy::Squash => {
+            (vec!["merge", "--squash", branch], &["reset", "--merge"][..])
+        }
+        _ => {
+            let mut args = vec!["cherry-pick"];
+            args.extend(result.commits.iter().map(|commit| commit.as_str()));
+            (args, &["cherry-pick", "--abort"][..])
+        }
+    };
+
+    let output = run_git_output(parent_root, &args).await?;
+    if !output.status.success() {
+        let conflicts =
+            git_core::run_git_command(parent_root, &["diff", "--name-only", "--diff-filter=U"])
+                .await
+                .unwrap_or_default();
+        let conflicts = conflicts
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let detail = command_failure(&output);
+        if let Err(abort_error) = git_core::run_git_command(parent_root, abort_args).await {
+            return Err(format!(
+                "{detail}\nFailed to roll back the parent repo: {abort_error}"
+            ));
+        }
+        if conflicts.is_empty() {
+            return Err(detail);
+        }
+        return Err(format!(
+            "Conflicts in {}. The parent repo was left unchanged.",
+            conflicts.join(", ")
+        ));
+    }
+
+    if strategy == WorktreeApplyStrategy::Squash {
+        let message = result.commit_message.clone().unwrap_or_default();
+        if let Err(error) =
+            git_core::run_git_command(parent_root, &["commit", "-m", &message]).await
+        {
+            let _ = git_core::run_git_command(parent_root, &["reset", "--merge"]).await;
+            return Err(error);
+        }
+    }
+
+    result.applied = true;
+    Ok(result)
+}
+
+fn build_squash_commit_message(branch: &str, subjects: &str) -> String {
+    let subjects = subjects
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if subjects.len() == 1 {
+        return subjects[0].to_string();
+    }
+    let mut message = format!("Squash merge branch '{branch}'\n");
+    if !subjects.is_empty() {
+        message.push('\n');
+        for subject in subjects {
+            message.push_str(&format!("* {subject}\n"));
+        }
+    }
+    message.trim_end().to_string()
+}
+
+fn parse_apply_check_conflicts(detail: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for line in detail.lines() {
+        let Some(rest) = line.trim().strip_prefix("error: ") else {
+            continue;
+        };
+        let path = if let Some(failed) = rest.strip_prefix("patch failed: ") {
+            failed
+                .rsplit_once(':')
+                .map(|(path, _)| path)
+                .unwrap_or(failed)
+        } else if let Some((path, _)) = rest.split_once(": ") {
+            path
+        } else {
+            continue;
+        };
+        let path = path.trim().to_string();
+        if !path.is_empty() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+fn parse_unmerged_paths(detail: &str) -> Vec<String> {
+    detail
+        .lines()
+        .filter_map(|line| line.strip_prefix("U "))
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+fn command_detail(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stderr.trim().is_empty() {
+        stdout.trim().to_string()
+    } else {
+        stderr.trim().to_string()
+    }
+}
+
+fn command_failure(output: &Output) -> String {
+    let detail = command_detail(output);
+    if detail.is_empty() {
+        "Git command failed.".to_string()
+    } else {
+        detail
+    }
+}
+
+async fn run_git_output(repo_root: &PathBuf, args: &[&str]) -> Result<Output, String> {
+    let git_bin =
+        crate::utils::resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
+    tokio_command(git_bin)
+        .args(args)
+        .current_dir(repo_root)
+        .env("PATH", crate::utils::git_env_path())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))
+}
+
+async fn run_git_with_input(
+    repo_root: &PathBuf,
+    args: &[&str],
+    input: &[u8],
+) -> Result<Output, String> {
     let git_bin =
         crate::utils::resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
     let mut child = tokio_command(git_bin)
-        .args(["apply", "--3way", "--whitespace=nowarn", "-"])
-        .current_dir(&parent_root)
+        .args(args)
+        .current_dir(repo_root)
         .env("PATH", crate::utils::git_env_path())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -130,43 +463,43 @@ pub(super) async fn apply_worktree_changes_inner_core(
 
     if let Some(mut stdin) = child.stdin.take() {
         stdin
-            .write_all(&patch)
+            .write_all(input)
             .await
             .map_err(|e| format!("Failed to write git apply input: {e}"))?;
     }
 
-    let output = child
+    child
         .wait_with_output()
         .await
-        .map_err(|e| format!("Failed to run git: {e}"))?;
+        .map_err(|e| format!("Failed to run git: {e}"))
+}
 
-    if output.status.success() {
-        return Ok(());
-    }
+#[cfg(test)]
+mod tests {
+    use super::{build_squash_commit_message, parse_apply_check_conflicts};
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let detail = if stderr.trim().is_empty() {
-        stdout.trim()
-    } else {
-        stderr.trim()
-    };
-    if detail.is_empty() {
-        return Err("Git apply failed.".to_string());
+    #[test]
+    fn squash_message_uses_single_subject() {
+        assert_eq!(
+            build_squash_commit_message("feature/a", "Add login form\n"),
+            "Add login form"
+        );
     }
 
-    if detail.contains("Applied patch to") {
-        if detail.contains("with conflicts") {
-            return Err(
-                "Applied with conflicts. Resolve conflicts in the parent repo before retrying."
-                    .to_string(),
-            );
-        }
-        return Err(
-            "Patch applied partially. Resolve changes in the parent repo before retrying."
-                .to_string(),
+    #[test]
+    fn squash_message_lists_all_subjects() {
+        assert_eq!(
+            build_squash_commit_message("feature/a", "Add form\nFix typo\n"),
+            "Squash merge branch 'feature/a'\n\n* Add form\n* Fix typo"
         );
     }
 
-    Err(detail.to_string())
+    #[test]
+    fn apply_check_conflicts_are_deduplicated_paths() {
+        let detail = "error: patch failed: src/main.rs:12\nerror: src/main.rs: patch does not apply\nerror: README.md: already exists in working directory";
+        assert_eq!(
+            parse_apply_check_conflicts(detail),
+            vec!["src/main.rs".to_string(), "README.md".to_string()]
+        );
+    }
 }
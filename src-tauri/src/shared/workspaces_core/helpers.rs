@@ -5,7 +5,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::backend::app_server::WorkspaceSession;
-use crate::types::{WorkspaceEntry, WorkspaceInfo};
+use crate::types::{WorkspaceCheckoutOptions, WorkspaceEntry, WorkspaceInfo};
 
 pub(crate) const WORKTREE_SETUP_MARKERS_DIR: &str = "worktree-setup";
 pub(crate) const WORKTREE_SETUP_MARKER_EXT: &str = "ran";
@@ -56,12 +56,145 @@ pub(crate) fn normalize_setup_script(script: Option<String>) -> Option<String> {
     }
 }
 
+/// Validates checkout options; returns `None` when nothing is set. Sparse
+/// paths are repo-relative directories, as cone mode requires.
+pub(crate) fn normalize_checkout_options(
+    options: Option<WorkspaceCheckoutOptions>,
+) -> Result<Option<WorkspaceCheckoutOptions>, String> {
+    let Some(options) = options else {
+        return Ok(None);
+    };
+    let mut sparse_paths = Vec::new();
+    for path in &options.sparse_paths {
+        let path = path.trim().trim_matches('/');
+        if path.is_empty() {
+            continue;
+        }
+        if path.starts_with('-')
+            || path.contains('\\')
+            || path
+                .split('/')
+                .any(|part| part == ".." || part == "." || part.is_empty())
+        {
+            return Err(format!("Invalid sparse-checkout path: {path}"));
+        }
+        if !sparse_paths.iter().any(|existing| existing == path) {
+            sparse_paths.push(path.to_string());
+        }
+    }
+    let depth = options.depth.filter(|depth| *depth > 0);
+    let filter = options
+        .filter
+        .map(|filter| filter.trim().to_string())
+        .filter(|filter| !filter.is_empty());
+    if let Some(filter) = filter.as_deref() {
+        if filter.starts_with('-') || filter.contains(char::is_whitespace) {
+            return Err(format!("Invalid partial clone filter: {filter}"));
+        }
+    }
+    if sparse_paths.is_empty() && depth.is_none() && filter.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(WorkspaceCheckoutOptions {
+        sparse_paths,
+        depth,
+        filter,
+    }))
+}
+
+/// Sparse paths of a checkout, empty for a full checkout.
+pub(super) fn checkout_sparse_paths(options: Option<&WorkspaceCheckoutOptions>) -> Vec<String> {
+    options
+        .map(|options| options.sparse_paths.clone())
+        .unwrap_or_default()
+}
+
+pub(super) fn sparse_checkout_set_args(sparse_paths: &[String]) -> Vec<&str> {
+    let mut args = vec!["sparse-checkout", "set", "--cone"];
+    args.extend(sparse_paths.iter().map(String::as_str));
+    args
+}
+
+/// Where a new worktree starts: a commit-ish (commit, tag, local or remote
+/// branch) or the head of a pull request, written as `#123`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum WorktreeBase {
+    Ref(String),
+    PullRequest(u64),
+}
+
+impl WorktreeBase {
+    /// Label used to name a detached worktree that has no branch.
+    pub(super) fn label(&self) -> String {
+        match self {
+            WorktreeBase::Ref(value) => value.clone(),
+            WorktreeBase::PullRequest(number) => format!("pr-{number}"),
+        }
+    }
+}
+
+pub(super) fn parse_worktree_base_ref(
+    base_ref: Option<String>,
+) -> Result<Option<WorktreeBase>, String> {
+    let Some(value) = base_ref
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+    if let Some(number) = value.strip_prefix('#') {
+        return match number.parse::<u64>() {
+            Ok(number) if number > 0 => Ok(Some(WorktreeBase::PullRequest(number))),
+            _ => Err(format!("Invalid pull request number: {value}")),
+        };
+    }
+    if value.starts_with('-')
+        || value
+            .chars()
+            .any(|ch| ch.is_whitespace() || ch.is_control())
+    {
+        return Err(format!("Invalid base ref: {value}"));
+    }
+    Ok(Some(WorktreeBase::Ref(value)))
+}
+
 pub(crate) fn worktree_setup_marker_path(data_dir: &PathBuf, workspace_id: &str) -> PathBuf {
     data_dir
         .join(WORKTREE_SETUP_MARKERS_DIR)
         .join(format!("{workspace_id}.{WORKTREE_SETUP_MARKER_EXT}"))
 }
 
+/// Output of the latest backend setup script run, next to the marker.
+pub(crate) fn worktree_setup_log_path(data_dir: &PathBuf, workspace_id: &str) -> PathBuf {
+    data_dir
+        .join(WORKTREE_SETUP_MARKERS_DIR)
+        .join(format!("{workspace_id}.log"))
+}
+
+/// Serialized `WorktreeSetupRun` of the latest backend setup script run.
+pub(crate) fn worktree_setup_run_path(data_dir: &PathBuf, workspace_id: &str) -> PathBuf {
+    data_dir
+        .join(WORKTREE_SETUP_MARKERS_DIR)
+        .join(format!("{workspace_id}.json"))
+}
+
+pub(crate) fn write_worktree_setup_marker(
+    data_dir: &PathBuf,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let marker_path = worktree_setup_marker_path(data_dir, workspace_id);
+    if let Some(parent) = marker_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
+    }
+    let ran_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    std::fs::write(&marker_path, format!("ran_at={ran_at}\n"))
+        .map_err(|err| format!("Failed to write worktree setup marker: {err}"))
+}
+
 pub(crate) fn is_workspace_path_dir_core(path: &str) -> bool {
     normalize_workspace_path_input(path).is_dir()
 }
@@ -147,8 +280,10 @@ pub(super) fn sort_workspaces(workspaces: &mut [WorkspaceInfo]) {
 #[cfg(test)]
 mod tests {
     use super::{
-        copy_agents_md_from_parent_to_worktree, normalize_workspace_path_input, AGENTS_MD_FILE_NAME,
+        copy_agents_md_from_parent_to_worktree, normalize_checkout_options,
+        normalize_workspace_path_input, parse_worktree_base_ref, WorktreeBase, AGENTS_MD_FILE_NAME,
     };
+    use crate::types::WorkspaceCheckoutOptions;
     use std::path::PathBuf;
     use std::sync::Mutex;
     use uuid::Uuid;
@@ -161,6 +296,65 @@ mod tests {
         dir
     }
 
+    #[test]
+    fn normalizes_checkout_options() {
+        let options = normalize_checkout_options(Some(WorkspaceCheckoutOptions {
+            sparse_paths: vec![
+                " /apps/web/ ".to_string(),
+                "".to_string(),
+                "apps/web".to_string(),
+                "libs/shared".to_string(),
+            ],
+            depth: Some(0),
+            filter: Some(" blob:none ".to_string()),
+        }))
+        .expect("valid options")
+        .expect("options kept");
+        assert_eq!(options.sparse_paths, vec!["apps/web", "libs/shared"]);
+        assert_eq!(options.depth, None);
+        assert_eq!(options.filter.as_deref(), Some("blob:none"));
+
+        let empty = normalize_checkout_options(Some(WorkspaceCheckoutOptions::default()))
+            .expect("valid options");
+        assert!(empty.is_none());
+
+        for path in ["../outside", "apps/./web", "--cone"] {
+            let invalid = normalize_checkout_options(Some(WorkspaceCheckoutOptions {
+                sparse_paths: vec![path.to_string()],
+                ..WorkspaceCheckoutOptions::default()
+            }));
+            assert!(invalid.is_err(), "{path} should be rejected");
+        }
+        let invalid_filter = normalize_checkout_options(Some(WorkspaceCheckoutOptions {
+            filter: Some("--upload-pack=evil".to_string()),
+            ..WorkspaceCheckoutOptions::default()
+        }));
+        assert!(invalid_filter.is_err());
+    }
+
+    #[test]
+    fn parses_worktree_base_refs() {
+        assert_eq!(parse_worktree_base_ref(None).expect("parsed"), None);
+        assert_eq!(
+            parse_worktree_base_ref(Some("  ".to_string())).expect("parsed"),
+            None
+        );
+        assert_eq!(
+            parse_worktree_base_ref(Some(" origin/release-2.3 ".to_string())).expect("parsed"),
+            Some(WorktreeBase::Ref("origin/release-2.3".to_string()))
+        );
+        assert_eq!(
+            parse_worktree_base_ref(Some("#123".to_string())).expect("parsed"),
+            Some(WorktreeBase::PullRequest(123))
+        );
+        for value in ["#0", "#abc", "--orphan", "main branch"] {
+            assert!(
+                parse_worktree_base_ref(Some(value.to_string())).is_err(),
+                "{value} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn copies_agents_md_when_missing_in_worktree() {
         let parent = make_temp_dir();
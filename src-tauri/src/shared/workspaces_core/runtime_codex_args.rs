@@ -37,11 +37,12 @@ where
     let (entry, parent_entry) = resolve_entry_and_parent(workspaces, &workspace_id).await?;
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
 
-    let (default_bin, resolved_args) = {
+    let (default_bin, resolved_args, codex_home) = {
         let settings = app_settings.lock().await;
         (
             settings.codex_bin.clone(),
             resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
+            resolve_workspace_codex_home(&entry, parent_entry.as_ref(), Some(&settings)),
         )
     };
 
@@ -54,20 +55,7 @@ where
 
     // If we are not connected, we can't respawn. Treat this as a no-op success; callers
     // should call again after connecting.
-    let (workspace_connected, current_session) = {
-        let sessions = sessions.lock().await;
-        (
-            sessions.contains_key(&entry.id),
-            sessions.values().next().cloned(),
-        )
-    };
-    if !workspace_connected {
-        return Ok(WorkspaceRuntimeCodexArgsResult {
-            applied_codex_args: target_args,
-            respawned: false,
-        });
-    }
-
+    let current_session = sessions.lock().await.get(&entry.id).cloned();
     let Some(current_session) = current_session else {
         return Ok(WorkspaceRuntimeCodexArgsResult {
             applied_codex_args: target_args,
@@ -82,12 +70,17 @@ where
         });
     }
 
-    let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
     let new_session =
         spawn_session(entry.clone(), default_bin, target_args.clone(), codex_home).await?;
+    // Only workspaces sharing this session move; sessions for other Codex
+    // homes keep running.
     let workspace_ids = {
         let mut sessions = sessions.lock().await;
-        let keys: Vec<String> = sessions.keys().cloned().collect();
+        let keys: Vec<String> = sessions
+            .iter()
+            .filter(|(_, session)| Arc::ptr_eq(session, &current_session))
+            .map(|(key, _)| key.clone())
+            .collect();
         for key in &keys {
             sessions.insert(key.clone(), Arc::clone(&new_session));
         }
@@ -169,6 +162,7 @@ mod tests {
 
         WorkspaceSession {
             codex_args,
+            codex_home: None,
             child: Mutex::new(child),
             stdin: Mutex::new(stdin),
             pending: Mutex::new(HashMap::new()),
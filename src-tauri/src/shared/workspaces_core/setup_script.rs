@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::Child;
+use tokio::sync::Mutex;
+
+use crate::backend::events::{EventSink, WorktreeSetupOutput};
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::types::{WorkspaceEntry, WorktreeSetupRun, WorktreeSetupRunState};
+
+use super::helpers::{
+    normalize_setup_script, worktree_setup_log_path, worktree_setup_marker_path,
+    worktree_setup_run_path, write_worktree_setup_marker,
+};
+
+const DEFAULT_SETUP_TIMEOUT_SECS: u64 = 30 * 60;
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Cancel flags of setup scripts currently running, keyed by workspace id.
+pub(crate) type WorktreeSetupRuns = Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>;
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The script to run for a workspace: its own, or for clones the script of
+/// the workspace it was cloned from.
+pub(super) fn resolve_setup_script(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    entry: &WorkspaceEntry,
+) -> Option<String> {
+    normalize_setup_script(entry.settings.worktree_setup_script.clone()).or_else(|| {
+        let source_id = entry.settings.clone_source_workspace_id.as_ref()?;
+        let source = workspaces.get(source_id)?;
+        normalize_setup_script(source.settings.worktree_setup_script.clone())
+    })
+}
+
+pub(super) fn read_worktree_setup_run(
+    data_dir: &PathBuf,
+    workspace_id: &str,
+) -> Option<WorktreeSetupRun> {
+    let raw = std::fs::read_to_string(worktree_setup_run_path(data_dir, workspace_id)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_worktree_setup_run(data_dir: &PathBuf, run: &WorktreeSetupRun) -> Result<(), String> {
+    let path = worktree_setup_run_path(data_dir, &run.workspace_id);
+    let data = serde_json::to_string_pretty(run).map_err(|err| err.to_string())?;
+    std::fs::write(&path, data)
+        .map_err(|err| format!("Failed to write worktree setup status: {err}"))
+}
+
+/// Latest run for a workspace. A run persisted as running without a live
+/// process was interrupted (e.g. the app quit mid-run) and reports as failed.
+pub(crate) async fn worktree_setup_run_core(
+    runs: &WorktreeSetupRuns,
+    data_dir: &PathBuf,
+    workspace_id: &str,
+) -> Option<WorktreeSetupRun> {
+    let mut run = read_worktree_setup_run(data_dir, workspace_id)?;
+    if run.state == WorktreeSetupRunState::Running && !runs.lock().await.contains_key(workspace_id)
+    {
+        run.state = WorktreeSetupRunState::Failed;
+        run.error = Some("Setup script was interrupted.".to_string());
+    }
+    Some(run)
+}
+
+fn setup_shell_command(script: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut command = tokio_command("cmd");
+        command.arg("/C").arg(script);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string());
+        let mut command = tokio_command(shell);
+        command.arg("-lc").arg(script);
+        // Own process group so cancel and timeout reach the script's children.
+        std::os::unix::process::CommandExt::process_group(command.as_std_mut(), 0);
+        command
+    }
+}
+
+async fn kill_setup_script(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGKILL);
+        }
+    }
+    kill_child_process_tree(child).await;
+}
+
+async fn append_log(log: &Mutex<Option<tokio::fs::File>>, text: &str) {
+    let mut log = log.lock().await;
+    if let Some(file) = log.as_mut() {
+        if let Err(err) = file.write_all(text.as_bytes()).await {
+            eprintln!("Failed to write worktree setup log: {err}");
+            *log = None;
+        }
+    }
+}
+
+async fn pump_output(
+    reader: impl AsyncRead + Unpin,
+    stream: &'static str,
+    workspace_id: String,
+    log: Arc<Mutex<Option<tokio::fs::File>>>,
+    event_sink: impl EventSink,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let data = format!("{line}\n");
+        append_log(&log, &data).await;
+        event_sink.emit_worktree_setup_output(WorktreeSetupOutput {
+            workspace_id: workspace_id.clone(),
+            stream: stream.to_string(),
+            data,
+        });
+    }
+}
+
+/// Runs the script to completion, cancellation or timeout and returns the
+/// final state, exit code and error.
+async fn execute_setup_script(
+    event_sink: impl EventSink,
+    workspace_id: &str,
+    script: &str,
+    cwd: &Path,
+    log_path: &Path,
+    cancel: &AtomicBool,
+    timeout: Duration,
+) -> (WorktreeSetupRunState, Option<i32>, Option<String>) {
+    let log = match tokio::fs::File::create(log_path).await {
+        Ok(file) => Some(file),
+        Err(err) => {
+            eprintln!("Failed to create worktree setup log: {err}");
+            None
+        }
+    };
+    let log = Arc::new(Mutex::new(log));
+
+    let mut command = setup_shell_command(script);
+    command
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            let message = format!("Failed to start setup script: {err}");
+            append_log(&log, &format!("{message}\n")).await;
+            return (WorktreeSetupRunState::Failed, None, Some(message));
+        }
+    };
+
+    let mut pumps = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        pumps.push(tokio::spawn(pump_output(
+            stdout,
+            "stdout",
+            workspace_id.to_string(),
+            log.clone(),
+            event_sink.clone(),
+        )));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        pumps.push(tokio::spawn(pump_output(
+            stderr,
+            "stderr",
+            workspace_id.to_string(),
+            log.clone(),
+            event_sink.clone(),
+        )));
+    }
+
+    let started = Instant::now();
+    let outcome = loop {
+        match tokio::time::timeout(WAIT_POLL_INTERVAL, child.wait()).await {
+            Ok(Ok(status)) => {
+                let state = if status.success() {
+                    WorktreeSetupRunState::Succeeded
+                } else {
+                    WorktreeSetupRunState::Failed
+                };
+                break (state, status.code(), None);
+            }
+            Ok(Err(err)) => {
+                kill_setup_script(&mut child).await;
+                let message = format!("Failed to wait for setup script: {err}");
+                break (WorktreeSetupRunState::Failed, None, Some(message));
+            }
+            Err(_) if cancel.load(Ordering::SeqCst) => {
+                kill_setup_script(&mut child).await;
+                break (WorktreeSetupRunState::Cancelled, None, None);
+            }
+            Err(_) if started.elapsed() >= timeout => {
+                kill_setup_script(&mut child).await;
+                let message = format!("Setup script timed out after {}s.", timeout.as_secs());
+                break (WorktreeSetupRunState::TimedOut, None, Some(message));
+            }
+            Err(_) => {}
+        }
+    };
+    // Children the script left in the background keep stdout and stderr open,
+    // so stop reading shortly after the script itself is done.
+    let drain = async {
+        for pump in &mut pumps {
+            let _ = pump.await;
+        }
+    };
+    if let Err(_elapsed) = tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, drain).await {
+        for pump in &pumps {
+            pump.abort();
+        }
+    }
+    if let Some(message) = outcome.2.as_ref() {
+        append_log(&log, &format!("{message}\n")).await;
+    }
+    outcome
+}
+
+/// Starts the setup script in the background. Without `force` nothing runs
+/// when the workspace has no script or its setup already ran; with `force`
+/// (re-run) a missing script is an error. The marker and a running status are
+/// written before returning, so the frontend never runs the script a second
+/// time.
+pub(crate) async fn start_worktree_setup_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    runs: &WorktreeSetupRuns,
+    data_dir: &PathBuf,
+    event_sink: impl EventSink,
+    workspace_id: String,
+    force: bool,
+    timeout_secs: Option<u64>,
+) -> Result<Option<WorktreeSetupRun>, String> {
+    let (entry, script) = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?;
+        let script = resolve_setup_script(&workspaces, &entry);
+        (entry, script)
+    };
+    let Some(script) = script else {
+        if force {
+            return Err("No setup script is configured for this workspace.".to_string());
+        }
+        return Ok(None);
+    };
+    if !force && worktree_setup_marker_path(data_dir, &entry.id).exists() {
+        return Ok(None);
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut runs = runs.lock().await;
+        if runs.contains_key(&entry.id) {
+            return Err("Setup script is already running.".to_string());
+        }
+        runs.insert(entry.id.clone(), cancel.clone());
+    }
+
+    let run = WorktreeSetupRun {
+        workspace_id: entry.id.clone(),
+        state: WorktreeSetupRunState::Running,
+        script: script.clone(),
+        exit_code: None,
+        started_at: now_ms(),
+        finished_at: None,
+        error: None,
+    };
+    let prepared = write_worktree_setup_marker(data_dir, &entry.id)
+        .and_then(|()| write_worktree_setup_run(data_dir, &run));
+    if let Err(err) = prepared {
+        runs.lock().await.remove(&entry.id);
+        return Err(err);
+    }
+    event_sink.emit_worktree_setup_status(run.clone());
+
+    let timeout = Duration::from_secs(
+        timeout_secs
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_SETUP_TIMEOUT_SECS),
+    );
+    let runs = runs.clone();
+    let data_dir = data_dir.clone();
+    let cwd = PathBuf::from(&entry.path);
+    let mut finished = run.clone();
+    tokio::spawn(async move {
+        let log_path = worktree_setup_log_path(&data_dir, &finished.workspace_id);
+        let (state, exit_code, error) = execute_setup_script(
+            event_sink.clone(),
+            &finished.workspace_id,
+            &finished.script,
+            &cwd,
+            &log_path,
+            &cancel,
+            timeout,
+        )
+        .await;
+        finished.state = state;
+        finished.exit_code = exit_code;
+        finished.error = error;
+        finished.finished_at = Some(now_ms());
+        if let Err(err) = write_worktree_setup_run(&data_dir, &finished) {
+            eprintln!("{err}");
+        }
+        runs.lock().await.remove(&finished.workspace_id);
+        event_sink.emit_worktree_setup_status(finished);
+    });
+    Ok(Some(run))
+}
+
+/// Asks a running setup script to stop. Returns whether one was running.
+pub(crate) async fn cancel_worktree_setup_core(
+    runs: &WorktreeSetupRuns,
+    workspace_id: &str,
+) -> bool {
+    match runs.lock().await.get(workspace_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Output of the latest run, empty when the script never ran in the backend.
+pub(crate) async fn worktree_setup_log_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: &PathBuf,
+    workspace_id: &str,
+) -> Result<String, String> {
+    if !workspaces.lock().await.contains_key(workspace_id) {
+        return Err("workspace not found".to_string());
+    }
+    match std::fs::read(worktree_setup_log_path(data_dir, workspace_id)) {
+        Ok(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(format!("Failed to read worktree setup log: {err}")),
+    }
+}
@@ -9,6 +9,7 @@ use uuid::Uuid;
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
+use crate::shared::git_ui_core;
 use crate::storage::write_workspaces;
 use crate::types::{
     AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
@@ -17,12 +18,15 @@ use crate::types::{
 
 use super::connect::{kill_session_by_id, take_live_shared_session, workspace_session_spawn_lock};
 use super::helpers::{
-    copy_agents_md_from_parent_to_worktree, normalize_setup_script, worktree_setup_marker_path,
-    AGENTS_MD_FILE_NAME,
+    checkout_sparse_paths, copy_agents_md_from_parent_to_worktree, normalize_setup_script,
+    parse_worktree_base_ref, sparse_checkout_set_args, worktree_setup_marker_path,
+    write_worktree_setup_marker, WorktreeBase, AGENTS_MD_FILE_NAME,
 };
+use super::setup_script::{worktree_setup_run_core, WorktreeSetupRuns};
 
 pub(crate) async fn worktree_setup_status_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    runs: &WorktreeSetupRuns,
     workspace_id: &str,
     data_dir: &PathBuf,
 ) -> Result<WorktreeSetupStatus, String> {
@@ -41,8 +45,13 @@ pub(crate) async fn worktree_setup_status_core(
         false
     };
     let should_run = entry.kind.is_worktree() && script.is_some() && !marker_exists;
+    let run = worktree_setup_run_core(runs, data_dir, &entry.id).await;
 
-    Ok(WorktreeSetupStatus { should_run, script })
+    Ok(WorktreeSetupStatus {
+        should_run,
+        script,
+        run,
+    })
 }
 
 pub(crate) async fn worktree_setup_mark_ran_core(
@@ -60,18 +69,7 @@ pub(crate) async fn worktree_setup_mark_ran_core(
     if !entry.kind.is_worktree() {
         return Err("Not a worktree workspace.".to_string());
     }
-    let marker_path = worktree_setup_marker_path(data_dir, &entry.id);
-    if let Some(parent) = marker_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
-    }
-    let ran_at = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|duration| duration.as_secs())
-        .unwrap_or(0);
-    std::fs::write(&marker_path, format!("ran_at={ran_at}\n"))
-        .map_err(|err| format!("Failed to write worktree setup marker: {err}"))?;
-    Ok(())
+    write_worktree_setup_marker(data_dir, &entry.id)
 }
 
 pub(crate) async fn add_worktree_core<
@@ -90,6 +88,8 @@ pub(crate) async fn add_worktree_core<
     branch: String,
     name: Option<String>,
     copy_agents_md: bool,
+    base_ref: Option<String>,
+    detach: bool,
     data_dir: &PathBuf,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
@@ -115,9 +115,10 @@ where
     FutRunGit: Future<Output = Result<(), String>>,
 {
     let branch = branch.trim().to_string();
-    if branch.is_empty() {
+    if branch.is_empty() && !detach {
         return Err("Branch name is required.".to_string());
     }
+    let base = parse_worktree_base_ref(base_ref)?;
     let name = name
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty());
@@ -138,45 +139,94 @@ where
     std::fs::create_dir_all(&worktree_root)
         .map_err(|err| format!("Failed to create worktree directory: {err}"))?;
 
-    let safe_name = sanitize_worktree_name(&branch);
-    let worktree_path = unique_worktree_path(&worktree_root, &safe_name)?;
-    let worktree_path_string = worktree_path.to_string_lossy().to_string();
-
+    let label = if branch.is_empty() {
+        base.as_ref()
+            .map(WorktreeBase::label)
+            .unwrap_or_else(|| "detached".to_string())
+    } else {
+        branch.clone()
+    };
     let repo_path = PathBuf::from(&parent_entry.path);
-    let branch_exists = git_branch_exists(&repo_path, &branch).await?;
-    if branch_exists {
-        run_git_command(
-            &repo_path,
-            &["worktree", "add", &worktree_path_string, &branch],
-        )
-        .await?;
-    } else if let Some(find_remote_tracking) = git_find_remote_tracking_branch {
-        if let Some(remote_ref) = find_remote_tracking(&repo_path, &branch).await? {
-            run_git_command(
-                &repo_path,
-                &[
-                    "worktree",
-                    "add",
-                    "-b",
-                    &branch,
-                    &worktree_path_string,
-                    &remote_ref,
-                ],
-            )
-            .await?;
-        } else {
-            run_git_command(
-                &repo_path,
-                &["worktree", "add", "-b", &branch, &worktree_path_string],
+    let start_point = match &base {
+        None => None,
+        Some(WorktreeBase::Ref(value)) => {
+            let commit = format!("{value}^{{commit}}");
+            run_git_command(&repo_path, &["rev-parse", "--verify", "--quiet", &commit])
+                .await
+                .map_err(|_| format!("Base ref `{value}` was not found."))?;
+            Some(value.clone())
+        }
+        Some(WorktreeBase::PullRequest(number)) => {
+            let head_ref = git_ui_core::pull_request_head_ref_core(
+                workspaces,
+                parent_entry.id.clone(),
+                *number,
             )
             .await?;
+            run_git_command(&repo_path, &["fetch", "origin", &head_ref]).await?;
+            Some("FETCH_HEAD".to_string())
         }
+    };
+    let branch_exists = if branch.is_empty() {
+        false
     } else {
-        run_git_command(
-            &repo_path,
-            &["worktree", "add", "-b", &branch, &worktree_path_string],
-        )
-        .await?;
+        git_branch_exists(&repo_path, &branch).await?
+    };
+    if branch_exists && !detach {
+        if let Some(base) = base.as_ref() {
+            return Err(format!(
+                "Branch `{branch}` already exists; pick a new branch name to start from `{}`.",
+                base.label()
+            ));
+        }
+    }
+
+    let safe_name = sanitize_worktree_name(&label);
+    let worktree_path = unique_worktree_path(&worktree_root, &safe_name)?;
+    let worktree_path_string = worktree_path.to_string_lossy().to_string();
+
+    let sparse_paths = checkout_sparse_paths(parent_entry.settings.checkout.as_ref());
+    let mut add_args = vec!["worktree", "add"];
+    if !sparse_paths.is_empty() {
+        add_args.push("--no-checkout");
+    }
+    let remote_ref = match git_find_remote_tracking_branch {
+        Some(find_remote_tracking) if !branch_exists && !detach && base.is_none() => {
+            find_remote_tracking(&repo_path, &branch).await?
+        }
+        _ => None,
+    };
+    if detach {
+        // Without a base the worktree detaches at the branch tip, or at the
+        // parent's HEAD when no branch was given either.
+        let start = start_point.as_deref().unwrap_or(if branch.is_empty() {
+            "HEAD"
+        } else {
+            branch.as_str()
+        });
+        add_args.extend(["--detach", worktree_path_string.as_str(), start]);
+    } else if branch_exists {
+        add_args.extend([worktree_path_string.as_str(), branch.as_str()]);
+    } else if let Some(start) = start_point.as_deref() {
+        // A new agent branch off a remote branch must not push back to it.
+        add_args.extend([
+            "--no-track",
+            "-b",
+            branch.as_str(),
+            worktree_path_string.as_str(),
+            start,
+        ]);
+    } else {
+        add_args.extend(["-b", branch.as_str(), worktree_path_string.as_str()]);
+        if let Some(remote_ref) = remote_ref.as_deref() {
+            add_args.push(remote_ref);
+        }
+    }
+    run_git_command(&repo_path, &add_args).await?;
+
+    if !sparse_paths.is_empty() {
+        run_git_command(&worktree_path, &sparse_checkout_set_args(&sparse_paths)).await?;
+        run_git_command(&worktree_path, &["checkout"]).await?;
     }
 
     if copy_agents_md {
@@ -192,32 +242,38 @@ where
 
     let entry = WorkspaceEntry {
         id: Uuid::new_v4().to_string(),
-        name: name.clone().unwrap_or_else(|| branch.clone()),
+        name: name.clone().unwrap_or(label),
         path: worktree_path_string,
         kind: WorkspaceKind::Worktree,
         parent_id: Some(parent_entry.id.clone()),
-        worktree: Some(WorktreeInfo { branch }),
+        worktree: Some(WorktreeInfo {
+            branch: if detach { String::new() } else { branch },
+        }),
         settings: WorkspaceSettings {
             worktree_setup_script: normalize_setup_script(
                 parent_entry.settings.worktree_setup_script.clone(),
             ),
+            forge: parent_entry.settings.forge,
+            forge_api_url: parent_entry.settings.forge_api_url.clone(),
+            checkout: parent_entry.settings.checkout.clone(),
             ..WorkspaceSettings::default()
         },
     };
 
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
-    let existing_session = take_live_shared_session(sessions).await;
+    let (default_bin, codex_args, codex_home) = {
+        let settings = app_settings.lock().await;
+        (
+            settings.codex_bin.clone(),
+            resolve_workspace_codex_args(&entry, Some(&parent_entry), Some(&settings)),
+            resolve_workspace_codex_home(&entry, Some(&parent_entry), Some(&settings)),
+        )
+    };
+    let existing_session =
+        take_live_shared_session(sessions, codex_home.as_deref(), codex_args.as_deref()).await;
     let session = if let Some(existing_session) = existing_session {
         existing_session
     } else {
-        let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
-            (
-                settings.codex_bin.clone(),
-                resolve_workspace_codex_args(&entry, Some(&parent_entry), Some(&settings)),
-            )
-        };
-        let codex_home = resolve_workspace_codex_home(&entry, Some(&parent_entry));
         spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?
     };
 
@@ -281,22 +337,58 @@ where
     };
 
     let parent_path = PathBuf::from(&parent.path);
-    let parent_path_exists = parent_path.is_dir();
     let entry_path = PathBuf::from(&entry.path);
     kill_session_by_id(sessions, &entry.id).await;
 
-    if entry_path.exists() {
+    remove_worktree_dir(
+        &parent_path,
+        &entry_path,
+        run_git_command,
+        is_missing_worktree_error,
+        remove_dir_all,
+    )
+    .await?;
+
+    {
+        let mut workspaces = workspaces.lock().await;
+        workspaces.remove(&entry.id);
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(storage_path, &list)?;
+    }
+
+    Ok(())
+}
+
+/// Removes a worktree directory through `git worktree remove` in the parent
+/// repo, falling back to deleting the folder when git no longer knows it or
+/// the parent repo is gone, then prunes the parent's worktree metadata.
+pub(super) async fn remove_worktree_dir<FRunGit, FutRunGit, FIsMissing, FRemoveDirAll>(
+    parent_path: &PathBuf,
+    worktree_path: &PathBuf,
+    run_git_command: FRunGit,
+    is_missing_worktree_error: FIsMissing,
+    remove_dir_all: FRemoveDirAll,
+) -> Result<(), String>
+where
+    FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
+    FutRunGit: Future<Output = Result<(), String>>,
+    FIsMissing: Fn(&str) -> bool,
+    FRemoveDirAll: Fn(&PathBuf) -> Result<(), String>,
+{
+    let parent_path_exists = parent_path.is_dir();
+    if worktree_path.exists() {
+        let worktree_path_string = worktree_path.to_string_lossy().to_string();
         if !parent_path_exists {
-            remove_dir_all(&entry_path)?;
+            remove_dir_all(worktree_path)?;
         } else if let Err(error) = run_git_command(
-            &parent_path,
-            &["worktree", "remove", "--force", &entry.path],
+            parent_path,
+            &["worktree", "remove", "--force", &worktree_path_string],
         )
         .await
         {
             if is_missing_worktree_error(&error) {
-                if entry_path.exists() {
-                    remove_dir_all(&entry_path)?;
+                if worktree_path.exists() {
+                    remove_dir_all(worktree_path)?;
                 }
             } else {
                 return Err(error);
@@ -304,16 +396,8 @@ where
         }
     }
     if parent_path_exists {
-        let _ = run_git_command(&parent_path, &["worktree", "prune", "--expire", "now"]).await;
+        let _ = run_git_command(parent_path, &["worktree", "prune", "--expire", "now"]).await;
     }
-
-    {
-        let mut workspaces = workspaces.lock().await;
-        workspaces.remove(&entry.id);
-        let list: Vec<_> = workspaces.values().cloned().collect();
-        write_workspaces(storage_path, &list)?;
-    }
-
     Ok(())
 }
 
@@ -383,6 +467,9 @@ where
         .as_ref()
         .map(|worktree| worktree.branch.clone())
         .ok_or_else(|| "worktree metadata missing".to_string())?;
+    if old_branch.is_empty() {
+        return Err("Worktree has a detached HEAD and no branch to rename.".to_string());
+    }
     if old_branch == trimmed {
         return Err("Branch name is unchanged.".to_string());
     }
@@ -0,0 +1,157 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::shared::skills_core::{self, InstallSkillInput, SkillEntry};
+use crate::state::AppState;
+
+#[tauri::command]
+pub(crate) async fn skills_local_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<SkillEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_local_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    skills_core::skills_local_list_core(&state.workspaces, &state.app_settings, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn skills_create(
+    workspace_id: String,
+    scope: String,
+    content: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SkillEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_create",
+            json!({ "workspaceId": workspace_id, "scope": scope, "content": content }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    skills_core::skills_create_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        scope,
+        content,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn skills_update(
+    workspace_id: String,
+    path: String,
+    content: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SkillEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_update",
+            json!({ "workspaceId": workspace_id, "path": path, "content": content }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    skills_core::skills_update_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        path,
+        content,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn skills_set_enabled(
+    workspace_id: String,
+    path: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SkillEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_set_enabled",
+            json!({ "workspaceId": workspace_id, "path": path, "enabled": enabled }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    skills_core::skills_set_enabled_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        path,
+        enabled,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn skills_delete(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_delete",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    skills_core::skills_delete_core(&state.workspaces, &state.app_settings, workspace_id, path)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn skills_install(
+    workspace_id: String,
+    input: InstallSkillInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SkillEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "skills_install",
+            json!({ "workspaceId": workspace_id, "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    skills_core::skills_install_core(&state.workspaces, &state.app_settings, workspace_id, input)
+        .await
+}
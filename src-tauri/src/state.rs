@@ -7,8 +7,12 @@ use tokio::sync::Mutex;
 
 use crate::dictation::DictationState;
 use crate::shared::codex_core::CodexLoginCancelState;
-use crate::storage::{read_settings, read_workspaces};
-use crate::types::{AppSettings, TcpDaemonState, TcpDaemonStatus, WorkspaceEntry};
+use crate::shared::git_ui_core::{GitHubChecksWatchers, GitStatusWatchers};
+use crate::shared::settings_sync_core::SettingsSyncState;
+use crate::shared::workspaces_core::WorktreeSetupRuns;
+use crate::shared::{agent_templates_core, config_history_core};
+use crate::storage::{load_settings, load_workspaces};
+use crate::types::{AppSettings, StorageRecovery, TcpDaemonState, TcpDaemonStatus, WorkspaceEntry};
 
 pub(crate) struct TcpDaemonRuntime {
     pub(crate) child: Option<Child>,
@@ -38,9 +42,15 @@ pub(crate) struct AppState {
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
+    pub(crate) settings_sync: Mutex<SettingsSyncState>,
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     pub(crate) tcp_daemon: Mutex<TcpDaemonRuntime>,
+    pub(crate) github_checks_watchers: GitHubChecksWatchers,
+    pub(crate) git_status_watchers: GitStatusWatchers,
+    pub(crate) worktree_setup_runs: WorktreeSetupRuns,
+    /// Storage files that were unreadable at startup and how they were recovered.
+    pub(crate) storage_recoveries: Vec<StorageRecovery>,
 }
 
 impl AppState {
@@ -51,8 +61,17 @@ impl AppState {
             .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()));
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
-        let workspaces = read_workspaces(&storage_path).unwrap_or_default();
-        let app_settings = read_settings(&settings_path).unwrap_or_default();
+        config_history_core::init_config_history(data_dir.join("config-history"));
+        agent_templates_core::init_agent_templates(data_dir.join("agent-templates"));
+        let (workspaces, workspaces_recovery) = load_workspaces(&storage_path);
+        let (app_settings, settings_recovery) = load_settings(&settings_path);
+        let storage_recoveries: Vec<_> = workspaces_recovery
+            .into_iter()
+            .chain(settings_recovery)
+            .collect();
+        for recovery in &storage_recoveries {
+            eprintln!("{}", recovery.summary());
+        }
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
@@ -61,9 +80,14 @@ impl AppState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            settings_sync: Mutex::new(SettingsSyncState::default()),
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
             tcp_daemon: Mutex::new(TcpDaemonRuntime::default()),
+            github_checks_watchers: Mutex::new(HashMap::new()),
+            git_status_watchers: Mutex::new(HashMap::new()),
+            worktree_setup_runs: WorktreeSetupRuns::default(),
+            storage_recoveries,
         }
     }
 }
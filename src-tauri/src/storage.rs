@@ -1,15 +1,58 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use crate::types::{AppSettings, WorkspaceEntry};
-use serde_json::Value;
+use crate::types::{AppSettings, StorageRecovery, WorkspaceEntry};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+const WORKSPACES_KEY: &str = "workspaces";
+/// Rolling backups kept next to each file as `<name>.bak.1` (newest) to
+/// `<name>.bak.N`.
+const MAX_BACKUPS: usize = 3;
+
+/// One step of a storage schema, applied to files older than `version`.
+struct StorageMigration {
+    version: u32,
+    migrate: fn(&mut Value),
+}
+
+/// Ordered settings migrations; the last version is the current schema.
+const SETTINGS_MIGRATIONS: &[StorageMigration] = &[
+    StorageMigration {
+        version: 1,
+        migrate: sanitize_remote_settings_for_tcp_only,
+    },
+    StorageMigration {
+        version: 2,
+        migrate: migrate_follow_up_message_behavior,
+    },
+];
+
+pub(crate) const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+/// Ordered `workspaces.json` migrations; the last version is the current
+/// schema. Version 0 is the bare array written by older builds.
+const WORKSPACES_MIGRATIONS: &[StorageMigration] = &[StorageMigration {
+    version: 1,
+    migrate: wrap_workspaces_list,
+}];
+
+pub(crate) const WORKSPACES_SCHEMA_VERSION: u32 = 1;
 
 pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, WorkspaceEntry>, String> {
     if !path.exists() {
         return Ok(HashMap::new());
     }
     let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let list: Vec<WorkspaceEntry> = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    let mut value: Value = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    migrate_value(&mut value, WORKSPACES_MIGRATIONS);
+    let list = value
+        .get_mut(WORKSPACES_KEY)
+        .map(Value::take)
+        .ok_or_else(|| format!("Missing `{WORKSPACES_KEY}` list"))?;
+    let list: Vec<WorkspaceEntry> = serde_json::from_value(list).map_err(|e| e.to_string())?;
     Ok(list
         .into_iter()
         .map(|entry| (entry.id.clone(), entry))
@@ -17,11 +60,12 @@ pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, Workspac
 }
 
 pub(crate) fn write_workspaces(path: &PathBuf, entries: &[WorkspaceEntry]) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
-    let data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
-    std::fs::write(path, data).map_err(|e| e.to_string())
+    let value = json!({
+        SCHEMA_VERSION_KEY: WORKSPACES_SCHEMA_VERSION,
+        WORKSPACES_KEY: entries,
+    });
+    let data = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    write_atomic(path, &data)
 }
 
 pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
@@ -30,23 +74,166 @@ pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
     }
     let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
     let mut value: Value = serde_json::from_str(&data).map_err(|e| e.to_string())?;
-    migrate_follow_up_message_behavior(&mut value);
-    match serde_json::from_value(value.clone()) {
-        Ok(settings) => Ok(settings),
-        Err(_) => {
-            sanitize_remote_settings_for_tcp_only(&mut value);
-            migrate_follow_up_message_behavior(&mut value);
-            serde_json::from_value(value).map_err(|e| e.to_string())
+    migrate_value(&mut value, SETTINGS_MIGRATIONS);
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
+    let mut value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    if let Value::Object(root) = &mut value {
+        root.insert(
+            SCHEMA_VERSION_KEY.to_string(),
+            Value::from(SETTINGS_SCHEMA_VERSION),
+        );
+    }
+    let data = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    write_atomic(path, &data)
+}
+
+/// Workspaces at startup, recovered from the newest readable backup when the
+/// file is unreadable.
+pub(crate) fn load_workspaces(
+    path: &PathBuf,
+) -> (HashMap<String, WorkspaceEntry>, Option<StorageRecovery>) {
+    load_with_recovery(path, read_workspaces)
+}
+
+/// Settings at startup, recovered from the newest readable backup when the
+/// file is unreadable.
+pub(crate) fn load_settings(path: &PathBuf) -> (AppSettings, Option<StorageRecovery>) {
+    load_with_recovery(path, read_settings)
+}
+
+/// Schema version stored in a file; files without one (including bare
+/// arrays) are version 0.
+fn schema_version(value: &Value) -> u32 {
+    value
+        .get(SCHEMA_VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map(|version| version as u32)
+        .unwrap_or(0)
+}
+
+/// Runs the migrations newer than the file's schema version, in order.
+/// Files written by a newer build are read as they are.
+fn migrate_value(value: &mut Value, migrations: &[StorageMigration]) {
+    let version = schema_version(value);
+    for migration in migrations
+        .iter()
+        .filter(|migration| migration.version > version)
+    {
+        (migration.migrate)(value);
+    }
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{file_name}{suffix}"))
+}
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    sibling_path(path, &format!(".bak.{index}"))
+}
+
+/// Shifts the backups and copies the current file into `.bak.1`. Files that
+/// are not valid JSON are not backed up, so they never push out a good copy.
+fn rotate_backups(path: &Path) -> Result<(), String> {
+    let Ok(current) = std::fs::read(path) else {
+        return Ok(());
+    };
+    if serde_json::from_slice::<Value>(&current).is_err() {
+        return Ok(());
+    }
+    for index in (1..MAX_BACKUPS).rev() {
+        let from = backup_path(path, index);
+        if from.exists() {
+            std::fs::rename(&from, backup_path(path, index + 1)).map_err(|e| e.to_string())?;
         }
     }
+    std::fs::write(backup_path(path, 1), current).map_err(|e| e.to_string())
 }
 
-pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+/// Writes through a synced temp file renamed over `path`, so a crash leaves
+/// either the old or the new contents.
+fn write_atomic(path: &Path, data: &str) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    if let Err(err) = rotate_backups(path) {
+        eprintln!("Failed to back up {}: {err}", path.display());
+    }
+
+    let temp_path = sibling_path(path, &format!(".{}.tmp", Uuid::new_v4()));
+    let written = std::fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(data.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| std::fs::rename(&temp_path, path)) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(err.to_string());
+    }
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+fn load_with_recovery<T: Default>(
+    path: &PathBuf,
+    read: fn(&PathBuf) -> Result<T, String>,
+) -> (T, Option<StorageRecovery>) {
+    let error = match read(path) {
+        Ok(value) => return (value, None),
+        Err(error) => error,
+    };
+    // Keep the unreadable file for inspection; the next write would
+    // otherwise replace it.
+    let quarantine = sibling_path(
+        path,
+        &format!(".corrupt-{}", chrono::Utc::now().format("%Y%m%d%H%M%S")),
+    );
+    let quarantined_path = std::fs::rename(path, &quarantine)
+        .ok()
+        .map(|()| quarantine.to_string_lossy().to_string());
+    let mut recovery = StorageRecovery {
+        file: path.to_string_lossy().to_string(),
+        error,
+        recovered_from: None,
+        quarantined_path,
+    };
+
+    for index in 1..=MAX_BACKUPS {
+        let backup = backup_path(path, index);
+        if !backup.exists() {
+            continue;
+        }
+        let Ok(value) = read(&backup) else {
+            continue;
+        };
+        if let Err(err) = std::fs::read_to_string(&backup)
+            .map_err(|e| e.to_string())
+            .and_then(|data| write_atomic(path, &data))
+        {
+            eprintln!("Failed to restore {}: {err}", path.display());
+        }
+        recovery.recovered_from = Some(backup.to_string_lossy().to_string());
+        return (value, Some(recovery));
+    }
+    (T::default(), Some(recovery))
+}
+
+/// Moves the bare workspace array of older builds into the versioned
+/// envelope.
+fn wrap_workspaces_list(value: &mut Value) {
+    if value.is_array() {
+        *value = json!({ WORKSPACES_KEY: value.take() });
     }
-    let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    std::fs::write(path, data).map_err(|e| e.to_string())
 }
 
 fn sanitize_remote_settings_for_tcp_only(value: &mut Value) {
@@ -94,10 +281,26 @@ fn migrate_follow_up_message_behavior(value: &mut Value) {
 
 #[cfg(test)]
 mod tests {
-    use super::{read_settings, read_workspaces, write_workspaces};
-    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use super::{
+        backup_path, load_settings, load_workspaces, read_settings, read_workspaces,
+        write_settings, write_workspaces, StorageMigration, MAX_BACKUPS, SETTINGS_MIGRATIONS,
+        SETTINGS_SCHEMA_VERSION, WORKSPACES_MIGRATIONS, WORKSPACES_SCHEMA_VERSION,
+    };
+    use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
     use uuid::Uuid;
 
+    fn workspace_entry(id: &str) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: "Workspace".to_string(),
+            path: "/tmp".to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
     #[test]
     fn write_read_workspaces_persists_sort_and_group() {
         let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
@@ -228,4 +431,143 @@ mod tests {
         let settings = read_settings(&path).expect("read settings");
         assert_eq!(settings.follow_up_message_behavior, "queue");
     }
+
+    #[test]
+    fn migrations_are_ordered_and_end_at_current_version() {
+        let check = |migrations: &[StorageMigration], current: u32| {
+            let versions: Vec<u32> = migrations
+                .iter()
+                .map(|migration| migration.version)
+                .collect();
+            assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+            assert_eq!(versions.last().copied(), Some(current));
+        };
+        check(SETTINGS_MIGRATIONS, SETTINGS_SCHEMA_VERSION);
+        check(WORKSPACES_MIGRATIONS, WORKSPACES_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn read_workspaces_migrates_bare_array_and_writes_versioned_envelope() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let path = temp_dir.join("workspaces.json");
+
+        let legacy = serde_json::to_string(&[workspace_entry("w1")]).expect("serialize legacy");
+        std::fs::write(&path, legacy).expect("write legacy workspaces");
+        let read = read_workspaces(&path).expect("read legacy workspaces");
+        assert!(read.contains_key("w1"));
+
+        let entries: Vec<_> = read.into_values().collect();
+        write_workspaces(&path, &entries).expect("write workspaces");
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read workspaces file"))
+                .expect("parse workspaces");
+        assert_eq!(
+            raw.get("schemaVersion").and_then(serde_json::Value::as_u64),
+            Some(u64::from(WORKSPACES_SCHEMA_VERSION))
+        );
+        assert_eq!(raw["workspaces"][0]["id"], "w1");
+        assert!(read_workspaces(&path)
+            .expect("read versioned workspaces")
+            .contains_key("w1"));
+    }
+
+    #[test]
+    fn read_settings_runs_only_migrations_newer_than_the_file() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let path = temp_dir.join("settings.json");
+
+        std::fs::write(&path, r#"{ "schemaVersion": 1, "steerEnabled": true }"#)
+            .expect("write settings");
+        let settings = read_settings(&path).expect("read settings");
+        assert_eq!(settings.follow_up_message_behavior, "steer");
+
+        std::fs::write(&path, r#"{ "schemaVersion": 2, "steerEnabled": true }"#)
+            .expect("write settings");
+        let settings = read_settings(&path).expect("read settings");
+        assert_eq!(settings.follow_up_message_behavior, "queue");
+    }
+
+    #[test]
+    fn write_settings_stamps_schema_version() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("settings.json");
+
+        write_settings(&path, &AppSettings::default()).expect("write settings");
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read settings file"))
+                .expect("parse settings");
+        assert_eq!(
+            raw.get("schemaVersion").and_then(serde_json::Value::as_u64),
+            Some(u64::from(SETTINGS_SCHEMA_VERSION))
+        );
+    }
+
+    #[test]
+    fn writes_keep_rolling_backups_and_no_temp_files() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("workspaces.json");
+
+        for index in 0..5 {
+            write_workspaces(&path, &[workspace_entry(&format!("w{index}"))])
+                .expect("write workspaces");
+        }
+
+        let previous = read_workspaces(&backup_path(&path, 1)).expect("read newest backup");
+        assert!(previous.contains_key("w3"));
+        let oldest = read_workspaces(&backup_path(&path, MAX_BACKUPS)).expect("read oldest");
+        assert!(oldest.contains_key("w1"));
+        assert!(!backup_path(&path, MAX_BACKUPS + 1).exists());
+        let leftovers: Vec<_> = std::fs::read_dir(&temp_dir)
+            .expect("list temp dir")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn load_settings_recovers_from_latest_backup_and_keeps_corrupt_file() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("settings.json");
+
+        let mut settings = AppSettings::default();
+        settings.theme = "dark".to_string();
+        write_settings(&path, &settings).expect("write settings");
+        settings.theme = "light".to_string();
+        write_settings(&path, &settings).expect("write settings");
+        std::fs::write(&path, "{ \"theme\": ").expect("corrupt settings");
+
+        let (loaded, recovery) = load_settings(&path);
+        assert_eq!(loaded.theme, "dark");
+        let recovery = recovery.expect("recovery report");
+        assert_eq!(
+            recovery.recovered_from.as_deref(),
+            Some(backup_path(&path, 1).to_string_lossy().as_ref())
+        );
+        let quarantined = recovery.quarantined_path.expect("quarantined file");
+        assert_eq!(
+            std::fs::read_to_string(quarantined).expect("read quarantined"),
+            "{ \"theme\": "
+        );
+        assert_eq!(read_settings(&path).expect("restored").theme, "dark");
+    }
+
+    #[test]
+    fn load_workspaces_reports_reset_when_no_backup_is_readable() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let path = temp_dir.join("workspaces.json");
+        std::fs::write(&path, "not json").expect("write corrupt workspaces");
+
+        let (workspaces, recovery) = load_workspaces(&path);
+        assert!(workspaces.is_empty());
+        let recovery = recovery.expect("recovery report");
+        assert!(recovery.recovered_from.is_none());
+        assert!(recovery.summary().contains("defaults"));
+
+        let (_, recovery) = load_workspaces(&path);
+        assert!(recovery.is_none(), "quarantined file is not read again");
+    }
 }
@@ -78,6 +78,86 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitTagInfo {
+    pub(crate) name: String,
+    pub(crate) sha: String,
+    pub(crate) annotated: bool,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubRelease {
+    #[serde(rename = "tagName")]
+    pub(crate) tag_name: String,
+    #[serde(default, rename = "previousTag")]
+    pub(crate) previous_tag: Option<String>,
+    pub(crate) url: String,
+    #[serde(rename = "isDraft")]
+    pub(crate) is_draft: bool,
+    pub(crate) notes: String,
+}
+
+/// State filter for issue and pull request listings. `Merged` only applies to
+/// pull requests; `Closed` pull requests exclude merged ones.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitHubItemState {
+    Open,
+    Closed,
+    Merged,
+    All,
+}
+
+impl Default for GitHubItemState {
+    fn default() -> Self {
+        GitHubItemState::Open
+    }
+}
+
+/// Filters and page position for issue and pull request listings. `cursor` is
+/// the `nextCursor` of the previous page.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubListQuery {
+    #[serde(default)]
+    pub(crate) state: GitHubItemState,
+    #[serde(default)]
+    pub(crate) labels: Vec<String>,
+    #[serde(default)]
+    pub(crate) assignee: Option<String>,
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) search: Option<String>,
+    #[serde(default)]
+    pub(crate) milestone: Option<String>,
+    #[serde(default)]
+    pub(crate) cursor: Option<String>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubLabel {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+}
+
+/// Issue or pull request referenced from another item, such as the pull
+/// requests that close an issue.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubLinkedItem {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) state: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
@@ -85,12 +165,29 @@ pub(crate) struct GitHubIssue {
     pub(crate) url: String,
     #[serde(rename = "updatedAt")]
     pub(crate) updated_at: String,
+    /// Lowercase `open` or `closed`.
+    #[serde(default)]
+    pub(crate) state: String,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    pub(crate) labels: Vec<GitHubLabel>,
+    #[serde(default)]
+    pub(crate) assignees: Vec<GitHubPullRequestAuthor>,
+    #[serde(default, rename = "commentCount")]
+    pub(crate) comment_count: u64,
+    #[serde(default, rename = "linkedPullRequests")]
+    pub(crate) linked_pull_requests: Vec<GitHubLinkedItem>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssuesResponse {
     pub(crate) total: usize,
     pub(crate) issues: Vec<GitHubIssue>,
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -116,6 +213,18 @@ pub(crate) struct GitHubPullRequest {
     pub(crate) is_draft: bool,
     #[serde(default)]
     pub(crate) author: Option<GitHubPullRequestAuthor>,
+    /// Lowercase `open`, `closed` or `merged`.
+    #[serde(default)]
+    pub(crate) state: String,
+    #[serde(default)]
+    pub(crate) labels: Vec<GitHubLabel>,
+    #[serde(default)]
+    pub(crate) assignees: Vec<GitHubPullRequestAuthor>,
+    #[serde(default, rename = "commentCount")]
+    pub(crate) comment_count: u64,
+    /// Issues this pull request closes when merged.
+    #[serde(default, rename = "linkedIssues")]
+    pub(crate) linked_issues: Vec<GitHubLinkedItem>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -123,6 +232,8 @@ pub(crate) struct GitHubPullRequestsResponse {
     pub(crate) total: usize,
     #[serde(rename = "pullRequests")]
     pub(crate) pull_requests: Vec<GitHubPullRequest>,
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -145,6 +256,135 @@ pub(crate) struct GitHubPullRequestComment {
     pub(crate) author: Option<GitHubPullRequestAuthor>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubCheckRun {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    /// `queued`, `in_progress`, `completed`, `waiting` or `pending`.
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) conclusion: Option<String>,
+    #[serde(default)]
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) app: Option<String>,
+    #[serde(default)]
+    pub(crate) started_at: Option<String>,
+    #[serde(default)]
+    pub(crate) completed_at: Option<String>,
+    /// Tail of the failed steps' log, for failed GitHub Actions jobs.
+    #[serde(default)]
+    pub(crate) log_excerpt: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitHubChecksState {
+    None,
+    Pending,
+    Success,
+    Failure,
+}
+
+impl Default for GitHubChecksState {
+    fn default() -> Self {
+        GitHubChecksState::None
+    }
+}
+
+impl GitHubChecksState {
+    pub(crate) fn is_finished(self) -> bool {
+        matches!(
+            self,
+            GitHubChecksState::Success | GitHubChecksState::Failure
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubChecksResponse {
+    pub(crate) git_ref: String,
+    pub(crate) sha: String,
+    #[serde(default)]
+    pub(crate) pr_number: Option<u64>,
+    pub(crate) state: GitHubChecksState,
+    pub(crate) checks: Vec<GitHubCheckRun>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitHubPullRequestReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+impl Default for GitHubPullRequestReviewEvent {
+    fn default() -> Self {
+        GitHubPullRequestReviewEvent::Comment
+    }
+}
+
+impl GitHubPullRequestReviewEvent {
+    pub(crate) fn as_api_value(self) -> &'static str {
+        match self {
+            GitHubPullRequestReviewEvent::Approve => "APPROVE",
+            GitHubPullRequestReviewEvent::RequestChanges => "REQUEST_CHANGES",
+            GitHubPullRequestReviewEvent::Comment => "COMMENT",
+        }
+    }
+}
+
+/// Which side of a pull request diff an inline comment is attached to: `left`
+/// for removed lines, `right` for added and context lines.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitHubDiffSide {
+    Left,
+    Right,
+}
+
+impl Default for GitHubDiffSide {
+    fn default() -> Self {
+        GitHubDiffSide::Right
+    }
+}
+
+impl GitHubDiffSide {
+    pub(crate) fn as_api_value(self) -> &'static str {
+        match self {
+            GitHubDiffSide::Left => "LEFT",
+            GitHubDiffSide::Right => "RIGHT",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubPullRequestReviewComment {
+    pub(crate) path: String,
+    pub(crate) line: u64,
+    #[serde(default)]
+    pub(crate) start_line: Option<u64>,
+    #[serde(default)]
+    pub(crate) side: GitHubDiffSide,
+    pub(crate) body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubPullRequestReviewResult {
+    pub(crate) id: u64,
+    pub(crate) url: String,
+    pub(crate) state: String,
+    pub(crate) inline_comments: usize,
+    /// Comments whose lines are not part of the pull request diff. GitHub rejects
+    /// those inline, so they are listed in the review body instead.
+    pub(crate) unanchored_comments: Vec<GitHubPullRequestReviewComment>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct LocalUsageDay {
@@ -325,6 +565,7 @@ impl WorkspaceKind {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeInfo {
+    /// Empty for a worktree created with a detached HEAD.
     pub(crate) branch: String,
 }
 
@@ -336,6 +577,9 @@ pub(crate) struct WorkspaceGroup {
     pub(crate) sort_order: Option<u32>,
     #[serde(default, rename = "copiesFolder")]
     pub(crate) copies_folder: Option<String>,
+    /// Codex home profile used by workspaces in this group that do not set their own.
+    #[serde(default, rename = "codexHomeProfileId")]
+    pub(crate) codex_home_profile_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -356,6 +600,125 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    /// Forge used for issues and pull requests; detected from the remote URL when unset.
+    #[serde(default)]
+    pub(crate) forge: Option<ForgeKind>,
+    /// API base URL for self-hosted forges whose API is not at the default path.
+    #[serde(default, rename = "forgeApiUrl")]
+    pub(crate) forge_api_url: Option<String>,
+    /// Issue an agent was started from in this worktree.
+    #[serde(default, rename = "issueLink")]
+    pub(crate) issue_link: Option<WorkspaceIssueLink>,
+    /// Sparse and shallow checkout options inherited by new worktrees and clones.
+    #[serde(default)]
+    pub(crate) checkout: Option<WorkspaceCheckoutOptions>,
+    /// Codex home profile for this workspace; worktrees inherit the parent's.
+    #[serde(default, rename = "codexHomeProfileId")]
+    pub(crate) codex_home_profile_id: Option<String>,
+    /// `[profiles.<name>]` from config.toml the app-server starts with;
+    /// worktrees inherit the parent's.
+    #[serde(default, rename = "activeProfile")]
+    pub(crate) active_profile: Option<String>,
+}
+
+/// A named `CODEX_HOME`, each with its own login, `config.toml`, rules and
+/// sessions.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeProfile {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) path: String,
+}
+
+/// What a Codex home profile is assigned to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CodexHomeScope {
+    Workspace,
+    Group,
+}
+
+/// A Codex home profile with what is on disk for it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeProfileInfo {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) exists: bool,
+    /// Whether the home holds an `auth.json` from a completed login.
+    pub(crate) signed_in: bool,
+    pub(crate) workspace_ids: Vec<String>,
+    pub(crate) group_ids: Vec<String>,
+}
+
+/// Outcome of switching a workspace or group to another Codex home.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeSwitchResult {
+    /// Workspaces whose resolved Codex home changed.
+    pub(crate) workspace_ids: Vec<String>,
+    /// Connected workspaces moved onto a session for their new home.
+    pub(crate) reconnected: Vec<String>,
+}
+
+/// Options that keep checkouts of large repositories small.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceCheckoutOptions {
+    /// Cone-mode sparse-checkout directories; empty checks out the full tree.
+    #[serde(default)]
+    pub(crate) sparse_paths: Vec<String>,
+    /// History depth for clones from a git URL.
+    #[serde(default)]
+    pub(crate) depth: Option<u32>,
+    /// Partial clone filter for clones from a git URL, e.g. `blob:none`.
+    #[serde(default)]
+    pub(crate) filter: Option<String>,
+}
+
+/// Ties a worktree to the issue it was created for and the thread working on
+/// it, so the pull request opened from the branch can reference the issue.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceIssueLink {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) branch: String,
+    pub(crate) thread_id: String,
+}
+
+/// Outcome of starting an agent from an issue: the new worktree and the
+/// thread the issue prompt was sent to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssueAgentStart {
+    pub(crate) workspace: WorkspaceInfo,
+    pub(crate) thread_id: String,
+    pub(crate) branch: String,
+    pub(crate) issue: GitHubIssue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ForgeKind {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+/// Forge resolved for a workspace, so the UI can label issues and merge requests.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ForgeInfo {
+    pub(crate) kind: ForgeKind,
+    /// Whether the kind comes from the workspace settings rather than detection.
+    pub(crate) overridden: bool,
+    pub(crate) host: Option<String>,
+    pub(crate) repository: Option<String>,
+    pub(crate) api_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -372,6 +735,235 @@ pub(crate) struct WorktreeSetupStatus {
     #[serde(rename = "shouldRun")]
     pub(crate) should_run: bool,
     pub(crate) script: Option<String>,
+    /// Latest backend run of the setup script, if any.
+    #[serde(default)]
+    pub(crate) run: Option<WorktreeSetupRun>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorktreeSetupRunState {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+/// Persisted status of a worktree setup script run, stored next to the
+/// setup marker together with the run's log.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeSetupRun {
+    pub(crate) workspace_id: String,
+    pub(crate) state: WorktreeSetupRunState,
+    pub(crate) script: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    /// Milliseconds since the Unix epoch.
+    pub(crate) started_at: u64,
+    #[serde(default)]
+    pub(crate) finished_at: Option<u64>,
+    /// Spawn or wait failure, when the script never produced an exit code.
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorktreeGcItemKind {
+    /// Worktree workspace known to the app.
+    Worktree,
+    /// Directory under the app's worktrees folder that no workspace points at.
+    OrphanedWorktreeDir,
+    /// Repository in a copies folder that no workspace points at, cloned from
+    /// a project the app tracks.
+    OrphanedClone,
+    /// `git worktree` metadata in a parent repo whose directory is gone.
+    StaleWorktreeMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeGcItem {
+    /// Stable id to pass back to `worktree_gc_apply`.
+    pub(crate) id: String,
+    pub(crate) kind: WorktreeGcItemKind,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) parent_id: Option<String>,
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+    /// Branch tip is contained in the parent's HEAD or `origin/HEAD`.
+    pub(crate) merged: bool,
+    /// Branch tracks an upstream that no longer exists.
+    pub(crate) upstream_gone: bool,
+    /// Worktree directory no longer exists.
+    pub(crate) missing: bool,
+    /// Uncommitted or untracked changes; skipped by apply unless allowed.
+    pub(crate) dirty: bool,
+    /// Seconds since the newest file change or commit.
+    #[serde(default)]
+    pub(crate) idle_secs: Option<u64>,
+    pub(crate) size_bytes: u64,
+    /// Whether the item looks safe to remove.
+    pub(crate) recommended: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeGcReport {
+    pub(crate) items: Vec<WorktreeGcItem>,
+    pub(crate) total_bytes: u64,
+    /// Size of the recommended items.
+    pub(crate) reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeGcSkipped {
+    pub(crate) id: String,
+    pub(crate) reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeGcApplyResult {
+    pub(crate) removed: Vec<String>,
+    pub(crate) skipped: Vec<WorktreeGcSkipped>,
+    pub(crate) freed_bytes: u64,
+}
+
+/// Portable description of the workspace list written by `export_workspaces`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundle {
+    pub(crate) version: u32,
+    /// Milliseconds since the Unix epoch.
+    pub(crate) exported_at: u64,
+    /// Copies folders under the export root are stored relative to it.
+    #[serde(default)]
+    pub(crate) groups: Vec<WorkspaceGroup>,
+    #[serde(default)]
+    pub(crate) workspaces: Vec<WorkspaceBundleEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleEntry {
+    /// Id on the exporting machine; only used to link clones and groups
+    /// inside the bundle.
+    pub(crate) id: String,
+    pub(crate) name: String,
+    /// Path relative to the export root, with `/` separators.
+    #[serde(default)]
+    pub(crate) relative_path: Option<String>,
+    /// Absolute path on the exporting machine. Entries outside the export
+    /// root are imported by its folder name under the import root.
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) remote_url: Option<String>,
+    #[serde(default)]
+    pub(crate) settings: WorkspaceSettings,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceExportResult {
+    pub(crate) path: String,
+    pub(crate) workspaces: usize,
+    pub(crate) groups: usize,
+    /// Worktrees are not exported; they are recreated from branches.
+    pub(crate) skipped_worktrees: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkspaceImportStatus {
+    /// Folder existed and was added as a workspace.
+    Added,
+    /// Folder was missing and re-cloned from its remote URL.
+    Cloned,
+    /// A workspace already points at the folder; its settings are kept.
+    Existing,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceImportItem {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) status: WorkspaceImportStatus,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    /// The workspace came with a worktree setup script that should be read
+    /// before the next worktree runs it.
+    #[serde(default)]
+    pub(crate) needs_review: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceImportResult {
+    pub(crate) items: Vec<WorkspaceImportItem>,
+    pub(crate) groups_added: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorktreeApplyStrategy {
+    /// Binary patch of staged, unstaged and untracked changes. Requires a clean parent.
+    #[default]
+    Patch,
+    /// Binary patch applied directly, falling back to a 3-way apply. Allows a dirty parent.
+    ThreeWayPatch,
+    /// `git merge --no-ff` of the worktree branch.
+    Merge,
+    /// `git merge --squash` of the worktree branch followed by a single commit.
+    Squash,
+    /// Cherry-pick every commit the worktree branch has on top of the parent HEAD.
+    CherryPick,
+}
+
+impl WorktreeApplyStrategy {
+    pub(crate) fn is_commit_based(&self) -> bool {
+        matches!(
+            self,
+            WorktreeApplyStrategy::Merge
+                | WorktreeApplyStrategy::Squash
+                | WorktreeApplyStrategy::CherryPick
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeApplyOptions {
+    #[serde(default)]
+    pub(crate) strategy: WorktreeApplyStrategy,
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+    #[serde(default)]
+    pub(crate) commit_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeApplyResult {
+    pub(crate) strategy: WorktreeApplyStrategy,
+    pub(crate) dry_run: bool,
+    pub(crate) applied: bool,
+    #[serde(default)]
+    pub(crate) conflicts: Vec<String>,
+    #[serde(default)]
+    pub(crate) commits: Vec<String>,
+    #[serde(default)]
+    pub(crate) commit_message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -400,6 +992,35 @@ pub(crate) struct RemoteBackendTarget {
     pub(crate) last_connected_at_ms: Option<i64>,
 }
 
+/// What happened when a storage file could not be read at startup.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageRecovery {
+    pub(crate) file: String,
+    pub(crate) error: String,
+    /// Backup the data was restored from; `None` means defaults were used.
+    #[serde(default)]
+    pub(crate) recovered_from: Option<String>,
+    /// Where the unreadable file was moved.
+    #[serde(default)]
+    pub(crate) quarantined_path: Option<String>,
+}
+
+impl StorageRecovery {
+    pub(crate) fn summary(&self) -> String {
+        match self.recovered_from.as_deref() {
+            Some(backup) => format!(
+                "Restored {} from {backup} after a read error: {}",
+                self.file, self.error
+            ),
+            None => format!(
+                "Reset {} to defaults after a read error: {}",
+                self.file, self.error
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct AppSettings {
     #[serde(default, rename = "codexBin")]
@@ -575,6 +1196,8 @@ pub(crate) struct AppSettings {
     pub(crate) commit_message_prompt: String,
     #[serde(default, rename = "commitMessageModelId")]
     pub(crate) commit_message_model_id: Option<String>,
+    #[serde(default = "default_issue_agent_prompt", rename = "issueAgentPrompt")]
+    pub(crate) issue_agent_prompt: String,
     #[serde(
         default = "default_system_notifications_enabled",
         rename = "systemNotificationsEnabled"
@@ -691,6 +1314,8 @@ pub(crate) struct AppSettings {
     pub(crate) composer_code_block_copy_use_modifier: bool,
     #[serde(default = "default_workspace_groups", rename = "workspaceGroups")]
     pub(crate) workspace_groups: Vec<WorkspaceGroup>,
+    #[serde(default, rename = "codexHomeProfiles")]
+    pub(crate) codex_home_profiles: Vec<CodexHomeProfile>,
     #[serde(default = "default_open_app_targets", rename = "openAppTargets")]
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
@@ -996,6 +1621,18 @@ Changes:\n{diff}"
         .to_string()
 }
 
+pub(crate) fn default_issue_agent_prompt() -> String {
+    "Resolve issue #{number}: {title}\n\
+{url}\n\n\
+You are working on the branch `{branch}` in a dedicated worktree. \
+Read the issue and its discussion, implement the change, \
+and add or update tests where it makes sense.\n\n\
+Labels: {labels}\n\n\
+Issue description:\n{body}\n\n\
+Comments:\n{comments}"
+        .to_string()
+}
+
 fn default_collaboration_modes_enabled() -> bool {
     true
 }
@@ -1260,6 +1897,7 @@ impl Default for AppSettings {
             git_diff_ignore_whitespace_changes: default_git_diff_ignore_whitespace_changes(),
             commit_message_prompt: default_commit_message_prompt(),
             commit_message_model_id: None,
+            issue_agent_prompt: default_issue_agent_prompt(),
             collaboration_modes_enabled: true,
             steer_enabled: true,
             follow_up_message_behavior: default_follow_up_message_behavior(),
@@ -1286,6 +1924,7 @@ impl Default for AppSettings {
             composer_list_continuation: default_composer_list_continuation(),
             composer_code_block_copy_use_modifier: default_composer_code_block_copy_use_modifier(),
             workspace_groups: default_workspace_groups(),
+            codex_home_profiles: Vec::new(),
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
         }
@@ -1428,6 +2067,7 @@ mod tests {
         assert!(settings.preload_git_diffs);
         assert!(!settings.git_diff_ignore_whitespace_changes);
         assert!(settings.commit_message_prompt.contains("{diff}"));
+        assert!(settings.issue_agent_prompt.contains("{body}"));
         assert!(settings.collaboration_modes_enabled);
         assert!(settings.steer_enabled);
         assert_eq!(settings.follow_up_message_behavior, "queue");
@@ -1477,6 +2117,7 @@ mod tests {
             name: "Group".to_string(),
             sort_order: Some(2),
             copies_folder: Some("/tmp/group-copies".to_string()),
+            codex_home_profile_id: None,
         }];
 
         let json = serde_json::to_string(&settings).expect("serialize settings");
@@ -18,11 +18,18 @@ use super::worktree::{
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::spawn_workspace_session;
+use crate::event_sink::TauriEventSink;
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
-use crate::shared::{workspace_rpc, workspaces_core};
+use crate::shared::{codex_home_core, issue_agent_core, workspace_rpc, workspaces_core};
 use crate::state::AppState;
-use crate::types::{WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus};
+use crate::types::{
+    CodexHomeProfileInfo, CodexHomeScope, CodexHomeSwitchResult, IssueAgentStart,
+    WorkspaceCheckoutOptions, WorkspaceEntry, WorkspaceExportResult, WorkspaceImportResult,
+    WorkspaceInfo, WorkspaceSettings, WorktreeApplyOptions, WorktreeApplyResult,
+    WorktreeApplyStrategy, WorktreeGcApplyResult, WorktreeGcReport, WorktreeSetupRun,
+    WorktreeSetupStatus,
+};
 
 fn spawn_with_app(
     app: &AppHandle,
@@ -34,6 +41,35 @@ fn spawn_with_app(
     spawn_workspace_session(entry, default_bin, codex_args, app.clone(), codex_home)
 }
 
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))
+}
+
+/// Starts the setup script of a freshly added worktree or clone. Failures are
+/// logged rather than failing the add.
+async fn start_worktree_setup_after_add(state: &AppState, app: &AppHandle, workspace_id: &str) {
+    let started = match app_data_dir(app) {
+        Ok(data_dir) => {
+            workspaces_core::start_worktree_setup_core(
+                &state.workspaces,
+                &state.worktree_setup_runs,
+                &data_dir,
+                TauriEventSink::new(app.clone()),
+                workspace_id.to_string(),
+                false,
+                None,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    if let Err(err) = started {
+        eprintln!("Failed to start setup script for {workspace_id}: {err}");
+    }
+}
+
 fn workspace_remote_params<T: serde::Serialize>(request: &T) -> Result<serde_json::Value, String> {
     workspace_rpc::to_params(request)
 }
@@ -179,6 +215,7 @@ pub(crate) async fn add_workspace_from_git_url(
     url: String,
     destination_path: String,
     target_folder_name: Option<String>,
+    checkout: Option<WorkspaceCheckoutOptions>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
@@ -188,6 +225,7 @@ pub(crate) async fn add_workspace_from_git_url(
             url,
             destination_path,
             target_folder_name,
+            checkout,
         };
         let response = remote_backend::call_remote(
             &*state,
@@ -203,6 +241,7 @@ pub(crate) async fn add_workspace_from_git_url(
         url,
         destination_path,
         target_folder_name,
+        checkout,
         &state.workspaces,
         &state.sessions,
         &state.app_settings,
@@ -214,6 +253,200 @@ pub(crate) async fn add_workspace_from_git_url(
     .await
 }
 
+/// Writes workspaces, groups and remote URLs to a portable bundle, with
+/// paths relative to `root`.
+#[tauri::command]
+pub(crate) async fn export_workspaces(
+    root: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceExportResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceBundleRequest {
+            root: remote_backend::normalize_path_for_remote(root),
+            path: remote_backend::normalize_path_for_remote(path),
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_workspaces",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::export_workspaces_core(root, path, &state.workspaces, &state.app_settings)
+        .await
+}
+
+/// Adds the workspaces of a bundle under `root`, re-cloning missing repos
+/// from their remote URLs.
+#[tauri::command]
+pub(crate) async fn import_workspaces(
+    root: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceImportResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceBundleRequest {
+            root: remote_backend::normalize_path_for_remote(root),
+            path: remote_backend::normalize_path_for_remote(path),
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "import_workspaces",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::import_workspaces_core(
+        root,
+        path,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        &state.settings_path,
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn list_codex_home_profiles(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<CodexHomeProfileInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_codex_home_profiles",
+            workspace_remote_empty_params(),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(
+        codex_home_core::list_codex_home_profiles_core(&state.workspaces, &state.app_settings)
+            .await,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn create_codex_home_profile(
+    name: String,
+    path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CodexHomeProfileInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::CreateCodexHomeProfileRequest {
+            name,
+            path: path.map(remote_backend::normalize_path_for_remote),
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_codex_home_profile",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_home_core::create_codex_home_profile_core(
+        name,
+        path,
+        &state.app_settings,
+        &state.settings_path,
+    )
+    .await
+}
+
+/// Assigns a Codex home profile to a workspace or group and moves connected
+/// workspaces onto a session for their new home.
+#[tauri::command]
+pub(crate) async fn switch_codex_home_profile(
+    scope: CodexHomeScope,
+    target_id: String,
+    profile_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CodexHomeSwitchResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SwitchCodexHomeProfileRequest {
+            scope,
+            target_id,
+            profile_id,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "switch_codex_home_profile",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_home_core::switch_codex_home_profile_core(
+        scope,
+        target_id,
+        profile_id,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.settings_path,
+        &state.storage_path,
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_codex_home_profile(
+    profile_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CodexHomeSwitchResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::DeleteCodexHomeProfileRequest { profile_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_codex_home_profile",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_home_core::delete_codex_home_profile_core(
+        profile_id,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.settings_path,
+        &state.storage_path,
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn add_clone(
     source_workspace_id: String,
@@ -222,7 +455,7 @@ pub(crate) async fn add_clone(
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
-    workspaces_core::add_clone_core(
+    let workspace = workspaces_core::add_clone_core(
         source_workspace_id,
         copy_name,
         copies_folder,
@@ -234,7 +467,9 @@ pub(crate) async fn add_clone(
             spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
         },
     )
-    .await
+    .await?;
+    start_worktree_setup_after_add(&state, &app, &workspace.id).await;
+    Ok(workspace)
 }
 
 #[tauri::command]
@@ -243,16 +478,21 @@ pub(crate) async fn add_worktree(
     branch: String,
     name: Option<String>,
     copy_agents_md: Option<bool>,
+    base_ref: Option<String>,
+    detach: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
     let copy_agents_md = copy_agents_md.unwrap_or(true);
+    let detach = detach.unwrap_or(false);
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::AddWorktreeRequest {
             parent_id,
             branch,
             name,
             copy_agents_md,
+            base_ref,
+            detach,
         };
         let response = remote_backend::call_remote(
             &*state,
@@ -264,16 +504,38 @@ pub(crate) async fn add_worktree(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    add_worktree_local(
+        &state,
+        &app,
+        parent_id,
+        branch,
+        name,
+        copy_agents_md,
+        base_ref,
+        detach,
+    )
+    .await
+}
 
-    workspaces_core::add_worktree_core(
+async fn add_worktree_local(
+    state: &AppState,
+    app: &AppHandle,
+    parent_id: String,
+    branch: String,
+    name: Option<String>,
+    copy_agents_md: bool,
+    base_ref: Option<String>,
+    detach: bool,
+) -> Result<WorkspaceInfo, String> {
+    let data_dir = app_data_dir(app)?;
+
+    let workspace = workspaces_core::add_worktree_core(
         parent_id,
         branch,
         name,
         copy_agents_md,
+        base_ref,
+        detach,
         &data_dir,
         &state.workspaces,
         &state.sessions,
@@ -293,7 +555,56 @@ pub(crate) async fn add_worktree(
             })
         },
         |entry, default_bin, codex_args, codex_home| {
-            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+            spawn_with_app(app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await?;
+    start_worktree_setup_after_add(state, app, &workspace.id).await;
+    Ok(workspace)
+}
+
+/// Starts an agent on a forge issue: creates a worktree for it, opens a thread
+/// there and sends the issue prompt built from the configured template.
+#[tauri::command]
+pub(crate) async fn start_issue_agent(
+    workspace_id: String,
+    issue_number: u64,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<IssueAgentStart, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::StartIssueAgentRequest {
+            workspace_id,
+            issue_number,
+            model,
+            effort,
+            access_mode,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "start_issue_agent",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    issue_agent_core::start_issue_agent_core(
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        workspace_id,
+        issue_number,
+        model,
+        effort,
+        access_mode,
+        |parent_id, branch, name| {
+            add_worktree_local(&state, &app, parent_id, branch, name, true, None, false)
         },
     )
     .await
@@ -317,11 +628,14 @@ pub(crate) async fn worktree_setup_status(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
-    workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir).await
+    let data_dir = app_data_dir(&app)?;
+    workspaces_core::worktree_setup_status_core(
+        &state.workspaces,
+        &state.worktree_setup_runs,
+        &workspace_id,
+        &data_dir,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -342,13 +656,93 @@ pub(crate) async fn worktree_setup_mark_ran(
         return Ok(());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = app_data_dir(&app)?;
     workspaces_core::worktree_setup_mark_ran_core(&state.workspaces, &workspace_id, &data_dir).await
 }
 
+/// Re-runs the setup script in the backend. Output streams as
+/// `worktree-setup-output` events and completion as `worktree-setup-status`.
+#[tauri::command]
+pub(crate) async fn worktree_setup_run(
+    workspace_id: String,
+    timeout_secs: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<WorktreeSetupRun>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorktreeSetupRunRequest {
+            workspace_id,
+            timeout_secs,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_run",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    workspaces_core::start_worktree_setup_core(
+        &state.workspaces,
+        &state.worktree_setup_runs,
+        &data_dir,
+        TauriEventSink::new(app),
+        workspace_id,
+        true,
+        timeout_secs,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_setup_cancel(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_cancel",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(
+        workspaces_core::cancel_worktree_setup_core(&state.worktree_setup_runs, &workspace_id)
+            .await,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_setup_log(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_log",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    workspaces_core::worktree_setup_log_core(&state.workspaces, &data_dir, &workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn remove_workspace(
     id: String,
@@ -425,6 +819,72 @@ pub(crate) async fn remove_worktree(
     .await
 }
 
+/// Lists worktrees, orphaned worktree/clone directories and stale worktree
+/// metadata with their size, idle age and merge state.
+#[tauri::command]
+pub(crate) async fn worktree_gc_report(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeGcReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_gc_report",
+            workspace_remote_empty_params(),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    workspaces_core::worktree_gc_report_core(&state.workspaces, &state.app_settings, &data_dir)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_gc_apply(
+    ids: Vec<String>,
+    allow_dirty: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeGcApplyResult, String> {
+    let allow_dirty = allow_dirty.unwrap_or(false);
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorktreeGcApplyRequest { ids, allow_dirty };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_gc_apply",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app_data_dir(&app)?;
+    workspaces_core::worktree_gc_apply_core(
+        ids,
+        allow_dirty,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &data_dir,
+        &state.storage_path,
+        |root, args| {
+            workspaces_core::run_git_command_unit(root, args, |repo, args_owned| {
+                run_git_command_owned(repo, args_owned)
+            })
+        },
+        |error| is_missing_worktree_error(error),
+        |path| {
+            std::fs::remove_dir_all(path)
+                .map_err(|err| format!("Failed to remove worktree folder: {err}"))
+        },
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn rename_worktree(
     id: String,
@@ -444,10 +904,7 @@ pub(crate) async fn rename_worktree(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let data_dir = app_data_dir(&app)?;
 
     workspaces_core::rename_worktree_core(
         id,
@@ -544,9 +1001,39 @@ pub(crate) async fn rename_worktree_upstream(
 #[tauri::command]
 pub(crate) async fn apply_worktree_changes(
     workspace_id: String,
+    strategy: Option<WorktreeApplyStrategy>,
+    dry_run: Option<bool>,
+    commit_message: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    workspaces_core::apply_worktree_changes_core(&state.workspaces, workspace_id).await
+    app: AppHandle,
+) -> Result<WorktreeApplyResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ApplyWorktreeChangesRequest {
+            workspace_id,
+            strategy,
+            dry_run,
+            commit_message,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "apply_worktree_changes",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::apply_worktree_changes_core(
+        &state.workspaces,
+        workspace_id,
+        WorktreeApplyOptions {
+            strategy: strategy.unwrap_or_default(),
+            dry_run: dry_run.unwrap_or(false),
+            commit_message,
+        },
+    )
+    .await
 }
 
 #[tauri::command]
@@ -3,17 +3,29 @@ use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use super::git::{is_missing_worktree_error, run_git_command_owned};
 use super::settings::{apply_workspace_settings_update, sort_workspaces};
 use super::worktree::{
     build_clone_destination_path, sanitize_clone_dir_name, sanitize_worktree_name,
 };
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, GitStatusChanged, TerminalExit,
+    TerminalOutput, WorktreeSetupOutput,
+};
+use crate::shared::git_core;
 use crate::shared::workspaces_core::{
-    remove_workspace_core, remove_worktree_core, rename_worktree_core,
+    add_worktree_core, apply_worktree_changes_core, cancel_worktree_setup_core,
+    export_workspaces_core, import_workspaces_core, remove_workspace_core, remove_worktree_core,
+    rename_worktree_core, run_git_command_unit, start_worktree_setup_core, worktree_gc_apply_core,
+    worktree_gc_report_core, worktree_setup_log_core, worktree_setup_status_core,
+    WorktreeSetupRuns,
 };
 use crate::storage::{read_workspaces, write_workspaces};
 use crate::types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
+    AppSettings, WorkspaceBundle, WorkspaceEntry, WorkspaceGroup, WorkspaceImportStatus,
+    WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeApplyOptions, WorktreeApplyStrategy,
+    WorktreeGcItemKind, WorktreeInfo, WorktreeSetupRun, WorktreeSetupRunState,
 };
 use tokio::runtime::Runtime;
 use tokio::sync::Mutex;
@@ -56,6 +68,12 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            forge: None,
+            forge_api_url: None,
+            issue_link: None,
+            checkout: None,
+            codex_home_profile_id: None,
+            active_profile: None,
         },
     }
 }
@@ -65,6 +83,78 @@ fn run_async<F: Future<Output = ()>>(future: F) {
     runtime.block_on(future);
 }
 
+fn git(repo_path: &PathBuf, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .expect("run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn create_repo_with_worktree(
+    branch: &str,
+) -> (
+    PathBuf,
+    Mutex<HashMap<String, WorkspaceEntry>>,
+    PathBuf,
+    PathBuf,
+) {
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    let repo_path = temp_dir.join("repo");
+    let worktree_path = temp_dir.join("worktrees").join("parent").join("wt");
+    std::fs::create_dir_all(&repo_path).expect("create repo path");
+    git(&repo_path, &["init", "-q", "-b", "main"]);
+    git(&repo_path, &["config", "user.name", "Test"]);
+    git(&repo_path, &["config", "user.email", "test@example.com"]);
+    std::fs::write(repo_path.join("a.txt"), "base\n").expect("write base file");
+    git(&repo_path, &["add", "."]);
+    git(&repo_path, &["commit", "-q", "-m", "init"]);
+    git(
+        &repo_path,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            branch,
+            worktree_path.to_string_lossy().as_ref(),
+        ],
+    );
+
+    let parent = WorkspaceEntry {
+        id: "parent".to_string(),
+        name: "Parent".to_string(),
+        path: repo_path.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let worktree = WorkspaceEntry {
+        id: "wt-1".to_string(),
+        name: branch.to_string(),
+        path: worktree_path.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Worktree,
+        parent_id: Some(parent.id.clone()),
+        worktree: Some(WorktreeInfo {
+            branch: branch.to_string(),
+        }),
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([
+        (parent.id.clone(), parent),
+        (worktree.id.clone(), worktree),
+    ]));
+    (temp_dir, workspaces, repo_path, worktree_path)
+}
+
 #[test]
 fn sanitize_worktree_name_rewrites_specials() {
     assert_eq!(
@@ -502,3 +592,683 @@ fn remove_worktree_succeeds_when_parent_repo_folder_is_missing() {
         assert!(!workspaces_guard.contains_key(&child.id));
     });
 }
+
+#[test]
+fn apply_worktree_changes_dry_run_reports_merge_conflicts() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, worktree_path) =
+            create_repo_with_worktree("feature/conflict");
+        std::fs::write(worktree_path.join("a.txt"), "worktree\n").expect("write worktree file");
+        git(
+            &worktree_path,
+            &["commit", "-q", "-am", "Change in worktree"],
+        );
+        std::fs::write(repo_path.join("a.txt"), "parent\n").expect("write parent file");
+        git(&repo_path, &["commit", "-q", "-am", "Change in parent"]);
+        let head_before = git(&repo_path, &["rev-parse", "HEAD"]);
+
+        let result = apply_worktree_changes_core(
+            &workspaces,
+            "wt-1".to_string(),
+            WorktreeApplyOptions {
+                strategy: WorktreeApplyStrategy::Merge,
+                dry_run: true,
+                commit_message: None,
+            },
+        )
+        .await
+        .expect("dry run");
+
+        assert!(result.dry_run);
+        assert!(!result.applied);
+        assert_eq!(result.conflicts, vec!["a.txt".to_string()]);
+        assert_eq!(result.commits.len(), 1);
+        assert_eq!(git(&repo_path, &["rev-parse", "HEAD"]), head_before);
+        assert!(git(&repo_path, &["status", "--porcelain"]).is_empty());
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[test]
+fn apply_worktree_changes_squash_commits_with_generated_message() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, worktree_path) =
+            create_repo_with_worktree("feature/squash");
+        std::fs::write(worktree_path.join("b.txt"), "one\n").expect("write first file");
+        git(&worktree_path, &["add", "."]);
+        git(&worktree_path, &["commit", "-q", "-m", "Add b"]);
+        std::fs::write(worktree_path.join("c.txt"), "two\n").expect("write second file");
+        git(&worktree_path, &["add", "."]);
+        git(&worktree_path, &["commit", "-q", "-m", "Add c"]);
+
+        let result = apply_worktree_changes_core(
+            &workspaces,
+            "wt-1".to_string(),
+            WorktreeApplyOptions {
+                strategy: WorktreeApplyStrategy::Squash,
+                dry_run: false,
+                commit_message: None,
+            },
+        )
+        .await
+        .expect("squash");
+
+        assert!(result.applied);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.commits.len(), 2);
+        assert_eq!(
+            git(&repo_path, &["log", "-1", "--format=%B"]),
+            "Squash merge branch 'feature/squash'\n\n* Add b\n* Add c"
+        );
+        assert_eq!(git(&repo_path, &["rev-list", "--count", "HEAD"]), "2");
+        assert!(repo_path.join("b.txt").is_file());
+        assert!(repo_path.join("c.txt").is_file());
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[test]
+fn apply_worktree_changes_patch_refuses_dirty_parent() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, worktree_path) =
+            create_repo_with_worktree("feature/patch");
+        std::fs::write(worktree_path.join("b.txt"), "new\n").expect("write worktree file");
+        std::fs::write(repo_path.join("dirty.txt"), "dirty\n").expect("write parent file");
+
+        let error = apply_worktree_changes_core(
+            &workspaces,
+            "wt-1".to_string(),
+            WorktreeApplyOptions::default(),
+        )
+        .await
+        .expect_err("patch should refuse a dirty parent");
+        assert!(error.contains("uncommitted changes"));
+
+        let result = apply_worktree_changes_core(
+            &workspaces,
+            "wt-1".to_string(),
+            WorktreeApplyOptions {
+                strategy: WorktreeApplyStrategy::ThreeWayPatch,
+                dry_run: false,
+                commit_message: None,
+            },
+        )
+        .await
+        .expect("three-way patch");
+        assert!(result.applied);
+        assert_eq!(
+            std::fs::read_to_string(repo_path.join("b.txt")).expect("read applied file"),
+            "new\n"
+        );
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[derive(Clone, Default)]
+struct SetupEventRecorder {
+    output: Arc<std::sync::Mutex<Vec<WorktreeSetupOutput>>>,
+    statuses: Arc<std::sync::Mutex<Vec<WorktreeSetupRun>>>,
+}
+
+impl EventSink for SetupEventRecorder {
+    fn emit_app_server_event(&self, _event: AppServerEvent) {}
+    fn emit_terminal_output(&self, _event: TerminalOutput) {}
+    fn emit_terminal_exit(&self, _event: TerminalExit) {}
+    fn emit_github_checks_finished(&self, _event: GitHubChecksFinished) {}
+    fn emit_git_status_changed(&self, _event: GitStatusChanged) {}
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        self.output.lock().unwrap().push(event);
+    }
+
+    fn emit_worktree_setup_status(&self, event: WorktreeSetupRun) {
+        self.statuses.lock().unwrap().push(event);
+    }
+}
+
+async fn wait_for_setup_run(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    runs: &WorktreeSetupRuns,
+    workspace_id: &str,
+    data_dir: &PathBuf,
+) -> WorktreeSetupRun {
+    for _ in 0..100 {
+        let status = worktree_setup_status_core(workspaces, runs, workspace_id, data_dir)
+            .await
+            .expect("setup status");
+        let run = status.run.expect("setup run");
+        if run.state != WorktreeSetupRunState::Running {
+            return run;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("setup script did not finish");
+}
+
+#[cfg(unix)]
+#[test]
+fn setup_script_for_clone_uses_source_script_and_persists_log_and_status() {
+    run_async(async {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let data_dir = temp_dir.join("data");
+        let clone_path = temp_dir.join("clone");
+        std::fs::create_dir_all(&clone_path).expect("create clone path");
+
+        let source = WorkspaceEntry {
+            id: "source".to_string(),
+            name: "Source".to_string(),
+            path: temp_dir.join("source").to_string_lossy().to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                worktree_setup_script: Some(
+                    "echo \"setup in $(basename \"$PWD\")\"; echo warn >&2; exit 3".to_string(),
+                ),
+                ..WorkspaceSettings::default()
+            },
+        };
+        let clone = WorkspaceEntry {
+            id: "clone".to_string(),
+            name: "Clone".to_string(),
+            path: clone_path.to_string_lossy().to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                clone_source_workspace_id: Some(source.id.clone()),
+                ..WorkspaceSettings::default()
+            },
+        };
+        let workspaces = Mutex::new(HashMap::from([
+            (source.id.clone(), source),
+            (clone.id.clone(), clone),
+        ]));
+        let runs = WorktreeSetupRuns::default();
+        let events = SetupEventRecorder::default();
+
+        let started = start_worktree_setup_core(
+            &workspaces,
+            &runs,
+            &data_dir,
+            events.clone(),
+            "clone".to_string(),
+            false,
+            None,
+        )
+        .await
+        .expect("start setup")
+        .expect("setup started");
+        assert_eq!(started.state, WorktreeSetupRunState::Running);
+
+        let run = wait_for_setup_run(&workspaces, &runs, "clone", &data_dir).await;
+        assert_eq!(run.state, WorktreeSetupRunState::Failed);
+        assert_eq!(run.exit_code, Some(3));
+        assert!(run.finished_at.is_some());
+
+        let log = worktree_setup_log_core(&workspaces, &data_dir, "clone")
+            .await
+            .expect("setup log");
+        assert!(log.contains("setup in clone\n"));
+        assert!(log.contains("warn\n"));
+        let output = events.output.lock().unwrap().clone();
+        assert!(output
+            .iter()
+            .any(|event| event.stream == "stderr" && event.data == "warn\n"));
+        let statuses = events.statuses.lock().unwrap().clone();
+        assert_eq!(
+            statuses.last().map(|run| run.state),
+            Some(WorktreeSetupRunState::Failed)
+        );
+
+        let again = start_worktree_setup_core(
+            &workspaces,
+            &runs,
+            &data_dir,
+            events.clone(),
+            "clone".to_string(),
+            false,
+            None,
+        )
+        .await
+        .expect("start setup again");
+        assert!(again.is_none(), "setup should only run once without force");
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn setup_script_can_be_cancelled() {
+    run_async(async {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let data_dir = temp_dir.join("data");
+        let worktree_path = temp_dir.join("worktree");
+        std::fs::create_dir_all(&worktree_path).expect("create worktree path");
+
+        let worktree = WorkspaceEntry {
+            id: "wt-setup".to_string(),
+            name: "feature-setup".to_string(),
+            path: worktree_path.to_string_lossy().to_string(),
+            kind: WorkspaceKind::Worktree,
+            parent_id: Some("parent".to_string()),
+            worktree: Some(WorktreeInfo {
+                branch: "feature-setup".to_string(),
+            }),
+            settings: WorkspaceSettings {
+                worktree_setup_script: Some("sleep 30".to_string()),
+                ..WorkspaceSettings::default()
+            },
+        };
+        let workspaces = Mutex::new(HashMap::from([(worktree.id.clone(), worktree)]));
+        let runs = WorktreeSetupRuns::default();
+
+        start_worktree_setup_core(
+            &workspaces,
+            &runs,
+            &data_dir,
+            SetupEventRecorder::default(),
+            "wt-setup".to_string(),
+            true,
+            None,
+        )
+        .await
+        .expect("start setup");
+        let status = worktree_setup_status_core(&workspaces, &runs, "wt-setup", &data_dir)
+            .await
+            .expect("setup status");
+        assert!(!status.should_run);
+
+        assert!(cancel_worktree_setup_core(&runs, "wt-setup").await);
+        let run = wait_for_setup_run(&workspaces, &runs, "wt-setup", &data_dir).await;
+        assert_eq!(run.state, WorktreeSetupRunState::Cancelled);
+        assert!(!cancel_worktree_setup_core(&runs, "wt-setup").await);
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn setup_script_finishes_when_a_background_child_keeps_output_open() {
+    run_async(async {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let data_dir = temp_dir.join("data");
+        let workspace_path = temp_dir.join("workspace");
+        std::fs::create_dir_all(&workspace_path).expect("create workspace path");
+
+        let workspace = WorkspaceEntry {
+            id: "bg-setup".to_string(),
+            name: "Background".to_string(),
+            path: workspace_path.to_string_lossy().to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                worktree_setup_script: Some("echo ready; sleep 30 &".to_string()),
+                ..WorkspaceSettings::default()
+            },
+        };
+        let workspaces = Mutex::new(HashMap::from([(workspace.id.clone(), workspace)]));
+        let runs = WorktreeSetupRuns::default();
+        let events = SetupEventRecorder::default();
+
+        start_worktree_setup_core(
+            &workspaces,
+            &runs,
+            &data_dir,
+            events.clone(),
+            "bg-setup".to_string(),
+            true,
+            None,
+        )
+        .await
+        .expect("start setup");
+        let run = wait_for_setup_run(&workspaces, &runs, "bg-setup", &data_dir).await;
+        assert_eq!(run.state, WorktreeSetupRunState::Succeeded);
+        let log = worktree_setup_log_core(&workspaces, &data_dir, "bg-setup")
+            .await
+            .expect("setup log");
+        assert!(log.contains("ready\n"));
+
+        // The final status is emitted once the run is no longer tracked.
+        for _ in 0..50 {
+            let finished = events
+                .statuses
+                .lock()
+                .unwrap()
+                .last()
+                .is_some_and(|run| run.state != WorktreeSetupRunState::Running);
+            if finished {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        let rerun = start_worktree_setup_core(
+            &workspaces,
+            &runs,
+            &data_dir,
+            events.clone(),
+            "bg-setup".to_string(),
+            true,
+            None,
+        )
+        .await
+        .expect("re-run setup");
+        assert!(rerun.is_some());
+        wait_for_setup_run(&workspaces, &runs, "bg-setup", &data_dir).await;
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[test]
+fn worktree_gc_reports_orphans_and_stale_metadata_and_skips_dirty_worktrees() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, worktree_path) =
+            create_repo_with_worktree("feature/gc");
+        let orphan_path = temp_dir.join("worktrees").join("parent").join("orphan");
+        let stale_path = temp_dir.join("stale");
+        for (branch, path) in [("orphan", &orphan_path), ("stale", &stale_path)] {
+            git(
+                &repo_path,
+                &[
+                    "worktree",
+                    "add",
+                    "-q",
+                    "-b",
+                    branch,
+                    path.to_string_lossy().as_ref(),
+                ],
+            );
+        }
+        std::fs::remove_dir_all(&stale_path).expect("remove stale worktree");
+        std::fs::write(worktree_path.join("notes.txt"), "wip\n").expect("write untracked file");
+
+        let app_settings = Mutex::new(AppSettings::default());
+        let report = worktree_gc_report_core(&workspaces, &app_settings, &temp_dir)
+            .await
+            .expect("gc report");
+        let item = |id: &str| {
+            report
+                .items
+                .iter()
+                .find(|item| item.id == id)
+                .unwrap_or_else(|| panic!("missing gc item {id}"))
+                .clone()
+        };
+        let worktree = item("worktree:wt-1");
+        assert_eq!(worktree.kind, WorktreeGcItemKind::Worktree);
+        assert_eq!(worktree.branch.as_deref(), Some("feature/gc"));
+        assert!(worktree.merged);
+        assert!(worktree.dirty);
+        assert!(!worktree.recommended);
+        assert!(worktree.size_bytes > 0);
+        let orphan_id = format!("orphan:{}", orphan_path.to_string_lossy());
+        let orphan = item(&orphan_id);
+        assert_eq!(orphan.kind, WorktreeGcItemKind::OrphanedWorktreeDir);
+        assert_eq!(orphan.parent_id.as_deref(), Some("parent"));
+        assert!(orphan.recommended);
+        let stale = item("stale:parent:stale");
+        assert_eq!(stale.kind, WorktreeGcItemKind::StaleWorktreeMetadata);
+        assert_eq!(report.items.len(), 3);
+
+        let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+        let result = worktree_gc_apply_core(
+            vec![
+                orphan_id.clone(),
+                "stale:parent:stale".to_string(),
+                "worktree:wt-1".to_string(),
+            ],
+            false,
+            &workspaces,
+            &sessions,
+            &app_settings,
+            &temp_dir,
+            &temp_dir.join("workspaces.json"),
+            |root, args| run_git_command_unit(root, args, run_git_command_owned),
+            |error| is_missing_worktree_error(error),
+            |path| std::fs::remove_dir_all(path).map_err(|err| err.to_string()),
+        )
+        .await
+        .expect("gc apply");
+
+        assert_eq!(
+            result.removed,
+            vec![orphan_id, "stale:parent:stale".to_string()]
+        );
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].id, "worktree:wt-1");
+        assert!(!orphan_path.exists());
+        assert!(worktree_path.exists());
+        let worktrees = git(&repo_path, &["worktree", "list", "--porcelain"]);
+        assert!(!worktrees.contains("orphan"));
+        assert!(!worktrees.contains("stale"));
+        assert!(workspaces.lock().await.contains_key("wt-1"));
+
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[test]
+fn add_worktree_starts_from_pull_request_head_or_remote_branch() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, _worktree_path) =
+            create_repo_with_worktree("feature/base");
+        git(&repo_path, &["checkout", "-q", "-b", "pr-source"]);
+        std::fs::write(repo_path.join("a.txt"), "pull request\n").expect("write pr file");
+        git(&repo_path, &["commit", "-q", "-am", "pr change"]);
+        let pr_commit = git(&repo_path, &["rev-parse", "HEAD"]);
+        git(&repo_path, &["checkout", "-q", "main"]);
+        let origin_path = temp_dir.join("origin.git");
+        git(
+            &temp_dir,
+            &[
+                "clone",
+                "-q",
+                "--bare",
+                repo_path.to_string_lossy().as_ref(),
+                origin_path.to_string_lossy().as_ref(),
+            ],
+        );
+        git(
+            &origin_path,
+            &["update-ref", "refs/pull/7/head", "pr-source"],
+        );
+        git(&origin_path, &["branch", "-q", "-D", "pr-source"]);
+        git(
+            &repo_path,
+            &[
+                "remote",
+                "add",
+                "origin",
+                origin_path.to_string_lossy().as_ref(),
+            ],
+        );
+        git(&repo_path, &["fetch", "-q", "origin"]);
+
+        let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+        let app_settings = Mutex::new(AppSettings::default());
+        let storage_path = temp_dir.join("workspaces.json");
+        let add = |branch: &str, base_ref: &str, detach: bool| {
+            add_worktree_core(
+                "parent".to_string(),
+                branch.to_string(),
+                None,
+                false,
+                Some(base_ref.to_string()),
+                detach,
+                &temp_dir,
+                &workspaces,
+                &sessions,
+                &app_settings,
+                &storage_path,
+                |value| sanitize_worktree_name(value),
+                |root, name| Ok(root.join(name)),
+                |root, branch| {
+                    let root = root.clone();
+                    let branch = branch.to_string();
+                    async move { git_core::git_branch_exists(&root, &branch).await }
+                },
+                None::<fn(&PathBuf, &str) -> std::future::Ready<Result<Option<String>, String>>>,
+                |root, args| {
+                    run_git_command_unit(root, args, |repo, args_owned| {
+                        run_git_command_owned(repo, args_owned)
+                    })
+                },
+                // The git work is done before the session spawns, so the
+                // missing app-server only fails the final step.
+                |_entry, _default_bin, _codex_args, _codex_home| async move {
+                    Err("no app-server in tests".to_string())
+                },
+            )
+        };
+        let worktree_root = temp_dir.join("worktrees").join("parent");
+
+        let detached = add("", "#7", true).await;
+        assert_eq!(detached.expect_err("spawn fails"), "no app-server in tests");
+        let detached_path = worktree_root.join("pr-7");
+        assert_eq!(git(&detached_path, &["rev-parse", "HEAD"]), pr_commit);
+        assert_eq!(git(&detached_path, &["branch", "--show-current"]), "");
+
+        let branched = add("agent/release", "origin/main", false).await;
+        assert_eq!(branched.expect_err("spawn fails"), "no app-server in tests");
+        let branched_path = worktree_root.join(sanitize_worktree_name("agent/release"));
+        assert_eq!(
+            git(&branched_path, &["branch", "--show-current"]),
+            "agent/release"
+        );
+        assert_eq!(
+            git(&branched_path, &["rev-parse", "HEAD"]),
+            git(&repo_path, &["rev-parse", "origin/main"])
+        );
+        let upstream = std::process::Command::new("git")
+            .args(["config", "--get", "branch.agent/release.remote"])
+            .current_dir(&repo_path)
+            .output()
+            .expect("run git");
+        assert!(
+            !upstream.status.success(),
+            "new branch must not track its base"
+        );
+
+        let existing = add("agent/release", "origin/main", false).await;
+        assert!(existing
+            .expect_err("existing branch is rejected")
+            .contains("already exists"));
+        let missing = add("agent/missing", "v9.9.9", false).await;
+        assert!(missing.expect_err("unknown ref").contains("was not found"));
+    });
+}
+
+#[test]
+fn export_workspaces_writes_portable_bundle_and_import_matches_existing_paths() {
+    run_async(async {
+        let (temp_dir, workspaces, repo_path, _worktree_path) =
+            create_repo_with_worktree("feature/export");
+        git(
+            &repo_path,
+            &[
+                "remote",
+                "add",
+                "origin",
+                "https://example.com/team/repo.git",
+            ],
+        );
+        let mut settings = AppSettings::default();
+        settings.workspace_groups = vec![WorkspaceGroup {
+            id: "group-1".to_string(),
+            name: "Team".to_string(),
+            sort_order: Some(1),
+            copies_folder: Some(temp_dir.join("copies").to_string_lossy().to_string()),
+            codex_home_profile_id: None,
+        }];
+        let app_settings = Mutex::new(settings);
+        workspaces
+            .lock()
+            .await
+            .get_mut("parent")
+            .expect("parent workspace")
+            .settings
+            .group_id = Some("group-1".to_string());
+        let bundle_path = temp_dir.join("export").join("bundle.json");
+
+        let exported = export_workspaces_core(
+            temp_dir.to_string_lossy().to_string(),
+            bundle_path.to_string_lossy().to_string(),
+            &workspaces,
+            &app_settings,
+        )
+        .await
+        .expect("export workspaces");
+        assert_eq!(exported.workspaces, 1);
+        assert_eq!(exported.skipped_worktrees, 1);
+
+        let raw = std::fs::read_to_string(&bundle_path).expect("read bundle");
+        let mut bundle: WorkspaceBundle = serde_json::from_str(&raw).expect("parse bundle");
+        let entry = &bundle.workspaces[0];
+        assert_eq!(entry.relative_path.as_deref(), Some("repo"));
+        assert_eq!(
+            entry.remote_url.as_deref(),
+            Some("https://example.com/team/repo.git")
+        );
+        assert_eq!(entry.settings.group_id.as_deref(), Some("group-1"));
+        assert_eq!(bundle.groups[0].copies_folder.as_deref(), Some("copies"));
+
+        let mut escaping = bundle.workspaces[0].clone();
+        escaping.id = "escaping".to_string();
+        escaping.relative_path = Some("../outside".to_string());
+        bundle.workspaces.push(escaping);
+        // Same folder name as the first entry, but exported from outside the
+        // root: it must not be mapped onto the first entry's workspace.
+        let mut absolute = bundle.workspaces[0].clone();
+        absolute.id = "absolute".to_string();
+        absolute.relative_path = None;
+        absolute.path = "/elsewhere/on/exporter/repo".to_string();
+        absolute.remote_url = None;
+        bundle.workspaces.push(absolute);
+        std::fs::write(
+            &bundle_path,
+            serde_json::to_string(&bundle).expect("serialize bundle"),
+        )
+        .expect("write bundle");
+
+        let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+        let imported = import_workspaces_core(
+            temp_dir.to_string_lossy().to_string(),
+            bundle_path.to_string_lossy().to_string(),
+            &workspaces,
+            &sessions,
+            &app_settings,
+            &temp_dir.join("workspaces.json"),
+            &temp_dir.join("settings.json"),
+            |_entry, _default_bin, _codex_args, _codex_home| async move {
+                Err("spawn not expected".to_string())
+            },
+        )
+        .await
+        .expect("import workspaces");
+
+        assert_eq!(imported.groups_added, 0);
+        assert_eq!(imported.items.len(), 3);
+        assert_eq!(imported.items[0].status, WorkspaceImportStatus::Existing);
+        assert_eq!(imported.items[0].workspace_id.as_deref(), Some("parent"));
+        assert_eq!(imported.items[1].status, WorkspaceImportStatus::Failed);
+        assert!(imported.items[1]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("Invalid path")));
+        assert!(!imported.items[0].needs_review);
+        assert_eq!(imported.items[2].status, WorkspaceImportStatus::Failed);
+        assert_eq!(
+            PathBuf::from(&imported.items[2].path),
+            temp_dir.join("repo-2")
+        );
+        assert_eq!(imported.items[2].workspace_id, None);
+        assert_eq!(workspaces.lock().await.len(), 2);
+    });
+}
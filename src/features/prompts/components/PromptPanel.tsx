@@ -70,6 +70,10 @@ function isWorkspacePrompt(prompt: CustomPromptOption) {
   return prompt.scope === "workspace";
 }
 
+function isTeamPrompt(prompt: CustomPromptOption) {
+  return prompt.scope === "team";
+}
+
 export function PromptPanel({
   prompts,
   workspacePath,
@@ -162,17 +166,24 @@ export function PromptPanel({
     });
   }, [normalizedQuery, prompts]);
 
-  const { workspacePrompts, globalPrompts } = useMemo(() => {
+  const { workspacePrompts, globalPrompts, teamPrompts } = useMemo(() => {
     const workspaceEntries: CustomPromptOption[] = [];
     const globalEntries: CustomPromptOption[] = [];
+    const teamEntries: CustomPromptOption[] = [];
     filteredPrompts.forEach((prompt) => {
       if (isWorkspacePrompt(prompt)) {
         workspaceEntries.push(prompt);
+      } else if (isTeamPrompt(prompt)) {
+        teamEntries.push(prompt);
       } else {
         globalEntries.push(prompt);
       }
     });
-    return { workspacePrompts: workspaceEntries, globalPrompts: globalEntries };
+    return {
+      workspacePrompts: workspaceEntries,
+      globalPrompts: globalEntries,
+      teamPrompts: teamEntries,
+    };
   }, [filteredPrompts]);
 
   const totalCount = filteredPrompts.length;
@@ -371,15 +382,17 @@ export function PromptPanel({
           >
             New agent
           </button>
-          <button
-            type="button"
-            className="ghost icon-button prompt-action-menu"
-            onClick={(event) => void showPromptMenu(event, prompt)}
-            aria-label="Prompt actions"
-            title="Prompt actions"
-          >
-            <MoreHorizontal aria-hidden />
-          </button>
+          {isTeamPrompt(prompt) ? null : (
+            <button
+              type="button"
+              className="ghost icon-button prompt-action-menu"
+              onClick={(event) => void showPromptMenu(event, prompt)}
+              aria-label="Prompt actions"
+              title="Prompt actions"
+            >
+              <MoreHorizontal aria-hidden />
+            </button>
+          )}
         </div>
         {pendingDeletePath === prompt.path && (
           <div className="prompt-delete-confirm">
@@ -597,6 +610,16 @@ export function PromptPanel({
             </div>
           )}
         </div>
+        {teamPrompts.length > 0 ? (
+          <div className="prompt-section">
+            <div className="prompt-section-header">
+              <div className="prompt-section-title">Team prompts</div>
+            </div>
+            <div className="prompt-list">
+              {teamPrompts.map((prompt) => renderPromptRow(prompt))}
+            </div>
+          </div>
+        ) : null}
       </div>
     </PanelShell>
   );
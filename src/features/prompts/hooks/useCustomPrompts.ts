@@ -81,7 +81,11 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
         }
 
         let scope: CustomPromptOption["scope"];
-        if (item.scope === "workspace" || item.scope === "global") {
+        if (
+          item.scope === "workspace" ||
+          item.scope === "global" ||
+          item.scope === "team"
+        ) {
           scope = item.scope;
         }
 
@@ -92,6 +96,7 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
           argumentHint,
           content: String(item.content ?? ""),
           scope,
+          variables: Array.isArray(item.variables) ? item.variables : undefined,
         };
       });
       setPrompts(data);
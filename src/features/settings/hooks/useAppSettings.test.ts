@@ -5,6 +5,7 @@ import type { AppSettings, CodexDoctorResult } from "@/types";
 import { useAppSettings } from "./useAppSettings";
 import {
   getAppSettings,
+  getSettingsSyncReport,
   runCodexDoctor,
   updateAppSettings,
 } from "@services/tauri";
@@ -12,11 +13,13 @@ import { UI_SCALE_DEFAULT, UI_SCALE_MAX } from "@utils/uiScale";
 
 vi.mock("@services/tauri", () => ({
   getAppSettings: vi.fn(),
+  getSettingsSyncReport: vi.fn(),
   updateAppSettings: vi.fn(),
   runCodexDoctor: vi.fn(),
 }));
 
 const getAppSettingsMock = vi.mocked(getAppSettings);
+const getSettingsSyncReportMock = vi.mocked(getSettingsSyncReport);
 const updateAppSettingsMock = vi.mocked(updateAppSettings);
 const runCodexDoctorMock = vi.mocked(runCodexDoctor);
 
@@ -125,6 +128,36 @@ describe("useAppSettings", () => {
     expect(result.current.settings.uiScale).toBe(2.4);
   });
 
+  it("refreshes the settings sync report after saving", async () => {
+    getAppSettingsMock.mockResolvedValue({} as AppSettings);
+    getSettingsSyncReportMock.mockResolvedValueOnce({
+      checkedAt: 1,
+      externalEdit: false,
+      adopted: [],
+      conflicts: [],
+      errors: [],
+    });
+    const { result } = renderHook(() => useAppSettings());
+
+    await waitFor(() => expect(result.current.syncReport?.checkedAt).toBe(1));
+
+    const report = {
+      checkedAt: 2,
+      externalEdit: true,
+      adopted: [],
+      conflicts: [],
+      errors: ["features.steer: `steerEnabled` must be a boolean"],
+    };
+    getSettingsSyncReportMock.mockResolvedValueOnce(report);
+    updateAppSettingsMock.mockResolvedValue(result.current.settings);
+
+    await act(async () => {
+      await result.current.saveSettings(result.current.settings);
+    });
+
+    await waitFor(() => expect(result.current.syncReport).toEqual(report));
+  });
+
   it("surfaces doctor errors", async () => {
     getAppSettingsMock.mockResolvedValue({} as AppSettings);
     runCodexDoctorMock.mockRejectedValue(new Error("doctor fail"));
@@ -1,6 +1,12 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
 import type { AppSettings } from "@/types";
-import { getAppSettings, runCodexDoctor, updateAppSettings } from "@services/tauri";
+import {
+  getAppSettings,
+  getSettingsSyncReport,
+  runCodexDoctor,
+  updateAppSettings,
+} from "@services/tauri";
+import type { SettingsSyncReport } from "@services/tauri";
 import { clampUiScale, UI_SCALE_DEFAULT } from "@utils/uiScale";
 import { CHAT_SCROLLBACK_DEFAULT, normalizeChatHistoryScrollbackItems } from "@utils/chatScrollback";
 import {
@@ -213,6 +219,7 @@ function buildDefaultSettings(): AppSettings {
     composerListContinuation: false,
     composerCodeBlockCopyUseModifier: false,
     workspaceGroups: [],
+    codexHomeProfiles: [],
     openAppTargets: DEFAULT_OPEN_APP_TARGETS,
     selectedOpenAppId: DEFAULT_OPEN_APP_ID,
   };
@@ -296,6 +303,15 @@ export function useAppSettings() {
   const defaultSettings = useMemo(() => buildDefaultSettings(), []);
   const [settings, setSettings] = useState<AppSettings>(defaultSettings);
   const [isLoading, setIsLoading] = useState(true);
+  const [syncReport, setSyncReport] = useState<SettingsSyncReport | null>(null);
+
+  const refreshSyncReport = useCallback(async () => {
+    try {
+      setSyncReport(await getSettingsSyncReport());
+    } catch {
+      // The report is informational; a failed read keeps the last one.
+    }
+  }, []);
 
   useEffect(() => {
     let active = true;
@@ -317,11 +333,14 @@ export function useAppSettings() {
           setIsLoading(false);
         }
       }
+      if (active) {
+        await refreshSyncReport();
+      }
     })();
     return () => {
       active = false;
     };
-  }, [defaultSettings]);
+  }, [defaultSettings, refreshSyncReport]);
 
   const saveSettings = useCallback(async (next: AppSettings) => {
     const normalized = normalizeAppSettings(next);
@@ -332,8 +351,9 @@ export function useAppSettings() {
         ...saved,
       }),
     );
+    void refreshSyncReport();
     return saved;
-  }, [defaultSettings]);
+  }, [defaultSettings, refreshSyncReport]);
 
   const doctor = useCallback(
     async (codexBin: string | null, codexArgs: string | null) => {
@@ -348,5 +368,6 @@ export function useAppSettings() {
     saveSettings,
     doctor,
     isLoading,
+    syncReport,
   };
 }
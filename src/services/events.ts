@@ -1,5 +1,11 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppServerEvent,
+  DictationEvent,
+  DictationModelStatus,
+  GitHubChecksResponse,
+} from "../types";
+import type { WorktreeSetupRun } from "./tauri";
 
 export type Unsubscribe = () => void;
 
@@ -14,6 +20,26 @@ export type TerminalExitEvent = {
   terminalId: string;
 };
 
+export type GitHubChecksFinishedEvent = {
+  workspaceId: string;
+  threadId: string | null;
+  timedOut: boolean;
+  checks: GitHubChecksResponse;
+  followUp: string | null;
+};
+
+export type GitStatusChangedEvent = {
+  workspaceId: string;
+  paths: string[];
+  truncated: boolean;
+};
+
+export type WorktreeSetupOutputEvent = {
+  workspaceId: string;
+  stream: "stdout" | "stderr";
+  data: string;
+};
+
 type SubscriptionOptions = {
   onError?: (error: unknown) => void;
 };
@@ -86,6 +112,14 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const githubChecksFinishedHub = createEventHub<GitHubChecksFinishedEvent>(
+  "github-checks-finished",
+);
+const gitStatusChangedHub = createEventHub<GitStatusChangedEvent>("git-status-changed");
+const worktreeSetupOutputHub = createEventHub<WorktreeSetupOutputEvent>(
+  "worktree-setup-output",
+);
+const worktreeSetupStatusHub = createEventHub<WorktreeSetupRun>("worktree-setup-status");
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -147,6 +181,34 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeGitHubChecksFinished(
+  onEvent: (event: GitHubChecksFinishedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return githubChecksFinishedHub.subscribe(onEvent, options);
+}
+
+export function subscribeGitStatusChanged(
+  onEvent: (event: GitStatusChangedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return gitStatusChangedHub.subscribe(onEvent, options);
+}
+
+export function subscribeWorktreeSetupOutput(
+  onEvent: (event: WorktreeSetupOutputEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return worktreeSetupOutputHub.subscribe(onEvent, options);
+}
+
+export function subscribeWorktreeSetupStatus(
+  onEvent: (event: WorktreeSetupRun) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return worktreeSetupStatusHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -5,8 +5,11 @@ import * as notification from "@tauri-apps/plugin-notification";
 import {
   exportMarkdownFile,
   addWorkspace,
+  addWorkspaceFromGitUrl,
+  addWorktree,
   compactThread,
   createGitHubRepo,
+  exportWorkspaces,
   fetchGit,
   forkThread,
   getAppsList,
@@ -14,9 +17,14 @@ import {
   getMcpSettings,
   getExperimentalFeatureList,
   getGitHubIssues,
+  getGitHubPullRequests,
   getGitLog,
   getGitStatus,
   getOpenAppIcon,
+  getTeamPromptRepo,
+  getPromptHistory,
+  getSettingsSyncReport,
+  getStorageRecoveryReport,
   listThreads,
   listMcpServerStatus,
   localThreadUsageSnapshot,
@@ -36,6 +44,29 @@ import {
   createMcpServer,
   updateMcpServer,
   deleteMcpServer,
+  testMcpServer,
+  getConfigProfiles,
+  createConfigProfile,
+  updateConfigProfile,
+  deleteConfigProfile,
+  getModelProviders,
+  createModelProvider,
+  testModelProvider,
+  previewConfigWrite,
+  listConfigHistory,
+  restoreConfigHistory,
+  getEffectiveConfig,
+  writeWorkspaceAgentsFile,
+  writeWorkspaceCodexConfigToml,
+  startIssueAgent,
+  unwatchGitStatus,
+  watchGitStatus,
+  runWorktreeSetup,
+  cancelWorktreeSetup,
+  applyWorktreeGc,
+  importWorkspaces,
+  createCodexHomeProfile,
+  switchCodexHomeProfile,
   startReview,
   setThreadName,
   tailscaleDaemonStart,
@@ -49,11 +80,25 @@ import {
   createAgent,
   updateAgent,
   deleteAgent,
+  listAgentTemplates,
+  saveAgentTemplate,
+  deleteAgentTemplate,
+  exportAgent,
+  importAgent,
   readAgentConfigToml,
   readImageAsDataUrl,
   generateAgentDescription,
   writeAgentConfigToml,
   writeAgentMd,
+  restorePromptVersion,
+  setTeamPromptRepo,
+  syncTeamPromptRepo,
+  getLocalSkills,
+  createSkill,
+  updateSkill,
+  setSkillEnabled,
+  deleteSkill,
+  installSkill,
 } from "./tauri";
 
 vi.mock("@tauri-apps/api/core", () => ({
@@ -103,6 +148,43 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("passes sparse and shallow checkout options for git URL clones", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ id: "ws-1" });
+
+    await addWorkspaceFromGitUrl("https://example.com/repo.git", "/tmp", null, {
+      sparsePaths: ["apps/web"],
+      depth: 1,
+      filter: "blob:none",
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("add_workspace_from_git_url", {
+      url: "https://example.com/repo.git",
+      destinationPath: "/tmp",
+      targetFolderName: null,
+      checkout: { sparsePaths: ["apps/web"], depth: 1, filter: "blob:none" },
+    });
+  });
+
+  it("creates a detached worktree on a pull request head", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ id: "wt-1" });
+
+    await addWorktree("ws-1", "", "Review #123", false, {
+      baseRef: "#123",
+      detach: true,
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("add_worktree", {
+      parentId: "ws-1",
+      branch: "",
+      name: "Review #123",
+      copyAgentsMd: false,
+      baseRef: "#123",
+      detach: true,
+    });
+  });
+
   it("returns an empty list when workspace picker is cancelled", async () => {
     const openMock = vi.mocked(open);
     openMock.mockResolvedValueOnce(null);
@@ -197,6 +279,259 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("passes issue and pull request list filters through", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ total: 0, pullRequests: [] });
+
+    await getGitHubPullRequests("ws-2", {
+      state: "merged",
+      labels: ["bug"],
+      cursor: "abc",
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("get_github_pull_requests", {
+      workspaceId: "ws-2",
+      query: { state: "merged", labels: ["bug"], cursor: "abc" },
+    });
+  });
+
+  it("starts an issue agent with optional model settings", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({});
+
+    await startIssueAgent("ws-2", 42, { model: "gpt-5-codex" });
+
+    expect(invokeMock).toHaveBeenCalledWith("start_issue_agent", {
+      workspaceId: "ws-2",
+      issueNumber: 42,
+      model: "gpt-5-codex",
+      effort: null,
+      accessMode: null,
+    });
+  });
+
+  it("starts and stops the git status watcher", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce(undefined).mockResolvedValueOnce(true);
+
+    await watchGitStatus("ws-3");
+    await expect(unwatchGitStatus("ws-3")).resolves.toBe(true);
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "watch_git_status", {
+      workspaceId: "ws-3",
+      debounceMs: null,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "unwatch_git_status", {
+      workspaceId: "ws-3",
+    });
+  });
+
+  it("applies worktree garbage collection to the selected items", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      removed: ["worktree:wt-1"],
+      skipped: [],
+      freedBytes: 42,
+    });
+
+    await expect(applyWorktreeGc(["worktree:wt-1"])).resolves.toEqual({
+      removed: ["worktree:wt-1"],
+      skipped: [],
+      freedBytes: 42,
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("worktree_gc_apply", {
+      ids: ["worktree:wt-1"],
+      allowDirty: false,
+    });
+  });
+
+  it("exports and imports workspace bundles", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock
+      .mockResolvedValueOnce({
+        path: "/tmp/bundle.json",
+        workspaces: 2,
+        groups: 1,
+        skippedWorktrees: 0,
+      })
+      .mockResolvedValueOnce({ items: [], groupsAdded: 0 });
+
+    await exportWorkspaces("/work", "/tmp/bundle.json");
+    await expect(importWorkspaces("/new-work", "/tmp/bundle.json")).resolves.toEqual({
+      items: [],
+      groupsAdded: 0,
+    });
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "export_workspaces", {
+      root: "/work",
+      path: "/tmp/bundle.json",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "import_workspaces", {
+      root: "/new-work",
+      path: "/tmp/bundle.json",
+    });
+  });
+
+  it("creates and switches codex home profiles", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock
+      .mockResolvedValueOnce({
+        id: "home-1",
+        name: "Work",
+        path: "/data/codex-homes/work",
+        exists: true,
+        signedIn: false,
+        workspaceIds: [],
+        groupIds: [],
+      })
+      .mockResolvedValueOnce({ workspaceIds: ["ws-1"], reconnected: ["ws-1"] });
+
+    await createCodexHomeProfile("Work");
+    await expect(switchCodexHomeProfile("group", "group-1", "home-1")).resolves.toEqual({
+      workspaceIds: ["ws-1"],
+      reconnected: ["ws-1"],
+    });
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "create_codex_home_profile", {
+      name: "Work",
+      path: null,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "switch_codex_home_profile", {
+      scope: "group",
+      targetId: "group-1",
+      profileId: "home-1",
+    });
+  });
+
+  it("reads the storage recovery report", async () => {
+    const invokeMock = vi.mocked(invoke);
+    const report = [
+      {
+        file: "/data/settings.json",
+        error: "EOF while parsing a value",
+        recoveredFrom: "/data/settings.json.bak.1",
+        quarantinedPath: "/data/settings.json.corrupt-20260101000000",
+      },
+    ];
+    invokeMock.mockResolvedValueOnce(report);
+
+    await expect(getStorageRecoveryReport()).resolves.toEqual(report);
+
+    expect(invokeMock).toHaveBeenCalledWith("get_storage_recovery_report");
+  });
+
+  it("reads the settings sync report", async () => {
+    const invokeMock = vi.mocked(invoke);
+    const report = {
+      checkedAt: 1767225600000,
+      externalEdit: true,
+      adopted: ["personality"],
+      conflicts: [
+        {
+          setting: "lastComposerModelId",
+          configKey: "model",
+          appValue: "gpt-5",
+          configValue: "o3",
+        },
+      ],
+      errors: [],
+    };
+    invokeMock.mockResolvedValueOnce(report);
+
+    await expect(getSettingsSyncReport()).resolves.toEqual(report);
+
+    expect(invokeMock).toHaveBeenCalledWith("get_settings_sync_report");
+  });
+
+  it("maps prompt history and team repository requests", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+
+    await getPromptHistory("ws-1", "/prompts/review.md");
+    await restorePromptVersion("ws-1", "/prompts/review.md", "abc1234");
+    await getTeamPromptRepo();
+    await setTeamPromptRepo("https://example.com/team/prompts.git");
+    await syncTeamPromptRepo();
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "prompts_history", {
+      workspaceId: "ws-1",
+      path: "/prompts/review.md",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "prompts_restore_version", {
+      workspaceId: "ws-1",
+      path: "/prompts/review.md",
+      commit: "abc1234",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "prompts_team_repo");
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "prompts_team_repo_set", {
+      url: "https://example.com/team/prompts.git",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(5, "prompts_team_repo_sync");
+  });
+
+  it("maps skill management requests", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+    const content = "---\nname: lint\ndescription: Runs linters.\n---\n";
+
+    await getLocalSkills("ws-1");
+    await createSkill("ws-1", "workspace", content);
+    await updateSkill("ws-1", "/repo/.agents/skills/lint", content);
+    await setSkillEnabled("ws-1", "/repo/.agents/skills/lint", false);
+    await deleteSkill("ws-1", "/repo/.agents/skills/lint");
+    await installSkill("ws-1", {
+      scope: "global",
+      source: { kind: "git", url: "https://example.com/skills.git", subdir: "lint" },
+    });
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "skills_local_list", {
+      workspaceId: "ws-1",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "skills_create", {
+      workspaceId: "ws-1",
+      scope: "workspace",
+      content,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "skills_update", {
+      workspaceId: "ws-1",
+      path: "/repo/.agents/skills/lint",
+      content,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "skills_set_enabled", {
+      workspaceId: "ws-1",
+      path: "/repo/.agents/skills/lint",
+      enabled: false,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(5, "skills_delete", {
+      workspaceId: "ws-1",
+      path: "/repo/.agents/skills/lint",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(6, "skills_install", {
+      workspaceId: "ws-1",
+      input: {
+        scope: "global",
+        source: { kind: "git", url: "https://example.com/skills.git", subdir: "lint" },
+      },
+    });
+  });
+
+  it("re-runs and cancels the worktree setup script", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce(null).mockResolvedValueOnce(false);
+
+    await runWorktreeSetup("wt-1", 600);
+    await expect(cancelWorktreeSetup("wt-1")).resolves.toBe(false);
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "worktree_setup_run", {
+      workspaceId: "wt-1",
+      timeoutSecs: 600,
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "worktree_setup_cancel", {
+      workspaceId: "wt-1",
+    });
+  });
+
   it("returns an empty list when the Tauri invoke bridge is missing", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockRejectedValueOnce(
@@ -610,6 +945,136 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("manages project-scoped config and nested AGENTS.md files", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await getMcpSettings("ws-1");
+    await writeWorkspaceCodexConfigToml("ws-1", "model = \"o3\"\n");
+    await writeWorkspaceAgentsFile("ws-1", "packages/api/AGENTS.md", "rules");
+    await getEffectiveConfig("ws-1");
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "get_mcp_settings", {
+      workspaceId: "ws-1",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "file_write", {
+      scope: "workspace",
+      kind: "config",
+      workspaceId: "ws-1",
+      content: "model = \"o3\"\n",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "write_workspace_agents_file", {
+      workspaceId: "ws-1",
+      path: "packages/api/AGENTS.md",
+      content: "rules",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "get_effective_config", {
+      workspaceId: "ws-1",
+    });
+  });
+
+  it("manages config.toml profiles", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+
+    await getConfigProfiles();
+    await createConfigProfile({
+      name: "deep-review",
+      model: "gpt-5-codex",
+      modelReasoningEffort: "high",
+    });
+    await updateConfigProfile({
+      originalName: "fast",
+      name: "fast-cheap",
+      workspaceId: "ws-1",
+    });
+    await deleteConfigProfile({ name: "fast-cheap" });
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "get_config_profiles");
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "create_config_profile", {
+      input: {
+        name: "deep-review",
+        model: "gpt-5-codex",
+        modelReasoningEffort: "high",
+      },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "update_config_profile", {
+      input: { originalName: "fast", name: "fast-cheap", workspaceId: "ws-1" },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "delete_config_profile", {
+      input: { name: "fast-cheap" },
+    });
+  });
+
+  it("manages and probes model providers", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+    const provider = {
+      id: "ollama",
+      baseUrl: "http://localhost:11434/v1",
+      wireApi: "chat" as const,
+    };
+
+    await getModelProviders("ws-1");
+    await createModelProvider({ ...provider, workspaceId: "ws-1" });
+    await testModelProvider(provider);
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "get_model_providers", {
+      workspaceId: "ws-1",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "create_model_provider", {
+      input: { ...provider, workspaceId: "ws-1" },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "test_model_provider", {
+      input: provider,
+    });
+  });
+
+  it("previews config writes and restores history snapshots", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+    const target = { kind: "config" as const, workspaceId: "ws-1" };
+
+    await previewConfigWrite(target, 'model = "gpt-5"\n');
+    await listConfigHistory();
+    await listConfigHistory({ kind: "agent", name: "reviewer" });
+    await restoreConfigHistory("0123456789abcdef/1760000000000.toml");
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "config_preview_write", {
+      target,
+      content: 'model = "gpt-5"\n',
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "config_history_list");
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "config_history_list", {
+      target: { kind: "agent", name: "reviewer" },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "config_history_restore", {
+      id: "0123456789abcdef/1760000000000.toml",
+    });
+  });
+
+  it("tests an MCP server before saving it", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ ok: false, error: "exited", stderr: "boom" });
+    const server = {
+      name: "github",
+      enabled: true,
+      command: "npx",
+      args: ["-y", "gh-mcp"],
+      env: { GITHUB_TOKEN: "token" },
+      url: null,
+      headers: {},
+      additionalToml: "",
+    };
+
+    await expect(testMcpServer(server)).resolves.toMatchObject({
+      ok: false,
+      stderr: "boom",
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("test_mcp_server", { input: server });
+  });
+
   it("updates core agents settings", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValueOnce({
@@ -696,6 +1161,39 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("manages agent templates and agent bundles", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue([]);
+
+    await listAgentTemplates();
+    await saveAgentTemplate({
+      id: "migrations",
+      label: "Migration helper",
+      reasoningEffort: "high",
+    });
+    await deleteAgentTemplate("migrations");
+    await exportAgent("reviewer");
+    await importAgent({ content: "{}", name: "reviewer-2" });
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "list_agent_templates");
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "save_agent_template", {
+      input: {
+        id: "migrations",
+        label: "Migration helper",
+        reasoningEffort: "high",
+      },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "delete_agent_template", {
+      id: "migrations",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "export_agent", {
+      name: "reviewer",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(5, "import_agent", {
+      input: { content: "{}", name: "reviewer-2" },
+    });
+  });
+
   it("reads an agent config file", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValueOnce("model = \"gpt-5-codex\"");
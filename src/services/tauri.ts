@@ -5,27 +5,42 @@ import type {
   AppSettings,
   CodexUpdateResult,
   CodexDoctorResult,
+  CustomPromptOption,
   DynamicToolCallResponse,
   DictationModelStatus,
   DictationSessionState,
   LocalThreadUsageSnapshot,
   LocalUsageSnapshot,
+  PromptVariable,
   TcpDaemonStatus,
   TailscaleDaemonCommandPreview,
   TailscaleStatus,
+  WorkspaceCheckoutOptions,
   WorkspaceInfo,
   AppMention,
   WorkspaceSettings,
+  WorktreeApplyOptions,
+  WorktreeApplyResult,
 } from "../types";
 import type {
   GitFileDiff,
   GitFileStatus,
   GitCommitDiff,
+  GitHubChecksResponse,
+  ForgeInfo,
   GitHubIssuesResponse,
+  GitHubListQuery,
+  IssueAgentStart,
+  GitHubPullRequest,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
+  GitHubPullRequestReviewComment,
+  GitHubPullRequestReviewEvent,
+  GitHubPullRequestReviewResult,
   GitHubPullRequestsResponse,
+  GitHubRelease,
   GitLogResponse,
+  GitTagInfo,
   ReviewTarget,
 } from "../types";
 
@@ -151,6 +166,38 @@ export type McpSettings = {
   servers: McpServerConfig[];
 };
 
+export type McpProbeResult = {
+  ok: boolean;
+  transport: "stdio" | "http";
+  protocolVersion: string | null;
+  serverInfo: Record<string, unknown> | null;
+  tools: {
+    name: string;
+    description: string | null;
+    inputSchema: unknown;
+  }[];
+  resources: {
+    uri: string;
+    name: string | null;
+    description: string | null;
+    mimeType: string | null;
+  }[];
+  prompts: {
+    name: string;
+    description: string | null;
+    arguments: Record<string, unknown>[];
+  }[];
+  latency: {
+    initializeMs: number | null;
+    toolsMs: number | null;
+    resourcesMs: number | null;
+    promptsMs: number | null;
+    totalMs: number;
+  };
+  error: string | null;
+  stderr: string | null;
+};
+
 export type CreateMcpServerInput = {
   name: string;
   enabled?: boolean | null;
@@ -160,6 +207,7 @@ export type CreateMcpServerInput = {
   url?: string | null;
   headers?: Record<string, string>;
   additionalToml?: string | null;
+  workspaceId?: string | null;
 };
 
 export type UpdateMcpServerInput = {
@@ -172,10 +220,120 @@ export type UpdateMcpServerInput = {
   url?: string | null;
   headers?: Record<string, string>;
   additionalToml?: string | null;
+  workspaceId?: string | null;
 };
 
 export type DeleteMcpServerInput = {
   name: string;
+  workspaceId?: string | null;
+};
+
+export type ConfigProfileFields = {
+  model?: string | null;
+  modelReasoningEffort?: string | null;
+  approvalPolicy?: string | null;
+  sandboxMode?: string | null;
+  modelProvider?: string | null;
+};
+
+export type ConfigProfile = ConfigProfileFields & {
+  name: string;
+  isDefault: boolean;
+};
+
+export type ConfigProfiles = {
+  configPath: string;
+  defaultProfile: string | null;
+  profiles: ConfigProfile[];
+};
+
+export type CreateConfigProfileInput = ConfigProfileFields & {
+  name: string;
+  workspaceId?: string | null;
+};
+
+export type UpdateConfigProfileInput = ConfigProfileFields & {
+  originalName: string;
+  name: string;
+  workspaceId?: string | null;
+};
+
+export type DeleteConfigProfileInput = {
+  name: string;
+  workspaceId?: string | null;
+};
+
+export type ModelProvider = {
+  id: string;
+  name?: string | null;
+  baseUrl?: string | null;
+  envKey?: string | null;
+  wireApi?: "responses" | "chat" | null;
+  httpHeaders?: Record<string, string>;
+  envHttpHeaders?: Record<string, string>;
+  queryParams?: Record<string, string>;
+};
+
+export type ModelProviders = {
+  configPath: string;
+  defaultProvider: string | null;
+  providers: ModelProvider[];
+};
+
+export type CreateModelProviderInput = ModelProvider & {
+  workspaceId?: string | null;
+};
+
+export type UpdateModelProviderInput = ModelProvider & {
+  originalId: string;
+  workspaceId?: string | null;
+};
+
+export type DeleteModelProviderInput = {
+  id: string;
+  workspaceId?: string | null;
+};
+
+export type ModelProviderProbeResult = {
+  ok: boolean;
+  url: string;
+  status: number | null;
+  models: string[];
+  latencyMs: number;
+  error: string | null;
+};
+
+export type ConfigFileTarget =
+  | { kind: "config"; workspaceId?: string | null }
+  | { kind: "agent"; name: string };
+
+export type ConfigDiagnostic = {
+  severity: "error" | "warning";
+  line: number;
+  column: number | null;
+  key: string | null;
+  message: string;
+};
+
+export type ConfigWritePreview = {
+  path: string;
+  exists: boolean;
+  changed: boolean;
+  diff: string;
+  diagnostics: ConfigDiagnostic[];
+  canSave: boolean;
+};
+
+export type ConfigHistoryEntry = {
+  id: string;
+  path: string;
+  createdAt: number;
+  sizeBytes: number;
+};
+
+export type ConfigHistoryRestore = {
+  path: string;
+  diagnostics: ConfigDiagnostic[];
 };
 
 export type SetAgentsCoreInput = {
@@ -206,6 +364,35 @@ export type DeleteAgentInput = {
   deleteManagedFile?: boolean;
 };
 
+export type AgentTemplate = {
+  id: string;
+  label: string;
+  description: string | null;
+  model: string | null;
+  reasoningEffort: string | null;
+  developerInstructions: string | null;
+  builtIn: boolean;
+};
+
+export type SaveAgentTemplateInput = {
+  id: string;
+  label: string;
+  description?: string | null;
+  model?: string | null;
+  reasoningEffort?: string | null;
+  developerInstructions?: string | null;
+};
+
+export type AgentBundleExport = {
+  fileName: string;
+  content: string;
+};
+
+export type ImportAgentInput = {
+  content: string;
+  name?: string | null;
+};
+
 type FileScope = "workspace" | "global";
 type FileKind = "agents" | "config";
 
@@ -258,7 +445,10 @@ export async function getAgentsSettings(): Promise<AgentsSettings> {
   return invoke<AgentsSettings>("get_agents_settings");
 }
 
-export async function getMcpSettings(): Promise<McpSettings> {
+export async function getMcpSettings(workspaceId?: string): Promise<McpSettings> {
+  if (workspaceId) {
+    return invoke<McpSettings>("get_mcp_settings", { workspaceId });
+  }
   return invoke<McpSettings>("get_mcp_settings");
 }
 
@@ -280,6 +470,97 @@ export async function deleteMcpServer(
   return invoke<McpSettings>("delete_mcp_server", { input });
 }
 
+export async function testMcpServer(
+  input: McpServerConfig,
+): Promise<McpProbeResult> {
+  return invoke<McpProbeResult>("test_mcp_server", { input });
+}
+
+export async function getConfigProfiles(
+  workspaceId?: string,
+): Promise<ConfigProfiles> {
+  if (workspaceId) {
+    return invoke<ConfigProfiles>("get_config_profiles", { workspaceId });
+  }
+  return invoke<ConfigProfiles>("get_config_profiles");
+}
+
+export async function createConfigProfile(
+  input: CreateConfigProfileInput,
+): Promise<ConfigProfiles> {
+  return invoke<ConfigProfiles>("create_config_profile", { input });
+}
+
+export async function updateConfigProfile(
+  input: UpdateConfigProfileInput,
+): Promise<ConfigProfiles> {
+  return invoke<ConfigProfiles>("update_config_profile", { input });
+}
+
+export async function deleteConfigProfile(
+  input: DeleteConfigProfileInput,
+): Promise<ConfigProfiles> {
+  return invoke<ConfigProfiles>("delete_config_profile", { input });
+}
+
+export async function getModelProviders(
+  workspaceId?: string,
+): Promise<ModelProviders> {
+  if (workspaceId) {
+    return invoke<ModelProviders>("get_model_providers", { workspaceId });
+  }
+  return invoke<ModelProviders>("get_model_providers");
+}
+
+export async function createModelProvider(
+  input: CreateModelProviderInput,
+): Promise<ModelProviders> {
+  return invoke<ModelProviders>("create_model_provider", { input });
+}
+
+export async function updateModelProvider(
+  input: UpdateModelProviderInput,
+): Promise<ModelProviders> {
+  return invoke<ModelProviders>("update_model_provider", { input });
+}
+
+export async function deleteModelProvider(
+  input: DeleteModelProviderInput,
+): Promise<ModelProviders> {
+  return invoke<ModelProviders>("delete_model_provider", { input });
+}
+
+export async function testModelProvider(
+  input: ModelProvider,
+): Promise<ModelProviderProbeResult> {
+  return invoke<ModelProviderProbeResult>("test_model_provider", { input });
+}
+
+export async function previewConfigWrite(
+  target: ConfigFileTarget,
+  content: string,
+): Promise<ConfigWritePreview> {
+  return invoke<ConfigWritePreview>("config_preview_write", {
+    target,
+    content,
+  });
+}
+
+export async function listConfigHistory(
+  target?: ConfigFileTarget | null,
+): Promise<ConfigHistoryEntry[]> {
+  if (target) {
+    return invoke<ConfigHistoryEntry[]>("config_history_list", { target });
+  }
+  return invoke<ConfigHistoryEntry[]>("config_history_list");
+}
+
+export async function restoreConfigHistory(
+  id: string,
+): Promise<ConfigHistoryRestore> {
+  return invoke<ConfigHistoryRestore>("config_history_restore", { id });
+}
+
 export async function setAgentsCoreSettings(
   input: SetAgentsCoreInput,
 ): Promise<AgentsSettings> {
@@ -298,6 +579,28 @@ export async function deleteAgent(input: DeleteAgentInput): Promise<AgentsSettin
   return invoke<AgentsSettings>("delete_agent", { input });
 }
 
+export async function listAgentTemplates(): Promise<AgentTemplate[]> {
+  return invoke<AgentTemplate[]>("list_agent_templates");
+}
+
+export async function saveAgentTemplate(
+  input: SaveAgentTemplateInput,
+): Promise<AgentTemplate[]> {
+  return invoke<AgentTemplate[]>("save_agent_template", { input });
+}
+
+export async function deleteAgentTemplate(id: string): Promise<AgentTemplate[]> {
+  return invoke<AgentTemplate[]>("delete_agent_template", { id });
+}
+
+export async function exportAgent(name: string): Promise<AgentBundleExport> {
+  return invoke<AgentBundleExport>("export_agent", { name });
+}
+
+export async function importAgent(input: ImportAgentInput): Promise<AgentsSettings> {
+  return invoke<AgentsSettings>("import_agent", { input });
+}
+
 export async function readAgentConfigToml(agentName: string): Promise<string> {
   return invoke<string>("read_agent_config_toml", { agentName });
 }
@@ -329,11 +632,13 @@ export async function addWorkspaceFromGitUrl(
   url: string,
   destinationPath: string,
   targetFolderName: string | null,
+  checkout?: WorkspaceCheckoutOptions | null,
 ): Promise<WorkspaceInfo> {
   return invoke<WorkspaceInfo>("add_workspace_from_git_url", {
     url,
     destinationPath,
     targetFolderName,
+    checkout: checkout ?? null,
   });
 }
 
@@ -358,13 +663,61 @@ export async function addWorktree(
   branch: string,
   name: string | null,
   copyAgentsMd = true,
+  options?: {
+    // Commit, tag, branch (e.g. `origin/release-2.3`) or PR head as `#123`.
+    baseRef?: string | null;
+    detach?: boolean;
+  },
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_worktree", { parentId, branch, name, copyAgentsMd });
+  return invoke<WorkspaceInfo>("add_worktree", {
+    parentId,
+    branch,
+    name,
+    copyAgentsMd,
+    baseRef: options?.baseRef ?? null,
+    detach: options?.detach ?? false,
+  });
+}
+
+export async function startIssueAgent(
+  workspaceId: string,
+  issueNumber: number,
+  options?: {
+    model?: string | null;
+    effort?: string | null;
+    accessMode?: string | null;
+  },
+): Promise<IssueAgentStart> {
+  return invoke<IssueAgentStart>("start_issue_agent", {
+    workspaceId,
+    issueNumber,
+    model: options?.model ?? null,
+    effort: options?.effort ?? null,
+    accessMode: options?.accessMode ?? null,
+  });
 }
 
+export type WorktreeSetupRunState =
+  | "running"
+  | "succeeded"
+  | "failed"
+  | "cancelled"
+  | "timedOut";
+
+export type WorktreeSetupRun = {
+  workspaceId: string;
+  state: WorktreeSetupRunState;
+  script: string;
+  exitCode: number | null;
+  startedAt: number;
+  finishedAt: number | null;
+  error: string | null;
+};
+
 export type WorktreeSetupStatus = {
   shouldRun: boolean;
   script: string | null;
+  run?: WorktreeSetupRun | null;
 };
 
 export async function getWorktreeSetupStatus(
@@ -377,6 +730,158 @@ export async function markWorktreeSetupRan(workspaceId: string): Promise<void> {
   return invoke("worktree_setup_mark_ran", { workspaceId });
 }
 
+export async function runWorktreeSetup(
+  workspaceId: string,
+  timeoutSecs?: number | null,
+): Promise<WorktreeSetupRun | null> {
+  return invoke<WorktreeSetupRun | null>("worktree_setup_run", {
+    workspaceId,
+    timeoutSecs: timeoutSecs ?? null,
+  });
+}
+
+export async function cancelWorktreeSetup(workspaceId: string): Promise<boolean> {
+  return invoke<boolean>("worktree_setup_cancel", { workspaceId });
+}
+
+export async function getWorktreeSetupLog(workspaceId: string): Promise<string> {
+  return invoke<string>("worktree_setup_log", { workspaceId });
+}
+
+export type WorktreeGcItemKind =
+  | "worktree"
+  | "orphanedWorktreeDir"
+  | "orphanedClone"
+  | "staleWorktreeMetadata";
+
+export type WorktreeGcItem = {
+  id: string;
+  kind: WorktreeGcItemKind;
+  path: string;
+  workspaceId: string | null;
+  parentId: string | null;
+  branch: string | null;
+  merged: boolean;
+  upstreamGone: boolean;
+  missing: boolean;
+  dirty: boolean;
+  idleSecs: number | null;
+  sizeBytes: number;
+  recommended: boolean;
+};
+
+export type WorktreeGcReport = {
+  items: WorktreeGcItem[];
+  totalBytes: number;
+  reclaimableBytes: number;
+};
+
+export type WorktreeGcApplyResult = {
+  removed: string[];
+  skipped: { id: string; reason: string }[];
+  freedBytes: number;
+};
+
+export async function getWorktreeGcReport(): Promise<WorktreeGcReport> {
+  return invoke<WorktreeGcReport>("worktree_gc_report");
+}
+
+export async function applyWorktreeGc(
+  ids: string[],
+  allowDirty?: boolean,
+): Promise<WorktreeGcApplyResult> {
+  return invoke<WorktreeGcApplyResult>("worktree_gc_apply", {
+    ids,
+    allowDirty: allowDirty ?? false,
+  });
+}
+
+export type WorkspaceExportResult = {
+  path: string;
+  workspaces: number;
+  groups: number;
+  skippedWorktrees: number;
+};
+
+export type WorkspaceImportStatus = "added" | "cloned" | "existing" | "failed";
+
+export type WorkspaceImportResult = {
+  items: {
+    name: string;
+    path: string;
+    status: WorkspaceImportStatus;
+    workspaceId: string | null;
+    error: string | null;
+    needsReview: boolean;
+  }[];
+  groupsAdded: number;
+};
+
+export async function exportWorkspaces(
+  root: string,
+  path: string,
+): Promise<WorkspaceExportResult> {
+  return invoke<WorkspaceExportResult>("export_workspaces", { root, path });
+}
+
+export async function importWorkspaces(
+  root: string,
+  path: string,
+): Promise<WorkspaceImportResult> {
+  return invoke<WorkspaceImportResult>("import_workspaces", { root, path });
+}
+
+export type CodexHomeProfileInfo = {
+  id: string;
+  name: string;
+  path: string;
+  exists: boolean;
+  signedIn: boolean;
+  workspaceIds: string[];
+  groupIds: string[];
+};
+
+export type CodexHomeScope = "workspace" | "group";
+
+export type CodexHomeSwitchResult = {
+  workspaceIds: string[];
+  reconnected: string[];
+};
+
+export async function listCodexHomeProfiles(): Promise<CodexHomeProfileInfo[]> {
+  return invoke<CodexHomeProfileInfo[]>("list_codex_home_profiles");
+}
+
+export async function createCodexHomeProfile(
+  name: string,
+  path?: string | null,
+): Promise<CodexHomeProfileInfo> {
+  return invoke<CodexHomeProfileInfo>("create_codex_home_profile", {
+    name,
+    path: path ?? null,
+  });
+}
+
+export async function switchCodexHomeProfile(
+  scope: CodexHomeScope,
+  targetId: string,
+  profileId: string | null,
+): Promise<CodexHomeSwitchResult> {
+  return invoke<CodexHomeSwitchResult>("switch_codex_home_profile", {
+    scope,
+    targetId,
+    profileId,
+  });
+}
+
+export async function deleteCodexHomeProfile(
+  profileId: string,
+): Promise<CodexHomeSwitchResult> {
+  return invoke<CodexHomeSwitchResult>("delete_codex_home_profile", {
+    profileId,
+  });
+}
+
 export async function updateWorkspaceSettings(
   id: string,
   settings: WorkspaceSettings,
@@ -407,8 +912,16 @@ export async function renameWorktreeUpstream(
   return invoke("rename_worktree_upstream", { id, oldBranch, newBranch });
 }
 
-export async function applyWorktreeChanges(workspaceId: string): Promise<void> {
-  return invoke("apply_worktree_changes", { workspaceId });
+export async function applyWorktreeChanges(
+  workspaceId: string,
+  options: WorktreeApplyOptions = {},
+): Promise<WorktreeApplyResult> {
+  return invoke<WorktreeApplyResult>("apply_worktree_changes", {
+    workspaceId,
+    strategy: options.strategy ?? null,
+    dryRun: options.dryRun ?? null,
+    commitMessage: options.commitMessage ?? null,
+  });
 }
 
 export async function openWorkspaceIn(
@@ -747,16 +1260,27 @@ export async function syncGit(workspaceId: string): Promise<void> {
   return invoke("sync_git", { workspaceId });
 }
 
+export async function getWorkspaceForge(
+  workspaceId: string,
+): Promise<ForgeInfo> {
+  return invoke("get_workspace_forge", { workspaceId });
+}
+
 export async function getGitHubIssues(
   workspace_id: string,
+  query?: GitHubListQuery,
 ): Promise<GitHubIssuesResponse> {
-  return invoke("get_github_issues", { workspaceId: workspace_id });
+  return invoke("get_github_issues", { workspaceId: workspace_id, query });
 }
 
 export async function getGitHubPullRequests(
   workspace_id: string,
+  query?: GitHubListQuery,
 ): Promise<GitHubPullRequestsResponse> {
-  return invoke("get_github_pull_requests", { workspaceId: workspace_id });
+  return invoke("get_github_pull_requests", {
+    workspaceId: workspace_id,
+    query,
+  });
 }
 
 export async function getGitHubPullRequestDiff(
@@ -868,6 +1392,68 @@ export async function getSkillsList(workspaceId: string) {
   return invoke<any>("skills_list", { workspaceId });
 }
 
+export type SkillScope = "workspace" | "global";
+
+export type LocalSkill = {
+  name: string;
+  description: string | null;
+  path: string;
+  scope: SkillScope;
+  enabled: boolean;
+  content: string;
+  issues?: string[];
+};
+
+export type SkillSource =
+  | { kind: "directory"; path: string }
+  | { kind: "git"; url: string; subdir?: string | null }
+  | { kind: "zip"; path: string };
+
+export type InstallSkillInput = {
+  scope: SkillScope;
+  source: SkillSource;
+  overwrite?: boolean;
+};
+
+export async function getLocalSkills(workspaceId: string): Promise<LocalSkill[]> {
+  return invoke<LocalSkill[]>("skills_local_list", { workspaceId });
+}
+
+export async function createSkill(
+  workspaceId: string,
+  scope: SkillScope,
+  content: string,
+): Promise<LocalSkill> {
+  return invoke<LocalSkill>("skills_create", { workspaceId, scope, content });
+}
+
+export async function updateSkill(
+  workspaceId: string,
+  path: string,
+  content: string,
+): Promise<LocalSkill> {
+  return invoke<LocalSkill>("skills_update", { workspaceId, path, content });
+}
+
+export async function setSkillEnabled(
+  workspaceId: string,
+  path: string,
+  enabled: boolean,
+): Promise<LocalSkill> {
+  return invoke<LocalSkill>("skills_set_enabled", { workspaceId, path, enabled });
+}
+
+export async function deleteSkill(workspaceId: string, path: string): Promise<void> {
+  return invoke("skills_delete", { workspaceId, path });
+}
+
+export async function installSkill(
+  workspaceId: string,
+  input: InstallSkillInput,
+): Promise<LocalSkill> {
+  return invoke<LocalSkill>("skills_install", { workspaceId, input });
+}
+
 export async function getAppsList(
   workspaceId: string,
   cursor?: string | null,
@@ -896,6 +1482,7 @@ export async function createPrompt(
     name: string;
     description?: string | null;
     argumentHint?: string | null;
+    variables?: PromptVariable[] | null;
     content: string;
   },
 ) {
@@ -905,6 +1492,7 @@ export async function createPrompt(
     name: data.name,
     description: data.description ?? null,
     argumentHint: data.argumentHint ?? null,
+    variables: data.variables ?? null,
     content: data.content,
   });
 }
@@ -916,6 +1504,7 @@ export async function updatePrompt(
     name: string;
     description?: string | null;
     argumentHint?: string | null;
+    variables?: PromptVariable[] | null;
     content: string;
   },
 ) {
@@ -925,6 +1514,7 @@ export async function updatePrompt(
     name: data.name,
     description: data.description ?? null,
     argumentHint: data.argumentHint ?? null,
+    variables: data.variables ?? null,
     content: data.content,
   });
 }
@@ -944,10 +1534,83 @@ export async function movePrompt(
   });
 }
 
+export type PromptVersion = {
+  commit: string;
+  message: string;
+  timestamp: number;
+};
+
+export async function getPromptHistory(
+  workspaceId: string,
+  path: string,
+): Promise<PromptVersion[]> {
+  return invoke<PromptVersion[]>("prompts_history", { workspaceId, path });
+}
+
+export async function restorePromptVersion(
+  workspaceId: string,
+  path: string,
+  commit: string,
+): Promise<CustomPromptOption> {
+  return invoke<CustomPromptOption>("prompts_restore_version", {
+    workspaceId,
+    path,
+    commit,
+  });
+}
+
+export type TeamPromptRepo = {
+  url: string | null;
+  path: string;
+  head: string | null;
+};
+
+export async function getTeamPromptRepo(): Promise<TeamPromptRepo> {
+  return invoke<TeamPromptRepo>("prompts_team_repo");
+}
+
+export async function setTeamPromptRepo(url: string | null): Promise<TeamPromptRepo> {
+  return invoke<TeamPromptRepo>("prompts_team_repo_set", { url });
+}
+
+export async function syncTeamPromptRepo(): Promise<TeamPromptRepo> {
+  return invoke<TeamPromptRepo>("prompts_team_repo_sync");
+}
+
 export async function getAppSettings(): Promise<AppSettings> {
   return invoke<AppSettings>("get_app_settings");
 }
 
+export type StorageRecovery = {
+  file: string;
+  error: string;
+  recoveredFrom: string | null;
+  quarantinedPath: string | null;
+};
+
+export async function getStorageRecoveryReport(): Promise<StorageRecovery[]> {
+  return invoke<StorageRecovery[]>("get_storage_recovery_report");
+}
+
+export type SettingsSyncConflict = {
+  setting: string;
+  configKey: string;
+  appValue: unknown;
+  configValue: unknown;
+};
+
+export type SettingsSyncReport = {
+  checkedAt: number;
+  externalEdit: boolean;
+  adopted: string[];
+  conflicts: SettingsSyncConflict[];
+  errors: string[];
+};
+
+export async function getSettingsSyncReport(): Promise<SettingsSyncReport> {
+  return invoke<SettingsSyncReport>("get_settings_sync_report");
+}
+
 export async function isMobileRuntime(): Promise<boolean> {
   return invoke<boolean>("is_mobile_runtime");
 }
@@ -1023,6 +1686,73 @@ export async function writeAgentMd(workspaceId: string, content: string): Promis
   return fileWrite("workspace", "agents", content, workspaceId);
 }
 
+export async function readWorkspaceCodexConfigToml(
+  workspaceId: string,
+): Promise<TextFileResponse> {
+  return fileRead("workspace", "config", workspaceId);
+}
+
+export async function writeWorkspaceCodexConfigToml(
+  workspaceId: string,
+  content: string,
+): Promise<void> {
+  return fileWrite("workspace", "config", content, workspaceId);
+}
+
+export type WorkspaceAgentsFile = {
+  path: string;
+  directory: string;
+  isOverride: boolean;
+  size: number;
+};
+
+export async function listWorkspaceAgentsFiles(
+  workspaceId: string,
+): Promise<WorkspaceAgentsFile[]> {
+  return invoke<WorkspaceAgentsFile[]>("list_workspace_agents_files", {
+    workspaceId,
+  });
+}
+
+export async function readWorkspaceAgentsFile(
+  workspaceId: string,
+  path: string,
+): Promise<TextFileResponse> {
+  return invoke<TextFileResponse>("read_workspace_agents_file", {
+    workspaceId,
+    path,
+  });
+}
+
+export async function writeWorkspaceAgentsFile(
+  workspaceId: string,
+  path: string,
+  content: string,
+): Promise<void> {
+  return invoke("write_workspace_agents_file", { workspaceId, path, content });
+}
+
+export type ConfigLayerSource = "global" | "project";
+
+export type EffectiveConfig = {
+  layers: {
+    source: ConfigLayerSource;
+    path: string;
+    exists: boolean;
+    error: string | null;
+  }[];
+  entries: {
+    key: string;
+    value: unknown;
+    source: ConfigLayerSource;
+    overridden: ConfigLayerSource[];
+  }[];
+};
+
+export async function getEffectiveConfig(workspaceId: string): Promise<EffectiveConfig> {
+  return invoke<EffectiveConfig>("get_effective_config", { workspaceId });
+}
+
 export async function listGitBranches(workspaceId: string) {
   return invoke<any>("list_git_branches", { workspaceId });
 }
@@ -1035,6 +1765,218 @@ export async function createGitBranch(workspaceId: string, name: string) {
   return invoke("create_git_branch", { workspaceId, name });
 }
 
+export async function listGitTags(workspaceId: string): Promise<GitTagInfo[]> {
+  return invoke<GitTagInfo[]>("list_git_tags", { workspaceId });
+}
+
+export async function createGitTag(
+  workspaceId: string,
+  name: string,
+  options: { message?: string | null; target?: string | null } = {},
+): Promise<void> {
+  return invoke("create_git_tag", {
+    workspaceId,
+    name,
+    message: options.message ?? null,
+    target: options.target ?? null,
+  });
+}
+
+export async function deleteGitTag(
+  workspaceId: string,
+  name: string,
+  remote?: string | null,
+): Promise<void> {
+  return invoke("delete_git_tag", { workspaceId, name, remote: remote ?? null });
+}
+
+export async function pushGitTags(
+  workspaceId: string,
+  options: { name?: string | null; remote?: string | null } = {},
+): Promise<void> {
+  return invoke("push_git_tags", {
+    workspaceId,
+    name: options.name ?? null,
+    remote: options.remote ?? null,
+  });
+}
+
+export async function generateReleaseNotes(
+  workspaceId: string,
+  tag: string,
+  modelId?: string | null,
+): Promise<string> {
+  return invoke<string>("generate_release_notes", {
+    workspaceId,
+    tag,
+    modelId: modelId ?? null,
+  });
+}
+
+export async function createGitHubRelease(
+  workspaceId: string,
+  tag: string,
+  options: {
+    title?: string | null;
+    notes?: string | null;
+    draft?: boolean;
+    modelId?: string | null;
+  } = {},
+): Promise<GitHubRelease> {
+  return invoke<GitHubRelease>("create_github_release", {
+    workspaceId,
+    tag,
+    title: options.title ?? null,
+    notes: options.notes ?? null,
+    draft: options.draft ?? true,
+    modelId: options.modelId ?? null,
+  });
+}
+
+export type GeneratedPullRequestContent = {
+  title: string;
+  body: string;
+};
+
+export async function generateGitHubPullRequestContent(
+  workspaceId: string,
+  base?: string | null,
+  modelId?: string | null,
+): Promise<GeneratedPullRequestContent> {
+  return invoke<GeneratedPullRequestContent>(
+    "generate_github_pull_request_content",
+    {
+      workspaceId,
+      base: base ?? null,
+      modelId: modelId ?? null,
+    },
+  );
+}
+
+export async function createGitHubPullRequest(
+  workspaceId: string,
+  options: {
+    title?: string | null;
+    body?: string | null;
+    base?: string | null;
+    draft?: boolean;
+    reviewers?: string[];
+    generate?: boolean;
+    modelId?: string | null;
+  } = {},
+): Promise<GitHubPullRequest> {
+  return invoke<GitHubPullRequest>("create_github_pull_request", {
+    workspaceId,
+    title: options.title ?? null,
+    body: options.body ?? null,
+    base: options.base ?? null,
+    draft: options.draft ?? false,
+    reviewers: options.reviewers ?? [],
+    generate: options.generate ?? false,
+    modelId: options.modelId ?? null,
+  });
+}
+
+export async function updateGitHubPullRequest(
+  workspaceId: string,
+  prNumber: number,
+  changes: { title?: string | null; body?: string | null },
+): Promise<GitHubPullRequest> {
+  return invoke<GitHubPullRequest>("update_github_pull_request", {
+    workspaceId,
+    prNumber,
+    title: changes.title ?? null,
+    body: changes.body ?? null,
+  });
+}
+
+export async function markGitHubPullRequestReady(
+  workspaceId: string,
+  prNumber: number,
+): Promise<GitHubPullRequest> {
+  return invoke<GitHubPullRequest>("mark_github_pull_request_ready", {
+    workspaceId,
+    prNumber,
+  });
+}
+
+export async function postGitHubPullRequestReview(
+  workspaceId: string,
+  prNumber: number,
+  review: {
+    body?: string | null;
+    event?: GitHubPullRequestReviewEvent;
+    comments?: GitHubPullRequestReviewComment[];
+    reviewThreadId?: string | null;
+  },
+): Promise<GitHubPullRequestReviewResult> {
+  return invoke<GitHubPullRequestReviewResult>(
+    "post_github_pull_request_review",
+    {
+      workspaceId,
+      prNumber,
+      body: review.body ?? null,
+      event: review.event ?? "comment",
+      comments: review.comments ?? [],
+      reviewThreadId: review.reviewThreadId ?? null,
+    },
+  );
+}
+
+export type GitHubChecksTarget = {
+  gitRef?: string | null;
+  prNumber?: number | null;
+};
+
+export async function getGitHubChecks(
+  workspaceId: string,
+  target: GitHubChecksTarget = {},
+): Promise<GitHubChecksResponse> {
+  return invoke<GitHubChecksResponse>("get_github_checks", {
+    workspaceId,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+  });
+}
+
+export async function watchGitHubChecks(
+  workspaceId: string,
+  target: GitHubChecksTarget & {
+    threadId?: string | null;
+    intervalSecs?: number | null;
+  } = {},
+): Promise<void> {
+  return invoke("watch_github_checks", {
+    workspaceId,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+    threadId: target.threadId ?? null,
+    intervalSecs: target.intervalSecs ?? null,
+  });
+}
+
+export async function unwatchGitHubChecks(
+  workspaceId: string,
+  target: GitHubChecksTarget = {},
+): Promise<boolean> {
+  return invoke<boolean>("unwatch_github_checks", {
+    workspaceId,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+  });
+}
+
+export async function watchGitStatus(
+  workspaceId: string,
+  debounceMs?: number | null,
+): Promise<void> {
+  return invoke("watch_git_status", { workspaceId, debounceMs: debounceMs ?? null });
+}
+
+export async function unwatchGitStatus(workspaceId: string): Promise<boolean> {
+  return invoke<boolean>("unwatch_git_status", { workspaceId });
+}
+
 function withModelId(modelId?: string | null) {
   return modelId ? { modelId } : {};
 }
@@ -7,6 +7,36 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
+  forge?: ForgeKind | null;
+  forgeApiUrl?: string | null;
+  issueLink?: WorkspaceIssueLink | null;
+  checkout?: WorkspaceCheckoutOptions | null;
+  codexHomeProfileId?: string | null;
+  activeProfile?: string | null;
+};
+
+export type WorkspaceCheckoutOptions = {
+  sparsePaths: string[];
+  depth?: number | null;
+  filter?: string | null;
+};
+
+export type WorkspaceIssueLink = {
+  number: number;
+  title: string;
+  url: string;
+  branch: string;
+  threadId: string;
+};
+
+export type ForgeKind = "github" | "gitlab" | "gitea";
+
+export type ForgeInfo = {
+  kind: ForgeKind;
+  overridden: boolean;
+  host: string | null;
+  repository: string | null;
+  apiUrl: string | null;
 };
 
 export type LaunchScriptIconId =
@@ -38,6 +68,13 @@ export type WorkspaceGroup = {
   name: string;
   sortOrder?: number | null;
   copiesFolder?: string | null;
+  codexHomeProfileId?: string | null;
+};
+
+export type CodexHomeProfile = {
+  id: string;
+  name: string;
+  path: string;
 };
 
 export type WorkspaceKind = "main" | "worktree";
@@ -46,6 +83,28 @@ export type WorktreeInfo = {
   branch: string;
 };
 
+export type WorktreeApplyStrategy =
+  | "patch"
+  | "threeWayPatch"
+  | "merge"
+  | "squash"
+  | "cherryPick";
+
+export type WorktreeApplyOptions = {
+  strategy?: WorktreeApplyStrategy;
+  dryRun?: boolean;
+  commitMessage?: string | null;
+};
+
+export type WorktreeApplyResult = {
+  strategy: WorktreeApplyStrategy;
+  dryRun: boolean;
+  applied: boolean;
+  conflicts: string[];
+  commits: string[];
+  commitMessage?: string | null;
+};
+
 export type WorkspaceInfo = {
   id: string;
   name: string;
@@ -259,6 +318,7 @@ export type AppSettings = {
   gitDiffIgnoreWhitespaceChanges: boolean;
   commitMessagePrompt: string;
   commitMessageModelId: string | null;
+  issueAgentPrompt?: string;
   collaborationModesEnabled: boolean;
   steerEnabled: boolean;
   followUpMessageBehavior: FollowUpMessageBehavior;
@@ -282,6 +342,7 @@ export type AppSettings = {
   composerListContinuation: boolean;
   composerCodeBlockCopyUseModifier: boolean;
   workspaceGroups: WorkspaceGroup[];
+  codexHomeProfiles?: CodexHomeProfile[];
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
 };
@@ -479,16 +540,56 @@ export type GitLogResponse = {
   upstream: string | null;
 };
 
+export type GitHubItemState = "open" | "closed" | "merged" | "all";
+
+export type GitHubListQuery = {
+  state?: GitHubItemState;
+  labels?: string[];
+  assignee?: string | null;
+  author?: string | null;
+  search?: string | null;
+  milestone?: string | null;
+  cursor?: string | null;
+  limit?: number | null;
+};
+
+export type GitHubLabel = {
+  name: string;
+  color: string | null;
+};
+
+export type GitHubLinkedItem = {
+  number: number;
+  title: string;
+  url: string;
+  state: string;
+};
+
 export type GitHubIssue = {
   number: number;
   title: string;
   url: string;
   updatedAt: string;
+  state?: string;
+  body?: string;
+  author?: GitHubUser | null;
+  labels?: GitHubLabel[];
+  assignees?: GitHubUser[];
+  commentCount?: number;
+  linkedPullRequests?: GitHubLinkedItem[];
+};
+
+export type IssueAgentStart = {
+  workspace: WorkspaceInfo;
+  threadId: string;
+  branch: string;
+  issue: GitHubIssue;
 };
 
 export type GitHubIssuesResponse = {
   total: number;
   issues: GitHubIssue[];
+  nextCursor?: string | null;
 };
 
 export type GitHubUser = {
@@ -506,11 +607,17 @@ export type GitHubPullRequest = {
   baseRefName: string;
   isDraft: boolean;
   author: GitHubUser | null;
+  state?: string;
+  labels?: GitHubLabel[];
+  assignees?: GitHubUser[];
+  commentCount?: number;
+  linkedIssues?: GitHubLinkedItem[];
 };
 
 export type GitHubPullRequestsResponse = {
   total: number;
   pullRequests: GitHubPullRequest[];
+  nextCursor?: string | null;
 };
 
 export type GitHubPullRequestDiff = {
@@ -527,6 +634,65 @@ export type GitHubPullRequestComment = {
   author: GitHubUser | null;
 };
 
+export type GitTagInfo = {
+  name: string;
+  sha: string;
+  annotated: boolean;
+  message?: string | null;
+  timestamp: number;
+};
+
+export type GitHubRelease = {
+  tagName: string;
+  previousTag?: string | null;
+  url: string;
+  isDraft: boolean;
+  notes: string;
+};
+
+export type GitHubCheckRun = {
+  id: number;
+  name: string;
+  status: string;
+  conclusion?: string | null;
+  url: string;
+  app?: string | null;
+  startedAt?: string | null;
+  completedAt?: string | null;
+  logExcerpt?: string | null;
+};
+
+export type GitHubChecksState = "none" | "pending" | "success" | "failure";
+
+export type GitHubChecksResponse = {
+  gitRef: string;
+  sha: string;
+  prNumber?: number | null;
+  state: GitHubChecksState;
+  checks: GitHubCheckRun[];
+};
+
+export type GitHubPullRequestReviewEvent =
+  | "approve"
+  | "requestChanges"
+  | "comment";
+
+export type GitHubPullRequestReviewComment = {
+  path: string;
+  line: number;
+  startLine?: number | null;
+  side?: "left" | "right";
+  body: string;
+};
+
+export type GitHubPullRequestReviewResult = {
+  id: number;
+  url: string;
+  state: string;
+  inlineComments: number;
+  unanchoredComments: GitHubPullRequestReviewComment[];
+};
+
 export type TokenUsageBreakdown = {
   totalTokens: number;
   inputTokens: number;
@@ -665,13 +831,23 @@ export type AppOption = {
   distributionChannel?: string | null;
 };
 
+export type PromptVariableType = "string" | "number" | "boolean" | "enum";
+
+export type PromptVariable = {
+  name: string;
+  type: PromptVariableType;
+  options?: string[];
+  default?: string;
+};
+
 export type CustomPromptOption = {
   name: string;
   path: string;
   description?: string;
   argumentHint?: string;
   content: string;
-  scope?: "workspace" | "global";
+  scope?: "workspace" | "global" | "team";
+  variables?: PromptVariable[];
 };
 
 export type BranchInfo = {
@@ -0,0 +1,55 @@
+import { describe, expect, it } from "vitest";
+import type { CustomPromptOption } from "../types";
+import {
+  buildPromptInsertText,
+  expandCustomPromptText,
+  getPromptArgumentHint,
+} from "./customPrompts";
+
+const triagePrompt: CustomPromptOption = {
+  name: "triage",
+  path: "/prompts/triage.md",
+  content: "Triage $TICKET at $PRIORITY priority (dry run: $DRY_RUN)",
+  variables: [
+    { name: "TICKET", type: "string" },
+    { name: "PRIORITY", type: "enum", options: ["low", "high"], default: "low" },
+    { name: "DRY_RUN", type: "boolean", default: "false" },
+  ],
+};
+
+describe("customPrompts variables", () => {
+  it("prefills declared defaults in hints and inserted text", () => {
+    expect(getPromptArgumentHint(triagePrompt)).toBe(
+      "TICKET= PRIORITY=low DRY_RUN=false",
+    );
+    expect(buildPromptInsertText(triagePrompt).text).toBe(
+      'prompts:triage TICKET="" PRIORITY="low" DRY_RUN="false"',
+    );
+  });
+
+  it("fills missing variables from defaults at send time", () => {
+    expect(
+      expandCustomPromptText("/prompts:triage TICKET=ABC-1", [triagePrompt]),
+    ).toEqual({
+      expanded: "Triage ABC-1 at low priority (dry run: false)",
+    });
+  });
+
+  it("rejects values that do not match the variable type", () => {
+    const result = expandCustomPromptText(
+      '/prompts:triage TICKET=ABC-1 PRIORITY=urgent DRY_RUN="maybe"',
+      [triagePrompt],
+    );
+    expect(result).toEqual({
+      error:
+        "Invalid args for /prompts:triage: PRIORITY must be one of low, high; DRY_RUN must be true or false.",
+    });
+  });
+
+  it("still requires variables without a default", () => {
+    const result = expandCustomPromptText("/prompts:triage", [triagePrompt]);
+    expect(result && "error" in result ? result.error : "").toContain(
+      "Missing required args for /prompts:triage: TICKET",
+    );
+  });
+});
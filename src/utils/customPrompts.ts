@@ -1,4 +1,4 @@
-import type { CustomPromptOption } from "../types";
+import type { CustomPromptOption, PromptVariable } from "../types";
 
 const PROMPTS_CMD_PREFIX = "prompts";
 const PROMPTS_CMD = `${PROMPTS_CMD_PREFIX}:`;
@@ -48,6 +48,29 @@ export function promptHasNumericPlaceholders(content: string) {
   return false;
 }
 
+function findPromptVariable(prompt: CustomPromptOption, name: string) {
+  return prompt.variables?.find((variable) => variable.name === name);
+}
+
+function checkPromptVariableValue(variable: PromptVariable, value: string) {
+  switch (variable.type) {
+    case "number":
+      return value.trim() !== "" && Number.isFinite(Number(value))
+        ? null
+        : `${variable.name} must be a number`;
+    case "boolean":
+      return value === "true" || value === "false"
+        ? null
+        : `${variable.name} must be true or false`;
+    case "enum":
+      return variable.options?.includes(value)
+        ? null
+        : `${variable.name} must be one of ${(variable.options ?? []).join(", ")}`;
+    default:
+      return null;
+  }
+}
+
 export function getPromptArgumentHint(prompt: CustomPromptOption) {
   const hint = prompt.argumentHint?.trim();
   if (hint) {
@@ -55,7 +78,9 @@ export function getPromptArgumentHint(prompt: CustomPromptOption) {
   }
   const names = promptArgumentNames(prompt.content);
   if (names.length > 0) {
-    return names.map((name) => `${name}=`).join(" ");
+    return names
+      .map((name) => `${name}=${findPromptVariable(prompt, name)?.default ?? ""}`)
+      .join(" ");
   }
   if (promptHasNumericPlaceholders(prompt.content)) {
     return "[args]";
@@ -71,7 +96,8 @@ export function buildPromptInsertText(prompt: CustomPromptOption) {
     if (cursorOffset === undefined) {
       cursorOffset = text.length + 1 + name.length + 2;
     }
-    text += ` ${name}=""`;
+    const defaultValue = findPromptVariable(prompt, name)?.default ?? "";
+    text += ` ${name}="${defaultValue}"`;
   });
   return { text, cursorOffset };
 }
@@ -343,14 +369,32 @@ export function expandCustomPromptText(
         error: formatPromptArgsError(`/${parsed.name}`, parsedInputs.error),
       } as const;
     }
-    const missing = required.filter((name) => !(name in parsedInputs.values));
+    const values = { ...parsedInputs.values };
+    required.forEach((name) => {
+      const fallback = findPromptVariable(prompt, name)?.default;
+      if (!(name in values) && fallback !== undefined) {
+        values[name] = fallback;
+      }
+    });
+    const missing = required.filter((name) => !(name in values));
     if (missing.length > 0) {
       return {
         error: `Missing required args for /${parsed.name}: ${missing.join(", ")}. Provide as key=value (quote values with spaces).`,
       } as const;
     }
+    const invalid = required
+      .map((name) => {
+        const variable = findPromptVariable(prompt, name);
+        return variable ? checkPromptVariableValue(variable, values[name]) : null;
+      })
+      .filter((message): message is string => message !== null);
+    if (invalid.length > 0) {
+      return {
+        error: `Invalid args for /${parsed.name}: ${invalid.join("; ")}.`,
+      } as const;
+    }
     return {
-      expanded: expandNamedPlaceholders(prompt.content, parsedInputs.values),
+      expanded: expandNamedPlaceholders(prompt.content, values),
     } as const;
   }
 